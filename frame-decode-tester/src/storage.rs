@@ -558,6 +558,7 @@ fn storage_block_level_failure(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn test_single_storage_block_with_retry(
     block_number: u64,
     state: &mut RpcTestState,
@@ -609,6 +610,7 @@ async fn test_single_storage_block_with_retry(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn test_single_storage_block(
     block_number: u64,
     state: &mut RpcTestState,
@@ -842,7 +844,7 @@ fn decode_storage_value_to_result(
     legacy_types_for_spec: &TypeRegistrySet,
     leftover_rule: Option<&IgnoreLeftoverBytesRule>,
 ) -> Result<scale_value::Value<String>, String> {
-    let mut cursor = &*bytes;
+    let mut cursor = bytes;
 
     let value = with_metadata_versioned!(metadata, legacy_types_for_spec, |m, resolver| {
         decode_storage_value_inner(&mut cursor, pallet_name, storage_entry, m, resolver)
@@ -872,8 +874,8 @@ fn decode_storage_value_to_result(
     Ok(value)
 }
 
-fn decode_storage_value_inner<'scale, Info, Resolver>(
-    cursor: &mut &'scale [u8],
+fn decode_storage_value_inner<Info, Resolver>(
+    cursor: &mut &[u8],
     pallet_name: &str,
     storage_entry: &str,
     info: &Info,
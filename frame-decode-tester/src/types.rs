@@ -18,8 +18,10 @@
 use scale_info_legacy::ChainTypeRegistry;
 
 /// Historic chain types configuration.
+#[derive(Default)]
 pub enum ChainTypes {
     /// Use Polkadot relay chain types.
+    #[default]
     Polkadot,
     /// Use Kusama relay chain types.
     Kusama,
@@ -27,12 +29,6 @@ pub enum ChainTypes {
     KusamaAssetHub,
 }
 
-impl Default for ChainTypes {
-    fn default() -> Self {
-        ChainTypes::Polkadot
-    }
-}
-
 impl ChainTypes {
     /// Load the chain type registry.
     pub fn load(&self) -> ChainTypeRegistry {
@@ -17,9 +17,17 @@
 //! like Polkadot.
 //!
 //! - See [`extrinsics`] for decoding Extrinsics.
+//! - See [`events`] for decoding Events.
+//! - See [`errors`] for decoding pallet errors and `DispatchError`s.
+//! - See [`outer_enums`] for decoding the aggregated `RuntimeCall`, `RuntimeEvent` and `RuntimeError` types.
+//! - See [`receipts`] for linking the extrinsics in a block to their events, outcome and fees.
 //! - See [`storage`] for encoding/decoding storage keys and decoding values.
 //! - See [`runtime_apis`] for encoding Runtime API inputs and decoding Runtime API responses
 //! - See [`legacy_types`] to access historic type information for certain chains.
+//! - See `crypto` (behind the `crypto` feature) for signing signer payloads and verifying extrinsic signatures.
+//! - See `metadata_hash` (behind the `metadata-hash` feature) for computing RFC-0078 metadata hashes and proofs.
+//! - See [`metadata`] for a wrapper around any supported metadata version which implements all of the
+//!   traits needed to decode things.
 //!
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -33,27 +41,148 @@ pub mod extrinsics {
     //! This module contains functions for decoding extrinsics.
     //!
     //! - See [`decode_extrinsic`] for a general function to decode modern or historic extrinsics.
+    //! - See [`decode_call_data`] to decode call data on its own, and [`call_hash`] to hash it.
+    //! - See [`decode_call_tree`] to decode call data along with any calls nested inside of it.
     //! - See [`ExtrinsicTypeInfo`] for the underlying trait which extracts the relevant information.
-
+    //! - See [`ExtrinsicDocsInfo`] for a trait which hands back the docs for calls and their arguments.
+    //! - See [`encode_call_data_checked`] to report or reject calls which are marked as deprecated.
+    //! - See [`CheckSpecVersion`], [`CheckNonce`], [`CheckMortality`] and friends for implementations
+    //!   of [`TransactionExtension`] for the standard FRAME transaction extensions.
+    //! - See [`decode_standard_extensions`] to decode the nonce, tip, era and asset ID given to the
+    //!   standard FRAME transaction extensions in an extrinsic.
+
+    pub use crate::methods::call_data_decoder::{
+        CallData, CallDataDecodeError, CallDataOwned, call_hash, decode_call_data,
+    };
+    pub use crate::methods::call_tree_decoder::{
+        CallTree, CallTreeDecodeError, CallTreeOwned, NestedCall, decode_call_tree,
+    };
+    pub use crate::methods::deprecation::{Deprecation, DeprecationPolicy, DeprecationWarning};
     pub use crate::methods::extrinsic_decoder::{
         Extrinsic, ExtrinsicDecodeError, ExtrinsicExtensions, ExtrinsicOwned, ExtrinsicSignature,
         ExtrinsicType, NamedArg, decode_extrinsic,
     };
     pub use crate::methods::extrinsic_encoder::{
-        ExtrinsicEncodeError, TransactionExtension, TransactionExtensionError,
-        TransactionExtensions, TransactionExtensionsError,
-        best_v5_general_transaction_extension_version, encode_call_data, encode_call_data_to,
-        encode_call_data_with_info, encode_call_data_with_info_to, encode_v4_signed,
-        encode_v4_signed_to, encode_v4_signed_with_info_to, encode_v4_signer_payload,
-        encode_v4_signer_payload_with_info, encode_v4_unsigned, encode_v4_unsigned_to,
-        encode_v4_unsigned_with_info_to, encode_v5_bare, encode_v5_bare_to,
+        ChargeAssetTxPayment, ChargeTransactionPayment, CheckGenesis, CheckMetadataHash,
+        CheckMortality, CheckNonZeroSender, CheckNonce, CheckSpecVersion, CheckTxVersion,
+        CheckWeight, Era, ExtrinsicEncodeError, Mortality, TransactionExtension,
+        TransactionExtensionError, TransactionExtensions, TransactionExtensionsError,
+        best_v5_general_transaction_extension_version, encode_call_data, encode_call_data_checked,
+        encode_call_data_to, encode_call_data_with_info, encode_call_data_with_info_to,
+        encode_v4_signed, encode_v4_signed_to, encode_v4_signed_with_info_to,
+        encode_v4_signer_payload, encode_v4_signer_payload_with_info, encode_v4_unsigned,
+        encode_v4_unsigned_to, encode_v4_unsigned_with_info_to, encode_v5_bare, encode_v5_bare_to,
         encode_v5_bare_with_info_to, encode_v5_general, encode_v5_general_to,
         encode_v5_general_with_info_to, encode_v5_signer_payload,
         encode_v5_signer_payload_with_info,
     };
     pub use crate::methods::extrinsic_type_info::{
-        ExtrinsicCallInfo, ExtrinsicCallInfoArg, ExtrinsicExtensionInfo, ExtrinsicExtensionInfoArg,
-        ExtrinsicInfoError, ExtrinsicSignatureInfo, ExtrinsicTypeInfo,
+        ExtrinsicCallInfo, ExtrinsicCallInfoArg, ExtrinsicCallTypeInfo, ExtrinsicDocsInfo,
+        ExtrinsicExtensionInfo, ExtrinsicExtensionInfoArg, ExtrinsicInfoError,
+        ExtrinsicSignatureInfo, ExtrinsicTypeInfo,
+    };
+    pub use crate::methods::standard_extensions_decoder::{
+        StandardExtensions, StandardExtensionsDecodeError, decode_standard_extensions,
+    };
+}
+
+#[cfg(feature = "crypto")]
+pub mod crypto {
+    //! This module contains functions for signing and verifying extrinsics, and is available
+    //! with the `crypto` feature.
+    //!
+    //! - See [`Sr25519Keypair`], [`Ed25519Keypair`] and [`EcdsaKeypair`] to sign the payloads
+    //!   returned from functions like [`crate::extrinsics::encode_v4_signer_payload`].
+    //! - See [`verify_extrinsic_signature`] to verify the signature of a decoded extrinsic against
+    //!   its signer payload, which can be rebuilt using [`crate::extrinsics::Extrinsic::signer_payload`].
+    //! - See [`MultiSignature`] for the signatures produced, which can be verified directly.
+
+    pub use crate::methods::crypto::{
+        EcdsaKeypair, Ed25519Keypair, KeypairError, MultiSignature, SignatureVerificationError,
+        Sr25519Keypair, verify_extrinsic_signature,
+    };
+}
+
+#[cfg(feature = "metadata-hash")]
+pub mod metadata_hash {
+    //! This module implements [RFC-0078](https://polkadot-fellows.github.io/RFCs/approved/0078-merkleized-metadata.html)
+    //! merkleized metadata, and is available with the `metadata-hash` feature.
+    //!
+    //! - See [`metadata_hash`] to compute the hash expected by the `CheckMetadataHash`
+    //!   transaction extension, given some metadata and [`ChainInfo`].
+    //! - See [`MerkleizedMetadata`] to generate a [`MetadataProof`] containing the types needed
+    //!   to decode some extrinsic or signer payload, which wallets can check against that hash.
+
+    pub use crate::methods::metadata_hash::{
+        ChainInfo, Hash, MerkleizedMetadata, MetadataHashError, MetadataProof, metadata_hash,
+    };
+}
+
+pub mod errors {
+    //! This module contains functions for decoding pallet errors and `DispatchError`s.
+    //!
+    //! - See [`decode_module_error`] to decode the bytes of a pallet error into its pallet and error names.
+    //! - See [`decode_dispatch_error`] to decode a `DispatchError`, including any pallet error within it.
+    //! - See [`ErrorTypeInfo`] for the underlying trait which extracts the relevant information.
+
+    pub use crate::methods::error_decoder::{
+        DispatchError, DispatchErrorDecodeError, DispatchErrorOwned, ModuleError,
+        ModuleErrorDecodeError, ModuleErrorOwned, decode_dispatch_error, decode_module_error,
+    };
+    pub use crate::methods::error_type_info::{
+        ErrorInfo, ErrorInfoArg, ErrorInfoError, ErrorTypeInfo,
+    };
+    pub use crate::methods::extrinsic_decoder::NamedArg;
+}
+
+pub mod events {
+    //! This module contains functions for decoding events.
+    //!
+    //! - See [`decode_event`] for a general function to decode modern or historic events.
+    //! - See [`decode_event_records`] to iterate over the event records in a `System.Events` storage value.
+    //! - See [`EventTypeInfo`] for the underlying trait which extracts the relevant information.
+    //! - See [`EventDocsInfo`] for a trait which hands back the docs for events.
+
+    pub use crate::methods::event_decoder::{Event, EventDecodeError, EventOwned, decode_event};
+    pub use crate::methods::event_records_decoder::{
+        EventRecord, EventRecordOwned, EventRecords, EventRecordsDecodeError, Phase,
+        decode_event_records, decode_event_records_with_info,
+    };
+    pub use crate::methods::event_type_info::{
+        EventDocsInfo, EventInfo, EventInfoArg, EventInfoError, EventTypeInfo,
+    };
+    pub use crate::methods::extrinsic_decoder::NamedArg;
+}
+
+pub mod outer_enums {
+    //! This module contains functions for decoding the outer enums; `RuntimeCall`, `RuntimeEvent`
+    //! and `RuntimeError`.
+    //!
+    //! - See [`decode_outer_enum`] to decode bytes as one of the outer enums, and
+    //!   [`decode_runtime_call`], [`decode_runtime_event`] and [`decode_runtime_error`] for
+    //!   shorthands for each one.
+    //! - See [`decode_outer_enum_with_type_id`] to decode bytes given the type ID of an outer enum.
+    //! - See [`OuterEnumTypeInfo`] for the underlying trait which extracts the relevant information.
+
+    pub use crate::methods::extrinsic_decoder::NamedArg;
+    pub use crate::methods::outer_enum_decoder::{
+        OuterEnumDecodeError, OuterEnumVariant, decode_outer_enum, decode_outer_enum_with_type_id,
+        decode_runtime_call, decode_runtime_error, decode_runtime_event,
+    };
+    pub use crate::methods::outer_enum_type_info::{
+        OuterEnum, OuterEnumInfo, OuterEnumInfoError, OuterEnumTypeInfo,
+    };
+}
+
+pub mod receipts {
+    //! This module contains functions for working out what happened to each extrinsic in a block.
+    //!
+    //! - See [`decode_extrinsic_receipts`] to decode the extrinsics in a block alongside the
+    //!   `System.Events` for that block, linking each extrinsic to its events, outcome and fee.
+
+    pub use crate::methods::extrinsic_receipts_decoder::{
+        ExtrinsicOutcome, ExtrinsicReceipt, ExtrinsicReceiptOwned, ExtrinsicReceiptsDecodeError,
+        decode_extrinsic_receipts, decode_extrinsic_receipts_with_info,
     };
 }
 
@@ -67,22 +196,44 @@ pub mod storage {
     //! - See [`StorageTypeInfo`] for the underlying trait which provides storage entry information.
     //! - See [`StorageEntryInfo`] for a underlying trait which provides information about the available
     //!   storage entries.
-
+    //! - See [`StorageDocsInfo`] for a trait which hands back the docs for storage entries.
+    //! - See [`encode_storage_key_checked`] to report or reject storage entries which are marked
+    //!   as deprecated.
+    //! - See [`decode_storage_key_with_options`] and [`DecodeStorageKeyOptions`] to also check
+    //!   that the hashes in storage keys match the key values that they contain, or to find the key
+    //!   values behind opaque hashes in storage keys given a [`StorageKeyLookup`] of candidate
+    //!   values.
+    //! - See [`StoragePrefixIndex`] and [`decode_any_storage_key`] to identify and decode raw storage
+    //!   keys without knowing which storage entry they belong to.
+    //! - See [`verify_storage_proof`] and [`verify_compact_storage_proof`] to verify storage proofs
+    //!   against a state root and obtain the key/value pairs that they contain.
+
+    pub use crate::methods::deprecation::{Deprecation, DeprecationPolicy, DeprecationWarning};
     pub use crate::methods::storage_decoder::{
-        StorageKey, StorageKeyDecodeError, StorageKeyPart, StorageKeyPartValue,
-        StorageKeyValueDecodeError, StorageValueDecodeError,
-        decode_default_storage_value_with_info, decode_storage_key, decode_storage_key_values,
-        decode_storage_key_with_info, decode_storage_value, decode_storage_value_with_info,
+        DecodeStorageKeyOptions, StorageKey, StorageKeyDecodeError, StorageKeyPart,
+        StorageKeyPartLookedUpValue, StorageKeyPartValue, StorageKeyValueDecodeError,
+        StorageValueDecodeError, decode_default_storage_value_with_info, decode_storage_key,
+        decode_storage_key_values, decode_storage_key_with_info,
+        decode_storage_key_with_info_and_options, decode_storage_key_with_options,
+        decode_storage_value, decode_storage_value_with_info,
     };
     pub use crate::methods::storage_encoder::{
-        StorageKeyEncodeError, encode_storage_key, encode_storage_key_prefix,
-        encode_storage_key_suffix, encode_storage_key_suffix_to,
+        StorageKeyEncodeError, encode_storage_key, encode_storage_key_checked,
+        encode_storage_key_prefix, encode_storage_key_suffix, encode_storage_key_suffix_to,
         encode_storage_key_suffix_with_info_to, encode_storage_key_to,
         encode_storage_key_with_info, encode_storage_key_with_info_to,
     };
+    pub use crate::methods::storage_key_lookup::StorageKeyLookup;
+    pub use crate::methods::storage_prefix_index::{
+        AnyStorageKey, AnyStorageKeyDecodeError, StoragePrefixIndex, WellKnownKey,
+        decode_any_storage_key,
+    };
+    pub use crate::methods::storage_proof::{
+        StorageProofError, VerifiedStorage, verify_compact_storage_proof, verify_storage_proof,
+    };
     pub use crate::methods::storage_type_info::{
-        StorageEntry, StorageEntryInfo, StorageHasher, StorageInfo, StorageInfoError,
-        StorageKeyInfo, StorageTypeInfo,
+        StorageDocsInfo, StorageEntry, StorageEntryInfo, StorageHasher, StorageInfo,
+        StorageInfoError, StorageKeyInfo, StorageTypeInfo,
     };
     pub use crate::utils::{
         DecodableValues, EncodableValues, IntoDecodableValues, IntoEncodableValues,
@@ -97,13 +248,16 @@ pub mod constants {
     //!   information from metadata.
     //! - See [`ConstantEntryInfo`] for a underlying trait which provides information about the available
     //!   constants.
+    //! - See [`ConstantDocsInfo`] for a trait which hands back the docs for constants.
 
     pub use crate::methods::constant_decoder::{
         ConstantDecodeError, decode_constant, decode_constant_with_info,
     };
     pub use crate::methods::constant_type_info::{
-        ConstantEntry, ConstantEntryInfo, ConstantInfo, ConstantInfoError, ConstantTypeInfo,
+        ConstantDocsInfo, ConstantEntry, ConstantEntryInfo, ConstantInfo, ConstantInfoError,
+        ConstantTypeInfo,
     };
+    pub use crate::methods::deprecation::Deprecation;
 }
 
 pub mod runtime_apis {
@@ -115,17 +269,22 @@ pub mod runtime_apis {
     //! - See [`RuntimeApiTypeInfo`] for the underlying trait which extracts the relevant information.
     //! - See [`RuntimeApiEntryInfo`] for a underlying trait which provides information about the available
     //!   Runtime APIs.
+    //! - See [`RuntimeApiDocsInfo`] for a trait which hands back the docs for Runtime APIs.
+    //! - See [`encode_runtime_api_inputs_checked`] to report or reject Runtime APIs which are
+    //!   marked as deprecated.
 
+    pub use crate::methods::deprecation::{Deprecation, DeprecationPolicy, DeprecationWarning};
     pub use crate::methods::runtime_api_decoder::{
         RuntimeApiDecodeError, decode_runtime_api_response, decode_runtime_api_response_with_info,
     };
     pub use crate::methods::runtime_api_encoder::{
-        RuntimeApiInputsEncodeError, encode_runtime_api_inputs, encode_runtime_api_inputs_to,
-        encode_runtime_api_inputs_with_info_to, encode_runtime_api_name,
+        RuntimeApiInputsEncodeError, encode_runtime_api_inputs, encode_runtime_api_inputs_checked,
+        encode_runtime_api_inputs_to, encode_runtime_api_inputs_with_info_to,
+        encode_runtime_api_name,
     };
     pub use crate::methods::runtime_api_type_info::{
-        RuntimeApiEntry, RuntimeApiEntryInfo, RuntimeApiInfo, RuntimeApiInfoError, RuntimeApiInput,
-        RuntimeApiTypeInfo,
+        RuntimeApiDocsInfo, RuntimeApiEntry, RuntimeApiEntryInfo, RuntimeApiInfo,
+        RuntimeApiInfoError, RuntimeApiInput, RuntimeApiTypeInfo,
     };
     pub use crate::utils::{EncodableValues, IntoEncodableValues};
 }
@@ -139,14 +298,18 @@ pub mod view_functions {
     //! - See [`ViewFunctionTypeInfo`] for the underlying trait which extracts the relevant information.
     //! - See [`ViewFunctionEntryInfo`] for a underlying trait which provides information about the available
     //!   View Functions.
+    //! - See [`encode_view_function_inputs_checked`] to report or reject View Functions which are
+    //!   marked as deprecated.
 
+    pub use crate::methods::deprecation::{Deprecation, DeprecationPolicy, DeprecationWarning};
     pub use crate::methods::view_function_decoder::{
         ViewFunctionDecodeError, decode_view_function_response,
         decode_view_function_response_with_info,
     };
     pub use crate::methods::view_function_encoder::{
         RUNTIME_API_NAME, ViewFunctionInputsEncodeError, encode_view_function_inputs,
-        encode_view_function_inputs_to, encode_view_function_inputs_with_info_to,
+        encode_view_function_inputs_checked, encode_view_function_inputs_to,
+        encode_view_function_inputs_with_info_to,
     };
     pub use crate::methods::view_function_type_info::{
         ViewFunctionEntry, ViewFunctionEntryInfo, ViewFunctionInfo, ViewFunctionInfoError,
@@ -219,6 +382,44 @@ pub mod legacy_types {
     }
 }
 
+pub mod metadata {
+    //! This module contains a wrapper around [`frame_metadata::RuntimeMetadata`] which can be
+    //! used to decode things from any supported metadata version with a single code path.
+    //!
+    //! - See [`AnyMetadata`] for the wrapper, which implements each of the `*TypeInfo` and
+    //!   `*EntryInfo` traits, as well as [`scale_type_resolver::TypeResolver`].
+    //! - See [`AnyTypeId`] for the type ID that it uses.
+    //! - See [`IndexedMetadata`] to precompute the information needed to decode things from some
+    //!   metadata, for fast lookups when decoding lots of things with it.
+    //! - See `legacy_metadata_to_v14` (requires the `legacy` and `std` features) to convert legacy
+    //!   (pre-V14) metadata into V14 metadata, given the legacy types for it.
+    //! - See [`diff_metadata`] to find the items which differ between two metadatas.
+    //! - See [`trim_metadata`] to trim V14+ metadata down to a [`MetadataSelection`] of pallets,
+    //!   calls, storage entries and Runtime APIs.
+    //! - See [`call_shape_hash`] and friends to compute structural hashes of items, to check
+    //!   whether code written against one runtime is compatible with another.
+
+    pub use crate::methods::any_metadata::{
+        AnyMetadata, AnyMetadataError, AnyTypeId, AnyTypeResolveError,
+    };
+    pub use crate::methods::indexed_metadata::IndexedMetadata;
+    pub use crate::methods::metadata_diff::{
+        IndexChange, ItemChange, ItemDiff, ItemIndex, ItemKind, MetadataDiff, diff_metadata,
+    };
+    pub use crate::methods::shape_hash::{
+        ShapeHash, ShapeHashError, call_shape_hash, constant_shape_hash, runtime_api_shape_hash,
+        storage_shape_hash, type_shape_hash, view_function_shape_hash,
+    };
+    #[cfg(all(feature = "legacy", feature = "std"))]
+    pub use crate::utils::{
+        LegacyMetadataConversionError, ToRuntimeMetadataV14, legacy_metadata_to_v14,
+        legacy_metadata_to_v14_any,
+    };
+    pub use crate::utils::{
+        MetadataSelection, TrimMetadataError, TrimmableMetadata, trim_metadata, trim_metadata_any,
+    };
+}
+
 pub mod helpers {
     //! Helper functions and types to assist with decoding.
     //!
@@ -227,8 +428,10 @@ pub mod helpers {
     //! - [`decode_with_error_tracing`] is like [`decode_with_visitor`], but
     //!   will use a tracing visitor (if the `error-tracing` feature is enabled) to provide more
     //!   information in the event that decoding fails.
+    //! - [`TypeDocsInfo`] hands back the docs for types and enum variants in a type registry.
 
     pub use crate::methods::Entry;
+    pub use crate::methods::type_docs_info::TypeDocsInfo;
 
     pub use crate::utils::{
         DecodableValues, DecodeErrorTrace, EncodableValues, IntoDecodableValues,
@@ -236,7 +439,8 @@ pub mod helpers {
     };
     #[cfg(feature = "legacy")]
     pub use crate::utils::{
-        ToTypeRegistry, type_registry_from_metadata, type_registry_from_metadata_any,
+        ToTypeRegistry, TypeRegistryFromMetadataError, type_registry_from_metadata,
+        type_registry_from_metadata_any,
     };
 
     /// An alias to [`scale_decode::visitor::decode_with_visitor`]. This can be used to decode the byte ranges
@@ -250,6 +454,10 @@ pub mod helpers {
 
 #[cfg(test)]
 mod test {
+    use crate::methods::constant_type_info::ConstantTypeInfo;
+    use crate::methods::custom_value_type_info::CustomValueTypeInfo;
+    use crate::methods::error_type_info::ErrorTypeInfo;
+    use crate::methods::event_type_info::EventTypeInfo;
     use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
     use crate::methods::runtime_api_type_info::RuntimeApiTypeInfo;
     use crate::methods::storage_type_info::StorageTypeInfo;
@@ -453,6 +661,26 @@ mod test {
         impls_trait!(frame_metadata::v15::RuntimeMetadataV15, ExtrinsicTypeInfo);
         impls_trait!(frame_metadata::v16::RuntimeMetadataV16, ExtrinsicTypeInfo);
 
+        impls_trait!(frame_metadata::v8::RuntimeMetadataV8, EventTypeInfo);
+        impls_trait!(frame_metadata::v9::RuntimeMetadataV9, EventTypeInfo);
+        impls_trait!(frame_metadata::v10::RuntimeMetadataV10, EventTypeInfo);
+        impls_trait!(frame_metadata::v11::RuntimeMetadataV11, EventTypeInfo);
+        impls_trait!(frame_metadata::v12::RuntimeMetadataV12, EventTypeInfo);
+        impls_trait!(frame_metadata::v13::RuntimeMetadataV13, EventTypeInfo);
+        impls_trait!(frame_metadata::v14::RuntimeMetadataV14, EventTypeInfo);
+        impls_trait!(frame_metadata::v15::RuntimeMetadataV15, EventTypeInfo);
+        impls_trait!(frame_metadata::v16::RuntimeMetadataV16, EventTypeInfo);
+
+        impls_trait!(frame_metadata::v8::RuntimeMetadataV8, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v9::RuntimeMetadataV9, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v10::RuntimeMetadataV10, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v11::RuntimeMetadataV11, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v12::RuntimeMetadataV12, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v13::RuntimeMetadataV13, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v14::RuntimeMetadataV14, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v15::RuntimeMetadataV15, ErrorTypeInfo);
+        impls_trait!(frame_metadata::v16::RuntimeMetadataV16, ErrorTypeInfo);
+
         impls_trait!(frame_metadata::v8::RuntimeMetadataV8, StorageTypeInfo);
         impls_trait!(frame_metadata::v9::RuntimeMetadataV9, StorageTypeInfo);
         impls_trait!(frame_metadata::v10::RuntimeMetadataV10, StorageTypeInfo);
@@ -475,6 +703,17 @@ mod test {
         impls_trait!(frame_metadata::v15::RuntimeMetadataV15, RuntimeApiTypeInfo);
         impls_trait!(frame_metadata::v16::RuntimeMetadataV16, RuntimeApiTypeInfo);
 
+        // AnyMetadata wraps all of the above and so implements every trait:
+        impls_trait!(crate::metadata::AnyMetadata<'static>, ExtrinsicTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, EventTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, ErrorTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, StorageTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, ConstantTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, CustomValueTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, ViewFunctionTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, RuntimeApiTypeInfo);
+        impls_trait!(crate::metadata::AnyMetadata<'static>, scale_type_resolver::TypeResolver);
+
         // This is a legacy trait and so only legacy metadata versions implement it:
         impls_trait!(frame_metadata::v8::RuntimeMetadataV8, ToTypeRegistry);
         impls_trait!(frame_metadata::v9::RuntimeMetadataV9, ToTypeRegistry);
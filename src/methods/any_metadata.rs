@@ -0,0 +1,1126 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::constant_type_info::{
+    ConstantDocsInfo, ConstantEntry, ConstantEntryInfo, ConstantInfo, ConstantInfoError,
+    ConstantTypeInfo,
+};
+use crate::methods::custom_value_type_info::{
+    CustomValue, CustomValueEntryInfo, CustomValueInfo, CustomValueInfoError, CustomValueTypeInfo,
+};
+use crate::methods::error_type_info::{ErrorInfo, ErrorInfoArg, ErrorInfoError, ErrorTypeInfo};
+use crate::methods::event_type_info::{
+    EventDocsInfo, EventInfo, EventInfoArg, EventInfoError, EventTypeInfo,
+};
+use crate::methods::extrinsic_type_info::{
+    ExtrinsicCallInfo, ExtrinsicCallInfoArg, ExtrinsicCallTypeInfo, ExtrinsicDocsInfo,
+    ExtrinsicExtensionInfo, ExtrinsicExtensionInfoArg, ExtrinsicInfoError, ExtrinsicSignatureInfo,
+    ExtrinsicTypeInfo,
+};
+use crate::methods::outer_enum_type_info::{OuterEnumInfo, OuterEnumInfoError, OuterEnumTypeInfo};
+use crate::methods::runtime_api_type_info::{
+    RuntimeApiDocsInfo, RuntimeApiEntry, RuntimeApiEntryInfo, RuntimeApiInfo, RuntimeApiInfoError,
+    RuntimeApiInput, RuntimeApiTypeInfo,
+};
+use crate::methods::storage_type_info::{
+    StorageDocsInfo, StorageEntry, StorageEntryInfo, StorageInfo, StorageInfoError, StorageKeyInfo,
+    StorageTypeInfo,
+};
+use crate::methods::type_docs_info::TypeDocsInfo;
+use crate::methods::view_function_type_info::{
+    ViewFunctionEntry, ViewFunctionEntryInfo, ViewFunctionInfo, ViewFunctionInfoError,
+    ViewFunctionInput, ViewFunctionTypeInfo,
+};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::vec::Vec;
+use frame_metadata::RuntimeMetadata;
+use scale_type_resolver::{
+    BitsOrderFormat, BitsStoreFormat, Field, FieldIter, PathIter, Primitive, ResolvedTypeVisitor,
+    TypeResolver, UnhandledKind, Variant, VariantIter,
+};
+
+#[cfg(feature = "legacy")]
+use scale_info_legacy::{LookupName, TypeRegistrySet};
+
+/// A type ID which can point to a type in either modern (V14+) or legacy (pre-V14) metadata.
+/// This is the type ID used by [`AnyMetadata`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnyTypeId {
+    /// A type ID in the type registry of modern (V14+) metadata.
+    Modern(u32),
+    /// A type name to look up in a set of legacy types.
+    #[cfg(feature = "legacy")]
+    Legacy(LookupName),
+}
+
+// This is required by scale_type_resolver::TypeId.
+impl Default for AnyTypeId {
+    fn default() -> Self {
+        AnyTypeId::Modern(0)
+    }
+}
+
+impl core::fmt::Display for AnyTypeId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AnyTypeId::Modern(id) => write!(f, "{id}"),
+            #[cfg(feature = "legacy")]
+            AnyTypeId::Legacy(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl From<u32> for AnyTypeId {
+    fn from(id: u32) -> Self {
+        AnyTypeId::Modern(id)
+    }
+}
+
+#[cfg(feature = "legacy")]
+impl From<LookupName> for AnyTypeId {
+    fn from(name: LookupName) -> Self {
+        AnyTypeId::Legacy(name)
+    }
+}
+
+/// An error constructing [`AnyMetadata`].
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AnyMetadataError {
+    #[error("Metadata version {0} is not supported")]
+    UnsupportedVersion(u32),
+    #[cfg(feature = "legacy")]
+    #[error("Cannot build a type registry from the metadata:\n\n{0}")]
+    CannotBuildTypeRegistry(crate::utils::TypeRegistryFromMetadataError),
+}
+
+/// An error resolving an [`AnyTypeId`] using [`AnyMetadata`].
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AnyTypeResolveError {
+    #[error("Cannot resolve modern type ID {0}: this metadata does not have a type registry")]
+    NoTypeRegistry(u32),
+    #[error("Cannot resolve modern type:\n\n{0:?}")]
+    Modern(scale_type_resolver::portable_registry::Error),
+    #[cfg(feature = "legacy")]
+    #[error("Cannot resolve legacy type {0}: no legacy types were provided")]
+    NoLegacyTypes(LookupName),
+    #[cfg(feature = "legacy")]
+    #[error("Cannot resolve legacy type:\n\n{0}")]
+    Legacy(scale_info_legacy::type_registry::TypeRegistryResolveError),
+}
+
+/// A wrapper around [`frame_metadata::RuntimeMetadata`] which implements all of the `*TypeInfo` and
+/// `*EntryInfo` traits in this crate, regardless of the metadata version (V8 to V16). It is also a
+/// [`TypeResolver`] for the [`AnyTypeId`]s that it hands back, and so it can be passed as both the
+/// info and the type resolver to functions like [`crate::extrinsics::decode_extrinsic`].
+///
+/// Legacy (pre-V14) metadata does not contain type information, and so [`AnyMetadata::with_legacy_types`]
+/// should be used to provide the types needed to decode things from it.
+/// Legacy types can also be provided for V14 metadata, which lacks Runtime API information.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::metadata::AnyMetadata;
+/// use frame_decode::extrinsics::decode_extrinsic;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// // The same code path works for modern and legacy metadata:
+/// fn decode_extrinsics(metadata: &AnyMetadata, extrinsics: &[Vec<u8>]) {
+///     for ext in extrinsics {
+///         let ext = decode_extrinsic(&mut &**ext, metadata, metadata).unwrap();
+///         println!("{}.{}", ext.pallet_name(), ext.call_name());
+///     }
+/// }
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let metadata = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap();
+/// let metadata = AnyMetadata::new(metadata).unwrap();
+///
+/// let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+/// let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+/// let extrinsics: Vec<Vec<u8>> = extrinsics_hex
+///     .iter()
+///     .map(|hex| hex::decode(hex.trim_start_matches("0x")).unwrap())
+///     .collect();
+///
+/// decode_extrinsics(&metadata, &extrinsics);
+///
+/// // Legacy metadata needs legacy types to decode with:
+/// let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+/// let metadata = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap();
+/// let legacy_types = frame_decode::legacy_types::polkadot::relay_chain();
+/// let metadata = AnyMetadata::new(metadata)
+///     .unwrap()
+///     .with_legacy_types(legacy_types.for_spec_version(30))
+///     .unwrap();
+///
+/// let extrinsics_bytes = std::fs::read("artifacts/exts_5000000_30.json").unwrap();
+/// let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+/// let extrinsics: Vec<Vec<u8>> = extrinsics_hex
+///     .iter()
+///     .map(|hex| hex::decode(hex.trim_start_matches("0x")).unwrap())
+///     .collect();
+///
+/// decode_extrinsics(&metadata, &extrinsics);
+/// ```
+#[derive(Debug)]
+pub struct AnyMetadata<'a> {
+    metadata: RuntimeMetadata,
+    #[cfg(feature = "legacy")]
+    legacy_types: Option<TypeRegistrySet<'a>>,
+    #[cfg(not(feature = "legacy"))]
+    _marker: core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> AnyMetadata<'a> {
+    /// Wrap some [`RuntimeMetadata`]. This will fail if the metadata version is not supported.
+    pub fn new(metadata: RuntimeMetadata) -> Result<Self, AnyMetadataError> {
+        let is_supported = match &metadata {
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V8(_)
+            | RuntimeMetadata::V9(_)
+            | RuntimeMetadata::V10(_)
+            | RuntimeMetadata::V11(_)
+            | RuntimeMetadata::V12(_)
+            | RuntimeMetadata::V13(_) => true,
+            RuntimeMetadata::V14(_) | RuntimeMetadata::V15(_) | RuntimeMetadata::V16(_) => true,
+            _ => false,
+        };
+
+        if !is_supported {
+            return Err(AnyMetadataError::UnsupportedVersion(metadata.version()));
+        }
+
+        Ok(AnyMetadata {
+            metadata,
+            #[cfg(feature = "legacy")]
+            legacy_types: None,
+            #[cfg(not(feature = "legacy"))]
+            _marker: core::marker::PhantomData,
+        })
+    }
+
+    /// Provide legacy types to use when resolving [`AnyTypeId::Legacy`] type IDs.
+    ///
+    /// For legacy (pre-V14) metadata, the types that can be extracted from the metadata itself (see
+    /// [`crate::helpers::type_registry_from_metadata`]) are added to these automatically.
+    #[cfg(feature = "legacy")]
+    pub fn with_legacy_types(
+        mut self,
+        legacy_types: TypeRegistrySet<'a>,
+    ) -> Result<Self, AnyMetadataError> {
+        let mut legacy_types = legacy_types;
+        let metadata_types = crate::utils::type_registry_from_metadata_any(&self.metadata)
+            .map_err(AnyMetadataError::CannotBuildTypeRegistry)?;
+        legacy_types.prepend(metadata_types);
+
+        self.legacy_types = Some(legacy_types);
+        Ok(self)
+    }
+
+    /// The underlying [`RuntimeMetadata`].
+    pub fn metadata(&self) -> &RuntimeMetadata {
+        &self.metadata
+    }
+
+    /// The legacy types, if any were provided.
+    #[cfg(feature = "legacy")]
+    pub fn legacy_types(&self) -> Option<&TypeRegistrySet<'a>> {
+        self.legacy_types.as_ref()
+    }
+
+    /// The metadata version.
+    pub fn version(&self) -> u32 {
+        self.metadata.version()
+    }
+
+    /// Return the underlying [`RuntimeMetadata`].
+    pub fn into_metadata(self) -> RuntimeMetadata {
+        self.metadata
+    }
+}
+
+// Dispatch to the underlying metadata, handing back type IDs for modern or legacy metadata
+// to the corresponding expression. The `unsupported` expression is never expected to be
+// hit, because we only construct `AnyMetadata` from supported metadata versions.
+macro_rules! with_metadata {
+    ($self:expr, |$m:ident| modern => $modern:expr, legacy => $legacy:expr, unsupported => $unsupported:expr $(,)?) => {
+        match &$self.metadata {
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V8($m) => $legacy,
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V9($m) => $legacy,
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V10($m) => $legacy,
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V11($m) => $legacy,
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V12($m) => $legacy,
+            #[cfg(feature = "legacy")]
+            RuntimeMetadata::V13($m) => $legacy,
+            RuntimeMetadata::V14($m) => $modern,
+            RuntimeMetadata::V15($m) => $modern,
+            RuntimeMetadata::V16($m) => $modern,
+            _ => $unsupported,
+        }
+    };
+}
+
+impl ExtrinsicTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn extrinsic_call_info_by_index(
+        &self,
+        pallet_index: u8,
+        call_index: u8,
+    ) -> Result<ExtrinsicCallInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_call_info_by_index(pallet_index, call_index).map(|i| map_call_info(i, AnyTypeId::Modern)),
+            legacy => m.extrinsic_call_info_by_index(pallet_index, call_index).map(|i| map_call_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(ExtrinsicInfoError::PalletNotFound { index: pallet_index }),
+        )
+    }
+
+    fn extrinsic_call_info_by_name(
+        &self,
+        pallet_name: &str,
+        call_name: &str,
+    ) -> Result<ExtrinsicCallInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_call_info_by_name(pallet_name, call_name).map(|i| map_call_info(i, AnyTypeId::Modern)),
+            legacy => m.extrinsic_call_info_by_name(pallet_name, call_name).map(|i| map_call_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(ExtrinsicInfoError::PalletNotFoundByName { name: Cow::Owned(pallet_name.to_owned()) }),
+        )
+    }
+
+    fn extrinsic_signature_info(
+        &self,
+    ) -> Result<ExtrinsicSignatureInfo<Self::TypeId>, ExtrinsicInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_signature_info().map(|i| map_signature_info(i, AnyTypeId::Modern)),
+            legacy => m.extrinsic_signature_info().map(|i| map_signature_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(ExtrinsicInfoError::ExtrinsicSignatureTypeNotFound),
+        )
+    }
+
+    fn extrinsic_extension_info(
+        &self,
+        extension_version: Option<u8>,
+    ) -> Result<ExtrinsicExtensionInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_extension_info(extension_version).map(|i| map_extension_info(i, AnyTypeId::Modern)),
+            legacy => m.extrinsic_extension_info(extension_version).map(|i| map_extension_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(ExtrinsicInfoError::ExtrinsicSignatureTypeNotFound),
+        )
+    }
+
+    fn extrinsic_extension_version_info(
+        &self,
+    ) -> Result<impl Iterator<Item = u8>, ExtrinsicInfoError<'_>> {
+        // Each version hands back a different iterator type, so we collect them.
+        let versions: Vec<u8> = with_metadata!(self, |m|
+            modern => m.extrinsic_extension_version_info()?.collect(),
+            legacy => m.extrinsic_extension_version_info()?.collect(),
+            unsupported => Vec::new(),
+        );
+        Ok(versions.into_iter())
+    }
+}
+
+impl ExtrinsicCallTypeInfo for AnyMetadata<'_> {
+    fn extrinsic_call_type_id(&self) -> Option<Self::TypeId> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_call_type_id().map(AnyTypeId::Modern),
+            legacy => m.extrinsic_call_type_id().map(AnyTypeId::Legacy),
+            unsupported => None,
+        )
+    }
+}
+
+impl StorageTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn storage_info(
+        &self,
+        pallet_name: &str,
+        storage_entry: &str,
+    ) -> Result<StorageInfo<'_, Self::TypeId>, StorageInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.storage_info(pallet_name, storage_entry).map(|i| map_storage_info(i, AnyTypeId::Modern)),
+            legacy => m.storage_info(pallet_name, storage_entry).map(|i| map_storage_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(StorageInfoError::PalletNotFound { pallet_name: pallet_name.to_owned() }),
+        )
+    }
+}
+
+impl StorageEntryInfo for AnyMetadata<'_> {
+    fn storage_entries(&self) -> impl Iterator<Item = StorageEntry<'_>> {
+        let entries: Vec<_> = with_metadata!(self, |m|
+            modern => m.storage_entries().collect(),
+            legacy => m.storage_entries().collect(),
+            unsupported => Vec::new(),
+        );
+        entries.into_iter()
+    }
+}
+
+impl ConstantTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn constant_info(
+        &self,
+        pallet_name: &str,
+        constant_name: &str,
+    ) -> Result<ConstantInfo<'_, Self::TypeId>, ConstantInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.constant_info(pallet_name, constant_name).map(|i| ConstantInfo {
+                bytes: i.bytes,
+                type_id: AnyTypeId::Modern(i.type_id),
+                deprecation: i.deprecation,
+            }),
+            legacy => m.constant_info(pallet_name, constant_name).map(|i| ConstantInfo {
+                bytes: i.bytes,
+                type_id: AnyTypeId::Legacy(i.type_id),
+                deprecation: i.deprecation,
+            }),
+            unsupported => Err(ConstantInfoError::PalletNotFound { pallet_name: pallet_name.to_owned() }),
+        )
+    }
+}
+
+impl ConstantEntryInfo for AnyMetadata<'_> {
+    fn constant_entries(&self) -> impl Iterator<Item = ConstantEntry<'_>> {
+        let entries: Vec<_> = with_metadata!(self, |m|
+            modern => m.constant_entries().collect(),
+            legacy => m.constant_entries().collect(),
+            unsupported => Vec::new(),
+        );
+        entries.into_iter()
+    }
+}
+
+impl EventTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn event_info_by_index(
+        &self,
+        pallet_index: u8,
+        event_index: u8,
+    ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.event_info_by_index(pallet_index, event_index).map(|i| map_event_info(i, AnyTypeId::Modern)),
+            legacy => m.event_info_by_index(pallet_index, event_index).map(|i| map_event_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(EventInfoError::PalletNotFound { index: pallet_index }),
+        )
+    }
+
+    fn event_info_by_name(
+        &self,
+        pallet_name: &str,
+        event_name: &str,
+    ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.event_info_by_name(pallet_name, event_name).map(|i| map_event_info(i, AnyTypeId::Modern)),
+            legacy => m.event_info_by_name(pallet_name, event_name).map(|i| map_event_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(EventInfoError::PalletNotFoundByName { name: Cow::Owned(pallet_name.to_owned()) }),
+        )
+    }
+}
+
+impl ErrorTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn error_info_by_index(
+        &self,
+        pallet_index: u8,
+        error_index: u8,
+    ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.error_info_by_index(pallet_index, error_index).map(|i| map_error_info(i, AnyTypeId::Modern)),
+            legacy => m.error_info_by_index(pallet_index, error_index).map(|i| map_error_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(ErrorInfoError::PalletNotFound { index: pallet_index }),
+        )
+    }
+
+    fn error_info_by_name(
+        &self,
+        pallet_name: &str,
+        error_name: &str,
+    ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>> {
+        with_metadata!(self, |m|
+            modern => m.error_info_by_name(pallet_name, error_name).map(|i| map_error_info(i, AnyTypeId::Modern)),
+            legacy => m.error_info_by_name(pallet_name, error_name).map(|i| map_error_info(i, AnyTypeId::Legacy)),
+            unsupported => Err(ErrorInfoError::PalletNotFoundByName { name: Cow::Owned(pallet_name.to_owned()) }),
+        )
+    }
+}
+
+impl RuntimeApiTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn runtime_api_info(
+        &self,
+        trait_name: &str,
+        method_name: &str,
+    ) -> Result<RuntimeApiInfo<'_, Self::TypeId>, RuntimeApiInfoError<'_>> {
+        // Runtime APIs are in the metadata from V15. Prior to this, we
+        // look for them in any legacy types that have been provided.
+        match &self.metadata {
+            RuntimeMetadata::V15(m) => m
+                .runtime_api_info(trait_name, method_name)
+                .map(|i| map_runtime_api_info(i, AnyTypeId::Modern)),
+            RuntimeMetadata::V16(m) => m
+                .runtime_api_info(trait_name, method_name)
+                .map(|i| map_runtime_api_info(i, AnyTypeId::Modern)),
+            #[cfg(feature = "legacy")]
+            _ => match &self.legacy_types {
+                Some(types) => types
+                    .runtime_api_info(trait_name, method_name)
+                    .map(|i| map_runtime_api_info(i, AnyTypeId::Legacy)),
+                None => Err(RuntimeApiInfoError::TraitNotFound {
+                    trait_name: trait_name.to_owned(),
+                }),
+            },
+            #[cfg(not(feature = "legacy"))]
+            _ => Err(RuntimeApiInfoError::TraitNotFound {
+                trait_name: trait_name.to_owned(),
+            }),
+        }
+    }
+}
+
+impl RuntimeApiEntryInfo for AnyMetadata<'_> {
+    fn runtime_api_entries(&self) -> impl Iterator<Item = RuntimeApiEntry<'_>> {
+        let entries: Vec<_> = match &self.metadata {
+            RuntimeMetadata::V15(m) => m.runtime_api_entries().collect(),
+            RuntimeMetadata::V16(m) => m.runtime_api_entries().collect(),
+            #[cfg(feature = "legacy")]
+            _ => match &self.legacy_types {
+                Some(types) => types.runtime_api_entries().collect(),
+                None => Vec::new(),
+            },
+            #[cfg(not(feature = "legacy"))]
+            _ => Vec::new(),
+        };
+        entries.into_iter()
+    }
+}
+
+impl ViewFunctionTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn view_function_info(
+        &self,
+        pallet_name: &str,
+        function_name: &str,
+    ) -> Result<ViewFunctionInfo<'_, Self::TypeId>, ViewFunctionInfoError<'_>> {
+        // View Functions only exist from V16 metadata.
+        match &self.metadata {
+            RuntimeMetadata::V16(m) => m
+                .view_function_info(pallet_name, function_name)
+                .map(|i| map_view_function_info(i, AnyTypeId::Modern)),
+            _ => Err(ViewFunctionInfoError::PalletNotFound {
+                pallet_name: pallet_name.to_owned(),
+            }),
+        }
+    }
+}
+
+impl ViewFunctionEntryInfo for AnyMetadata<'_> {
+    fn view_function_entries(&self) -> impl Iterator<Item = ViewFunctionEntry<'_>> {
+        let entries: Vec<_> = match &self.metadata {
+            RuntimeMetadata::V16(m) => m.view_function_entries().collect(),
+            _ => Vec::new(),
+        };
+        entries.into_iter()
+    }
+}
+
+impl CustomValueTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn custom_value_info(
+        &self,
+        name: &str,
+    ) -> Result<CustomValueInfo<'_, Self::TypeId>, CustomValueInfoError> {
+        // Custom values only exist from V15 metadata.
+        let info = match &self.metadata {
+            RuntimeMetadata::V15(m) => m.custom_value_info(name)?,
+            RuntimeMetadata::V16(m) => m.custom_value_info(name)?,
+            _ => {
+                return Err(CustomValueInfoError {
+                    not_found: name.to_owned(),
+                });
+            }
+        };
+        Ok(CustomValueInfo {
+            bytes: info.bytes,
+            type_id: AnyTypeId::Modern(info.type_id),
+        })
+    }
+}
+
+impl CustomValueEntryInfo for AnyMetadata<'_> {
+    fn custom_values(&self) -> impl Iterator<Item = CustomValue<'_>> {
+        let entries: Vec<_> = match &self.metadata {
+            RuntimeMetadata::V15(m) => m.custom_values().collect(),
+            RuntimeMetadata::V16(m) => m.custom_values().collect(),
+            _ => Vec::new(),
+        };
+        entries.into_iter()
+    }
+}
+
+impl OuterEnumTypeInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn outer_enum_info(&self) -> Result<OuterEnumInfo<Self::TypeId>, OuterEnumInfoError> {
+        // Outer enums only exist from V15 metadata.
+        let info = match &self.metadata {
+            RuntimeMetadata::V15(m) => m.outer_enum_info()?,
+            RuntimeMetadata::V16(m) => m.outer_enum_info()?,
+            _ => {
+                return Err(OuterEnumInfoError::NotAvailable {
+                    metadata_version: self.version(),
+                });
+            }
+        };
+        Ok(info.map_type_id(AnyTypeId::Modern))
+    }
+}
+
+impl ExtrinsicDocsInfo for AnyMetadata<'_> {
+    fn extrinsic_call_docs(&self, pallet_name: &str, call_name: &str) -> Option<Vec<&str>> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_call_docs(pallet_name, call_name),
+            legacy => m.extrinsic_call_docs(pallet_name, call_name),
+            unsupported => None,
+        )
+    }
+
+    fn extrinsic_call_arg_docs(
+        &self,
+        pallet_name: &str,
+        call_name: &str,
+        arg_name: &str,
+    ) -> Option<Vec<&str>> {
+        with_metadata!(self, |m|
+            modern => m.extrinsic_call_arg_docs(pallet_name, call_name, arg_name),
+            legacy => m.extrinsic_call_arg_docs(pallet_name, call_name, arg_name),
+            unsupported => None,
+        )
+    }
+}
+
+impl EventDocsInfo for AnyMetadata<'_> {
+    fn event_docs(&self, pallet_name: &str, event_name: &str) -> Option<Vec<&str>> {
+        with_metadata!(self, |m|
+            modern => m.event_docs(pallet_name, event_name),
+            legacy => m.event_docs(pallet_name, event_name),
+            unsupported => None,
+        )
+    }
+}
+
+impl StorageDocsInfo for AnyMetadata<'_> {
+    fn storage_docs(&self, pallet_name: &str, storage_entry: &str) -> Option<Vec<&str>> {
+        with_metadata!(self, |m|
+            modern => m.storage_docs(pallet_name, storage_entry),
+            legacy => m.storage_docs(pallet_name, storage_entry),
+            unsupported => None,
+        )
+    }
+}
+
+impl ConstantDocsInfo for AnyMetadata<'_> {
+    fn constant_docs(&self, pallet_name: &str, constant_name: &str) -> Option<Vec<&str>> {
+        with_metadata!(self, |m|
+            modern => m.constant_docs(pallet_name, constant_name),
+            legacy => m.constant_docs(pallet_name, constant_name),
+            unsupported => None,
+        )
+    }
+}
+
+impl RuntimeApiDocsInfo for AnyMetadata<'_> {
+    // Legacy types carry no docs, so only V15+ metadata has any to hand back.
+    fn runtime_api_trait_docs(&self, trait_name: &str) -> Option<Vec<&str>> {
+        match &self.metadata {
+            RuntimeMetadata::V15(m) => m.runtime_api_trait_docs(trait_name),
+            RuntimeMetadata::V16(m) => m.runtime_api_trait_docs(trait_name),
+            _ => None,
+        }
+    }
+
+    fn runtime_api_docs(&self, trait_name: &str, method_name: &str) -> Option<Vec<&str>> {
+        match &self.metadata {
+            RuntimeMetadata::V15(m) => m.runtime_api_docs(trait_name, method_name),
+            RuntimeMetadata::V16(m) => m.runtime_api_docs(trait_name, method_name),
+            _ => None,
+        }
+    }
+}
+
+impl TypeDocsInfo for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+
+    fn type_docs(&self, type_id: Self::TypeId) -> Option<Vec<&str>> {
+        match type_id {
+            AnyTypeId::Modern(id) => self.modern_types()?.type_docs(id),
+            // Legacy type registries carry no docs.
+            #[cfg(feature = "legacy")]
+            AnyTypeId::Legacy(_) => None,
+        }
+    }
+
+    fn variant_docs(&self, type_id: Self::TypeId, variant_name: &str) -> Option<Vec<&str>> {
+        match type_id {
+            AnyTypeId::Modern(id) => self.modern_types()?.variant_docs(id, variant_name),
+            // Legacy type registries carry no docs.
+            #[cfg(feature = "legacy")]
+            AnyTypeId::Legacy(_) => None,
+        }
+    }
+}
+
+impl AnyMetadata<'_> {
+    fn modern_types(&self) -> Option<&scale_info::PortableRegistry> {
+        match &self.metadata {
+            RuntimeMetadata::V14(m) => Some(&m.types),
+            RuntimeMetadata::V15(m) => Some(&m.types),
+            RuntimeMetadata::V16(m) => Some(&m.types),
+            _ => None,
+        }
+    }
+}
+
+impl TypeResolver for AnyMetadata<'_> {
+    type TypeId = AnyTypeId;
+    type Error = AnyTypeResolveError;
+
+    fn resolve_type<'this, V: ResolvedTypeVisitor<'this, TypeId = Self::TypeId>>(
+        &'this self,
+        type_id: Self::TypeId,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match type_id {
+            AnyTypeId::Modern(id) => {
+                let Some(types) = self.modern_types() else {
+                    return Err(AnyTypeResolveError::NoTypeRegistry(id));
+                };
+                types
+                    .resolve_type(id, MapTypeIds::new(visitor, AnyTypeId::Modern))
+                    .map_err(AnyTypeResolveError::Modern)
+            }
+            #[cfg(feature = "legacy")]
+            AnyTypeId::Legacy(name) => {
+                let Some(types) = &self.legacy_types else {
+                    return Err(AnyTypeResolveError::NoLegacyTypes(name));
+                };
+                types
+                    .resolve_type(name, MapTypeIds::new(visitor, AnyTypeId::Legacy))
+                    .map_err(AnyTypeResolveError::Legacy)
+            }
+        }
+    }
+}
+
+// A visitor which wraps another visitor that expects `AnyTypeId`s, and maps the
+// type IDs from some specific type resolver into these.
+struct MapTypeIds<V, TypeId> {
+    visitor: V,
+    f: fn(TypeId) -> AnyTypeId,
+}
+
+impl<V, TypeId> MapTypeIds<V, TypeId> {
+    fn new(visitor: V, f: fn(TypeId) -> AnyTypeId) -> Self {
+        MapTypeIds { visitor, f }
+    }
+}
+
+impl<'resolver, V, TypeId> ResolvedTypeVisitor<'resolver> for MapTypeIds<V, TypeId>
+where
+    V: ResolvedTypeVisitor<'resolver, TypeId = AnyTypeId>,
+    TypeId: scale_type_resolver::TypeId + 'static,
+{
+    type TypeId = TypeId;
+    type Value = V::Value;
+
+    fn visit_unhandled(self, kind: UnhandledKind) -> Self::Value {
+        self.visitor.visit_unhandled(kind)
+    }
+
+    fn visit_not_found(self) -> Self::Value {
+        self.visitor.visit_not_found()
+    }
+
+    fn visit_composite<Path, Fields>(self, path: Path, fields: Fields) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+        Fields: FieldIter<'resolver, Self::TypeId>,
+    {
+        let f = self.f;
+        let fields = fields.map(move |field| Field {
+            name: field.name,
+            id: f(field.id),
+        });
+        self.visitor.visit_composite(path, fields)
+    }
+
+    fn visit_variant<Path, Fields, Var>(self, path: Path, variants: Var) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+        Fields: FieldIter<'resolver, Self::TypeId>,
+        Var: VariantIter<'resolver, Fields>,
+    {
+        let f = self.f;
+        let variants = variants.map(move |variant| Variant {
+            index: variant.index,
+            name: variant.name,
+            fields: variant.fields.map(move |field| Field {
+                name: field.name,
+                id: f(field.id),
+            }),
+        });
+        self.visitor.visit_variant(path, variants)
+    }
+
+    fn visit_sequence<Path>(self, path: Path, type_id: Self::TypeId) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+    {
+        self.visitor.visit_sequence(path, (self.f)(type_id))
+    }
+
+    fn visit_array(self, type_id: Self::TypeId, len: usize) -> Self::Value {
+        self.visitor.visit_array((self.f)(type_id), len)
+    }
+
+    fn visit_tuple<TypeIds>(self, type_ids: TypeIds) -> Self::Value
+    where
+        TypeIds: ExactSizeIterator<Item = Self::TypeId>,
+    {
+        self.visitor.visit_tuple(type_ids.map(self.f))
+    }
+
+    fn visit_primitive(self, primitive: Primitive) -> Self::Value {
+        self.visitor.visit_primitive(primitive)
+    }
+
+    fn visit_compact(self, type_id: Self::TypeId) -> Self::Value {
+        self.visitor.visit_compact((self.f)(type_id))
+    }
+
+    fn visit_bit_sequence(
+        self,
+        store_format: BitsStoreFormat,
+        order_format: BitsOrderFormat,
+    ) -> Self::Value {
+        self.visitor.visit_bit_sequence(store_format, order_format)
+    }
+}
+
+fn map_call_info<TypeId>(
+    info: ExtrinsicCallInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> ExtrinsicCallInfo<'_, AnyTypeId> {
+    ExtrinsicCallInfo {
+        pallet_index: info.pallet_index,
+        call_index: info.call_index,
+        pallet_name: info.pallet_name,
+        call_name: info.call_name,
+        args: info
+            .args
+            .into_iter()
+            .map(|arg| ExtrinsicCallInfoArg {
+                name: arg.name,
+                id: f(arg.id),
+            })
+            .collect(),
+        deprecation: info.deprecation,
+    }
+}
+
+fn map_signature_info<TypeId>(
+    info: ExtrinsicSignatureInfo<TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> ExtrinsicSignatureInfo<AnyTypeId> {
+    ExtrinsicSignatureInfo {
+        address_id: f(info.address_id),
+        signature_id: f(info.signature_id),
+    }
+}
+
+fn map_extension_info<TypeId>(
+    info: ExtrinsicExtensionInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> ExtrinsicExtensionInfo<'_, AnyTypeId> {
+    ExtrinsicExtensionInfo {
+        extension_ids: info
+            .extension_ids
+            .into_iter()
+            .map(|arg| ExtrinsicExtensionInfoArg {
+                name: arg.name,
+                id: f(arg.id),
+                implicit_id: f(arg.implicit_id),
+            })
+            .collect(),
+    }
+}
+
+fn map_storage_info<TypeId: Clone>(
+    info: StorageInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> StorageInfo<'_, AnyTypeId> {
+    StorageInfo {
+        keys: info
+            .keys
+            .iter()
+            .map(|k| StorageKeyInfo {
+                hasher: k.hasher,
+                key_id: f(k.key_id.clone()),
+            })
+            .collect(),
+        value_id: f(info.value_id),
+        default_value: info.default_value,
+        use_old_v9_storage_hashers: info.use_old_v9_storage_hashers,
+        deprecation: info.deprecation,
+    }
+}
+
+fn map_event_info<TypeId>(
+    info: EventInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> EventInfo<'_, AnyTypeId> {
+    EventInfo {
+        pallet_index: info.pallet_index,
+        event_index: info.event_index,
+        pallet_name: info.pallet_name,
+        event_name: info.event_name,
+        args: info
+            .args
+            .into_iter()
+            .map(|arg| EventInfoArg {
+                name: arg.name,
+                id: f(arg.id),
+            })
+            .collect(),
+    }
+}
+
+fn map_error_info<TypeId>(
+    info: ErrorInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> ErrorInfo<'_, AnyTypeId> {
+    ErrorInfo {
+        pallet_index: info.pallet_index,
+        error_index: info.error_index,
+        pallet_name: info.pallet_name,
+        error_name: info.error_name,
+        args: info
+            .args
+            .into_iter()
+            .map(|arg| ErrorInfoArg {
+                name: arg.name,
+                id: f(arg.id),
+            })
+            .collect(),
+        docs: info.docs,
+    }
+}
+
+fn map_runtime_api_info<TypeId: Clone>(
+    info: RuntimeApiInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> RuntimeApiInfo<'_, AnyTypeId> {
+    RuntimeApiInfo {
+        inputs: info
+            .inputs
+            .iter()
+            .map(|input| RuntimeApiInput {
+                name: input.name.clone(),
+                id: f(input.id.clone()),
+            })
+            .collect(),
+        output_id: f(info.output_id),
+        deprecation: info.deprecation,
+    }
+}
+
+fn map_view_function_info<TypeId: Clone>(
+    info: ViewFunctionInfo<'_, TypeId>,
+    f: fn(TypeId) -> AnyTypeId,
+) -> ViewFunctionInfo<'_, AnyTypeId> {
+    ViewFunctionInfo {
+        query_id: info.query_id,
+        inputs: info
+            .inputs
+            .iter()
+            .map(|input| ViewFunctionInput {
+                name: input.name.clone(),
+                id: f(input.id.clone()),
+            })
+            .collect(),
+        output_id: f(info.output_id),
+        deprecation: info.deprecation,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::event_decoder::decode_event;
+    use parity_scale_codec::{Decode, Encode};
+    use scale_value::scale::ValueVisitor;
+
+    fn load_metadata(path: &str) -> RuntimeMetadata {
+        let metadata_bytes = std::fs::read(path).unwrap();
+        RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+    }
+
+    // Encode and then decode a `Balances.Transfer` event, which exists in both metadatas.
+    fn decode_transfer(metadata: &AnyMetadata) {
+        let info = metadata.event_info_by_name("Balances", "Transfer").unwrap();
+        let mut bytes = vec![info.pallet_index, info.event_index];
+        [1u8; 32].encode_to(&mut bytes);
+        [2u8; 32].encode_to(&mut bytes);
+        1000u128.encode_to(&mut bytes);
+
+        let event = decode_event(&mut &*bytes, metadata, metadata).unwrap();
+        assert_eq!(event.pallet_name(), "Balances");
+        assert_eq!(event.event_name(), "Transfer");
+        assert_eq!(event.range(), 0..bytes.len());
+
+        let amount = event.fields().last().unwrap();
+        let amount = scale_decode::visitor::decode_with_visitor(
+            &mut &bytes[amount.range()],
+            amount.ty().clone(),
+            metadata,
+            ValueVisitor::new(),
+        )
+        .unwrap();
+        assert_eq!(amount.as_u128(), Some(1000));
+    }
+
+    #[test]
+    fn decode_with_modern_metadata() {
+        let metadata = load_metadata("artifacts/metadata_10000000_9180.scale");
+        let metadata = AnyMetadata::new(metadata).unwrap();
+        assert_eq!(metadata.version(), 14);
+
+        decode_transfer(&metadata);
+
+        let info = metadata.storage_info("System", "Account").unwrap();
+        assert!(matches!(info.value_id, AnyTypeId::Modern(_)));
+    }
+
+    #[test]
+    fn decode_with_legacy_metadata() {
+        let metadata = load_metadata("artifacts/metadata_5000000_30.scale");
+        let metadata = AnyMetadata::new(metadata).unwrap();
+        assert_eq!(metadata.version(), 12);
+
+        // Without legacy types we can get info but can't resolve the types:
+        let info = metadata.storage_info("System", "Account").unwrap();
+        let AnyTypeId::Legacy(name) = info.value_id.clone() else {
+            panic!("Expected legacy type ID");
+        };
+        let err = scale_decode::visitor::decode_with_visitor(
+            &mut &[0u8; 64][..],
+            info.value_id,
+            &metadata,
+            ValueVisitor::new(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(&name.to_string()));
+
+        let legacy_types = crate::legacy_types::polkadot::relay_chain();
+        let metadata = metadata
+            .with_legacy_types(legacy_types.for_spec_version(30))
+            .unwrap();
+
+        decode_transfer(&metadata);
+
+        let constant = metadata
+            .constant_info("Balances", "ExistentialDeposit")
+            .unwrap();
+        let value = scale_decode::visitor::decode_with_visitor(
+            &mut &*constant.bytes,
+            constant.type_id,
+            &metadata,
+            ValueVisitor::new(),
+        )
+        .unwrap();
+        assert!(value.as_u128().is_some());
+    }
+
+    #[test]
+    fn docs_are_available_for_modern_and_legacy_metadata() {
+        for path in [
+            "artifacts/metadata_10000000_9180.scale",
+            "artifacts/metadata_5000000_30.scale",
+        ] {
+            let metadata = AnyMetadata::new(load_metadata(path)).unwrap();
+
+            let call_docs = metadata
+                .extrinsic_call_docs("Balances", "transfer")
+                .unwrap();
+            assert!(
+                call_docs
+                    .iter()
+                    .any(|d| d.contains("Transfer some liquid free balance"))
+            );
+            let event_docs = metadata.event_docs("Balances", "Transfer").unwrap();
+            assert!(!event_docs.is_empty());
+            let storage_docs = metadata.storage_docs("System", "Account").unwrap();
+            assert!(!storage_docs.is_empty());
+            let constant_docs = metadata
+                .constant_docs("Balances", "ExistentialDeposit")
+                .unwrap();
+            assert!(!constant_docs.is_empty());
+
+            assert!(
+                metadata
+                    .extrinsic_call_arg_docs("Balances", "transfer", "dest")
+                    .is_some()
+            );
+            assert!(
+                metadata
+                    .extrinsic_call_arg_docs("Balances", "transfer", "nope")
+                    .is_none()
+            );
+            assert!(metadata.extrinsic_call_docs("Balances", "nope").is_none());
+            assert!(metadata.storage_docs("Nope", "Account").is_none());
+        }
+
+        // Variant docs can also be looked up in the type registry:
+        let metadata = load_metadata("artifacts/metadata_10000000_9180.scale");
+        let metadata = AnyMetadata::new(metadata).unwrap();
+        let RuntimeMetadata::V14(m) = metadata.metadata() else {
+            panic!("Expected V14 metadata");
+        };
+        let balances = m.pallets.iter().find(|p| p.name == "Balances").unwrap();
+        let call_ty = AnyTypeId::Modern(balances.calls.as_ref().unwrap().ty.id);
+        assert_eq!(
+            metadata.variant_docs(call_ty, "transfer"),
+            metadata.extrinsic_call_docs("Balances", "transfer")
+        );
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::extrinsic_decoder::NamedArg;
+use crate::methods::extrinsic_type_info::{
+    ExtrinsicCallInfo, ExtrinsicInfoError, ExtrinsicTypeInfo,
+};
+use crate::utils::{DecodeErrorTrace, decode_with_error_tracing};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use parity_scale_codec::Decode;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode call data bytes.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CallDataDecodeError {
+    #[error("Cannot get call info:\n\n{0}")]
+    CannotGetInfo(ExtrinsicInfoError<'static>),
+    #[error("Cannot decode pallet index byte:\n\n{0}")]
+    CannotDecodePalletIndex(parity_scale_codec::Error),
+    #[error("Cannot decode call index byte:\n\n{0}")]
+    CannotDecodeCallIndex(parity_scale_codec::Error),
+    #[error(
+        "Cannot decode call data for argument {argument_name} in {pallet_name}.{call_name}:\n\n{reason}"
+    )]
+    CannotDecodeCallData {
+        pallet_name: String,
+        call_name: String,
+        argument_name: String,
+        reason: DecodeErrorTrace,
+    },
+}
+
+/// An owned variant of some [`CallData`].
+pub type CallDataOwned<TypeId> = CallData<'static, TypeId>;
+
+/// Information about some call data.
+#[derive(Clone, Debug)]
+pub struct CallData<'info, TypeId> {
+    pub(crate) pallet_name: Cow<'info, str>,
+    pub(crate) pallet_index: u8,
+    pub(crate) pallet_index_idx: u32,
+    pub(crate) call_name: Cow<'info, str>,
+    pub(crate) call_index: u8,
+    pub(crate) args: Vec<NamedArg<'info, TypeId>>,
+}
+
+impl<'info, TypeId> CallData<'info, TypeId> {
+    /// Take ownership of the call data, so that it no longer references
+    /// the call info.
+    pub fn into_owned(self) -> CallDataOwned<TypeId> {
+        CallData {
+            pallet_name: Cow::Owned(self.pallet_name.into_owned()),
+            pallet_index: self.pallet_index,
+            pallet_index_idx: self.pallet_index_idx,
+            call_name: Cow::Owned(self.call_name.into_owned()),
+            call_index: self.call_index,
+            args: self.args.into_iter().map(|e| e.into_owned()).collect(),
+        }
+    }
+
+    /// The name of the pallet that this call is calling into.
+    pub fn pallet_name(&self) -> &str {
+        &self.pallet_name
+    }
+
+    /// The index of the pallet that this call is calling into.
+    pub fn pallet_index(&self) -> u8 {
+        self.pallet_index
+    }
+
+    /// The name of the call.
+    pub fn call_name(&self) -> &str {
+        &self.call_name
+    }
+
+    /// The index of the call.
+    pub fn call_index(&self) -> u8 {
+        self.call_index
+    }
+
+    /// Iterate over the call argument names and types.
+    pub fn args(&self) -> impl ExactSizeIterator<Item = &NamedArg<'info, TypeId>> {
+        self.args.iter()
+    }
+
+    /// Return a range denoting the call data bytes. This includes the pallet index and
+    /// call index bytes and then any encoded arguments for the call.
+    pub fn range(&self) -> Range<usize> {
+        let start = self.pallet_index_idx as usize;
+        let end = self.args_range().end;
+        Range { start, end }
+    }
+
+    /// Return a range denoting the arguments given to the call. This does *not* include
+    /// the pallet index and call index bytes.
+    pub fn args_range(&self) -> Range<usize> {
+        let start = (self.pallet_index_idx + 2) as usize;
+        let end = self
+            .args()
+            .map(|a| a.range.end as usize)
+            .max()
+            .unwrap_or(start);
+
+        Range { start, end }
+    }
+
+    /// Map the argument type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> CallData<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        CallData {
+            pallet_name: self.pallet_name,
+            pallet_index: self.pallet_index,
+            pallet_index_idx: self.pallet_index_idx,
+            call_name: self.call_name,
+            call_index: self.call_index,
+            args: self
+                .args
+                .into_iter()
+                .map(|s| s.map_type_id(&mut f))
+                .collect(),
+        }
+    }
+}
+
+/// Decode some call data (ie the bytes `pallet_index ++ call_index ++ args`), returning
+/// information about it. The byte ranges handed back are relative to the start of the bytes
+/// that the cursor points to when this is called.
+///
+/// This is the inverse of [`crate::extrinsics::encode_call_data`], and is useful for decoding
+/// call data found outside of an extrinsic, for instance in multisig operations, preimages
+/// or scheduler agendas. Use [`call_hash`] to compute the hash that these are often
+/// referred to by.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::extrinsics::{call_hash, decode_call_data, ExtrinsicTypeInfo};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::{Compact, Decode, Encode};
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// // Some call data to decode:
+/// let call_info = metadata.extrinsic_call_info_by_name("Timestamp", "set").unwrap();
+/// let mut call_bytes = vec![call_info.pallet_index, call_info.call_index];
+/// Compact(123u64).encode_to(&mut call_bytes);
+///
+/// // Decode it, returning information about the call and where each argument is:
+/// let call = decode_call_data(&mut &*call_bytes, &metadata, &metadata.types).unwrap();
+///
+/// assert_eq!(call.pallet_name(), "Timestamp");
+/// assert_eq!(call.call_name(), "set");
+/// assert_eq!(call.args().len(), 1);
+/// assert_eq!(call.range(), 0..call_bytes.len());
+///
+/// // Hash it, as is done when referring to calls in multisigs and preimages:
+/// let hash = call_hash(&call_bytes[call.range()]);
+/// ```
+pub fn decode_call_data<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<CallData<'info, Info::TypeId>, CallDataDecodeError>
+where
+    Info: ExtrinsicTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_call_data_at_offset(cursor, 0, info, type_resolver)
+}
+
+/// Decode some call data, offsetting the returned ranges by the offset given.
+pub(crate) fn decode_call_data_at_offset<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    offset: u32,
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<CallData<'info, Info::TypeId>, CallDataDecodeError>
+where
+    Info: ExtrinsicTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let bytes = *cursor;
+    let curr_idx = |cursor: &mut &[u8]| offset + (bytes.len() - cursor.len()) as u32;
+
+    let pallet_index_idx = curr_idx(cursor);
+    let call_info = decode_call_info(cursor, info)?;
+
+    let mut args = Vec::with_capacity(call_info.args.len());
+    for arg in call_info.args {
+        let start_idx = curr_idx(cursor);
+        decode_with_error_tracing(
+            cursor,
+            arg.id.clone(),
+            type_resolver,
+            scale_decode::visitor::IgnoreVisitor::new(),
+        )
+        .map_err(|e| CallDataDecodeError::CannotDecodeCallData {
+            pallet_name: call_info.pallet_name.to_string(),
+            call_name: call_info.call_name.to_string(),
+            argument_name: arg.name.to_string(),
+            reason: e,
+        })?;
+        let end_idx = curr_idx(cursor);
+
+        args.push(NamedArg {
+            name: arg.name,
+            range: Range {
+                start: start_idx,
+                end: end_idx,
+            },
+            ty: arg.id,
+        })
+    }
+
+    Ok(CallData {
+        pallet_name: call_info.pallet_name,
+        pallet_index: call_info.pallet_index,
+        pallet_index_idx,
+        call_name: call_info.call_name,
+        call_index: call_info.call_index,
+        args,
+    })
+}
+
+/// Decode the pallet and call index bytes at the start of some call data, returning
+/// the information about the call that they point to.
+pub(crate) fn decode_call_info<'info, Info>(
+    cursor: &mut &[u8],
+    info: &'info Info,
+) -> Result<ExtrinsicCallInfo<'info, Info::TypeId>, CallDataDecodeError>
+where
+    Info: ExtrinsicTypeInfo,
+{
+    let pallet_index: u8 =
+        Decode::decode(cursor).map_err(CallDataDecodeError::CannotDecodePalletIndex)?;
+    let call_index: u8 =
+        Decode::decode(cursor).map_err(CallDataDecodeError::CannotDecodeCallIndex)?;
+    info.extrinsic_call_info_by_index(pallet_index, call_index)
+        .map_err(|e| CallDataDecodeError::CannotGetInfo(e.into_owned()))
+}
+
+/// Compute the hash of some encoded call data. This is the `blake2_256` hash used to refer to
+/// calls in places like `Multisig.Multisigs`, `Preimage.PreimageFor` and `Proxy.Announcements`.
+pub fn call_hash(call_data: &[u8]) -> [u8; 32] {
+    sp_crypto_hashing::blake2_256(call_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use frame_metadata::RuntimeMetadata;
+
+    #[test]
+    fn call_data_matches_extrinsic_call_data() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+
+        for ext_hex in extrinsics_hex {
+            let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+
+            let call_bytes = &ext_bytes[ext.call_data_range()];
+            let cursor = &mut &*call_bytes;
+            let call = decode_call_data(cursor, &metadata, &metadata.types).unwrap();
+
+            assert!(cursor.is_empty());
+            assert_eq!(call.pallet_name(), ext.pallet_name());
+            assert_eq!(call.call_name(), ext.call_name());
+            assert_eq!(call.range(), 0..call_bytes.len());
+            assert_eq!(call.args_range(), 2..call_bytes.len());
+
+            let offset = ext.call_data_range().start;
+            for (call_arg, ext_arg) in call.args().zip(ext.call_data()) {
+                assert_eq!(call_arg.name(), ext_arg.name());
+                assert_eq!(call_arg.range().start + offset, ext_arg.range().start);
+                assert_eq!(call_arg.range().end + offset, ext_arg.range().end);
+            }
+        }
+    }
+
+    #[test]
+    fn call_hash_of_real_call() {
+        // The `Timestamp.set` call from the first extrinsic in block 10,000,000.
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+        let ext_bytes = hex::decode(extrinsics_hex[0].trim_start_matches("0x")).unwrap();
+
+        // Skip the length prefix and extrinsic version to get at the call data.
+        let call_bytes = &ext_bytes[2..];
+        assert_eq!(hex::encode(call_bytes), "03000b99e04d568001");
+        assert_eq!(
+            hex::encode(call_hash(call_bytes)),
+            "00f19730d4d7dfcd18957d75cd7f2f80804b07b1e1e145cfac5f8396f542410a"
+        );
+    }
+}
@@ -0,0 +1,637 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::call_data_decoder::{CallData, CallDataDecodeError, decode_call_info};
+use crate::methods::extrinsic_decoder::NamedArg;
+use crate::methods::extrinsic_type_info::ExtrinsicCallTypeInfo;
+use crate::utils::{TypeShape, VariantShape, decode_with_error_tracing, resolve_type_shape};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use parity_scale_codec::{Compact, Decode};
+use scale_type_resolver::TypeResolver;
+
+// How deeply values (including calls) can be nested inside of each other before we give up.
+// This stops malicious input from overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// An error returned trying to decode a tree of calls.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CallTreeDecodeError {
+    #[error("Cannot decode call data:\n\n{0}")]
+    CannotDecodeCallData(CallDataDecodeError),
+    #[error("Cannot find the type of calls in the metadata.")]
+    CallTypeNotFound,
+    #[error("Cannot resolve type {type_id}:\n\n{reason}")]
+    CannotResolveType { type_id: String, reason: String },
+    #[error(
+        "Cannot look for nested calls in argument {argument_name} of {pallet_name}.{call_name}:\n\n{reason}"
+    )]
+    CannotDecodeArgument {
+        pallet_name: String,
+        call_name: String,
+        argument_name: String,
+        reason: String,
+    },
+    #[error("Values are nested more than {max_depth} levels deep.")]
+    TooDeeplyNested { max_depth: usize },
+}
+
+/// An owned variant of a [`CallTree`].
+pub type CallTreeOwned<TypeId> = CallTree<'static, TypeId>;
+
+/// Some call data, along with any calls which are nested inside of its arguments.
+#[derive(Clone, Debug)]
+pub struct CallTree<'info, TypeId> {
+    call: CallData<'info, TypeId>,
+    nested: Vec<NestedCall<'info, TypeId>>,
+}
+
+/// A call which was found nested inside one of the arguments of another call.
+#[derive(Clone, Debug)]
+pub struct NestedCall<'info, TypeId> {
+    arg_index: usize,
+    tree: CallTree<'info, TypeId>,
+}
+
+impl<'info, TypeId> CallTree<'info, TypeId> {
+    /// Take ownership of the call tree, so that it no longer references
+    /// the call info.
+    pub fn into_owned(self) -> CallTreeOwned<TypeId> {
+        CallTree {
+            call: self.call.into_owned(),
+            nested: self
+                .nested
+                .into_iter()
+                .map(|n| NestedCall {
+                    arg_index: n.arg_index,
+                    tree: n.tree.into_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The call at the root of this tree.
+    pub fn call(&self) -> &CallData<'info, TypeId> {
+        &self.call
+    }
+
+    /// Iterate over the calls nested directly inside of the arguments of this call, in the
+    /// order that they appear in the bytes.
+    pub fn nested_calls(&self) -> impl ExactSizeIterator<Item = &NestedCall<'info, TypeId>> {
+        self.nested.iter()
+    }
+
+    /// Iterate over every call in this tree, depth first, starting with the root call.
+    pub fn iter(&self) -> impl Iterator<Item = &CallData<'info, TypeId>> {
+        let mut stack = alloc::vec![self];
+        core::iter::from_fn(move || {
+            let tree = stack.pop()?;
+            stack.extend(tree.nested.iter().rev().map(|n| &n.tree));
+            Some(&tree.call)
+        })
+    }
+
+    /// Map the argument type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> CallTree<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        self.map_type_id_inner(&mut f)
+    }
+
+    fn map_type_id_inner<NewTypeId, F>(self, f: &mut F) -> CallTree<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        CallTree {
+            call: self.call.map_type_id(&mut *f),
+            nested: self
+                .nested
+                .into_iter()
+                .map(|n| NestedCall {
+                    arg_index: n.arg_index,
+                    tree: n.tree.map_type_id_inner(f),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'info, TypeId> NestedCall<'info, TypeId> {
+    /// The index of the argument in the parent call that this call was found in.
+    pub fn arg_index(&self) -> usize {
+        self.arg_index
+    }
+
+    /// The nested call, and any calls nested inside of it.
+    pub fn tree(&self) -> &CallTree<'info, TypeId> {
+        &self.tree
+    }
+
+    /// The nested call.
+    pub fn call(&self) -> &CallData<'info, TypeId> {
+        &self.tree.call
+    }
+}
+
+/// Decode some call data, like [`crate::extrinsics::decode_call_data`], but also look through
+/// each of the arguments for values whose type is the outer `RuntimeCall` type, decoding them
+/// too. This finds the calls nested inside of calls like `Utility.batch_all`, `Proxy.proxy`,
+/// `Sudo.sudo`, `Multisig.as_multi` and `Scheduler.schedule`, returning a tree of calls.
+///
+/// The byte ranges handed back are all relative to the start of the bytes that the cursor
+/// points to when this is called.
+///
+/// Calls are found by comparing the shape of each enum value in the arguments with the shape
+/// of the `RuntimeCall` type handed back from [`ExtrinsicCallTypeInfo`]. Calls which are
+/// stored as opaque bytes (eg `WrapperKeepOpaque<Call>`) are not decoded, but those bytes can
+/// be decoded separately using [`crate::extrinsics::decode_call_data`].
+///
+/// An error is returned if the values in the call data, including any nested calls, are
+/// nested more than 128 levels deep.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::extrinsics::{decode_call_tree, decode_extrinsic};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+/// let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+///
+/// for ext_hex in extrinsics_hex {
+///     let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+///     let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+///
+///     // Decode the call data, finding any nested calls as we go:
+///     let call_bytes = &ext_bytes[ext.call_data_range()];
+///     let tree = decode_call_tree(&mut &*call_bytes, &metadata, &metadata.types).unwrap();
+///
+///     // Print every call in the tree:
+///     for call in tree.iter() {
+///         println!("{}.{}", call.pallet_name(), call.call_name());
+///     }
+/// }
+/// ```
+pub fn decode_call_tree<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<CallTree<'info, Info::TypeId>, CallTreeDecodeError>
+where
+    Info: ExtrinsicCallTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone + 'static,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let call_type_id = info
+        .extrinsic_call_type_id()
+        .ok_or(CallTreeDecodeError::CallTypeNotFound)?;
+    let TypeShape::Variant(call_variants) = resolve_shape(type_resolver, call_type_id)? else {
+        return Err(CallTreeDecodeError::CallTypeNotFound);
+    };
+
+    let decoder = CallTreeDecoder {
+        bytes: cursor,
+        info,
+        type_resolver,
+        call_variants,
+    };
+    decoder.decode_tree(cursor, 0)
+}
+
+struct CallTreeDecoder<'a, 'info, Info: ExtrinsicCallTypeInfo, Resolver> {
+    bytes: &'a [u8],
+    info: &'info Info,
+    type_resolver: &'a Resolver,
+    call_variants: Vec<VariantShape<Info::TypeId>>,
+}
+
+// The pallet, call and argument that we are currently looking for nested calls in.
+struct ArgContext<'a> {
+    pallet_name: &'a str,
+    call_name: &'a str,
+    argument_name: &'a str,
+}
+
+impl ArgContext<'_> {
+    fn error(&self, reason: impl ToString) -> CallTreeDecodeError {
+        CallTreeDecodeError::CannotDecodeArgument {
+            pallet_name: self.pallet_name.to_string(),
+            call_name: self.call_name.to_string(),
+            argument_name: self.argument_name.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl<'info, Info, Resolver> CallTreeDecoder<'_, 'info, Info, Resolver>
+where
+    Info: ExtrinsicCallTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone + 'static,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    fn curr_idx(&self, cursor: &[u8]) -> u32 {
+        (self.bytes.len() - cursor.len()) as u32
+    }
+
+    // Decode the call at the cursor, and any calls nested inside of its arguments. Cursors are
+    // always a suffix of the original bytes, so that we know where we are.
+    fn decode_tree(
+        &self,
+        cursor: &mut &[u8],
+        depth: usize,
+    ) -> Result<CallTree<'info, Info::TypeId>, CallTreeDecodeError> {
+        let pallet_index_idx = self.curr_idx(cursor);
+        let call_info = decode_call_info(cursor, self.info)
+            .map_err(CallTreeDecodeError::CannotDecodeCallData)?;
+
+        let mut args = Vec::with_capacity(call_info.args.len());
+        let mut nested = Vec::new();
+        for (arg_index, arg) in call_info.args.into_iter().enumerate() {
+            let ctx = ArgContext {
+                pallet_name: &call_info.pallet_name,
+                call_name: &call_info.call_name,
+                argument_name: &arg.name,
+            };
+
+            // Each argument is decoded once, decoding any calls inside it as we go.
+            let mut trees = Vec::new();
+            let start = self.curr_idx(cursor);
+            self.find_calls(cursor, arg.id.clone(), &ctx, &mut trees, depth + 1)?;
+            let end = self.curr_idx(cursor);
+
+            args.push(NamedArg {
+                name: arg.name,
+                range: Range { start, end },
+                ty: arg.id,
+            });
+            nested.extend(trees.into_iter().map(|tree| NestedCall { arg_index, tree }));
+        }
+
+        let call = CallData {
+            pallet_name: call_info.pallet_name,
+            pallet_index: call_info.pallet_index,
+            pallet_index_idx,
+            call_name: call_info.call_name,
+            call_index: call_info.call_index,
+            args,
+        };
+        Ok(CallTree { call, nested })
+    }
+
+    // Decode the value at the cursor, decoding any calls that we find within it.
+    fn find_calls(
+        &self,
+        cursor: &mut &[u8],
+        type_id: Info::TypeId,
+        ctx: &ArgContext<'_>,
+        trees: &mut Vec<CallTree<'info, Info::TypeId>>,
+        depth: usize,
+    ) -> Result<(), CallTreeDecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(CallTreeDecodeError::TooDeeplyNested {
+                max_depth: MAX_DEPTH,
+            });
+        }
+
+        match resolve_shape(self.type_resolver, type_id.clone())? {
+            TypeShape::Variant(variants) if self.is_call_type(&variants) => {
+                trees.push(self.decode_tree(cursor, depth)?);
+            }
+            TypeShape::Variant(variants) => {
+                let index = u8::decode(cursor).map_err(|e| ctx.error(e))?;
+                let variant = variants
+                    .into_iter()
+                    .find(|v| v.index == index)
+                    .ok_or_else(|| ctx.error(format!("Variant with index {index} not found")))?;
+                for field in variant.fields {
+                    self.find_calls(cursor, field.id, ctx, trees, depth + 1)?;
+                }
+            }
+            TypeShape::Composite(fields) => {
+                for field in fields {
+                    self.find_calls(cursor, field.id, ctx, trees, depth + 1)?;
+                }
+            }
+            TypeShape::Tuple(ids) => {
+                for id in ids {
+                    self.find_calls(cursor, id, ctx, trees, depth + 1)?;
+                }
+            }
+            TypeShape::Array(id, len) if !self.is_leaf_type(id.clone())? => {
+                for _ in 0..len {
+                    self.find_calls(cursor, id.clone(), ctx, trees, depth + 1)?;
+                }
+            }
+            TypeShape::Sequence(id) if !self.is_leaf_type(id.clone())? => {
+                let len = Compact::<u32>::decode(cursor).map_err(|e| ctx.error(e))?.0;
+                for _ in 0..len {
+                    let remaining = cursor.len();
+                    self.find_calls(cursor, id.clone(), ctx, trees, depth + 1)?;
+                    // Elements which take up no bytes (eg `()`) can't contain calls, so
+                    // don't loop over what may be billions of them.
+                    if cursor.len() == remaining {
+                        break;
+                    }
+                }
+            }
+            // Nothing else can contain a call, so we just skip over it.
+            _ => {
+                decode_with_error_tracing(
+                    cursor,
+                    type_id,
+                    self.type_resolver,
+                    scale_decode::visitor::IgnoreVisitor::new(),
+                )
+                .map_err(|e| ctx.error(e))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Is the given variant type the outer call type?
+    fn is_call_type(&self, variants: &[VariantShape<Info::TypeId>]) -> bool {
+        variants.len() == self.call_variants.len()
+            && variants
+                .iter()
+                .zip(&self.call_variants)
+                .all(|(a, b)| a.index == b.index && a.name == b.name && a.fields.len() == 1)
+    }
+
+    // Types which can't contain a call, which we can skip over in one go.
+    fn is_leaf_type(&self, type_id: Info::TypeId) -> Result<bool, CallTreeDecodeError> {
+        Ok(matches!(
+            resolve_shape(self.type_resolver, type_id)?,
+            TypeShape::Primitive(_) | TypeShape::Compact(_) | TypeShape::BitSequence(..)
+        ))
+    }
+}
+
+fn resolve_shape<Resolver>(
+    type_resolver: &Resolver,
+    type_id: Resolver::TypeId,
+) -> Result<TypeShape<Resolver::TypeId>, CallTreeDecodeError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone + 'static,
+{
+    resolve_type_shape(type_resolver, type_id.clone()).map_err(|e| {
+        CallTreeDecodeError::CannotResolveType {
+            type_id: format!("{type_id:?}"),
+            reason: e.to_string(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Encode;
+    use scale_info::{TypeInfo, build, meta_type};
+
+    // Encode a `Utility.batch` call containing a `Timestamp.set` call and a `Proxy.proxy`
+    // call which wraps another `Timestamp.set` call.
+    fn nested_call_bytes<Info: ExtrinsicTypeInfo>(info: &Info) -> Vec<u8> {
+        let indexes = |pallet, call| {
+            let info = info.extrinsic_call_info_by_name(pallet, call).unwrap();
+            [info.pallet_index, info.call_index]
+        };
+
+        let mut timestamp_set = indexes("Timestamp", "set").to_vec();
+        Compact(123u64).encode_to(&mut timestamp_set);
+
+        let mut proxy = indexes("Proxy", "proxy").to_vec();
+        [1u8; 32].encode_to(&mut proxy); // real: AccountId
+        None::<()>.encode_to(&mut proxy); // force_proxy_type: Option<ProxyType>
+        proxy.extend(&timestamp_set); // call: Box<Call>
+
+        let mut batch = indexes("Utility", "batch").to_vec();
+        Compact(2u32).encode_to(&mut batch);
+        batch.extend(&timestamp_set);
+        batch.extend(&proxy);
+        batch
+    }
+
+    fn assert_nested_calls<TypeId>(tree: &CallTree<'_, TypeId>, bytes: &[u8]) {
+        let names: Vec<_> = tree
+            .iter()
+            .map(|c| (c.pallet_name(), c.call_name()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("Utility", "batch"),
+                ("Timestamp", "set"),
+                ("Proxy", "proxy"),
+                ("Timestamp", "set")
+            ]
+        );
+
+        assert_eq!(tree.call().range(), 0..bytes.len());
+        assert_eq!(tree.nested_calls().len(), 2);
+
+        let mut nested = tree.nested_calls();
+        let first = nested.next().unwrap();
+        assert_eq!(first.arg_index(), 0);
+        assert_eq!(first.call().range(), 3..7);
+
+        let proxy = nested.next().unwrap();
+        assert_eq!(proxy.arg_index(), 0);
+        assert_eq!(proxy.call().range(), 7..bytes.len());
+
+        let inner = proxy.tree().nested_calls().next().unwrap();
+        assert_eq!(inner.arg_index(), 2);
+        assert_eq!(inner.call().range(), bytes.len() - 4..bytes.len());
+        assert_eq!(&bytes[inner.call().range()], &bytes[first.call().range()]);
+    }
+
+    #[test]
+    fn decode_modern_call_tree() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let bytes = nested_call_bytes(&metadata);
+        let cursor = &mut &*bytes;
+        let tree = decode_call_tree(cursor, &metadata, &metadata.types).unwrap();
+
+        assert!(cursor.is_empty());
+        assert_nested_calls(&tree, &bytes);
+    }
+
+    #[test]
+    fn deeply_nested_calls_are_rejected() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        // Wrap a `Timestamp.set` call in the given number of `Proxy.proxy` calls.
+        let nested_proxies = |depth: usize| {
+            let indexes = |pallet, call| {
+                let info = metadata.extrinsic_call_info_by_name(pallet, call).unwrap();
+                [info.pallet_index, info.call_index]
+            };
+            let mut bytes = Vec::new();
+            for _ in 0..depth {
+                bytes.extend(indexes("Proxy", "proxy"));
+                [1u8; 32].encode_to(&mut bytes);
+                None::<()>.encode_to(&mut bytes);
+            }
+            bytes.extend(indexes("Timestamp", "set"));
+            Compact(123u64).encode_to(&mut bytes);
+            bytes
+        };
+
+        let bytes = nested_proxies(100);
+        let tree = decode_call_tree(&mut &*bytes, &metadata, &metadata.types).unwrap();
+        assert_eq!(tree.iter().count(), 101);
+
+        let bytes = nested_proxies(10_000);
+        let err = decode_call_tree(&mut &*bytes, &metadata, &metadata.types).unwrap_err();
+        assert!(matches!(
+            err,
+            CallTreeDecodeError::TooDeeplyNested {
+                max_depth: MAX_DEPTH
+            }
+        ));
+    }
+
+    #[test]
+    fn decode_legacy_call_tree() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        let metadata_types = crate::helpers::type_registry_from_metadata(&metadata).unwrap();
+        types.prepend(metadata_types);
+
+        let bytes = nested_call_bytes(&metadata);
+        let cursor = &mut &*bytes;
+        let tree = decode_call_tree(cursor, &metadata, &types).unwrap();
+
+        assert!(cursor.is_empty());
+        assert_nested_calls(&tree, &bytes);
+    }
+
+    // A pallet call enum with a single call taking a `Vec<()>`.
+    struct FooCall;
+    impl TypeInfo for FooCall {
+        type Identity = Self;
+        fn type_info() -> scale_info::Type {
+            scale_info::Type::builder()
+                .path(scale_info::Path::new("Call", "test"))
+                .variant(build::Variants::new().variant("units", |v| {
+                    v.index(0)
+                        .fields(build::Fields::named().field(|f| f.ty::<Vec<()>>().name("units")))
+                }))
+        }
+    }
+
+    // The outer call enum, containing only `FooCall`.
+    struct RuntimeCall;
+    impl TypeInfo for RuntimeCall {
+        type Identity = Self;
+        fn type_info() -> scale_info::Type {
+            scale_info::Type::builder()
+                .path(scale_info::Path::new("RuntimeCall", "test"))
+                .variant(build::Variants::new().variant("Foo", |v| {
+                    v.index(0)
+                        .fields(build::Fields::unnamed().field(|f| f.ty::<FooCall>()))
+                }))
+        }
+    }
+
+    fn metadata_with_unit_sequence_call() -> frame_metadata::v16::RuntimeMetadataV16 {
+        use frame_metadata::v16::*;
+
+        let pallet = PalletMetadata {
+            name: "Foo",
+            storage: None,
+            calls: Some(PalletCallMetadata {
+                ty: meta_type::<FooCall>(),
+                deprecation_info: EnumDeprecationInfo::nothing_deprecated(),
+            }),
+            event: None,
+            constants: vec![],
+            error: None,
+            associated_types: vec![],
+            view_functions: vec![],
+            index: 0,
+            docs: vec![],
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+
+        RuntimeMetadataV16::new(
+            vec![pallet],
+            ExtrinsicMetadata {
+                versions: vec![4],
+                address_ty: meta_type::<u32>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<u32>(),
+                transaction_extensions_by_version: Default::default(),
+                transaction_extensions: vec![],
+            },
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<u32>(),
+                error_enum_ty: meta_type::<u32>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        )
+    }
+
+    #[test]
+    fn sequences_of_zero_sized_values_are_skipped() {
+        let metadata = metadata_with_unit_sequence_call();
+
+        // A call whose argument claims to contain u32::MAX units:
+        let mut bytes = vec![0, 0];
+        Compact(u32::MAX).encode_to(&mut bytes);
+
+        let cursor = &mut &*bytes;
+        let tree = decode_call_tree(cursor, &metadata, &metadata.types).unwrap();
+
+        assert!(cursor.is_empty());
+        assert_eq!(tree.call().pallet_name(), "Foo");
+        assert_eq!(tree.call().call_name(), "units");
+        assert_eq!(tree.call().args().next().unwrap().range(), 2..bytes.len());
+        assert_eq!(tree.nested_calls().len(), 0);
+    }
+}
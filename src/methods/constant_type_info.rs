@@ -14,9 +14,11 @@
 // limitations under the License.
 
 use super::Entry;
-use crate::utils::Either;
+use super::deprecation::{Deprecation, DeprecationOf};
+use crate::utils::{Either, to_docs};
 use alloc::borrow::Cow;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 /// This can be implemented for anything capable of providing Constant information.
 pub trait ConstantTypeInfo {
@@ -30,6 +32,14 @@ pub trait ConstantTypeInfo {
     ) -> Result<ConstantInfo<'_, Self::TypeId>, ConstantInfoError<'_>>;
 }
 
+/// Implementations of this are responsible for handing back the docs for constants, where
+/// the metadata contains them. This is implemented for the same metadata versions as
+/// [`ConstantTypeInfo`].
+pub trait ConstantDocsInfo {
+    /// Get the docs for a given constant, or `None` if the constant can't be found.
+    fn constant_docs(&self, pallet_name: &str, constant_name: &str) -> Option<Vec<&str>>;
+}
+
 /// This can be implemented for anything capable of providing information about the available Constants
 pub trait ConstantEntryInfo {
     /// Iterate over all of the available Constants, returning [`Entry`] as we go.
@@ -65,6 +75,9 @@ pub enum ConstantInfoError<'info> {
         name: Cow<'info, str>,
         reason: scale_info_legacy::lookup_name::ParseError,
     },
+    #[cfg(feature = "legacy")]
+    #[error("The metadata was not SCALE decoded and so cannot be read")]
+    MetadataNotDecoded,
 }
 
 impl<'info> ConstantInfoError<'info> {
@@ -88,6 +101,8 @@ impl<'info> ConstantInfoError<'info> {
                     reason,
                 }
             }
+            #[cfg(feature = "legacy")]
+            ConstantInfoError::MetadataNotDecoded => ConstantInfoError::MetadataNotDecoded,
         }
     }
 }
@@ -102,6 +117,8 @@ pub struct ConstantInfo<'info, TypeId: Clone> {
     pub bytes: &'info [u8],
     /// The type of this constant.
     pub type_id: TypeId,
+    /// Whether the constant is deprecated.
+    pub deprecation: Deprecation<'info>,
 }
 
 macro_rules! impl_constant_type_info_for_v14_to_v16 {
@@ -137,6 +154,7 @@ macro_rules! impl_constant_type_info_for_v14_to_v16 {
                     Ok(ConstantInfo {
                         bytes: &constant.value,
                         type_id: constant.ty.id,
+                        deprecation: pallet.deprecation_of(constant),
                     })
                 }
             }
@@ -173,10 +191,26 @@ impl_constant_type_info_for_v14_to_v16!(frame_metadata::v14, RuntimeMetadataV14)
 impl_constant_type_info_for_v14_to_v16!(frame_metadata::v15, RuntimeMetadataV15);
 impl_constant_type_info_for_v14_to_v16!(frame_metadata::v16, RuntimeMetadataV16);
 
+macro_rules! impl_constant_docs_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl ConstantDocsInfo for $path {
+            fn constant_docs(&self, pallet_name: &str, constant_name: &str) -> Option<Vec<&str>> {
+                let pallet = self.pallets.iter().find(|p| p.name == pallet_name)?;
+                let constant = pallet.constants.iter().find(|c| c.name == constant_name)?;
+                Some(to_docs(&constant.docs))
+            }
+        }
+    };
+}
+
+impl_constant_docs_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_constant_docs_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_constant_docs_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
 #[cfg(feature = "legacy")]
 mod legacy {
     use super::*;
-    use crate::utils::as_decoded;
+    use crate::utils::as_decoded_str;
     use frame_metadata::decode_different::DecodeDifferent;
     use scale_info_legacy::LookupName;
 
@@ -191,45 +225,47 @@ mod legacy {
                         pallet_name: &str,
                         constant_name: &str,
                     ) -> Result<ConstantInfo<'_, Self::TypeId>, ConstantInfoError<'_>> {
-                        let modules = as_decoded(&self.modules);
+                        let modules = as_decoded(&self.modules)?;
 
                         let m = modules
                             .iter()
-                            .find(|m| as_decoded(&m.name).as_ref() as &str == pallet_name)
+                            .find(|m| as_decoded_str(&m.name) == pallet_name)
                             .ok_or_else(|| ConstantInfoError::PalletNotFound {
                                 pallet_name: pallet_name.into(),
                             })?;
 
-                        let pallet_name = as_decoded(&m.name);
-                        let constants = as_decoded(&m.constants);
+                        let pallet_name = as_decoded_str(&m.name);
+                        let constants = as_decoded(&m.constants)?;
 
                         let constant = constants
                             .iter()
-                            .find(|c| as_decoded(&c.name).as_ref() as &str == constant_name)
+                            .find(|c| as_decoded_str(&c.name) == constant_name)
                             .ok_or_else(|| ConstantInfoError::ConstantNotFound {
                                 pallet_name: Cow::Borrowed(pallet_name),
                                 constant_name: constant_name.into(),
                             })?;
 
                         let type_id = decode_lookup_name_or_err(&constant.ty, pallet_name)?;
-                        let data = as_decoded(&constant.value);
+                        let data = as_decoded(&constant.value)?;
 
                         Ok(ConstantInfo {
                             bytes: &**data,
                             type_id,
+                            deprecation: Deprecation::NotDeprecated,
                         })
                     }
                 }
                 impl ConstantEntryInfo for path::$name {
                     fn constant_entries(&self) -> impl Iterator<Item = ConstantEntry<'_>> {
-                        as_decoded(&self.modules).iter().flat_map(|module| {
-                            let pallet_name = as_decoded(&module.name);
-                            let constants = as_decoded(&module.constants);
-
-                            core::iter::once(Entry::In(Cow::Borrowed(&**pallet_name))).chain(
-                                constants.iter().map(|c| {
-                                    let constant_name = as_decoded(&c.name);
-                                    Entry::Name(Cow::Borrowed(&**constant_name))
+                        let modules = as_decoded(&self.modules).into_iter().flatten();
+                        modules.flat_map(|module| {
+                            let pallet_name = as_decoded_str(&module.name);
+                            let constants = as_decoded(&module.constants).into_iter().flatten();
+
+                            core::iter::once(Entry::In(Cow::Borrowed(pallet_name))).chain(
+                                constants.map(|c| {
+                                    let constant_name = as_decoded_str(&c.name);
+                                    Entry::Name(Cow::Borrowed(constant_name))
                                 }),
                             )
                         })
@@ -239,17 +275,21 @@ mod legacy {
                         pallet_name: &str,
                     ) -> impl Iterator<Item = Cow<'_, str>> {
                         let module = as_decoded(&self.modules)
-                            .iter()
-                            .find(|p| as_decoded(&p.name) == &pallet_name);
+                            .into_iter()
+                            .flatten()
+                            .find(|p| as_decoded_str(&p.name) == pallet_name);
 
                         let Some(module) = module else {
                             return Either::Left(core::iter::empty());
                         };
 
-                        let module_constants = as_decoded(&module.constants).iter().map(|c| {
-                            let constant_name = as_decoded(&c.name);
-                            Cow::Borrowed(&**constant_name)
-                        });
+                        let module_constants = as_decoded(&module.constants)
+                            .into_iter()
+                            .flatten()
+                            .map(|c| {
+                                let constant_name = as_decoded_str(&c.name);
+                                Cow::Borrowed(constant_name)
+                            });
 
                         Either::Right(module_constants)
                     }
@@ -265,11 +305,44 @@ mod legacy {
     impl_constant_type_info_for_v8_to_v13!(frame_metadata::v12, RuntimeMetadataV12);
     impl_constant_type_info_for_v8_to_v13!(frame_metadata::v13, RuntimeMetadataV13);
 
+    macro_rules! impl_constant_docs_info_for_v8_to_v13 {
+        ($path:path) => {
+            impl ConstantDocsInfo for $path {
+                fn constant_docs(
+                    &self,
+                    pallet_name: &str,
+                    constant_name: &str,
+                ) -> Option<Vec<&str>> {
+                    let m = as_decoded(&self.modules)
+                        .ok()?
+                        .iter()
+                        .find(|m| as_decoded_str(&m.name) == pallet_name)?;
+                    let constant = as_decoded(&m.constants)
+                        .ok()?
+                        .iter()
+                        .find(|c| as_decoded_str(&c.name) == constant_name)?;
+                    Some(to_docs(&as_decoded(&constant.documentation).ok()?[..]))
+                }
+            }
+        };
+    }
+
+    impl_constant_docs_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_constant_docs_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_constant_docs_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_constant_docs_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_constant_docs_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12);
+    impl_constant_docs_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13);
+
+    fn as_decoded<A, B>(item: &DecodeDifferent<A, B>) -> Result<&B, ConstantInfoError<'static>> {
+        crate::utils::as_decoded(item).map_err(|_| ConstantInfoError::MetadataNotDecoded)
+    }
+
     fn decode_lookup_name_or_err<S: AsRef<str>>(
         s: &DecodeDifferent<&str, S>,
         pallet_name: &str,
     ) -> Result<LookupName, ConstantInfoError<'static>> {
-        let ty = sanitize_type_name(as_decoded(s).as_ref());
+        let ty = sanitize_type_name(as_decoded_str(s));
         lookup_name_or_err(&ty, pallet_name)
     }
 
@@ -0,0 +1,522 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::extrinsic_decoder::ExtrinsicSignature;
+use crate::utils::{TypeShape, resolve_type_shape};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use scale_encode::{Composite, CompositeField, EncodeAsType, Variant};
+use scale_type_resolver::TypeResolver;
+
+/// The signing context used for sr25519 signatures in Substrate based chains.
+const SR25519_SIGNING_CONTEXT: &[u8] = b"substrate";
+
+/// An error returned trying to create a keypair.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum KeypairError {
+    #[error("The seed given is not a valid ecdsa secret key.")]
+    InvalidEcdsaSeed,
+}
+
+/// An error returned trying to verify the signature of an extrinsic.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SignatureVerificationError {
+    #[error("Cannot resolve type {type_id}: {reason}")]
+    CannotResolveType { type_id: String, reason: String },
+    #[error("Cannot verify signature: the address is not a 32 byte account ID.")]
+    UnsupportedAddress,
+    #[error(
+        "Cannot verify signature: the signature is not an Ed25519, Sr25519 or Ecdsa MultiSignature."
+    )]
+    UnsupportedSignature,
+    #[error("The signature is not valid for the given signer payload and address.")]
+    InvalidSignature,
+    #[error("The bytes given are not those that the extrinsic signature was decoded from.")]
+    BytesMismatch,
+}
+
+/// A signature, as found in the `MultiSignature` type used by most Substrate based chains.
+///
+/// This implements [`EncodeAsType`], and so can be handed to functions like
+/// [`crate::extrinsics::encode_v4_signed`] to be encoded into an extrinsic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MultiSignature {
+    /// An ed25519 signature.
+    Ed25519([u8; 64]),
+    /// An sr25519 signature.
+    Sr25519([u8; 64]),
+    /// An ecdsa signature over the `blake2_256` hash of the payload. The last byte is the
+    /// recovery ID.
+    Ecdsa([u8; 65]),
+}
+
+impl MultiSignature {
+    /// Verify that this signature was produced by signing the given payload with the key
+    /// belonging to the given account ID.
+    ///
+    /// For sr25519 and ed25519 signatures, the account ID is the public key. For ecdsa
+    /// signatures, it is the `blake2_256` hash of the compressed public key.
+    pub fn verify(&self, payload: &[u8], account_id: &[u8; 32]) -> bool {
+        match self {
+            MultiSignature::Ed25519(sig) => {
+                let Ok(public) = ed25519_zebra::VerificationKey::try_from(*account_id) else {
+                    return false;
+                };
+                let sig = ed25519_zebra::Signature::from_bytes(sig);
+                public.verify(&sig, payload).is_ok()
+            }
+            MultiSignature::Sr25519(sig) => {
+                let Ok(public) = schnorrkel::PublicKey::from_bytes(account_id) else {
+                    return false;
+                };
+                let Ok(sig) = schnorrkel::Signature::from_bytes(sig) else {
+                    return false;
+                };
+                public
+                    .verify_simple(SR25519_SIGNING_CONTEXT, payload, &sig)
+                    .is_ok()
+            }
+            MultiSignature::Ecdsa(sig) => {
+                let Ok(signature) = libsecp256k1::Signature::parse_standard_slice(&sig[..64])
+                else {
+                    return false;
+                };
+                // Some tools encode the recovery ID as 27 or 28 rather than 0 or 1.
+                let recovery_id = if sig[64] >= 27 { sig[64] - 27 } else { sig[64] };
+                let Ok(recovery_id) = libsecp256k1::RecoveryId::parse(recovery_id) else {
+                    return false;
+                };
+                let message = libsecp256k1::Message::parse(&sp_crypto_hashing::blake2_256(payload));
+                let Ok(public) = libsecp256k1::recover(&message, &signature, &recovery_id) else {
+                    return false;
+                };
+                sp_crypto_hashing::blake2_256(&public.serialize_compressed()) == *account_id
+            }
+        }
+    }
+
+    fn variant_name_and_bytes(&self) -> (&'static str, &[u8]) {
+        match self {
+            MultiSignature::Ed25519(sig) => ("Ed25519", sig),
+            MultiSignature::Sr25519(sig) => ("Sr25519", sig),
+            MultiSignature::Ecdsa(sig) => ("Ecdsa", sig),
+        }
+    }
+}
+
+impl EncodeAsType for MultiSignature {
+    fn encode_as_type_to<R: TypeResolver>(
+        &self,
+        type_id: R::TypeId,
+        types: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), scale_encode::Error> {
+        let (name, bytes) = self.variant_name_and_bytes();
+        let bytes = bytes.to_vec();
+        let fields = [(None, CompositeField::new(&bytes))];
+        Variant {
+            name,
+            fields: Composite::new(fields.into_iter()),
+        }
+        .encode_variant_as_type_to(type_id, types, out)
+    }
+}
+
+/// An sr25519 keypair.
+pub struct Sr25519Keypair(schnorrkel::Keypair);
+
+impl Sr25519Keypair {
+    /// Create a keypair from a 32 byte seed (a "mini secret key"), expanding it in the same
+    /// way as Substrate does.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mini_secret_key = schnorrkel::MiniSecretKey::from_bytes(seed)
+            .expect("mini secret keys are 32 bytes and seeds are 32 bytes; qed");
+        Sr25519Keypair(mini_secret_key.expand_to_keypair(schnorrkel::ExpansionMode::Ed25519))
+    }
+
+    /// The public key, which is also the account ID for this keypair.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.0.public.to_bytes()
+    }
+
+    /// The account ID for this keypair.
+    pub fn account_id(&self) -> [u8; 32] {
+        self.public_key()
+    }
+
+    /// Sign some payload, for instance one returned from [`crate::extrinsics::encode_v4_signer_payload`].
+    ///
+    /// Without the `std` feature, no source of randomness is available and so signatures are
+    /// derived deterministically from the secret key and payload. These are still valid and
+    /// secure signatures.
+    pub fn sign(&self, payload: &[u8]) -> MultiSignature {
+        let transcript = schnorrkel::signing_context(SR25519_SIGNING_CONTEXT).bytes(payload);
+
+        #[cfg(feature = "std")]
+        let signature = self.0.sign(transcript);
+        #[cfg(not(feature = "std"))]
+        let signature = self
+            .0
+            .sign(schnorrkel::context::attach_rng(transcript, NoRandomness));
+
+        MultiSignature::Sr25519(signature.to_bytes())
+    }
+}
+
+/// An ed25519 keypair.
+pub struct Ed25519Keypair(ed25519_zebra::SigningKey);
+
+impl Ed25519Keypair {
+    /// Create a keypair from a 32 byte seed.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Ed25519Keypair(ed25519_zebra::SigningKey::from_bytes(seed))
+    }
+
+    /// The public key, which is also the account ID for this keypair.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.0.verification_key().into()
+    }
+
+    /// The account ID for this keypair.
+    pub fn account_id(&self) -> [u8; 32] {
+        self.public_key()
+    }
+
+    /// Sign some payload, for instance one returned from [`crate::extrinsics::encode_v4_signer_payload`].
+    pub fn sign(&self, payload: &[u8]) -> MultiSignature {
+        MultiSignature::Ed25519(self.0.sign(payload).to_bytes())
+    }
+}
+
+/// An ecdsa (secp256k1) keypair.
+pub struct EcdsaKeypair(libsecp256k1::SecretKey);
+
+impl EcdsaKeypair {
+    /// Create a keypair from a 32 byte seed. This fails if the seed is not a valid
+    /// secp256k1 secret key.
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self, KeypairError> {
+        libsecp256k1::SecretKey::parse(seed)
+            .map(EcdsaKeypair)
+            .map_err(|_| KeypairError::InvalidEcdsaSeed)
+    }
+
+    /// The compressed public key.
+    pub fn public_key(&self) -> [u8; 33] {
+        libsecp256k1::PublicKey::from_secret_key(&self.0).serialize_compressed()
+    }
+
+    /// The account ID for this keypair, which is the `blake2_256` hash of the compressed
+    /// public key.
+    pub fn account_id(&self) -> [u8; 32] {
+        sp_crypto_hashing::blake2_256(&self.public_key())
+    }
+
+    /// Sign some payload, for instance one returned from [`crate::extrinsics::encode_v4_signer_payload`].
+    /// As in Substrate, the `blake2_256` hash of the payload is what is actually signed.
+    pub fn sign(&self, payload: &[u8]) -> MultiSignature {
+        let message = libsecp256k1::Message::parse(&sp_crypto_hashing::blake2_256(payload));
+        let (signature, recovery_id) = libsecp256k1::sign(&message, &self.0);
+
+        let mut bytes = [0u8; 65];
+        bytes[..64].copy_from_slice(&signature.serialize());
+        bytes[64] = recovery_id.serialize();
+        MultiSignature::Ecdsa(bytes)
+    }
+}
+
+/// Verify the signature of a decoded extrinsic.
+///
+/// `bytes` must be the bytes that the extrinsic was decoded from, and `signer_payload` the
+/// payload that was signed, which can be rebuilt using [`crate::extrinsics::Extrinsic::signer_payload`].
+///
+/// The address must be a 32 byte account ID (either on its own or as the `Id` or `Address32`
+/// variant of a `MultiAddress`), and the signature must be a `MultiSignature`. The type
+/// information is used to find out which variants of these were provided.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use frame_decode::crypto::verify_extrinsic_signature;
+/// use frame_decode::extrinsics::{decode_extrinsic, TransactionExtensions};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+/// use scale_info::PortableRegistry;
+///
+/// fn is_valid<Implicits: TransactionExtensions<PortableRegistry>>(
+///     ext_bytes: &[u8],
+///     implicits: &Implicits,
+/// ) -> bool {
+///     let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+///     let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { panic!() };
+///
+///     let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+///     let signer_payload = ext.signer_payload(ext_bytes, implicits, &metadata, &metadata.types).unwrap();
+///     let signature = ext.signature_payload().unwrap();
+///
+///     verify_extrinsic_signature(signature, ext_bytes, &signer_payload, &metadata.types).is_ok()
+/// }
+/// ```
+pub fn verify_extrinsic_signature<Resolver>(
+    signature: &ExtrinsicSignature<Resolver::TypeId>,
+    bytes: &[u8],
+    signer_payload: &[u8],
+    type_resolver: &Resolver,
+) -> Result<(), SignatureVerificationError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone,
+{
+    let address_bytes = bytes
+        .get(signature.address_range())
+        .ok_or(SignatureVerificationError::BytesMismatch)?;
+    let account_id = account_id_from_address(
+        address_bytes,
+        signature.address_type().clone(),
+        type_resolver,
+    )?;
+
+    let signature_bytes = bytes
+        .get(signature.signature_range())
+        .ok_or(SignatureVerificationError::BytesMismatch)?;
+    let signature = multi_signature_from_bytes(
+        signature_bytes,
+        signature.signature_type().clone(),
+        type_resolver,
+    )?;
+
+    if signature.verify(signer_payload, &account_id) {
+        Ok(())
+    } else {
+        Err(SignatureVerificationError::InvalidSignature)
+    }
+}
+
+fn account_id_from_address<Resolver>(
+    bytes: &[u8],
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<[u8; 32], SignatureVerificationError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone,
+{
+    let account_id_bytes = match resolve_shape(type_id, type_resolver)? {
+        // A MultiAddress; only the variants containing 32 byte account IDs are supported.
+        TypeShape::Variant(variants) => {
+            let (&index, rest) = bytes
+                .split_first()
+                .ok_or(SignatureVerificationError::UnsupportedAddress)?;
+            let variant = variants
+                .iter()
+                .find(|v| v.index == index)
+                .ok_or(SignatureVerificationError::UnsupportedAddress)?;
+            if variant.name != "Id" && variant.name != "Address32" {
+                return Err(SignatureVerificationError::UnsupportedAddress);
+            }
+            rest
+        }
+        // Some chains use an account ID directly as the address.
+        _ => bytes,
+    };
+
+    account_id_bytes
+        .try_into()
+        .map_err(|_| SignatureVerificationError::UnsupportedAddress)
+}
+
+fn multi_signature_from_bytes<Resolver>(
+    bytes: &[u8],
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<MultiSignature, SignatureVerificationError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone,
+{
+    let TypeShape::Variant(variants) = resolve_shape(type_id, type_resolver)? else {
+        return Err(SignatureVerificationError::UnsupportedSignature);
+    };
+    let (&index, rest) = bytes
+        .split_first()
+        .ok_or(SignatureVerificationError::UnsupportedSignature)?;
+    let variant = variants
+        .iter()
+        .find(|v| v.index == index)
+        .ok_or(SignatureVerificationError::UnsupportedSignature)?;
+
+    let signature = match &*variant.name {
+        "Ed25519" => rest.try_into().ok().map(MultiSignature::Ed25519),
+        "Sr25519" => rest.try_into().ok().map(MultiSignature::Sr25519),
+        "Ecdsa" => rest.try_into().ok().map(MultiSignature::Ecdsa),
+        _ => None,
+    };
+
+    signature.ok_or(SignatureVerificationError::UnsupportedSignature)
+}
+
+fn resolve_shape<Resolver>(
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<TypeShape<Resolver::TypeId>, SignatureVerificationError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone,
+{
+    resolve_type_shape(type_resolver, type_id.clone()).map_err(|e| {
+        SignatureVerificationError::CannotResolveType {
+            type_id: format!("{type_id:?}"),
+            reason: e.to_string(),
+        }
+    })
+}
+
+// Without `std` we have no source of randomness, and so we sign deterministically
+// by handing this to schnorrkel. The secret nonce is still mixed in to the signing
+// transcript, so the resulting signatures remain secure.
+#[cfg(not(feature = "std"))]
+struct NoRandomness;
+
+#[cfg(not(feature = "std"))]
+impl rand_core::RngCore for NoRandomness {
+    fn next_u32(&mut self) -> u32 {
+        0
+    }
+    fn next_u64(&mut self) -> u64 {
+        0
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(0)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        dest.fill(0);
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl rand_core::CryptoRng for NoRandomness {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use crate::methods::extrinsic_encoder::test_extensions::RawExtensions;
+    use crate::methods::extrinsic_encoder::{encode_v4_signed, encode_v4_signer_payload};
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Decode;
+    use scale_value::Value;
+
+    #[test]
+    fn sign_and_verify_payloads() {
+        let payload = b"some payload";
+        let other_payload = b"some other payload";
+
+        let sr25519 = Sr25519Keypair::from_seed(&[1; 32]);
+        let ed25519 = Ed25519Keypair::from_seed(&[2; 32]);
+        let ecdsa = EcdsaKeypair::from_seed(&[3; 32]).unwrap();
+
+        let keys = [
+            (sr25519.sign(payload), sr25519.account_id()),
+            (ed25519.sign(payload), ed25519.account_id()),
+            (ecdsa.sign(payload), ecdsa.account_id()),
+        ];
+
+        for (signature, account_id) in keys {
+            assert!(signature.verify(payload, &account_id));
+            assert!(!signature.verify(other_payload, &account_id));
+            assert!(!signature.verify(payload, &[0; 32]));
+        }
+
+        assert!(EcdsaKeypair::from_seed(&[0; 32]).is_err());
+    }
+
+    #[test]
+    fn verify_signed_extrinsics() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let call_data = Value::unnamed_composite([Value::from_bytes(b"hello")]);
+        let payload = encode_v4_signer_payload(
+            "System",
+            "remark",
+            &call_data,
+            &RawExtensions::polkadot(1, 0),
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+
+        let sr25519 = Sr25519Keypair::from_seed(&[1; 32]);
+        let ed25519 = Ed25519Keypair::from_seed(&[2; 32]);
+        let ecdsa = EcdsaKeypair::from_seed(&[3; 32]).unwrap();
+
+        let keys = [
+            (sr25519.sign(&payload), sr25519.account_id()),
+            (ed25519.sign(&payload), ed25519.account_id()),
+            (ecdsa.sign(&payload), ecdsa.account_id()),
+        ];
+
+        for (signature, account_id) in keys {
+            for signer in [account_id, [0; 32]] {
+                let address = Value::unnamed_variant("Id", [Value::from_bytes(signer)]);
+                let ext_bytes = encode_v4_signed(
+                    "System",
+                    "remark",
+                    &call_data,
+                    &RawExtensions::polkadot(1, 0),
+                    &address,
+                    &signature,
+                    &metadata,
+                    &metadata.types,
+                )
+                .unwrap();
+
+                let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+                let signer_payload = ext
+                    .signer_payload(
+                        &ext_bytes,
+                        &RawExtensions::polkadot(1, 0),
+                        &metadata,
+                        &metadata.types,
+                    )
+                    .unwrap();
+                let result = verify_extrinsic_signature(
+                    ext.signature_payload().unwrap(),
+                    &ext_bytes,
+                    &signer_payload,
+                    &metadata.types,
+                );
+
+                if signer == account_id {
+                    assert!(result.is_ok());
+                } else {
+                    assert!(matches!(
+                        result,
+                        Err(SignatureVerificationError::InvalidSignature)
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,527 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+use frame_metadata::v16::{ItemDeprecationInfo, VariantDeprecationInfo};
+use scale_info::form::PortableForm;
+
+/// Whether some item in the metadata is deprecated. Only V16 metadata and above contains
+/// deprecation information; items from older metadata are never deprecated.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Deprecation<'info> {
+    /// The item is not deprecated.
+    #[default]
+    NotDeprecated,
+    /// The item is deprecated.
+    Deprecated {
+        /// A note explaining the deprecation, if one was given.
+        note: Option<Cow<'info, str>>,
+        /// The version at which the item was deprecated, if one was given.
+        since: Option<Cow<'info, str>>,
+    },
+}
+
+impl<'info> Deprecation<'info> {
+    /// Is the item deprecated?
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self, Deprecation::Deprecated { .. })
+    }
+
+    /// Take ownership of this, turning any lifetimes to `'static`.
+    pub fn into_owned(self) -> Deprecation<'static> {
+        match self {
+            Deprecation::NotDeprecated => Deprecation::NotDeprecated,
+            Deprecation::Deprecated { note, since } => Deprecation::Deprecated {
+                note: note.map(|n| Cow::Owned(n.into_owned())),
+                since: since.map(|s| Cow::Owned(s.into_owned())),
+            },
+        }
+    }
+
+    // If this is not deprecated, fall back to the given deprecation. This is used to have
+    // items inherit the deprecation of the pallet or Runtime API that they live in.
+    pub(crate) fn or(self, other: Deprecation<'info>) -> Deprecation<'info> {
+        if self.is_deprecated() { self } else { other }
+    }
+}
+
+impl core::fmt::Display for Deprecation<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Deprecation::NotDeprecated => write!(f, "not deprecated"),
+            Deprecation::Deprecated { note, since } => {
+                write!(f, "deprecated")?;
+                if let Some(since) = since {
+                    write!(f, " since {since}")?;
+                }
+                if let Some(note) = note {
+                    write!(f, ": {note}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// What to do when asked to encode something that the metadata marks as deprecated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeprecationPolicy {
+    /// Encode deprecated items without reporting anything.
+    #[default]
+    Ignore,
+    /// Encode deprecated items, but hand back a [`DeprecationWarning`] alongside the bytes.
+    Warn,
+    /// Refuse to encode deprecated items, returning an error containing a [`DeprecationWarning`].
+    Deny,
+}
+
+/// Details about some deprecated item that was encoded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{container}.{name} is {deprecation}")]
+pub struct DeprecationWarning {
+    /// The pallet or Runtime API trait name.
+    pub container: String,
+    /// The name of the deprecated item.
+    pub name: String,
+    /// Details about the deprecation.
+    pub deprecation: Deprecation<'static>,
+}
+
+// Apply a deprecation policy to some item. This hands back `Ok(Some(warning))` if we should warn
+// about the item, and `Err(warning)` if we should refuse to encode it.
+pub(crate) fn check_deprecation(
+    policy: DeprecationPolicy,
+    container: &str,
+    name: &str,
+    deprecation: &Deprecation<'_>,
+) -> Result<Option<DeprecationWarning>, DeprecationWarning> {
+    if policy == DeprecationPolicy::Ignore || !deprecation.is_deprecated() {
+        return Ok(None);
+    }
+
+    let warning = DeprecationWarning {
+        container: container.to_owned(),
+        name: name.to_owned(),
+        deprecation: deprecation.clone().into_owned(),
+    };
+
+    match policy {
+        DeprecationPolicy::Deny => Err(warning),
+        _ => Ok(Some(warning)),
+    }
+}
+
+pub(crate) fn from_v16_item_deprecation(
+    info: &ItemDeprecationInfo<PortableForm>,
+) -> Deprecation<'_> {
+    match info {
+        ItemDeprecationInfo::NotDeprecated => Deprecation::NotDeprecated,
+        ItemDeprecationInfo::DeprecatedWithoutNote => Deprecation::Deprecated {
+            note: None,
+            since: None,
+        },
+        ItemDeprecationInfo::Deprecated { note, since } => Deprecation::Deprecated {
+            note: Some(Cow::Borrowed(note.as_ref())),
+            since: since.as_ref().map(|s| Cow::Borrowed(s.as_ref())),
+        },
+    }
+}
+
+pub(crate) fn from_v16_variant_deprecation(
+    info: Option<&VariantDeprecationInfo<PortableForm>>,
+) -> Deprecation<'_> {
+    match info {
+        None => Deprecation::NotDeprecated,
+        Some(VariantDeprecationInfo::DeprecatedWithoutNote) => Deprecation::Deprecated {
+            note: None,
+            since: None,
+        },
+        Some(VariantDeprecationInfo::Deprecated { note, since }) => Deprecation::Deprecated {
+            note: Some(Cow::Borrowed(note.as_ref())),
+            since: since.as_ref().map(|s| Cow::Borrowed(s.as_ref())),
+        },
+    }
+}
+
+// Deprecation information only exists from V16 metadata onwards. This trait allows code which
+// is shared across V14 to V16 metadata to ask for it regardless, and hands back
+// `Deprecation::NotDeprecated` for older versions. Items inherit any deprecation of the pallet
+// or Runtime API that they are a part of.
+pub(crate) trait DeprecationOf<'a, Item> {
+    fn deprecation_of(&'a self, item: Item) -> Deprecation<'a>;
+}
+
+macro_rules! impl_not_deprecated {
+    ($container:ty, $($item:ty),+) => {
+        $(
+            impl<'a> DeprecationOf<'a, $item> for $container {
+                fn deprecation_of(&'a self, _item: $item) -> Deprecation<'a> {
+                    Deprecation::NotDeprecated
+                }
+            }
+        )+
+    };
+}
+
+impl_not_deprecated!(
+    frame_metadata::v14::PalletMetadata<PortableForm>,
+    u8,
+    &'a frame_metadata::v14::StorageEntryMetadata<PortableForm>,
+    &'a frame_metadata::v14::PalletConstantMetadata<PortableForm>
+);
+impl_not_deprecated!(
+    frame_metadata::v15::PalletMetadata<PortableForm>,
+    u8,
+    &'a frame_metadata::v15::StorageEntryMetadata<PortableForm>,
+    &'a frame_metadata::v15::PalletConstantMetadata<PortableForm>
+);
+impl_not_deprecated!(
+    frame_metadata::v15::RuntimeApiMetadata<PortableForm>,
+    &'a frame_metadata::v15::RuntimeApiMethodMetadata<PortableForm>
+);
+
+// Deprecation of a call, given its index.
+impl<'a> DeprecationOf<'a, u8> for frame_metadata::v16::PalletMetadata<PortableForm> {
+    fn deprecation_of(&'a self, call_index: u8) -> Deprecation<'a> {
+        let call_deprecation = self
+            .calls
+            .as_ref()
+            .and_then(|c| c.deprecation_info.0.get(&call_index));
+        from_v16_variant_deprecation(call_deprecation)
+            .or(from_v16_item_deprecation(&self.deprecation_info))
+    }
+}
+
+macro_rules! impl_v16_deprecation {
+    ($container:ty, $($item:ty),+) => {
+        $(
+            impl<'a> DeprecationOf<'a, &'a $item> for $container {
+                fn deprecation_of(&'a self, item: &'a $item) -> Deprecation<'a> {
+                    from_v16_item_deprecation(&item.deprecation_info)
+                        .or(from_v16_item_deprecation(&self.deprecation_info))
+                }
+            }
+        )+
+    };
+}
+
+impl_v16_deprecation!(
+    frame_metadata::v16::PalletMetadata<PortableForm>,
+    frame_metadata::v16::StorageEntryMetadata<PortableForm>,
+    frame_metadata::v16::PalletConstantMetadata<PortableForm>,
+    frame_metadata::v16::PalletViewFunctionMetadata<PortableForm>
+);
+impl_v16_deprecation!(
+    frame_metadata::v16::RuntimeApiMetadata<PortableForm>,
+    frame_metadata::v16::RuntimeApiMethodMetadata<PortableForm>
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_encoder::{ExtrinsicEncodeError, encode_call_data_checked};
+    use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
+    use crate::methods::runtime_api_encoder::{
+        RuntimeApiInputsEncodeError, encode_runtime_api_inputs_checked,
+    };
+    use crate::methods::runtime_api_type_info::RuntimeApiTypeInfo;
+    use crate::methods::storage_encoder::{StorageKeyEncodeError, encode_storage_key_checked};
+    use crate::methods::storage_type_info::StorageTypeInfo;
+    use crate::methods::view_function_encoder::{
+        ViewFunctionInputsEncodeError, encode_view_function_inputs_checked,
+    };
+    use crate::methods::view_function_type_info::ViewFunctionTypeInfo;
+    use alloc::collections::BTreeMap;
+    use frame_metadata::v16::*;
+    use scale_info::{TypeInfo, build, meta_type};
+
+    // A pallet call enum with an old and a new call, neither taking arguments.
+    struct FooCall;
+    impl TypeInfo for FooCall {
+        type Identity = Self;
+        fn type_info() -> scale_info::Type {
+            scale_info::Type::builder()
+                .path(scale_info::Path::new("Call", "test"))
+                .variant(
+                    build::Variants::new()
+                        .variant("old", |v| v.index(0))
+                        .variant("new", |v| v.index(1)),
+                )
+        }
+    }
+
+    fn storage_entry(
+        name: &'static str,
+        deprecation_info: ItemDeprecationInfo,
+    ) -> StorageEntryMetadata {
+        StorageEntryMetadata {
+            name,
+            modifier: StorageEntryModifier::Default,
+            ty: StorageEntryType::Plain(meta_type::<u32>()),
+            default: vec![0, 0, 0, 0],
+            docs: vec![],
+            deprecation_info,
+        }
+    }
+
+    fn view_function(
+        name: &'static str,
+        deprecation_info: ItemDeprecationInfo,
+    ) -> PalletViewFunctionMetadata {
+        PalletViewFunctionMetadata {
+            id: [name.len() as u8; 32],
+            name,
+            inputs: vec![],
+            output: meta_type::<u32>(),
+            docs: vec![],
+            deprecation_info,
+        }
+    }
+
+    fn metadata() -> RuntimeMetadataV16 {
+        let pallet = PalletMetadata {
+            name: "Foo",
+            storage: Some(PalletStorageMetadata {
+                prefix: "Foo",
+                entries: vec![
+                    storage_entry(
+                        "Old",
+                        ItemDeprecationInfo::Deprecated {
+                            note: "Use New instead",
+                            since: Some("1.2.0"),
+                        },
+                    ),
+                    storage_entry("New", ItemDeprecationInfo::NotDeprecated),
+                ],
+            }),
+            calls: Some(PalletCallMetadata {
+                ty: meta_type::<FooCall>(),
+                deprecation_info: EnumDeprecationInfo(BTreeMap::from([(
+                    0,
+                    VariantDeprecationInfo::Deprecated {
+                        note: "Use new instead",
+                        since: None,
+                    },
+                )])),
+            }),
+            event: None,
+            constants: vec![],
+            error: None,
+            associated_types: vec![],
+            view_functions: vec![
+                view_function("old_view", ItemDeprecationInfo::DeprecatedWithoutNote),
+                view_function("new_view", ItemDeprecationInfo::NotDeprecated),
+            ],
+            index: 0,
+            docs: vec![],
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+
+        // The whole trait is deprecated, so its methods should be too.
+        let api = RuntimeApiMetadata {
+            name: "OldApi",
+            methods: vec![RuntimeApiMethodMetadata {
+                name: "method",
+                inputs: vec![],
+                output: meta_type::<u32>(),
+                docs: vec![],
+                deprecation_info: ItemDeprecationInfo::NotDeprecated,
+            }],
+            docs: vec![],
+            version: 1u32.into(),
+            deprecation_info: ItemDeprecationInfo::DeprecatedWithoutNote,
+        };
+
+        RuntimeMetadataV16::new(
+            vec![pallet],
+            ExtrinsicMetadata {
+                versions: vec![4],
+                address_ty: meta_type::<u32>(),
+                call_ty: meta_type::<u32>(),
+                signature_ty: meta_type::<u32>(),
+                transaction_extensions_by_version: Default::default(),
+                transaction_extensions: vec![],
+            },
+            vec![api],
+            OuterEnums {
+                call_enum_ty: meta_type::<u32>(),
+                event_enum_ty: meta_type::<u32>(),
+                error_enum_ty: meta_type::<u32>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        )
+    }
+
+    #[test]
+    fn deprecation_is_found() {
+        let metadata = metadata();
+
+        let old = metadata.storage_info("Foo", "Old").unwrap();
+        assert_eq!(
+            old.deprecation,
+            Deprecation::Deprecated {
+                note: Some(Cow::Borrowed("Use New instead")),
+                since: Some(Cow::Borrowed("1.2.0")),
+            }
+        );
+        assert_eq!(
+            old.deprecation.to_string(),
+            "deprecated since 1.2.0: Use New instead"
+        );
+
+        let new = metadata.storage_info("Foo", "New").unwrap();
+        assert_eq!(new.deprecation, Deprecation::NotDeprecated);
+
+        let old = metadata.extrinsic_call_info_by_name("Foo", "old").unwrap();
+        assert_eq!(
+            old.deprecation,
+            Deprecation::Deprecated {
+                note: Some(Cow::Borrowed("Use new instead")),
+                since: None,
+            }
+        );
+
+        let new = metadata.extrinsic_call_info_by_name("Foo", "new").unwrap();
+        assert_eq!(new.deprecation, Deprecation::NotDeprecated);
+
+        let old = metadata.view_function_info("Foo", "old_view").unwrap();
+        assert!(old.deprecation.is_deprecated());
+
+        let api = metadata.runtime_api_info("OldApi", "method").unwrap();
+        assert_eq!(
+            api.deprecation,
+            Deprecation::Deprecated {
+                note: None,
+                since: None
+            }
+        );
+    }
+
+    #[test]
+    fn deprecation_policy_is_applied_when_encoding() {
+        let metadata = metadata();
+        let encode_storage_key = |entry, policy| {
+            encode_storage_key_checked("Foo", entry, (), &metadata, &metadata.types, policy)
+        };
+
+        let (_, warning) = encode_storage_key("Old", DeprecationPolicy::Ignore).unwrap();
+        assert!(warning.is_none());
+
+        let (_, warning) = encode_storage_key("Old", DeprecationPolicy::Warn).unwrap();
+        let warning = warning.unwrap();
+        assert_eq!(warning.container, "Foo");
+        assert_eq!(warning.name, "Old");
+
+        assert!(matches!(
+            encode_storage_key("Old", DeprecationPolicy::Deny),
+            Err(StorageKeyEncodeError::Deprecated(_))
+        ));
+
+        let (_, warning) = encode_storage_key("New", DeprecationPolicy::Deny).unwrap();
+        assert!(warning.is_none());
+
+        assert!(matches!(
+            encode_runtime_api_inputs_checked(
+                "OldApi",
+                "method",
+                (),
+                &metadata,
+                &metadata.types,
+                DeprecationPolicy::Deny
+            ),
+            Err(RuntimeApiInputsEncodeError::Deprecated(_))
+        ));
+    }
+
+    #[test]
+    fn deprecation_policy_is_applied_when_encoding_call_data() {
+        let metadata = metadata();
+        let encode_call_data = |call, policy| {
+            encode_call_data_checked("Foo", call, &(), &metadata, &metadata.types, policy)
+        };
+
+        let (bytes, warning) = encode_call_data("old", DeprecationPolicy::Ignore).unwrap();
+        assert_eq!(bytes, vec![0, 0]);
+        assert!(warning.is_none());
+
+        let (bytes, warning) = encode_call_data("old", DeprecationPolicy::Warn).unwrap();
+        assert_eq!(bytes, vec![0, 0]);
+        let warning = warning.unwrap();
+        assert_eq!(warning.container, "Foo");
+        assert_eq!(warning.name, "old");
+        assert_eq!(
+            warning.deprecation,
+            Deprecation::Deprecated {
+                note: Some(Cow::Borrowed("Use new instead")),
+                since: None,
+            }
+        );
+
+        assert!(matches!(
+            encode_call_data("old", DeprecationPolicy::Deny),
+            Err(ExtrinsicEncodeError::Deprecated(w)) if w.name == "old"
+        ));
+
+        // Calls which are not deprecated are encoded regardless of the policy.
+        for policy in [DeprecationPolicy::Warn, DeprecationPolicy::Deny] {
+            let (bytes, warning) = encode_call_data("new", policy).unwrap();
+            assert_eq!(bytes, vec![0, 1]);
+            assert!(warning.is_none());
+        }
+    }
+
+    #[test]
+    fn deprecation_policy_is_applied_when_encoding_view_function_inputs() {
+        let metadata = metadata();
+        let encode_inputs = |function, policy| {
+            encode_view_function_inputs_checked(
+                "Foo",
+                function,
+                (),
+                &metadata,
+                &metadata.types,
+                policy,
+            )
+        };
+
+        let (_, warning) = encode_inputs("old_view", DeprecationPolicy::Ignore).unwrap();
+        assert!(warning.is_none());
+
+        let (_, warning) = encode_inputs("old_view", DeprecationPolicy::Warn).unwrap();
+        let warning = warning.unwrap();
+        assert_eq!(warning.container, "Foo");
+        assert_eq!(warning.name, "old_view");
+        assert_eq!(
+            warning.deprecation,
+            Deprecation::Deprecated {
+                note: None,
+                since: None
+            }
+        );
+
+        assert!(matches!(
+            encode_inputs("old_view", DeprecationPolicy::Deny),
+            Err(ViewFunctionInputsEncodeError::Deprecated(w)) if w.name == "old_view"
+        ));
+
+        for policy in [DeprecationPolicy::Warn, DeprecationPolicy::Deny] {
+            let (_, warning) = encode_inputs("new_view", policy).unwrap();
+            assert!(warning.is_none());
+        }
+    }
+}
@@ -0,0 +1,664 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::error_type_info::{ErrorInfoError, ErrorTypeInfo};
+use crate::methods::extrinsic_decoder::NamedArg;
+use crate::utils::{DecodeErrorTrace, decode_with_error_tracing};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Range;
+use parity_scale_codec::Decode;
+use scale_decode::visitor::TypeIdFor;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode a pallet error.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ModuleErrorDecodeError {
+    #[error("Cannot get error info:\n\n{0}")]
+    CannotGetInfo(ErrorInfoError<'static>),
+    #[error("Cannot decode error index byte:\n\n{0}")]
+    CannotDecodeErrorIndex(parity_scale_codec::Error),
+    #[error(
+        "Cannot decode error data for field {field_name} in {pallet_name}.{error_name}:\n\n{reason}"
+    )]
+    CannotDecodeErrorData {
+        pallet_name: String,
+        error_name: String,
+        field_name: String,
+        reason: DecodeErrorTrace,
+    },
+}
+
+/// An error returned trying to decode a `DispatchError`.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DispatchErrorDecodeError {
+    #[error("Cannot decode DispatchError:\n\n{0}")]
+    CannotDecodeDispatchError(DecodeErrorTrace),
+    #[error("Cannot decode the module error in DispatchError::Module:\n\n{0}")]
+    CannotDecodeModuleError(ModuleErrorDecodeError),
+}
+
+/// An owned variant of a [`ModuleError`].
+pub type ModuleErrorOwned<TypeId> = ModuleError<'static, TypeId>;
+
+/// Information about a pallet error.
+#[derive(Clone, Debug)]
+pub struct ModuleError<'info, TypeId> {
+    pallet_name: Cow<'info, str>,
+    pallet_index: u8,
+    error_name: Cow<'info, str>,
+    error_index: u8,
+    error_index_idx: u32,
+    fields: Vec<NamedArg<'info, TypeId>>,
+    docs: Vec<Cow<'info, str>>,
+}
+
+impl<'info, TypeId> ModuleError<'info, TypeId> {
+    /// Take ownership of the error, so that it no longer references
+    /// the error info.
+    pub fn into_owned(self) -> ModuleErrorOwned<TypeId> {
+        ModuleError {
+            pallet_name: Cow::Owned(self.pallet_name.into_owned()),
+            pallet_index: self.pallet_index,
+            error_name: Cow::Owned(self.error_name.into_owned()),
+            error_index: self.error_index,
+            error_index_idx: self.error_index_idx,
+            fields: self.fields.into_iter().map(|e| e.into_owned()).collect(),
+            docs: self
+                .docs
+                .into_iter()
+                .map(|d| Cow::Owned(d.into_owned()))
+                .collect(),
+        }
+    }
+
+    /// The name of the pallet that this error comes from.
+    pub fn pallet_name(&self) -> &str {
+        &self.pallet_name
+    }
+
+    /// The index of the pallet that this error comes from.
+    pub fn pallet_index(&self) -> u8 {
+        self.pallet_index
+    }
+
+    /// The name of the error.
+    pub fn error_name(&self) -> &str {
+        &self.error_name
+    }
+
+    /// The index of the error.
+    pub fn error_index(&self) -> u8 {
+        self.error_index
+    }
+
+    /// Iterate over the error field names and types.
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = &NamedArg<'info, TypeId>> {
+        self.fields.iter()
+    }
+
+    /// Return a range denoting the error fields. This does *not* include
+    /// the error index byte, or any padding bytes after the fields.
+    pub fn fields_range(&self) -> Range<usize> {
+        let start = (self.error_index_idx + 1) as usize;
+        let end = self
+            .fields()
+            .map(|a| a.range.end as usize)
+            .max()
+            .unwrap_or(start);
+
+        Range { start, end }
+    }
+
+    /// Iterate over the lines of documentation for this error.
+    pub fn docs(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.docs.iter().map(|d| &**d)
+    }
+
+    /// Map the field type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> ModuleError<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        ModuleError {
+            pallet_name: self.pallet_name,
+            pallet_index: self.pallet_index,
+            error_name: self.error_name,
+            error_index: self.error_index,
+            error_index_idx: self.error_index_idx,
+            fields: self
+                .fields
+                .into_iter()
+                .map(|s| s.map_type_id(&mut f))
+                .collect(),
+            docs: self.docs,
+        }
+    }
+}
+
+/// An owned variant of a [`DispatchError`].
+pub type DispatchErrorOwned<TypeId> = DispatchError<'static, TypeId>;
+
+/// A decoded `DispatchError`.
+#[derive(Clone, Debug)]
+pub enum DispatchError<'info, TypeId> {
+    /// A `DispatchError::Module` error, which has been decoded into
+    /// details about the specific pallet error.
+    Module(ModuleError<'info, TypeId>),
+    /// Any other `DispatchError` variant (for example `BadOrigin` or `Token`).
+    /// We hand back the name of the variant.
+    Other {
+        /// The name of the `DispatchError` variant.
+        name: String,
+    },
+}
+
+impl<'info, TypeId> DispatchError<'info, TypeId> {
+    /// Take ownership of the error, so that it no longer references
+    /// the error info.
+    pub fn into_owned(self) -> DispatchErrorOwned<TypeId> {
+        match self {
+            DispatchError::Module(e) => DispatchError::Module(e.into_owned()),
+            DispatchError::Other { name } => DispatchError::Other { name },
+        }
+    }
+
+    /// Map the field type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, f: F) -> DispatchError<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        match self {
+            DispatchError::Module(e) => DispatchError::Module(e.map_type_id(f)),
+            DispatchError::Other { name } => DispatchError::Other { name },
+        }
+    }
+}
+
+/// Decode the bytes of a pallet error, given the index of the pallet that it comes from.
+///
+/// The first of the error bytes is expected to be the error index, and any error fields
+/// follow this. In modern runtimes, pallet errors are encoded into a fixed number of bytes,
+/// and so any trailing padding bytes are ignored. The byte ranges handed back are relative
+/// to the start of the error bytes given.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::errors::{decode_module_error, ErrorTypeInfo};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// // The first error byte is the error index. Newer runtimes pad pallet
+/// // errors out to 4 bytes, and any such trailing bytes are ignored.
+/// let info = metadata.error_info_by_name("Balances", "InsufficientBalance").unwrap();
+/// let error_bytes = [info.error_index, 0, 0, 0];
+///
+/// let error = decode_module_error(info.pallet_index, &error_bytes, &metadata, &metadata.types).unwrap();
+///
+/// assert_eq!(error.pallet_name(), "Balances");
+/// assert_eq!(error.error_name(), "InsufficientBalance");
+/// assert_eq!(error.fields().len(), 0);
+/// assert!(error.docs().len() > 0);
+/// ```
+pub fn decode_module_error<'info, Info, Resolver>(
+    pallet_index: u8,
+    error_bytes: &[u8],
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<ModuleError<'info, Info::TypeId>, ModuleErrorDecodeError>
+where
+    Info: ErrorTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_module_error_at_offset(pallet_index, error_bytes, 0, info, type_resolver)
+}
+
+fn decode_module_error_at_offset<'info, Info, Resolver>(
+    pallet_index: u8,
+    error_bytes: &[u8],
+    offset: u32,
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<ModuleError<'info, Info::TypeId>, ModuleErrorDecodeError>
+where
+    Info: ErrorTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let cursor = &mut &*error_bytes;
+    let curr_idx = |cursor: &mut &[u8]| offset + (error_bytes.len() - cursor.len()) as u32;
+
+    let error_index_idx = curr_idx(cursor);
+    let error_index: u8 =
+        Decode::decode(cursor).map_err(ModuleErrorDecodeError::CannotDecodeErrorIndex)?;
+    let error_info = info
+        .error_info_by_index(pallet_index, error_index)
+        .map_err(|e| ModuleErrorDecodeError::CannotGetInfo(e.into_owned()))?;
+
+    let mut fields = Vec::with_capacity(error_info.args.len());
+    for arg in error_info.args {
+        let start_idx = curr_idx(cursor);
+        decode_with_error_tracing(
+            cursor,
+            arg.id.clone(),
+            type_resolver,
+            scale_decode::visitor::IgnoreVisitor::new(),
+        )
+        .map_err(|e| ModuleErrorDecodeError::CannotDecodeErrorData {
+            pallet_name: error_info.pallet_name.to_string(),
+            error_name: error_info.error_name.to_string(),
+            field_name: arg.name.to_string(),
+            reason: e,
+        })?;
+        let end_idx = curr_idx(cursor);
+
+        fields.push(NamedArg {
+            name: arg.name,
+            range: Range {
+                start: start_idx,
+                end: end_idx,
+            },
+            ty: arg.id,
+        })
+    }
+
+    Ok(ModuleError {
+        pallet_name: error_info.pallet_name,
+        pallet_index,
+        error_name: error_info.error_name,
+        error_index,
+        error_index_idx,
+        fields,
+        docs: error_info.docs,
+    })
+}
+
+/// Decode a `DispatchError`, given its type ID. If this is a `DispatchError::Module` error, then
+/// the pallet error is decoded too (see [`decode_module_error`]). The byte ranges handed back are
+/// relative to the start of the bytes that the cursor points to when this is called.
+///
+/// The modern `Module(ModuleError { index: u8, error: [u8; 4] })` shape, the earlier
+/// `Module(ModuleError { index: u8, error: u8 })` shape and the historic
+/// `Module { index: u8, error: u8 }` shape are all supported.
+pub fn decode_dispatch_error<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    dispatch_error_ty: Info::TypeId,
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<DispatchError<'info, Info::TypeId>, DispatchErrorDecodeError>
+where
+    Info: ErrorTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_dispatch_error_at_offset(cursor, 0, dispatch_error_ty, info, type_resolver)
+}
+
+/// Decode a `DispatchError`, offsetting the returned ranges by the offset given.
+pub(crate) fn decode_dispatch_error_at_offset<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    offset: u32,
+    dispatch_error_ty: Info::TypeId,
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<DispatchError<'info, Info::TypeId>, DispatchErrorDecodeError>
+where
+    Info: ErrorTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let initial_len = cursor.len();
+    let raw = decode_with_error_tracing(
+        cursor,
+        dispatch_error_ty,
+        type_resolver,
+        DispatchErrorVisitor(PhantomData),
+    )
+    .map_err(DispatchErrorDecodeError::CannotDecodeDispatchError)?;
+
+    match raw {
+        RawDispatchError::Other(name) => Ok(DispatchError::Other { name }),
+        RawDispatchError::Module(m) => {
+            let offset = offset + (initial_len - m.error_remaining) as u32;
+            let module_error =
+                decode_module_error_at_offset(m.index, m.error, offset, info, type_resolver)
+                    .map_err(DispatchErrorDecodeError::CannotDecodeModuleError)?;
+            Ok(DispatchError::Module(module_error))
+        }
+    }
+}
+
+enum RawDispatchError<'scale> {
+    Module(RawModuleError<'scale>),
+    Other(String),
+}
+
+struct RawModuleError<'scale> {
+    index: u8,
+    error: &'scale [u8],
+    // The number of bytes remaining in the input at the start of the error bytes,
+    // which lets us work out where the error bytes are in the original input.
+    error_remaining: usize,
+}
+
+struct DispatchErrorVisitor<R>(PhantomData<R>);
+
+impl<R: TypeResolver> scale_decode::Visitor for DispatchErrorVisitor<R> {
+    type Value<'scale, 'resolver> = RawDispatchError<'scale>;
+    type Error = scale_decode::Error;
+    type TypeResolver = R;
+
+    fn visit_variant<'scale, 'resolver>(
+        self,
+        value: &mut scale_decode::visitor::types::Variant<'scale, 'resolver, Self::TypeResolver>,
+        _type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        if value.name() != "Module" {
+            return Ok(RawDispatchError::Other(value.name().to_string()));
+        }
+
+        let fields = value.fields();
+        let has_inline_fields = fields.fields().iter().any(|f| f.name == Some("index"));
+
+        let module_error = if has_inline_fields {
+            // Module { index: u8, error: u8 }
+            module_error_from_composite(fields)?
+        } else {
+            // Module(ModuleError { index: u8, error: [u8; 4] }), or
+            // Module(ModuleError { index: u8, error: u8 }) in older runtimes. Use `decode_item` rather than iterating, so that the bytes handed to the
+            // visitor extend to the end of the input and `error_remaining` stays accurate.
+            fields
+                .decode_item(ModuleErrorVisitor(PhantomData))
+                .ok_or_else(|| {
+                    scale_decode::Error::custom_str("DispatchError::Module should have a field")
+                })??
+        };
+
+        Ok(RawDispatchError::Module(module_error))
+    }
+}
+
+struct ModuleErrorVisitor<R>(PhantomData<R>);
+
+impl<R: TypeResolver> scale_decode::Visitor for ModuleErrorVisitor<R> {
+    type Value<'scale, 'resolver> = RawModuleError<'scale>;
+    type Error = scale_decode::Error;
+    type TypeResolver = R;
+
+    fn visit_composite<'scale, 'resolver>(
+        self,
+        value: &mut scale_decode::visitor::types::Composite<'scale, 'resolver, Self::TypeResolver>,
+        _type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        module_error_from_composite(value)
+    }
+}
+
+fn module_error_from_composite<'scale, R: TypeResolver>(
+    value: &mut scale_decode::visitor::types::Composite<'scale, '_, R>,
+) -> Result<RawModuleError<'scale>, scale_decode::Error> {
+    let mut index = None;
+    let mut error = None;
+
+    loop {
+        let remaining = value.bytes_from_undecoded().len();
+        let Some(field) = value.next() else { break };
+        let field = field?;
+        match field.name() {
+            Some("index") => index = Some(field.decode_as_type::<u8>()?),
+            Some("error") => error = Some((field.bytes(), remaining)),
+            _ => {}
+        }
+    }
+
+    let (Some(index), Some((error, error_remaining))) = (index, error) else {
+        return Err(scale_decode::Error::custom_str(
+            "DispatchError::Module should have 'index' and 'error' fields",
+        ));
+    };
+
+    Ok(RawModuleError {
+        index,
+        error,
+        error_remaining,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::error_type_info::ErrorTypeInfo;
+    use frame_metadata::RuntimeMetadata;
+
+    #[test]
+    fn decode_modern_dispatch_error() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let dispatch_error_ty = metadata
+            .types
+            .types
+            .iter()
+            .find(|t| t.ty.path.segments == ["sp_runtime", "DispatchError"])
+            .unwrap();
+        let scale_info::TypeDef::Variant(dispatch_error_variants) = &dispatch_error_ty.ty.type_def
+        else {
+            panic!("DispatchError should be a variant");
+        };
+        let variant_index = |name: &str| {
+            dispatch_error_variants
+                .variants
+                .iter()
+                .find(|v| v.name == name)
+                .unwrap()
+                .index
+        };
+
+        // This runtime has the DispatchError::Module(ModuleError { index: u8, error: u8 })
+        // shape; see `decode_modern_dispatch_error_with_padded_module_error` for the
+        // `error: [u8; 4]` shape used by newer runtimes.
+        let info = metadata
+            .error_info_by_name("Balances", "InsufficientBalance")
+            .unwrap();
+        let bytes = [variant_index("Module"), info.pallet_index, info.error_index];
+
+        let cursor = &mut &bytes[..];
+        let err = decode_dispatch_error(cursor, dispatch_error_ty.id, &metadata, &metadata.types)
+            .unwrap();
+        assert!(cursor.is_empty());
+
+        let DispatchError::Module(err) = err else {
+            panic!("Expected a module error");
+        };
+        assert_eq!(err.pallet_name(), "Balances");
+        assert_eq!(err.error_name(), "InsufficientBalance");
+        assert_eq!(err.error_index(), info.error_index);
+        assert_eq!(err.fields_range(), 3..3);
+        assert_eq!(err.docs().collect::<Vec<_>>(), info.docs);
+
+        // DispatchError::BadOrigin:
+        let bytes = [variant_index("BadOrigin")];
+        let err = decode_dispatch_error(
+            &mut &bytes[..],
+            dispatch_error_ty.id,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        assert!(matches!(err, DispatchError::Other { name } if name == "BadOrigin"));
+    }
+
+    #[test]
+    fn decode_modern_dispatch_error_with_padded_module_error() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(mut metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        // Give ModuleError the `error: [u8; 4]` field that newer runtimes have.
+        let u8_ty = metadata
+            .types
+            .types
+            .iter()
+            .find(|t| {
+                t.ty.type_def == scale_info::TypeDef::Primitive(scale_info::TypeDefPrimitive::U8)
+            })
+            .unwrap()
+            .id;
+        let array_ty = metadata.types.types.len() as u32;
+        metadata.types.types.push(scale_info::PortableType {
+            id: array_ty,
+            ty: scale_info::Type {
+                path: Default::default(),
+                type_params: vec![],
+                type_def: scale_info::TypeDef::Array(scale_info::TypeDefArray {
+                    len: 4,
+                    type_param: u8_ty.into(),
+                }),
+                docs: vec![],
+            },
+        });
+        let module_error_ty = metadata
+            .types
+            .types
+            .iter_mut()
+            .find(|t| t.ty.path.segments == ["sp_runtime", "ModuleError"])
+            .unwrap();
+        let scale_info::TypeDef::Composite(module_error) = &mut module_error_ty.ty.type_def else {
+            panic!("ModuleError should be a composite");
+        };
+        let error_field = module_error
+            .fields
+            .iter_mut()
+            .find(|f| f.name.as_deref() == Some("error"))
+            .unwrap();
+        error_field.ty = array_ty.into();
+
+        let dispatch_error_ty = metadata
+            .types
+            .types
+            .iter()
+            .find(|t| t.ty.path.segments == ["sp_runtime", "DispatchError"])
+            .unwrap();
+        let scale_info::TypeDef::Variant(dispatch_error_variants) = &dispatch_error_ty.ty.type_def
+        else {
+            panic!("DispatchError should be a variant");
+        };
+        let module_variant_index = dispatch_error_variants
+            .variants
+            .iter()
+            .find(|v| v.name == "Module")
+            .unwrap()
+            .index;
+
+        // DispatchError::Module(ModuleError { index: u8, error: [u8; 4] }), with
+        // non-zero bytes following the error index, which should be ignored:
+        let info = metadata
+            .error_info_by_name("Balances", "InsufficientBalance")
+            .unwrap();
+        let bytes = [
+            module_variant_index,
+            info.pallet_index,
+            info.error_index,
+            1,
+            2,
+            3,
+        ];
+
+        let cursor = &mut &bytes[..];
+        let err = decode_dispatch_error(cursor, dispatch_error_ty.id, &metadata, &metadata.types)
+            .unwrap();
+        assert!(cursor.is_empty());
+
+        let DispatchError::Module(err) = err else {
+            panic!("Expected a module error");
+        };
+        assert_eq!(err.pallet_name(), "Balances");
+        assert_eq!(err.pallet_index(), info.pallet_index);
+        assert_eq!(err.error_name(), "InsufficientBalance");
+        assert_eq!(err.error_index(), info.error_index);
+        // The fields start after the error index byte and exclude the trailing bytes:
+        assert_eq!(err.fields_range(), 3..3);
+    }
+
+    #[test]
+    fn decode_legacy_dispatch_error() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        let metadata_types = crate::helpers::type_registry_from_metadata(&metadata).unwrap();
+        types.prepend(metadata_types);
+
+        let info = metadata
+            .error_info_by_name("Balances", "InsufficientBalance")
+            .unwrap();
+        assert!(info.args.is_empty());
+
+        // DispatchError::Module { index: u8, error: u8 }:
+        let bytes = [3, info.pallet_index, info.error_index];
+
+        let err = decode_dispatch_error(
+            &mut &bytes[..],
+            scale_info_legacy::LookupName::parse("DispatchError").unwrap(),
+            &metadata,
+            &types,
+        )
+        .unwrap();
+
+        let DispatchError::Module(err) = err else {
+            panic!("Expected a module error");
+        };
+        assert_eq!(err.pallet_name(), "Balances");
+        assert_eq!(err.pallet_index(), info.pallet_index);
+        assert_eq!(err.error_name(), "InsufficientBalance");
+        assert_eq!(err.fields_range(), 3..3);
+
+        // The error indexes line up with those in the synthesised `builtin::Error` type:
+        let builtin_error = scale_decode::visitor::decode_with_visitor(
+            &mut &bytes[1..],
+            scale_info_legacy::LookupName::parse("builtin::Error").unwrap(),
+            &types,
+            scale_value::scale::ValueVisitor::new(),
+        )
+        .unwrap();
+        let scale_value::ValueDef::Variant(v) = builtin_error.value else {
+            panic!("Expected variant");
+        };
+        assert_eq!(v.name, "Balances");
+    }
+}
@@ -0,0 +1,451 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Implementations of this are responsible for handing back the information we need to
+/// decode pallet errors. This is expected to be implemented for runtime metadata types or
+/// derivatives thereof where needed.
+pub trait ErrorTypeInfo {
+    /// The type of type IDs that we are using to obtain type information.
+    type TypeId;
+
+    /// Get the information about a given error, given u8 pallet and error indexes.
+    fn error_info_by_index(
+        &self,
+        pallet_index: u8,
+        error_index: u8,
+    ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>>;
+
+    /// Get the information about a given error, given the pallet and error name.
+    fn error_info_by_name(
+        &self,
+        pallet_name: &str,
+        error_name: &str,
+    ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>>;
+}
+
+/// An error returned trying to access pallet error type information.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ErrorInfoError<'a> {
+    #[error("Pallet with index {index} not found")]
+    PalletNotFound { index: u8 },
+    #[error("Pallet with name '{name}' not found")]
+    PalletNotFoundByName { name: Cow<'a, str> },
+    #[error(
+        "Error with index {index} not found in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    ErrorNotFound {
+        index: u8,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[error(
+        "Error with name '{error_name}' not found in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    ErrorNotFoundByName {
+        error_name: Cow<'a, str>,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[error(
+        "Cannot find errors type with id {id} in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    ErrorsTypeNotFound {
+        id: u32,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[error(
+        "Errors type with id {id} should be a variant in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    ErrorsTypeShouldBeVariant {
+        id: u32,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[cfg(feature = "legacy")]
+    #[error("The metadata was not SCALE decoded and so cannot be read")]
+    MetadataNotDecoded,
+}
+
+impl ErrorInfoError<'_> {
+    /// Take ownership of this error.
+    pub fn into_owned(self) -> ErrorInfoError<'static> {
+        match self {
+            ErrorInfoError::PalletNotFound { index } => ErrorInfoError::PalletNotFound { index },
+            ErrorInfoError::PalletNotFoundByName { name } => ErrorInfoError::PalletNotFoundByName {
+                name: Cow::Owned(name.into_owned()),
+            },
+            ErrorInfoError::ErrorNotFound {
+                index,
+                pallet_index,
+                pallet_name,
+            } => ErrorInfoError::ErrorNotFound {
+                index,
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            ErrorInfoError::ErrorNotFoundByName {
+                error_name,
+                pallet_index,
+                pallet_name,
+            } => ErrorInfoError::ErrorNotFoundByName {
+                error_name: Cow::Owned(error_name.into_owned()),
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            ErrorInfoError::ErrorsTypeNotFound {
+                id,
+                pallet_index,
+                pallet_name,
+            } => ErrorInfoError::ErrorsTypeNotFound {
+                id,
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            ErrorInfoError::ErrorsTypeShouldBeVariant {
+                id,
+                pallet_index,
+                pallet_name,
+            } => ErrorInfoError::ErrorsTypeShouldBeVariant {
+                id,
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            #[cfg(feature = "legacy")]
+            ErrorInfoError::MetadataNotDecoded => ErrorInfoError::MetadataNotDecoded,
+        }
+    }
+}
+
+/// Information about a single pallet error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorInfo<'info, TypeId> {
+    /// Index of the pallet.
+    pub pallet_index: u8,
+    /// Index of the error.
+    pub error_index: u8,
+    /// Name of the pallet.
+    pub pallet_name: Cow<'info, str>,
+    /// Name of the error.
+    pub error_name: Cow<'info, str>,
+    /// Names and types of each of the error fields. Errors in
+    /// legacy (pre-V14) metadata never have any fields.
+    pub args: Vec<ErrorInfoArg<'info, TypeId>>,
+    /// Documentation for the error.
+    pub docs: Vec<Cow<'info, str>>,
+}
+
+/// A field in some pallet error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorInfoArg<'info, TypeId> {
+    /// Field name. This is empty if the field has no name.
+    pub name: Cow<'info, str>,
+    /// Field type ID.
+    pub id: TypeId,
+}
+
+macro_rules! impl_error_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl ErrorTypeInfo for $path {
+            type TypeId = u32;
+            fn error_info_by_index(
+                &self,
+                pallet_index: u8,
+                error_index: u8,
+            ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>> {
+                let pallet = self
+                    .pallets
+                    .iter()
+                    .find(|p| p.index == pallet_index)
+                    .ok_or(ErrorInfoError::PalletNotFound {
+                        index: pallet_index,
+                    })?;
+
+                let pallet_name = &pallet.name;
+
+                let errors_id = pallet
+                    .error
+                    .as_ref()
+                    .ok_or_else(|| ErrorInfoError::ErrorNotFound {
+                        index: error_index,
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?
+                    .ty
+                    .id;
+
+                let errors_enum =
+                    get_errors_enum(&self.types, errors_id, pallet_index, pallet_name)?;
+
+                let error_variant = errors_enum
+                    .variants
+                    .iter()
+                    .find(|v| v.index == error_index)
+                    .ok_or_else(|| ErrorInfoError::ErrorNotFound {
+                        index: error_index,
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?;
+
+                Ok(error_info_from_variant(
+                    pallet_index,
+                    pallet_name,
+                    error_variant,
+                ))
+            }
+            fn error_info_by_name(
+                &self,
+                pallet_name: &str,
+                error_name: &str,
+            ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>> {
+                use alloc::string::ToString;
+
+                let pallet = self
+                    .pallets
+                    .iter()
+                    .find(|p| p.name == pallet_name)
+                    .ok_or_else(|| ErrorInfoError::PalletNotFoundByName {
+                        name: Cow::Owned(pallet_name.to_string()),
+                    })?;
+
+                let pallet_index = pallet.index;
+                let pallet_name = &pallet.name;
+
+                let errors_id = pallet
+                    .error
+                    .as_ref()
+                    .ok_or_else(|| ErrorInfoError::ErrorNotFoundByName {
+                        error_name: Cow::Owned(error_name.to_string()),
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?
+                    .ty
+                    .id;
+
+                let errors_enum =
+                    get_errors_enum(&self.types, errors_id, pallet_index, pallet_name)?;
+
+                let error_variant = errors_enum
+                    .variants
+                    .iter()
+                    .find(|v| v.name == error_name)
+                    .ok_or_else(|| ErrorInfoError::ErrorNotFoundByName {
+                        error_name: Cow::Owned(error_name.to_string()),
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?;
+
+                Ok(error_info_from_variant(
+                    pallet_index,
+                    pallet_name,
+                    error_variant,
+                ))
+            }
+        }
+    };
+}
+
+impl_error_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_error_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_error_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
+fn get_errors_enum<'a>(
+    types: &'a scale_info::PortableRegistry,
+    errors_id: u32,
+    pallet_index: u8,
+    pallet_name: &'a str,
+) -> Result<&'a scale_info::TypeDefVariant<scale_info::form::PortableForm>, ErrorInfoError<'a>> {
+    let errors_ty = types
+        .resolve(errors_id)
+        .ok_or(ErrorInfoError::ErrorsTypeNotFound {
+            id: errors_id,
+            pallet_index,
+            pallet_name: Cow::Borrowed(pallet_name),
+        })?;
+
+    match &errors_ty.type_def {
+        scale_info::TypeDef::Variant(v) => Ok(v),
+        _ => Err(ErrorInfoError::ErrorsTypeShouldBeVariant {
+            id: errors_id,
+            pallet_index,
+            pallet_name: Cow::Borrowed(pallet_name),
+        }),
+    }
+}
+
+fn error_info_from_variant<'a>(
+    pallet_index: u8,
+    pallet_name: &'a str,
+    variant: &'a scale_info::Variant<scale_info::form::PortableForm>,
+) -> ErrorInfo<'a, u32> {
+    let args = variant
+        .fields
+        .iter()
+        .map(|f| ErrorInfoArg {
+            name: f
+                .name
+                .as_ref()
+                .map(|n| Cow::Borrowed(&**n))
+                .unwrap_or(Cow::Owned(String::new())),
+            id: f.ty.id,
+        })
+        .collect();
+
+    let docs = variant.docs.iter().map(|d| Cow::Borrowed(&**d)).collect();
+
+    ErrorInfo {
+        pallet_index,
+        error_index: variant.index,
+        pallet_name: Cow::Borrowed(pallet_name),
+        error_name: Cow::Borrowed(&variant.name),
+        args,
+        docs,
+    }
+}
+
+#[cfg(feature = "legacy")]
+const _: () = {
+    use crate::utils::as_decoded_str;
+    use frame_metadata::decode_different::DecodeDifferent;
+    use scale_info_legacy::LookupName;
+
+    fn as_decoded<A, B>(item: &DecodeDifferent<A, B>) -> Result<&B, ErrorInfoError<'static>> {
+        crate::utils::as_decoded(item).map_err(|_| ErrorInfoError::MetadataNotDecoded)
+    }
+
+    // Given some module and its position in the list of modules, return the index that
+    // its errors are encoded with. This lines up with the indexes used in the `builtin::Error`
+    // type that `type_registry_from_metadata` synthesises: prior to V12, every module counts
+    // towards the index (errors are not optional), and from V12 each module has an explicit
+    // index that is used instead.
+    macro_rules! legacy_error_pallet_index {
+        ($module:ident, $nth:expr $(, $builtin_index:ident)?) => {{
+            let _ = &$module;
+            let idx = $nth as u8;
+            $(
+                let $builtin_index = true;
+                let idx = if $builtin_index { $module.index } else { idx };
+            )?
+            idx
+        }};
+    }
+
+    macro_rules! legacy_error_info_body {
+        ($pallet_index:ident, $error_index:ident, $pallet_name:ident, $error:ident) => {{
+            let error_name: &str = as_decoded_str(&$error.name);
+            let docs = as_decoded(&$error.documentation)?
+                .iter()
+                .map(|d| {
+                    let d: &str = d.as_ref();
+                    Cow::Borrowed(d)
+                })
+                .collect();
+
+            Ok(ErrorInfo {
+                pallet_index: $pallet_index,
+                error_index: $error_index,
+                pallet_name: Cow::Borrowed($pallet_name),
+                error_name: Cow::Borrowed(error_name),
+                // Legacy errors never have any fields.
+                args: Vec::new(),
+                docs,
+            })
+        }};
+    }
+
+    macro_rules! impl_error_info_for_v8_to_v13 {
+        ($path:path $(, $builtin_index:ident)?) => {
+            impl ErrorTypeInfo for $path {
+                type TypeId = LookupName;
+                fn error_info_by_index(
+                    &self,
+                    pallet_index: u8,
+                    error_index: u8,
+                ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>> {
+                    let modules = as_decoded(&self.modules)?;
+
+                    let m = modules
+                        .iter()
+                        .enumerate()
+                        .find(|(n, m)| {
+                            legacy_error_pallet_index!(m, *n $(, $builtin_index)?) == pallet_index
+                        })
+                        .map(|(_, m)| m)
+                        .ok_or(ErrorInfoError::PalletNotFound {
+                            index: pallet_index,
+                        })?;
+
+                    let m_name: &str = as_decoded_str(&m.name);
+
+                    let error = as_decoded(&m.errors)?
+                        .get(error_index as usize)
+                        .ok_or_else(|| ErrorInfoError::ErrorNotFound {
+                            index: error_index,
+                            pallet_index,
+                            pallet_name: Cow::Borrowed(m_name),
+                        })?;
+
+                    legacy_error_info_body!(pallet_index, error_index, m_name, error)
+                }
+                fn error_info_by_name(
+                    &self,
+                    pallet_name: &str,
+                    error_name: &str,
+                ) -> Result<ErrorInfo<'_, Self::TypeId>, ErrorInfoError<'_>> {
+                    use alloc::string::ToString;
+
+                    let modules = as_decoded(&self.modules)?;
+
+                    let (nth, m) = modules
+                        .iter()
+                        .enumerate()
+                        .find(|(_, m)| as_decoded_str(&m.name) == pallet_name)
+                        .ok_or_else(|| ErrorInfoError::PalletNotFoundByName {
+                            name: Cow::Owned(pallet_name.to_string()),
+                        })?;
+
+                    let m_name: &str = as_decoded_str(&m.name);
+                    let pallet_index = legacy_error_pallet_index!(m, nth $(, $builtin_index)?);
+
+                    let (error_index, error) = as_decoded(&m.errors)?
+                        .iter()
+                        .enumerate()
+                        .find(|(_, e)| as_decoded_str(&e.name) == error_name)
+                        .ok_or_else(|| ErrorInfoError::ErrorNotFoundByName {
+                            error_name: Cow::Owned(error_name.to_string()),
+                            pallet_index,
+                            pallet_name: Cow::Borrowed(m_name),
+                        })?;
+
+                    let error_index = error_index as u8;
+                    legacy_error_info_body!(pallet_index, error_index, m_name, error)
+                }
+            }
+        };
+    }
+
+    impl_error_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_error_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_error_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_error_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_error_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12, use_builtin_index);
+    impl_error_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13, use_builtin_index);
+};
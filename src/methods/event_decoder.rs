@@ -0,0 +1,317 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::event_type_info::{EventInfoError, EventTypeInfo};
+use crate::methods::extrinsic_decoder::NamedArg;
+use crate::utils::{DecodeErrorTrace, decode_with_error_tracing};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use parity_scale_codec::Decode;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode event bytes.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum EventDecodeError {
+    #[error("Cannot get event info:\n\n{0}")]
+    CannotGetInfo(EventInfoError<'static>),
+    #[error("Cannot decode pallet index byte:\n\n{0}")]
+    CannotDecodePalletIndex(parity_scale_codec::Error),
+    #[error("Cannot decode event index byte:\n\n{0}")]
+    CannotDecodeEventIndex(parity_scale_codec::Error),
+    #[error(
+        "Cannot decode event data for field {field_name} in {pallet_name}.{event_name}:\n\n{reason}"
+    )]
+    CannotDecodeEventData {
+        pallet_name: String,
+        event_name: String,
+        field_name: String,
+        reason: DecodeErrorTrace,
+    },
+}
+
+/// An owned variant of an Event.
+pub type EventOwned<TypeId> = Event<'static, TypeId>;
+
+/// Information about the event.
+#[derive(Clone, Debug)]
+pub struct Event<'info, TypeId> {
+    pallet_name: Cow<'info, str>,
+    pallet_index: u8,
+    pallet_index_idx: u32,
+    event_name: Cow<'info, str>,
+    event_index: u8,
+    fields: Vec<NamedArg<'info, TypeId>>,
+}
+
+impl<'info, TypeId> Event<'info, TypeId> {
+    /// Take ownership of the event, so that it no longer references
+    /// the event info.
+    pub fn into_owned(self) -> EventOwned<TypeId> {
+        Event {
+            pallet_name: Cow::Owned(self.pallet_name.into_owned()),
+            pallet_index: self.pallet_index,
+            pallet_index_idx: self.pallet_index_idx,
+            event_name: Cow::Owned(self.event_name.into_owned()),
+            event_index: self.event_index,
+            fields: self.fields.into_iter().map(|e| e.into_owned()).collect(),
+        }
+    }
+
+    /// The name of the pallet that this event was emitted from.
+    pub fn pallet_name(&self) -> &str {
+        &self.pallet_name
+    }
+
+    /// The index of the pallet that this event was emitted from.
+    pub fn pallet_index(&self) -> u8 {
+        self.pallet_index
+    }
+
+    /// The name of the event.
+    pub fn event_name(&self) -> &str {
+        &self.event_name
+    }
+
+    /// The index of the event.
+    pub fn event_index(&self) -> u8 {
+        self.event_index
+    }
+
+    /// Iterate over the event field names and types.
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = &NamedArg<'info, TypeId>> {
+        self.fields.iter()
+    }
+
+    /// Return a range denoting the event bytes. This includes the pallet index and
+    /// event index bytes and then any encoded fields for the event.
+    pub fn range(&self) -> Range<usize> {
+        let start = self.pallet_index_idx as usize;
+        let end = self.fields_range().end;
+        Range { start, end }
+    }
+
+    /// Return a range denoting the event fields. This does *not* include
+    /// the pallet index and event index bytes.
+    pub fn fields_range(&self) -> Range<usize> {
+        let start = (self.pallet_index_idx + 2) as usize;
+        let end = self
+            .fields()
+            .map(|a| a.range.end as usize)
+            .max()
+            .unwrap_or(start);
+
+        Range { start, end }
+    }
+
+    /// Map the field type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> Event<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        Event {
+            pallet_name: self.pallet_name,
+            pallet_index: self.pallet_index,
+            pallet_index_idx: self.pallet_index_idx,
+            event_name: self.event_name,
+            event_index: self.event_index,
+            fields: self
+                .fields
+                .into_iter()
+                .map(|s| s.map_type_id(&mut f))
+                .collect(),
+        }
+    }
+}
+
+/// Decode an event, returning information about it. The byte ranges handed back are relative
+/// to the start of the bytes that the cursor points to when this is called.
+///
+/// This information can be used to then decode each of the event fields to concrete types.
+///
+/// # Example
+///
+/// Here, we decode a `Balances.Deposit` event and then each of its fields into
+/// [`scale_value::Value`]s.
+///
+/// ```rust
+/// use frame_decode::events::{decode_event, EventTypeInfo};
+/// use frame_decode::helpers::decode_with_visitor;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::{Decode, Encode};
+/// use scale_value::scale::ValueVisitor;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// // Encode some event bytes to decode:
+/// let deposit_info = metadata.event_info_by_name("Balances", "Deposit").unwrap();
+/// let mut event_bytes = vec![deposit_info.pallet_index, deposit_info.event_index];
+/// [1u8; 32].encode_to(&mut event_bytes);
+/// 12345u128.encode_to(&mut event_bytes);
+///
+/// // Decode the event, returning information about it:
+/// let event = decode_event(&mut &*event_bytes, &metadata, &metadata.types).unwrap();
+///
+/// assert_eq!(event.pallet_name(), "Balances");
+/// assert_eq!(event.event_name(), "Deposit");
+///
+/// // Decode the fields to scale_value::Value's.
+/// for field in event.fields() {
+///     let field_name = field.name();
+///     let field_bytes = &event_bytes[field.range()];
+///     let field_value = decode_with_visitor(
+///         &mut &*field_bytes,
+///         *field.ty(),
+///         &metadata.types,
+///         ValueVisitor::new()
+///     ).unwrap();
+/// }
+/// ```
+pub fn decode_event<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<Event<'info, Info::TypeId>, EventDecodeError>
+where
+    Info: EventTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_event_at_offset(cursor, 0, info, type_resolver)
+}
+
+/// Decode an event, offsetting the returned ranges by the offset given.
+pub(crate) fn decode_event_at_offset<'info, Info, Resolver>(
+    cursor: &mut &[u8],
+    offset: u32,
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<Event<'info, Info::TypeId>, EventDecodeError>
+where
+    Info: EventTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let bytes = *cursor;
+    let curr_idx = |cursor: &mut &[u8]| offset + (bytes.len() - cursor.len()) as u32;
+
+    let pallet_index_idx = curr_idx(cursor);
+    let pallet_index: u8 =
+        Decode::decode(cursor).map_err(EventDecodeError::CannotDecodePalletIndex)?;
+    let event_index: u8 =
+        Decode::decode(cursor).map_err(EventDecodeError::CannotDecodeEventIndex)?;
+    let event_info = info
+        .event_info_by_index(pallet_index, event_index)
+        .map_err(|e| EventDecodeError::CannotGetInfo(e.into_owned()))?;
+
+    let mut fields = Vec::with_capacity(event_info.args.len());
+    for arg in event_info.args {
+        let start_idx = curr_idx(cursor);
+        decode_with_error_tracing(
+            cursor,
+            arg.id.clone(),
+            type_resolver,
+            scale_decode::visitor::IgnoreVisitor::new(),
+        )
+        .map_err(|e| EventDecodeError::CannotDecodeEventData {
+            pallet_name: event_info.pallet_name.to_string(),
+            event_name: event_info.event_name.to_string(),
+            field_name: arg.name.to_string(),
+            reason: e,
+        })?;
+        let end_idx = curr_idx(cursor);
+
+        fields.push(NamedArg {
+            name: arg.name,
+            range: Range {
+                start: start_idx,
+                end: end_idx,
+            },
+            ty: arg.id,
+        })
+    }
+
+    Ok(Event {
+        pallet_name: event_info.pallet_name,
+        pallet_index,
+        pallet_index_idx,
+        event_name: event_info.event_name,
+        event_index,
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::event_type_info::EventTypeInfo;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Encode;
+    use scale_value::scale::ValueVisitor;
+
+    #[test]
+    fn decode_legacy_event() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        let metadata_types = crate::helpers::type_registry_from_metadata(&metadata).unwrap();
+        types.prepend(metadata_types);
+
+        let info = metadata.event_info_by_name("Balances", "Transfer").unwrap();
+        let mut bytes = vec![info.pallet_index, info.event_index];
+        [1u8; 32].encode_to(&mut bytes);
+        [2u8; 32].encode_to(&mut bytes);
+        1000u128.encode_to(&mut bytes);
+
+        let event = decode_event(&mut &*bytes, &metadata, &types).unwrap();
+        assert_eq!(event.pallet_name(), "Balances");
+        assert_eq!(event.event_name(), "Transfer");
+        assert_eq!(event.range(), 0..bytes.len());
+        assert_eq!(event.fields().len(), 3);
+
+        let amount = event.fields().last().unwrap();
+        let amount = scale_decode::visitor::decode_with_visitor(
+            &mut &bytes[amount.range()],
+            amount.ty().clone(),
+            &types,
+            ValueVisitor::new(),
+        )
+        .unwrap();
+        assert_eq!(amount.as_u128(), Some(1000));
+
+        // Events are also available via the synthesised `builtin::Event` type:
+        let builtin_event = scale_decode::visitor::decode_with_visitor(
+            &mut &*bytes,
+            scale_info_legacy::LookupName::parse("builtin::Event").unwrap(),
+            &types,
+            ValueVisitor::new(),
+        )
+        .unwrap();
+        let scale_value::ValueDef::Variant(v) = builtin_event.value else {
+            panic!("Expected variant");
+        };
+        assert_eq!(v.name, "Balances");
+    }
+}
@@ -0,0 +1,647 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::event_decoder::{Event, EventDecodeError, decode_event_at_offset};
+use crate::methods::event_type_info::EventTypeInfo;
+use crate::methods::storage_type_info::{StorageInfoError, StorageTypeInfo};
+use crate::utils::{DecodeErrorTrace, decode_with_error_tracing};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::Range;
+use parity_scale_codec::{Compact, Decode};
+use scale_decode::visitor::TypeIdFor;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode event records.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum EventRecordsDecodeError<TypeId> {
+    #[error("Cannot get System.Events storage info:\n\n{0}")]
+    CannotGetInfo(StorageInfoError<'static>),
+    #[error("Cannot resolve type {ty:?}: {reason}")]
+    CannotResolveType { ty: TypeId, reason: String },
+    #[error("The System.Events type {ty:?} was expected to be a sequence of event records.")]
+    UnexpectedEventsType { ty: TypeId },
+    #[error(
+        "The event record type {ty:?} was expected to be a struct containing at least a phase and an event."
+    )]
+    UnexpectedEventRecordType { ty: TypeId },
+    #[error("Cannot decode the number of event records:\n\n{0}")]
+    CannotDecodeLength(parity_scale_codec::Error),
+    #[error("Cannot decode the phase of event record {record_index}:\n\n{reason}")]
+    CannotDecodePhase {
+        record_index: usize,
+        reason: DecodeErrorTrace,
+    },
+    #[error("Cannot decode the event in event record {record_index}:\n\n{reason}")]
+    CannotDecodeEvent {
+        record_index: usize,
+        reason: Box<EventDecodeError>,
+    },
+    #[error("Cannot decode field {field_name} of event record {record_index}:\n\n{reason}")]
+    CannotDecodeField {
+        record_index: usize,
+        field_name: String,
+        reason: DecodeErrorTrace,
+    },
+    #[error("There were {num_leftover_bytes} leftover bytes after decoding all event records.")]
+    LeftoverBytes { num_leftover_bytes: usize },
+}
+
+/// The phase of block execution that an event was emitted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Phase {
+    /// The event was emitted while applying the extrinsic at the given index.
+    ApplyExtrinsic(u32),
+    /// The event was emitted during block finalization.
+    Finalization,
+    /// The event was emitted during block initialization.
+    Initialization,
+}
+
+/// An owned variant of an [`EventRecord`].
+pub type EventRecordOwned<TypeId> = EventRecord<'static, TypeId>;
+
+/// Information about a single event record.
+#[derive(Clone, Debug)]
+pub struct EventRecord<'info, TypeId> {
+    range: Range<u32>,
+    phase: Phase,
+    event: Event<'info, TypeId>,
+    topics: Option<(Range<u32>, TypeId)>,
+}
+
+impl<'info, TypeId> EventRecord<'info, TypeId> {
+    /// Take ownership of the event record, so that it no longer references
+    /// the event info.
+    pub fn into_owned(self) -> EventRecordOwned<TypeId> {
+        EventRecord {
+            range: self.range,
+            phase: self.phase,
+            event: self.event.into_owned(),
+            topics: self.topics,
+        }
+    }
+
+    /// Return a range denoting the bytes of this event record.
+    pub fn range(&self) -> Range<usize> {
+        Range {
+            start: self.range.start as usize,
+            end: self.range.end as usize,
+        }
+    }
+
+    /// The phase of block execution that this event was emitted in.
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// The event itself.
+    pub fn event(&self) -> &Event<'info, TypeId> {
+        &self.event
+    }
+
+    /// The name of the pallet that this event was emitted from.
+    pub fn pallet_name(&self) -> &str {
+        self.event.pallet_name()
+    }
+
+    /// The name of the event.
+    pub fn event_name(&self) -> &str {
+        self.event.event_name()
+    }
+
+    /// Return a range denoting the topics bytes. This is `None` if the event
+    /// record does not contain any topics, which is the case in early runtimes.
+    pub fn topics_range(&self) -> Option<Range<usize>> {
+        self.topics.as_ref().map(|(range, _)| Range {
+            start: range.start as usize,
+            end: range.end as usize,
+        })
+    }
+
+    /// The type ID of the topics, if they exist in this event record.
+    pub fn topics_type(&self) -> Option<&TypeId> {
+        self.topics.as_ref().map(|(_, ty)| ty)
+    }
+
+    /// Map the type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> EventRecord<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        EventRecord {
+            range: self.range,
+            phase: self.phase,
+            event: self.event.map_type_id(&mut f),
+            topics: self.topics.map(|(range, ty)| (range, f(ty))),
+        }
+    }
+}
+
+/// An iterator over the event records in some `System.Events` storage value. This is
+/// handed back from [`decode_event_records`] and [`decode_event_records_with_info`].
+pub struct EventRecords<'bytes, 'info, 'resolver, Info, Resolver: TypeResolver> {
+    bytes: &'bytes [u8],
+    cursor: &'bytes [u8],
+    record_index: usize,
+    num_records: usize,
+    finished: bool,
+    record_ty: Resolver::TypeId,
+    fields: Vec<(RecordField, String, Resolver::TypeId)>,
+    info: &'info Info,
+    type_resolver: &'resolver Resolver,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RecordField {
+    Phase,
+    Event,
+    Topics,
+    Other,
+}
+
+impl<Info, Resolver: TypeResolver> EventRecords<'_, '_, '_, Info, Resolver> {
+    /// The total number of event records, including those already iterated over.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.num_records
+    }
+}
+
+impl<'info, Info, Resolver> Iterator for EventRecords<'_, 'info, '_, Info, Resolver>
+where
+    Info: EventTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    type Item = Result<EventRecord<'info, Info::TypeId>, EventRecordsDecodeError<Info::TypeId>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if self.record_index == self.num_records {
+            self.finished = true;
+            if !self.cursor.is_empty() {
+                return Some(Err(EventRecordsDecodeError::LeftoverBytes {
+                    num_leftover_bytes: self.cursor.len(),
+                }));
+            }
+            return None;
+        }
+
+        let res = self.decode_next();
+        if res.is_err() {
+            self.finished = true;
+        }
+        self.record_index += 1;
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = if self.finished {
+            0
+        } else {
+            self.num_records - self.record_index
+        };
+        // The number of records comes from the input and may be bogus, and an error stops
+        // iteration early, so we can't promise any lower bound. We may hand back one extra
+        // error if there are leftover bytes.
+        (0, Some(remaining.saturating_add(1)))
+    }
+}
+
+impl<'info, Info, Resolver> EventRecords<'_, 'info, '_, Info, Resolver>
+where
+    Info: EventTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    fn decode_next(
+        &mut self,
+    ) -> Result<EventRecord<'info, Info::TypeId>, EventRecordsDecodeError<Info::TypeId>> {
+        let record_index = self.record_index;
+        let bytes_len = self.bytes.len();
+        let curr_idx = |cursor: &[u8]| (bytes_len - cursor.len()) as u32;
+        let cursor = &mut self.cursor;
+
+        let start_idx = curr_idx(cursor);
+        let mut phase = None;
+        let mut event = None;
+        let mut topics = None;
+
+        for (field, field_name, ty) in &self.fields {
+            match field {
+                RecordField::Phase => {
+                    let p = decode_with_error_tracing(
+                        cursor,
+                        ty.clone(),
+                        self.type_resolver,
+                        PhaseVisitor(PhantomData),
+                    )
+                    .map_err(|e| {
+                        EventRecordsDecodeError::CannotDecodePhase {
+                            record_index,
+                            reason: e,
+                        }
+                    })?;
+                    phase = Some(p);
+                }
+                RecordField::Event => {
+                    let offset = curr_idx(cursor);
+                    let e = decode_event_at_offset(cursor, offset, self.info, self.type_resolver)
+                        .map_err(|e| EventRecordsDecodeError::CannotDecodeEvent {
+                        record_index,
+                        reason: Box::new(e),
+                    })?;
+                    event = Some(e);
+                }
+                RecordField::Topics | RecordField::Other => {
+                    let field_start_idx = curr_idx(cursor);
+                    decode_with_error_tracing(
+                        cursor,
+                        ty.clone(),
+                        self.type_resolver,
+                        scale_decode::visitor::IgnoreVisitor::new(),
+                    )
+                    .map_err(|e| {
+                        EventRecordsDecodeError::CannotDecodeField {
+                            record_index,
+                            field_name: field_name.clone(),
+                            reason: e,
+                        }
+                    })?;
+                    let field_end_idx = curr_idx(cursor);
+
+                    if *field == RecordField::Topics {
+                        topics = Some((field_start_idx..field_end_idx, ty.clone()));
+                    }
+                }
+            }
+        }
+
+        let end_idx = curr_idx(cursor);
+
+        let (Some(phase), Some(event)) = (phase, event) else {
+            return Err(EventRecordsDecodeError::UnexpectedEventRecordType {
+                ty: self.record_ty.clone(),
+            });
+        };
+
+        Ok(EventRecord {
+            range: start_idx..end_idx,
+            phase,
+            event,
+            topics,
+        })
+    }
+}
+
+/// Decode the bytes of a `System.Events` storage value, handing back an iterator over each of
+/// the event records within it. Each record contains the [`Phase`] that the event was emitted in,
+/// the [`Event`] itself and, if present, the range of bytes containing the topics.
+///
+/// All byte ranges are relative to the start of the given bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::events::{decode_event_records, EventTypeInfo, Phase};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::{Compact, Decode, Encode};
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// // Encode a System.Events value containing a single event record:
+/// let deposit_info = metadata.event_info_by_name("Balances", "Deposit").unwrap();
+/// let mut events_bytes = Compact(1u32).encode();
+/// // Phase::ApplyExtrinsic(1):
+/// events_bytes.extend([0u8, 1, 0, 0, 0]);
+/// // The event:
+/// events_bytes.extend([deposit_info.pallet_index, deposit_info.event_index]);
+/// [1u8; 32].encode_to(&mut events_bytes);
+/// 12345u128.encode_to(&mut events_bytes);
+/// // No topics:
+/// Vec::<[u8; 32]>::new().encode_to(&mut events_bytes);
+///
+/// let records = decode_event_records(&events_bytes, &metadata, &metadata.types).unwrap();
+/// for record in records {
+///     let record = record.unwrap();
+///     assert_eq!(record.phase(), Phase::ApplyExtrinsic(1));
+///     assert_eq!(record.pallet_name(), "Balances");
+///     assert_eq!(record.event_name(), "Deposit");
+///     assert_eq!(record.topics_range(), Some(events_bytes.len() - 1..events_bytes.len()));
+/// }
+/// ```
+pub fn decode_event_records<'bytes, 'info, 'resolver, Info, Resolver>(
+    bytes: &'bytes [u8],
+    info: &'info Info,
+    type_resolver: &'resolver Resolver,
+) -> Result<
+    EventRecords<'bytes, 'info, 'resolver, Info, Resolver>,
+    EventRecordsDecodeError<<Info as StorageTypeInfo>::TypeId>,
+>
+where
+    Info: StorageTypeInfo + EventTypeInfo<TypeId = <Info as StorageTypeInfo>::TypeId>,
+    <Info as StorageTypeInfo>::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = <Info as StorageTypeInfo>::TypeId>,
+{
+    let events_ty = info
+        .storage_info("System", "Events")
+        .map_err(|e| EventRecordsDecodeError::CannotGetInfo(e.into_owned()))?
+        .value_id;
+
+    decode_event_records_with_info(bytes, events_ty, info, type_resolver)
+}
+
+/// Decode the bytes of a `System.Events` storage value, handing back an iterator over each of
+/// the event records within it. This is like [`decode_event_records`], except that the type
+/// ID of the `System.Events` storage value is provided.
+pub fn decode_event_records_with_info<'bytes, 'info, 'resolver, Info, Resolver>(
+    bytes: &'bytes [u8],
+    events_ty: Info::TypeId,
+    info: &'info Info,
+    type_resolver: &'resolver Resolver,
+) -> Result<
+    EventRecords<'bytes, 'info, 'resolver, Info, Resolver>,
+    EventRecordsDecodeError<Info::TypeId>,
+>
+where
+    Info: EventTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    // System.Events should be a sequence of event records. Find the event record type.
+    let record_ty = sequence_inner_type(events_ty.clone(), type_resolver)
+        .map_err(|e| EventRecordsDecodeError::CannotResolveType {
+            ty: events_ty.clone(),
+            reason: e.to_string(),
+        })?
+        .ok_or(EventRecordsDecodeError::UnexpectedEventsType { ty: events_ty })?;
+
+    // Work out what fields the event record contains. In early runtimes, there were no topics,
+    // so rather than assuming a shape we look at the fields that we actually have.
+    let fields = record_fields(record_ty.clone(), type_resolver)
+        .map_err(|e| EventRecordsDecodeError::CannotResolveType {
+            ty: record_ty.clone(),
+            reason: e.to_string(),
+        })?
+        .ok_or_else(|| EventRecordsDecodeError::UnexpectedEventRecordType {
+            ty: record_ty.clone(),
+        })?;
+
+    let count = |field| fields.iter().filter(|(f, _, _)| *f == field).count();
+    if count(RecordField::Phase) != 1 || count(RecordField::Event) != 1 {
+        return Err(EventRecordsDecodeError::UnexpectedEventRecordType { ty: record_ty });
+    }
+
+    let mut cursor = bytes;
+    let num_records = Compact::<u32>::decode(&mut cursor)
+        .map_err(EventRecordsDecodeError::CannotDecodeLength)?
+        .0 as usize;
+
+    Ok(EventRecords {
+        bytes,
+        cursor,
+        record_index: 0,
+        num_records,
+        finished: false,
+        record_ty,
+        fields,
+        info,
+        type_resolver,
+    })
+}
+
+// If the type is a sequence, return the type ID of the sequence items.
+fn sequence_inner_type<Resolver: TypeResolver>(
+    type_id: Resolver::TypeId,
+    types: &Resolver,
+) -> Result<Option<Resolver::TypeId>, Resolver::Error> {
+    let visitor = scale_type_resolver::visitor::new((), |_, _| None)
+        .visit_sequence(|_, _path, inner| Some(inner));
+    types.resolve_type(type_id, visitor)
+}
+
+// If the type is a composite, return the fields that it contains.
+#[allow(clippy::type_complexity)]
+fn record_fields<Resolver: TypeResolver>(
+    type_id: Resolver::TypeId,
+    types: &Resolver,
+) -> Result<Option<Vec<(RecordField, String, Resolver::TypeId)>>, Resolver::Error> {
+    let visitor =
+        scale_type_resolver::visitor::new((), |_, _| None).visit_composite(|_, _path, fields| {
+            let fields = fields
+                .enumerate()
+                .map(|(idx, f)| {
+                    let field = match (f.name, idx) {
+                        (Some("phase"), _) | (None, 0) => RecordField::Phase,
+                        (Some("event"), _) | (None, 1) => RecordField::Event,
+                        (Some("topics"), _) | (None, 2) => RecordField::Topics,
+                        _ => RecordField::Other,
+                    };
+                    let name = f.name.map(|n| n.to_string()).unwrap_or(format!("{idx}"));
+                    (field, name, f.id)
+                })
+                .collect();
+            Some(fields)
+        });
+    types.resolve_type(type_id, visitor)
+}
+
+struct PhaseVisitor<R>(PhantomData<R>);
+
+impl<R: TypeResolver> scale_decode::Visitor for PhaseVisitor<R> {
+    type Value<'scale, 'resolver> = Phase;
+    type Error = scale_decode::Error;
+    type TypeResolver = R;
+
+    fn visit_variant<'scale, 'resolver>(
+        self,
+        value: &mut scale_decode::visitor::types::Variant<'scale, 'resolver, Self::TypeResolver>,
+        _type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        match value.name() {
+            "ApplyExtrinsic" => {
+                let idx = value
+                    .fields()
+                    .next()
+                    .ok_or_else(|| {
+                        scale_decode::Error::custom_str("ApplyExtrinsic phase should have a field")
+                    })??
+                    .decode_as_type::<u32>()?;
+                Ok(Phase::ApplyExtrinsic(idx))
+            }
+            "Finalization" => Ok(Phase::Finalization),
+            "Initialization" => Ok(Phase::Initialization),
+            name => Err(scale_decode::Error::custom_string(format!(
+                "Unexpected phase variant '{name}'"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Encode;
+    use scale_info_legacy::type_shape::{Field, TypeShape};
+    use scale_info_legacy::{InsertName, LookupName, TypeRegistry};
+
+    fn legacy_events_bytes(
+        metadata: &frame_metadata::v12::RuntimeMetadataV12,
+        with_topics: bool,
+    ) -> Vec<u8> {
+        let transfer = metadata.event_info_by_name("Balances", "Transfer").unwrap();
+        let mut bytes = Compact(2u32).encode();
+
+        // Phase::ApplyExtrinsic(3) and a Balances.Transfer event:
+        bytes.extend([0, 3, 0, 0, 0, transfer.pallet_index, transfer.event_index]);
+        [1u8; 32].encode_to(&mut bytes);
+        [2u8; 32].encode_to(&mut bytes);
+        1000u128.encode_to(&mut bytes);
+        if with_topics {
+            vec![[3u8; 32]].encode_to(&mut bytes);
+        }
+
+        // Phase::Finalization and a Balances.Transfer event:
+        bytes.extend([1, transfer.pallet_index, transfer.event_index]);
+        [1u8; 32].encode_to(&mut bytes);
+        [2u8; 32].encode_to(&mut bytes);
+        2000u128.encode_to(&mut bytes);
+        if with_topics {
+            Vec::<[u8; 32]>::new().encode_to(&mut bytes);
+        }
+
+        bytes
+    }
+
+    fn legacy_metadata_and_types() -> (
+        frame_metadata::v12::RuntimeMetadataV12,
+        scale_info_legacy::TypeRegistrySet<'static>,
+    ) {
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        let metadata_types = crate::helpers::type_registry_from_metadata(&metadata).unwrap();
+        types.prepend(metadata_types);
+
+        (metadata, types)
+    }
+
+    #[test]
+    fn decode_legacy_event_records() {
+        let (metadata, types) = legacy_metadata_and_types();
+        let bytes = legacy_events_bytes(&metadata, true);
+
+        let records: Vec<_> = decode_event_records(&bytes, &metadata, &types)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].phase(), Phase::ApplyExtrinsic(3));
+        assert_eq!(records[0].pallet_name(), "Balances");
+        assert_eq!(records[0].event_name(), "Transfer");
+        assert_eq!(records[0].topics_range().unwrap().len(), 33);
+        assert_eq!(records[1].phase(), Phase::Finalization);
+        assert_eq!(records[1].topics_range().unwrap().len(), 1);
+        assert_eq!(records[1].range().end, bytes.len());
+
+        let amount = records[1].event().fields().last().unwrap();
+        assert_eq!(u128::decode(&mut &bytes[amount.range()]).unwrap(), 2000u128);
+    }
+
+    #[test]
+    fn decode_legacy_event_records_without_topics() {
+        let (metadata, mut types) = legacy_metadata_and_types();
+        let bytes = legacy_events_bytes(&metadata, false);
+
+        // Early runtimes had no topics in their event records. Appended types take precedence:
+        let mut no_topics = TypeRegistry::empty();
+        no_topics.insert(
+            InsertName::parse("EventRecord<Event, Hash>").unwrap(),
+            TypeShape::NamedStructOf(vec![
+                Field {
+                    name: "phase".into(),
+                    value: LookupName::parse("Phase").unwrap(),
+                },
+                Field {
+                    name: "event".into(),
+                    value: LookupName::parse("Event").unwrap(),
+                },
+            ]),
+        );
+        types.append(no_topics);
+
+        let records: Vec<_> = decode_event_records(&bytes, &metadata, &types)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].phase(), Phase::ApplyExtrinsic(3));
+        assert_eq!(records[0].topics_range(), None);
+        assert_eq!(records[1].phase(), Phase::Finalization);
+        assert_eq!(records[1].event_name(), "Transfer");
+        assert_eq!(records[1].range().end, bytes.len());
+    }
+
+    #[test]
+    fn leftover_bytes_are_reported() {
+        let (metadata, types) = legacy_metadata_and_types();
+        let mut bytes = legacy_events_bytes(&metadata, true);
+        bytes.push(0);
+
+        let mut records = decode_event_records(&bytes, &metadata, &types).unwrap();
+        assert!(records.next().unwrap().is_ok());
+        assert!(records.next().unwrap().is_ok());
+        assert!(matches!(
+            records.next(),
+            Some(Err(EventRecordsDecodeError::LeftoverBytes {
+                num_leftover_bytes: 1
+            }))
+        ));
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn huge_record_counts_are_not_trusted() {
+        let (metadata, types) = legacy_metadata_and_types();
+        let bytes = Compact(u32::MAX).encode();
+
+        // This would try to allocate space for billions of records if the count was trusted:
+        let records: Vec<_> = decode_event_records(&bytes, &metadata, &types)
+            .unwrap()
+            .collect();
+
+        assert_eq!(records.len(), 1);
+        assert!(records[0].is_err());
+    }
+}
@@ -0,0 +1,546 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::outer_enum_type_info::OuterEnum;
+use crate::utils::{OuterEnumPalletIndexes, find_variant, to_docs};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Implementations of this are responsible for handing back the information we need to
+/// decode events. This is expected to be implemented for runtime metadata types or
+/// derivatives thereof where needed.
+pub trait EventTypeInfo {
+    /// The type of type IDs that we are using to obtain type information.
+    type TypeId;
+
+    /// Get the information about a given event, given u8 pallet and event indexes.
+    fn event_info_by_index(
+        &self,
+        pallet_index: u8,
+        event_index: u8,
+    ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>>;
+
+    /// Get the information about a given event, given the pallet and event name.
+    fn event_info_by_name(
+        &self,
+        pallet_name: &str,
+        event_name: &str,
+    ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>>;
+}
+
+/// Implementations of this are responsible for handing back the docs for events, where the
+/// metadata contains them. This is implemented for the same metadata versions as
+/// [`EventTypeInfo`].
+pub trait EventDocsInfo {
+    /// Get the docs for a given event, or `None` if the event can't be found.
+    fn event_docs(&self, pallet_name: &str, event_name: &str) -> Option<Vec<&str>>;
+}
+
+/// An error returned trying to access event type information.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EventInfoError<'a> {
+    #[error("Pallet with index {index} not found")]
+    PalletNotFound { index: u8 },
+    #[error("Pallet with name '{name}' not found")]
+    PalletNotFoundByName { name: Cow<'a, str> },
+    #[error(
+        "Event with index {index} not found in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    EventNotFound {
+        index: u8,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[error(
+        "Event with name '{event_name}' not found in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    EventNotFoundByName {
+        event_name: Cow<'a, str>,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[cfg(feature = "legacy")]
+    #[error("Cannot parse type name '{name}':\n\n{reason}")]
+    CannotParseTypeName {
+        name: Cow<'a, str>,
+        reason: scale_info_legacy::lookup_name::ParseError,
+    },
+    #[error(
+        "Cannot find events type with id {id} in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    EventsTypeNotFound {
+        id: u32,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[error(
+        "Events type with id {id} should be a variant in pallet '{pallet_name}' (pallet index {pallet_index})."
+    )]
+    EventsTypeShouldBeVariant {
+        id: u32,
+        pallet_index: u8,
+        pallet_name: Cow<'a, str>,
+    },
+    #[cfg(feature = "legacy")]
+    #[error("The metadata was not SCALE decoded and so cannot be read")]
+    MetadataNotDecoded,
+}
+
+impl EventInfoError<'_> {
+    /// Take ownership of this error.
+    pub fn into_owned(self) -> EventInfoError<'static> {
+        match self {
+            EventInfoError::PalletNotFound { index } => EventInfoError::PalletNotFound { index },
+            EventInfoError::PalletNotFoundByName { name } => EventInfoError::PalletNotFoundByName {
+                name: Cow::Owned(name.into_owned()),
+            },
+            EventInfoError::EventNotFound {
+                index,
+                pallet_index,
+                pallet_name,
+            } => EventInfoError::EventNotFound {
+                index,
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            EventInfoError::EventNotFoundByName {
+                event_name,
+                pallet_index,
+                pallet_name,
+            } => EventInfoError::EventNotFoundByName {
+                event_name: Cow::Owned(event_name.into_owned()),
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            #[cfg(feature = "legacy")]
+            EventInfoError::CannotParseTypeName { name, reason } => {
+                EventInfoError::CannotParseTypeName {
+                    name: Cow::Owned(name.into_owned()),
+                    reason,
+                }
+            }
+            EventInfoError::EventsTypeNotFound {
+                id,
+                pallet_index,
+                pallet_name,
+            } => EventInfoError::EventsTypeNotFound {
+                id,
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            EventInfoError::EventsTypeShouldBeVariant {
+                id,
+                pallet_index,
+                pallet_name,
+            } => EventInfoError::EventsTypeShouldBeVariant {
+                id,
+                pallet_index,
+                pallet_name: Cow::Owned(pallet_name.into_owned()),
+            },
+            #[cfg(feature = "legacy")]
+            EventInfoError::MetadataNotDecoded => EventInfoError::MetadataNotDecoded,
+        }
+    }
+}
+
+/// Information about a single event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventInfo<'info, TypeId> {
+    /// Index of the pallet.
+    pub pallet_index: u8,
+    /// Index of the event.
+    pub event_index: u8,
+    /// Name of the pallet.
+    pub pallet_name: Cow<'info, str>,
+    /// Name of the event.
+    pub event_name: Cow<'info, str>,
+    /// Names and types of each of the event fields.
+    pub args: Vec<EventInfoArg<'info, TypeId>>,
+}
+
+/// A field in some event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventInfoArg<'info, TypeId> {
+    /// Field name. This is empty if the field has no name.
+    pub name: Cow<'info, str>,
+    /// Field type ID.
+    pub id: TypeId,
+}
+
+macro_rules! impl_event_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl EventTypeInfo for $path {
+            type TypeId = u32;
+            fn event_info_by_index(
+                &self,
+                pallet_index: u8,
+                event_index: u8,
+            ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+                let pallet = match self.outer_enum_pallet_variants(OuterEnum::Event) {
+                    Some(variants) => variants
+                        .iter()
+                        .find(|v| v.index == pallet_index)
+                        .and_then(|v| self.pallets.iter().find(|p| p.name == v.name)),
+                    None => self.pallets.iter().find(|p| p.index == pallet_index),
+                }
+                .ok_or(EventInfoError::PalletNotFound {
+                    index: pallet_index,
+                })?;
+
+                let pallet_name = &pallet.name;
+
+                let events_id = pallet
+                    .event
+                    .as_ref()
+                    .ok_or_else(|| EventInfoError::EventNotFound {
+                        index: event_index,
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?
+                    .ty
+                    .id;
+
+                let events_enum =
+                    get_events_enum(&self.types, events_id, pallet_index, pallet_name)?;
+
+                let event_variant = events_enum
+                    .variants
+                    .iter()
+                    .find(|v| v.index == event_index)
+                    .ok_or_else(|| EventInfoError::EventNotFound {
+                        index: event_index,
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?;
+
+                Ok(event_info_from_variant(
+                    pallet_index,
+                    pallet_name,
+                    event_variant,
+                ))
+            }
+            fn event_info_by_name(
+                &self,
+                pallet_name: &str,
+                event_name: &str,
+            ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+                use alloc::string::ToString;
+
+                let pallet = self
+                    .pallets
+                    .iter()
+                    .find(|p| p.name == pallet_name)
+                    .ok_or_else(|| EventInfoError::PalletNotFoundByName {
+                        name: Cow::Owned(pallet_name.to_string()),
+                    })?;
+
+                let pallet_index = self
+                    .outer_enum_pallet_variants(OuterEnum::Event)
+                    .and_then(|variants| variants.iter().find(|v| v.name == pallet.name))
+                    .map_or(pallet.index, |v| v.index);
+                let pallet_name = &pallet.name;
+
+                let events_id = pallet
+                    .event
+                    .as_ref()
+                    .ok_or_else(|| EventInfoError::EventNotFoundByName {
+                        event_name: Cow::Owned(event_name.to_string()),
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?
+                    .ty
+                    .id;
+
+                let events_enum =
+                    get_events_enum(&self.types, events_id, pallet_index, pallet_name)?;
+
+                let event_variant = events_enum
+                    .variants
+                    .iter()
+                    .find(|v| v.name == event_name)
+                    .ok_or_else(|| EventInfoError::EventNotFoundByName {
+                        event_name: Cow::Owned(event_name.to_string()),
+                        pallet_index,
+                        pallet_name: Cow::Borrowed(pallet_name),
+                    })?;
+
+                Ok(event_info_from_variant(
+                    pallet_index,
+                    pallet_name,
+                    event_variant,
+                ))
+            }
+        }
+    };
+}
+
+impl_event_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_event_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_event_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
+macro_rules! impl_event_docs_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl EventDocsInfo for $path {
+            fn event_docs(&self, pallet_name: &str, event_name: &str) -> Option<Vec<&str>> {
+                let pallet = self.pallets.iter().find(|p| p.name == pallet_name)?;
+                let event_ty = pallet.event.as_ref()?.ty.id;
+                let event = find_variant(&self.types, event_ty, event_name)?;
+                Some(to_docs(&event.docs))
+            }
+        }
+    };
+}
+
+impl_event_docs_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_event_docs_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_event_docs_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
+fn get_events_enum<'a>(
+    types: &'a scale_info::PortableRegistry,
+    events_id: u32,
+    pallet_index: u8,
+    pallet_name: &'a str,
+) -> Result<&'a scale_info::TypeDefVariant<scale_info::form::PortableForm>, EventInfoError<'a>> {
+    let events_ty = types
+        .resolve(events_id)
+        .ok_or(EventInfoError::EventsTypeNotFound {
+            id: events_id,
+            pallet_index,
+            pallet_name: Cow::Borrowed(pallet_name),
+        })?;
+
+    match &events_ty.type_def {
+        scale_info::TypeDef::Variant(v) => Ok(v),
+        _ => Err(EventInfoError::EventsTypeShouldBeVariant {
+            id: events_id,
+            pallet_index,
+            pallet_name: Cow::Borrowed(pallet_name),
+        }),
+    }
+}
+
+fn event_info_from_variant<'a>(
+    pallet_index: u8,
+    pallet_name: &'a str,
+    variant: &'a scale_info::Variant<scale_info::form::PortableForm>,
+) -> EventInfo<'a, u32> {
+    let args = variant
+        .fields
+        .iter()
+        .map(|f| EventInfoArg {
+            name: f
+                .name
+                .as_ref()
+                .map(|n| Cow::Borrowed(&**n))
+                .unwrap_or(Cow::Owned(String::new())),
+            id: f.ty.id,
+        })
+        .collect();
+
+    EventInfo {
+        pallet_index,
+        event_index: variant.index,
+        pallet_name: Cow::Borrowed(pallet_name),
+        event_name: Cow::Borrowed(&variant.name),
+        args,
+    }
+}
+
+#[cfg(feature = "legacy")]
+const _: () = {
+    use crate::utils::as_decoded_str;
+    use frame_metadata::decode_different::DecodeDifferent;
+    use scale_info_legacy::LookupName;
+
+    fn as_decoded<A, B>(item: &DecodeDifferent<A, B>) -> Result<&B, EventInfoError<'static>> {
+        crate::utils::as_decoded(item).map_err(|_| EventInfoError::MetadataNotDecoded)
+    }
+
+    // Given some module and its position among modules which have events, return the index
+    // that its events are encoded with. This lines up with the indexes used in the `builtin::Event`
+    // type that `type_registry_from_metadata` synthesises: prior to V12, pallets without events don't
+    // count towards the index, and from V12 each module has an explicit index that is used instead.
+    macro_rules! legacy_event_pallet_index {
+        ($module:ident, $nth_with_events:expr $(, $builtin_index:ident)?) => {{
+            let _ = &$module;
+            let idx = $nth_with_events as u8;
+            $(
+                let $builtin_index = true;
+                let idx = if $builtin_index { $module.index } else { idx };
+            )?
+            idx
+        }};
+    }
+
+    macro_rules! legacy_event_info_body {
+        ($pallet_index:ident, $event_index:ident, $pallet_name:ident, $event:ident) => {{
+            let event_name: &str = as_decoded_str(&$event.name);
+            let args = as_decoded(&$event.arguments)?
+                .iter()
+                .map(|ty| {
+                    let ty: &str = ty.as_ref();
+                    let id = LookupName::parse(ty)
+                        .map_err(|e| EventInfoError::CannotParseTypeName {
+                            name: Cow::Borrowed(ty),
+                            reason: e,
+                        })?
+                        .in_pallet($pallet_name);
+                    // Legacy event fields have no names.
+                    Ok(EventInfoArg {
+                        name: Cow::Owned(String::new()),
+                        id,
+                    })
+                })
+                .collect::<Result<_, EventInfoError>>()?;
+
+            Ok(EventInfo {
+                pallet_index: $pallet_index,
+                event_index: $event_index,
+                pallet_name: Cow::Borrowed($pallet_name),
+                event_name: Cow::Borrowed(event_name),
+                args,
+            })
+        }};
+    }
+
+    macro_rules! impl_event_info_for_v8_to_v13 {
+        ($path:path $(, $builtin_index:ident)?) => {
+            impl EventTypeInfo for $path {
+                type TypeId = LookupName;
+                fn event_info_by_index(
+                    &self,
+                    pallet_index: u8,
+                    event_index: u8,
+                ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+                    let modules = as_decoded(&self.modules)?;
+
+                    let m = modules
+                        .iter()
+                        .filter(|m| m.event.is_some())
+                        .enumerate()
+                        .find(|(n, m)| {
+                            legacy_event_pallet_index!(m, *n $(, $builtin_index)?) == pallet_index
+                        })
+                        .map(|(_, m)| m)
+                        .ok_or(EventInfoError::PalletNotFound {
+                            index: pallet_index,
+                        })?;
+
+                    let m_name: &str = as_decoded_str(&m.name);
+
+                    let events = m
+                        .event
+                        .as_ref()
+                        .ok_or_else(|| EventInfoError::EventNotFound {
+                            index: event_index,
+                            pallet_index,
+                            pallet_name: Cow::Borrowed(m_name),
+                        })?;
+
+                    let event = as_decoded(events)?
+                        .get(event_index as usize)
+                        .ok_or_else(|| EventInfoError::EventNotFound {
+                            index: event_index,
+                            pallet_index,
+                            pallet_name: Cow::Borrowed(m_name),
+                        })?;
+
+                    legacy_event_info_body!(pallet_index, event_index, m_name, event)
+                }
+                fn event_info_by_name(
+                    &self,
+                    pallet_name: &str,
+                    event_name: &str,
+                ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+                    use alloc::string::ToString;
+
+                    let modules = as_decoded(&self.modules)?;
+
+                    let m = modules
+                        .iter()
+                        .find(|m| as_decoded_str(&m.name) == pallet_name)
+                        .ok_or_else(|| EventInfoError::PalletNotFoundByName {
+                            name: Cow::Owned(pallet_name.to_string()),
+                        })?;
+
+                    let m_name: &str = as_decoded_str(&m.name);
+
+                    let nth_with_events = modules
+                        .iter()
+                        .filter(|m| m.event.is_some())
+                        .position(|m| as_decoded_str(&m.name) == pallet_name);
+
+                    let (Some(nth_with_events), Some(events)) = (nth_with_events, m.event.as_ref()) else {
+                        return Err(EventInfoError::EventNotFoundByName {
+                            event_name: Cow::Owned(event_name.to_string()),
+                            pallet_index: legacy_event_pallet_index!(m, 0 $(, $builtin_index)?),
+                            pallet_name: Cow::Borrowed(m_name),
+                        });
+                    };
+
+                    let pallet_index = legacy_event_pallet_index!(m, nth_with_events $(, $builtin_index)?);
+
+                    let (event_index, event) = as_decoded(events)?
+                        .iter()
+                        .enumerate()
+                        .find(|(_, e)| as_decoded_str(&e.name) == event_name)
+                        .ok_or_else(|| EventInfoError::EventNotFoundByName {
+                            event_name: Cow::Owned(event_name.to_string()),
+                            pallet_index,
+                            pallet_name: Cow::Borrowed(m_name),
+                        })?;
+
+                    let event_index = event_index as u8;
+                    legacy_event_info_body!(pallet_index, event_index, m_name, event)
+                }
+            }
+        };
+    }
+
+    impl_event_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_event_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_event_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_event_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_event_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12, use_builtin_index);
+    impl_event_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13, use_builtin_index);
+
+    macro_rules! impl_event_docs_info_for_v8_to_v13 {
+        ($path:path) => {
+            impl EventDocsInfo for $path {
+                fn event_docs(&self, pallet_name: &str, event_name: &str) -> Option<Vec<&str>> {
+                    let m = as_decoded(&self.modules)
+                        .ok()?
+                        .iter()
+                        .find(|m| as_decoded_str(&m.name) == pallet_name)?;
+                    let event = as_decoded(m.event.as_ref()?)
+                        .ok()?
+                        .iter()
+                        .find(|e| as_decoded_str(&e.name) == event_name)?;
+                    Some(to_docs(&as_decoded(&event.documentation).ok()?[..]))
+                }
+            }
+        };
+    }
+
+    impl_event_docs_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_event_docs_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_event_docs_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_event_docs_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_event_docs_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12);
+    impl_event_docs_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13);
+};
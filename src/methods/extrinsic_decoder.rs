@@ -13,15 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::methods::call_data_decoder::{CallDataDecodeError, decode_call_data_at_offset};
+use crate::methods::extrinsic_encoder::{
+    ExtrinsicEncodeError, TransactionExtensions, TransactionExtensionsError,
+    encode_v4_signer_payload_with_info, encode_v5_signer_payload_with_info,
+};
 use crate::methods::extrinsic_type_info::ExtrinsicInfoError;
 use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
 use crate::utils::{DecodeErrorTrace, decode_with_error_tracing};
-use alloc::borrow::Cow;
-use alloc::string::{String, ToString};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::ops::Range;
 use parity_scale_codec::{Compact, Decode};
+use scale_encode::{EncodeAsFields, FieldIter};
 use scale_type_resolver::TypeResolver;
 
 /// An error returned trying to decode extrinsic bytes.
@@ -67,6 +73,31 @@ pub enum ExtrinsicDecodeError {
     },
 }
 
+impl ExtrinsicDecodeError {
+    fn from_call_data_error(e: CallDataDecodeError) -> Self {
+        match e {
+            CallDataDecodeError::CannotGetInfo(e) => ExtrinsicDecodeError::CannotGetInfo(e),
+            CallDataDecodeError::CannotDecodePalletIndex(e) => {
+                ExtrinsicDecodeError::CannotDecodePalletIndex(e)
+            }
+            CallDataDecodeError::CannotDecodeCallIndex(e) => {
+                ExtrinsicDecodeError::CannotDecodeCallIndex(e)
+            }
+            CallDataDecodeError::CannotDecodeCallData {
+                pallet_name,
+                call_name,
+                argument_name,
+                reason,
+            } => ExtrinsicDecodeError::CannotDecodeCallData {
+                pallet_name,
+                call_name,
+                argument_name,
+                reason,
+            },
+        }
+    }
+}
+
 /// An owned variant of an Extrinsic (note: this may still contain
 /// references if the visitor used to decode the extrinsic contents holds
 /// onto any)
@@ -193,6 +224,117 @@ impl<'info, TypeId> Extrinsic<'info, TypeId> {
         Range { start, end }
     }
 
+    /// Compute the hash of this extrinsic. This is the `blake2_256` hash of the extrinsic
+    /// bytes (including the compact-encoded length prefix), and is what is used to identify
+    /// transactions in a node's transaction pool.
+    ///
+    /// `bytes` must be the bytes that this extrinsic was decoded from. An
+    /// [`ExtrinsicEncodeError::BytesMismatch`] error is returned if they are shorter than the
+    /// extrinsic that was decoded.
+    pub fn hash(&self, bytes: &[u8]) -> Result<[u8; 32], ExtrinsicEncodeError> {
+        let bytes = bytes
+            .get(..self.byte_len as usize)
+            .ok_or(ExtrinsicEncodeError::BytesMismatch)?;
+        Ok(sp_crypto_hashing::blake2_256(bytes))
+    }
+
+    /// Rebuild the signer payload for this extrinsic; ie the bytes that the signature in a V4
+    /// signed extrinsic or a V5 general extrinsic was produced from. This can be used to verify
+    /// signatures offline, or to audit exactly what was signed.
+    ///
+    /// `bytes` must be the bytes that this extrinsic was decoded from. The call data and
+    /// transaction extension values are taken directly from these. The implicit values for
+    /// each transaction extension (for instance the genesis hash and spec version), which do
+    /// not appear in the extrinsic bytes, are encoded using the `implicits` provided.
+    ///
+    /// As with [`crate::extrinsics::encode_v4_signer_payload`], V4 signer payloads are hashed
+    /// if they are longer than 256 bytes, and V5 signer payloads are always hashed.
+    ///
+    /// Transaction extension values are taken from the extrinsic bytes, except where `implicits`
+    /// contains the extension, in which case its
+    /// [`TransactionExtensions::encode_extension_value_for_signer_payload_to`] is used instead.
+    /// This allows V5 extrinsics which provide their signature in a transaction extension to
+    /// leave it out of the signer payload.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use frame_decode::extrinsics::{decode_extrinsic, TransactionExtensions};
+    /// use frame_metadata::RuntimeMetadata;
+    /// use parity_scale_codec::Decode;
+    /// use scale_info::PortableRegistry;
+    ///
+    /// fn signer_payload_for<Implicits: TransactionExtensions<PortableRegistry>>(
+    ///     ext_bytes: &[u8],
+    ///     implicits: &Implicits,
+    /// ) -> Vec<u8> {
+    ///     let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+    ///     let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { panic!() };
+    ///
+    ///     let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+    ///     ext.signer_payload(ext_bytes, implicits, &metadata, &metadata.types).unwrap()
+    /// }
+    /// ```
+    pub fn signer_payload<Info, Resolver, Implicits>(
+        &self,
+        bytes: &[u8],
+        implicits: &Implicits,
+        info: &Info,
+        type_resolver: &Resolver,
+    ) -> Result<Vec<u8>, ExtrinsicEncodeError>
+    where
+        TypeId: Clone,
+        Info: ExtrinsicTypeInfo<TypeId = TypeId>,
+        Resolver: TypeResolver<TypeId = TypeId>,
+        Implicits: TransactionExtensions<Resolver>,
+    {
+        let Some(extensions) = &self.extensions else {
+            return Err(ExtrinsicEncodeError::NoSignerPayload);
+        };
+        // Every range we look at below is within the decoded extrinsic.
+        if bytes.len() < self.byte_len as usize {
+            return Err(ExtrinsicEncodeError::BytesMismatch);
+        }
+
+        let call_info = info
+            .extrinsic_call_info_by_index(self.pallet_index, self.call_index)
+            .map_err(|i| i.into_owned())
+            .map_err(ExtrinsicEncodeError::CannotGetInfo)?;
+
+        let extension_version =
+            (self.version_ty == ExtrinsicType::General).then_some(extensions.version());
+        let ext_info = info
+            .extrinsic_extension_info(extension_version)
+            .map_err(|i| i.into_owned())
+            .map_err(ExtrinsicEncodeError::CannotGetInfo)?;
+
+        let call_data = RawCallArgs(&bytes[self.call_data_args_range()]);
+        let transaction_extensions = DecodedExtensions {
+            bytes,
+            extensions,
+            implicits,
+        };
+
+        if self.version_ty == ExtrinsicType::General {
+            encode_v5_signer_payload_with_info(
+                &call_data,
+                &transaction_extensions,
+                type_resolver,
+                &call_info,
+                &ext_info,
+            )
+            .map(|hash| hash.to_vec())
+        } else {
+            encode_v4_signer_payload_with_info(
+                &call_data,
+                &transaction_extensions,
+                type_resolver,
+                &call_info,
+                &ext_info,
+            )
+        }
+    }
+
     /// Map the signature type IDs to something else.
     pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> Extrinsic<'info, NewTypeId>
     where
@@ -219,6 +361,88 @@ impl<'info, TypeId> Extrinsic<'info, TypeId> {
     }
 }
 
+// Already-encoded call arguments, which are written out as-is when
+// rebuilding a signer payload.
+struct RawCallArgs<'a>(&'a [u8]);
+
+impl EncodeAsFields for RawCallArgs<'_> {
+    fn encode_as_fields_to<R: TypeResolver>(
+        &self,
+        _fields: &mut dyn FieldIter<'_, R::TypeId>,
+        _types: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), scale_encode::Error> {
+        out.extend_from_slice(self.0);
+        Ok(())
+    }
+}
+
+// Transaction extensions whose values are taken from the bytes of a decoded
+// extrinsic, and whose implicits (and signer payload values, where provided)
+// are handed off to some user provided extensions.
+struct DecodedExtensions<'a, 'info, TypeId, Implicits> {
+    bytes: &'a [u8],
+    extensions: &'a ExtrinsicExtensions<'info, TypeId>,
+    implicits: &'a Implicits,
+}
+
+impl<Resolver, Implicits> TransactionExtensions<Resolver>
+    for DecodedExtensions<'_, '_, Resolver::TypeId, Implicits>
+where
+    Resolver: TypeResolver,
+    Implicits: TransactionExtensions<Resolver>,
+{
+    fn contains_extension(&self, name: &str) -> bool {
+        self.extensions.iter().any(|e| e.name() == name)
+    }
+
+    fn encode_extension_value_to(
+        &self,
+        name: &str,
+        _type_id: Resolver::TypeId,
+        _type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionsError> {
+        let ext = self
+            .extensions
+            .iter()
+            .find(|e| e.name() == name)
+            .ok_or_else(|| TransactionExtensionsError::NotFound(name.to_owned()))?;
+        out.extend_from_slice(&self.bytes[ext.range()]);
+        Ok(())
+    }
+
+    fn encode_extension_value_for_signer_payload_to(
+        &self,
+        name: &str,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionsError> {
+        if self.implicits.contains_extension(name) {
+            self.implicits.encode_extension_value_for_signer_payload_to(
+                name,
+                type_id,
+                type_resolver,
+                out,
+            )
+        } else {
+            self.encode_extension_value_to(name, type_id, type_resolver, out)
+        }
+    }
+
+    fn encode_extension_implicit_to(
+        &self,
+        name: &str,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionsError> {
+        self.implicits
+            .encode_extension_implicit_to(name, type_id, type_resolver, out)
+    }
+}
+
 /// The type of the extrinsic.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum ExtrinsicType {
@@ -355,9 +579,9 @@ impl<'info, TypeId> ExtrinsicExtensions<'info, TypeId> {
 /// A single named argument.
 #[derive(Clone, Debug)]
 pub struct NamedArg<'info, TypeId> {
-    name: Cow<'info, str>,
-    range: Range<u32>,
-    ty: TypeId,
+    pub(crate) name: Cow<'info, str>,
+    pub(crate) range: Range<u32>,
+    pub(crate) ty: TypeId,
 }
 
 impl<'info, TypeId> NamedArg<'info, TypeId> {
@@ -613,41 +837,9 @@ where
         .transpose()?;
 
     // All extrinsics now have the encoded call data.
-    let pallet_index_idx = curr_idx(cursor);
-    let pallet_index: u8 =
-        Decode::decode(cursor).map_err(ExtrinsicDecodeError::CannotDecodePalletIndex)?;
-    let call_index: u8 =
-        Decode::decode(cursor).map_err(ExtrinsicDecodeError::CannotDecodeCallIndex)?;
-    let call_info = info
-        .extrinsic_call_info_by_index(pallet_index, call_index)
-        .map_err(|e| ExtrinsicDecodeError::CannotGetInfo(e.into_owned()))?;
-
-    let mut call_data = vec![];
-    for arg in call_info.args {
-        let start_idx = curr_idx(cursor);
-        decode_with_error_tracing(
-            cursor,
-            arg.id.clone(),
-            type_resolver,
-            scale_decode::visitor::IgnoreVisitor::new(),
-        )
-        .map_err(|e| ExtrinsicDecodeError::CannotDecodeCallData {
-            pallet_name: call_info.pallet_name.to_string(),
-            call_name: call_info.call_name.to_string(),
-            argument_name: arg.name.to_string(),
-            reason: e,
-        })?;
-        let end_idx = curr_idx(cursor);
-
-        call_data.push(NamedArg {
-            name: arg.name,
-            range: Range {
-                start: start_idx,
-                end: end_idx,
-            },
-            ty: arg.id,
-        })
-    }
+    let call_data_offset = curr_idx(cursor);
+    let call_data = decode_call_data_at_offset(cursor, call_data_offset, info, type_resolver)
+        .map_err(ExtrinsicDecodeError::from_call_data_error)?;
 
     let ext = Extrinsic {
         compact_prefix_len,
@@ -656,13 +848,179 @@ where
         byte_len: bytes.len() as u32,
         signature,
         extensions,
-        pallet_name: call_info.pallet_name,
-        pallet_index,
-        pallet_index_idx,
-        call_name: call_info.call_name,
-        call_index,
-        call_data,
+        pallet_name: call_data.pallet_name,
+        pallet_index: call_data.pallet_index,
+        pallet_index_idx: call_data.pallet_index_idx,
+        call_name: call_data.call_name,
+        call_index: call_data.call_index,
+        call_data: call_data.args,
     };
 
     Ok(ext)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_encoder::test_extensions::RawExtensions;
+    use crate::methods::extrinsic_encoder::{
+        encode_v4_signed, encode_v4_signer_payload, encode_v5_general, encode_v5_signer_payload,
+    };
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Encode;
+    use scale_value::Value;
+
+    #[test]
+    fn signer_payload_matches_encoded_signer_payload() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let exts = RawExtensions::polkadot(7, 100);
+        let address = Value::unnamed_variant("Id", [Value::from_bytes([2u8; 32])]);
+        let signature = Value::unnamed_variant("Sr25519", [Value::from_bytes([3u8; 64])]);
+
+        // Short remarks lead to unhashed signer payloads and long ones to hashed payloads.
+        for remark_len in [10, 1000] {
+            let call_data = Value::unnamed_composite([Value::from_bytes(vec![4u8; remark_len])]);
+
+            let ext_bytes = encode_v4_signed(
+                "System",
+                "remark",
+                &call_data,
+                &exts,
+                &address,
+                &signature,
+                &metadata,
+                &metadata.types,
+            )
+            .unwrap();
+            let expected_payload = encode_v4_signer_payload(
+                "System",
+                "remark",
+                &call_data,
+                &exts,
+                &metadata,
+                &metadata.types,
+            )
+            .unwrap();
+
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+            let payload = ext
+                .signer_payload(&ext_bytes, &exts, &metadata, &metadata.types)
+                .unwrap();
+
+            assert_eq!(payload, expected_payload);
+            assert_eq!(
+                ext.hash(&ext_bytes).unwrap(),
+                sp_crypto_hashing::blake2_256(&ext_bytes)
+            );
+            assert!(matches!(
+                ext.hash(&ext_bytes[..ext_bytes.len() - 1]),
+                Err(ExtrinsicEncodeError::BytesMismatch)
+            ));
+        }
+    }
+
+    #[test]
+    fn v5_signer_payload_matches_encoded_signer_payload() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        // Pretend that the nonce extension carries something (like a signature) which
+        // is left out of the signer payload.
+        let exts = RawExtensions {
+            signer_payload_values: vec![("CheckNonce", Compact(0u32).encode())],
+            ..RawExtensions::polkadot(7, 100)
+        };
+        let call_data = Value::unnamed_composite([Value::from_bytes(vec![4u8; 10])]);
+
+        let ext_bytes = encode_v5_general(
+            "System",
+            "remark",
+            &call_data,
+            0,
+            &exts,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let expected_payload = encode_v5_signer_payload(
+            "System",
+            "remark",
+            &call_data,
+            0,
+            &exts,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+        assert_eq!(ext.ty(), ExtrinsicType::General);
+        let payload = ext
+            .signer_payload(&ext_bytes, &exts, &metadata, &metadata.types)
+            .unwrap();
+        assert_eq!(payload, expected_payload.to_vec());
+
+        // If the extensions we're given don't know about an extension, its value is
+        // taken from the extrinsic bytes instead.
+        let implicits_only = RawExtensions {
+            values: vec![],
+            signer_payload_values: vec![],
+            implicits: exts.implicits.clone(),
+        };
+        let payload = ext
+            .signer_payload(&ext_bytes, &implicits_only, &metadata, &metadata.types)
+            .unwrap();
+        let exts_without_override = RawExtensions {
+            signer_payload_values: vec![],
+            ..exts
+        };
+        let expected_payload = encode_v5_signer_payload(
+            "System",
+            "remark",
+            &call_data,
+            0,
+            &exts_without_override,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        assert_eq!(payload, expected_payload.to_vec());
+    }
+
+    #[test]
+    fn bare_extrinsics_have_no_signer_payload() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let call_data = Value::unnamed_composite([Value::u128(123)]);
+        let ext_bytes = crate::methods::extrinsic_encoder::encode_v4_unsigned(
+            "Timestamp",
+            "set",
+            &call_data,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+        let err = ext
+            .signer_payload(&ext_bytes, &(), &metadata, &metadata.types)
+            .unwrap_err();
+
+        assert!(matches!(err, ExtrinsicEncodeError::NoSignerPayload));
+    }
+}
@@ -13,8 +13,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod standard_extensions;
+#[cfg(test)]
+pub(crate) mod test_extensions;
 mod transaction_extension;
 mod transaction_extensions;
+use super::deprecation::{DeprecationPolicy, DeprecationWarning, check_deprecation};
 use super::extrinsic_type_info::{
     ExtrinsicCallInfo, ExtrinsicExtensionInfo, ExtrinsicInfoError, ExtrinsicSignatureInfo,
     ExtrinsicTypeInfo,
@@ -24,6 +28,11 @@ use parity_scale_codec::Encode;
 use scale_encode::{EncodeAsFields, EncodeAsType};
 use scale_type_resolver::{Field, TypeResolver};
 
+pub use standard_extensions::{
+    ChargeAssetTxPayment, ChargeTransactionPayment, CheckGenesis, CheckMetadataHash,
+    CheckMortality, CheckNonZeroSender, CheckNonce, CheckSpecVersion, CheckTxVersion, CheckWeight,
+    Era, Mortality,
+};
 pub use transaction_extension::{TransactionExtension, TransactionExtensionError};
 pub use transaction_extensions::{TransactionExtensions, TransactionExtensionsError};
 
@@ -46,6 +55,14 @@ pub enum ExtrinsicEncodeError {
         "Extrinsic encoding failed: cannot find a transaction extensions version which relies only on the transaction extensions we were given."
     )]
     CannotFindGoodExtensionVersion,
+    #[error("Extrinsic encoding failed: {0}")]
+    Deprecated(DeprecationWarning),
+    #[error("Extrinsic encoding failed: bare extrinsics have no signer payload.")]
+    NoSignerPayload,
+    #[error(
+        "Extrinsic encoding failed: the bytes given are not those that the extrinsic was decoded from."
+    )]
+    BytesMismatch,
 }
 
 /// Encode a V4 unsigned extrinsic (also known as an inherent).
@@ -1094,6 +1111,44 @@ where
     encode_call_data_with_info_to(call_data, &call_info, type_resolver, out)
 }
 
+/// Encode the call data for an extrinsic, applying the given [`DeprecationPolicy`] if the call
+/// is marked as deprecated in the metadata.
+///
+/// This is like [`encode_call_data`], but also hands back a [`DeprecationWarning`] if the call
+/// is deprecated and the policy is [`DeprecationPolicy::Warn`], and returns
+/// [`ExtrinsicEncodeError::Deprecated`] if the call is deprecated and the policy is
+/// [`DeprecationPolicy::Deny`].
+pub fn encode_call_data_checked<CallData, Info, Resolver>(
+    pallet_name: &str,
+    call_name: &str,
+    call_data: &CallData,
+    info: &Info,
+    type_resolver: &Resolver,
+    policy: DeprecationPolicy,
+) -> Result<(Vec<u8>, Option<DeprecationWarning>), ExtrinsicEncodeError>
+where
+    CallData: EncodeAsFields,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+    Info: ExtrinsicTypeInfo,
+{
+    let call_info = info
+        .extrinsic_call_info_by_name(pallet_name, call_name)
+        .map_err(|i| i.into_owned())
+        .map_err(ExtrinsicEncodeError::CannotGetInfo)?;
+
+    let warning = check_deprecation(
+        policy,
+        &call_info.pallet_name,
+        &call_info.call_name,
+        &call_info.deprecation,
+    )
+    .map_err(ExtrinsicEncodeError::Deprecated)?;
+
+    let mut out = Vec::new();
+    encode_call_data_with_info_to(call_data, &call_info, type_resolver, &mut out)?;
+    Ok((out, warning))
+}
+
 /// Encode the call data for an extrinsic, given some already-computed [`ExtrinsicCallInfo`].
 ///
 /// This is basically an alias for [`scale_encode::EncodeAsFields::encode_as_fields()`], but
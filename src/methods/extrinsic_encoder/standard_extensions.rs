@@ -0,0 +1,787 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::transaction_extension::{TransactionExtension, TransactionExtensionError};
+use alloc::format;
+use alloc::vec::Vec;
+use scale_encode::{Composite, CompositeField, EncodeAsType, Variant};
+use scale_type_resolver::TypeResolver;
+
+// Encode some value to the given type, converting any error into a TransactionExtensionError.
+fn encode_as_type<T: EncodeAsType + ?Sized, R: TypeResolver>(
+    value: &T,
+    type_id: R::TypeId,
+    type_resolver: &R,
+    out: &mut Vec<u8>,
+) -> Result<(), TransactionExtensionError> {
+    value
+        .encode_as_type_to(type_id, type_resolver, out)
+        .map_err(|e| e.into())
+}
+
+// Implement TransactionExtension for extensions which have no value and no implicit.
+macro_rules! impl_empty_extension {
+    ($ty:ident, $name:literal) => {
+        impl<Resolver: TypeResolver> TransactionExtension<Resolver> for $ty {
+            const NAME: &str = $name;
+
+            fn encode_value_to(
+                &self,
+                type_id: Resolver::TypeId,
+                type_resolver: &Resolver,
+                out: &mut Vec<u8>,
+            ) -> Result<(), TransactionExtensionError> {
+                encode_as_type(&(), type_id, type_resolver, out)
+            }
+
+            fn encode_implicit_to(
+                &self,
+                type_id: Resolver::TypeId,
+                type_resolver: &Resolver,
+                out: &mut Vec<u8>,
+            ) -> Result<(), TransactionExtensionError> {
+                encode_as_type(&(), type_id, type_resolver, out)
+            }
+        }
+    };
+}
+
+// Implement TransactionExtension for extensions which have no value, and
+// whose implicit is the single field of the extension struct.
+macro_rules! impl_implicit_only_extension {
+    ($ty:ident, $name:literal) => {
+        impl<Resolver: TypeResolver> TransactionExtension<Resolver> for $ty {
+            const NAME: &str = $name;
+
+            fn encode_value_to(
+                &self,
+                type_id: Resolver::TypeId,
+                type_resolver: &Resolver,
+                out: &mut Vec<u8>,
+            ) -> Result<(), TransactionExtensionError> {
+                encode_as_type(&(), type_id, type_resolver, out)
+            }
+
+            fn encode_implicit_to(
+                &self,
+                type_id: Resolver::TypeId,
+                type_resolver: &Resolver,
+                out: &mut Vec<u8>,
+            ) -> Result<(), TransactionExtensionError> {
+                encode_as_type(&self.0, type_id, type_resolver, out)
+            }
+        }
+    };
+}
+
+/// The `CheckSpecVersion` transaction extension, which ensures that the transaction is
+/// only valid for a runtime with the given spec version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckSpecVersion(u32);
+
+impl CheckSpecVersion {
+    /// Construct this extension given the current runtime spec version.
+    pub fn new(spec_version: u32) -> Self {
+        CheckSpecVersion(spec_version)
+    }
+}
+
+impl_implicit_only_extension!(CheckSpecVersion, "CheckSpecVersion");
+
+/// The `CheckTxVersion` transaction extension, which ensures that the transaction is
+/// only valid for a runtime with the given transaction version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckTxVersion(u32);
+
+impl CheckTxVersion {
+    /// Construct this extension given the current runtime transaction version.
+    pub fn new(transaction_version: u32) -> Self {
+        CheckTxVersion(transaction_version)
+    }
+}
+
+impl_implicit_only_extension!(CheckTxVersion, "CheckTxVersion");
+
+/// The `CheckGenesis` transaction extension, which ensures that the transaction is only
+/// valid on the chain with the given genesis hash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckGenesis([u8; 32]);
+
+impl CheckGenesis {
+    /// Construct this extension given the genesis hash of the chain.
+    pub fn new(genesis_hash: [u8; 32]) -> Self {
+        CheckGenesis(genesis_hash)
+    }
+}
+
+impl_implicit_only_extension!(CheckGenesis, "CheckGenesis");
+
+/// The `CheckWeight` transaction extension. This has no value or implicit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheckWeight;
+
+impl_empty_extension!(CheckWeight, "CheckWeight");
+
+/// The `CheckNonZeroSender` transaction extension. This has no value or implicit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheckNonZeroSender;
+
+impl_empty_extension!(CheckNonZeroSender, "CheckNonZeroSender");
+
+/// The `CheckNonce` transaction extension, which provides the account nonce of the signer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckNonce(u64);
+
+impl CheckNonce {
+    /// Construct this extension given the account nonce of the signer.
+    pub fn new(nonce: u64) -> Self {
+        CheckNonce(nonce)
+    }
+}
+
+impl<Resolver: TypeResolver> TransactionExtension<Resolver> for CheckNonce {
+    const NAME: &str = "CheckNonce";
+
+    fn encode_value_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&self.0, type_id, type_resolver, out)
+    }
+
+    fn encode_implicit_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&(), type_id, type_resolver, out)
+    }
+}
+
+/// The `ChargeTransactionPayment` transaction extension, which provides a tip to be paid
+/// alongside the transaction fees.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChargeTransactionPayment(u128);
+
+impl ChargeTransactionPayment {
+    /// Construct this extension given a tip to pay (which may be 0).
+    pub fn new(tip: u128) -> Self {
+        ChargeTransactionPayment(tip)
+    }
+}
+
+impl<Resolver: TypeResolver> TransactionExtension<Resolver> for ChargeTransactionPayment {
+    const NAME: &str = "ChargeTransactionPayment";
+
+    fn encode_value_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&self.0, type_id, type_resolver, out)
+    }
+
+    fn encode_implicit_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&(), type_id, type_resolver, out)
+    }
+}
+
+/// The `ChargeAssetTxPayment` transaction extension, which provides a tip to be paid alongside
+/// the transaction fees, and optionally the ID of an asset to pay the fees and tip in.
+///
+/// The asset ID can be anything implementing [`EncodeAsType`], since its shape differs between
+/// chains (for instance it may be a `u32` or an XCM `Location`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChargeAssetTxPayment<AssetId = ()> {
+    tip: u128,
+    asset_id: Option<AssetId>,
+}
+
+impl ChargeAssetTxPayment {
+    /// Construct this extension given a tip to pay (which may be 0). Fees will be paid
+    /// in the native currency.
+    pub fn new(tip: u128) -> Self {
+        ChargeAssetTxPayment {
+            tip,
+            asset_id: None,
+        }
+    }
+}
+
+impl<AssetId> ChargeAssetTxPayment<AssetId> {
+    /// Pay the fees and tip in the asset with the given ID.
+    pub fn with_asset_id<NewAssetId>(
+        self,
+        asset_id: NewAssetId,
+    ) -> ChargeAssetTxPayment<NewAssetId> {
+        ChargeAssetTxPayment {
+            tip: self.tip,
+            asset_id: Some(asset_id),
+        }
+    }
+}
+
+impl<Resolver: TypeResolver, AssetId: EncodeAsType> TransactionExtension<Resolver>
+    for ChargeAssetTxPayment<AssetId>
+{
+    const NAME: &str = "ChargeAssetTxPayment";
+
+    fn encode_value_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        Composite::new(
+            [
+                (Some("tip"), CompositeField::new(&self.tip)),
+                (Some("asset_id"), CompositeField::new(&self.asset_id)),
+            ]
+            .into_iter(),
+        )
+        .encode_composite_as_type_to(type_id, type_resolver, out)
+        .map_err(|e| e.into())
+    }
+
+    fn encode_implicit_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&(), type_id, type_resolver, out)
+    }
+}
+
+/// The `CheckMetadataHash` transaction extension, which optionally checks that the transaction
+/// was constructed using metadata with the given hash. This is used by wallets (eg hardware
+/// wallets) which rely on metadata to display transaction information.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheckMetadataHash(Option<[u8; 32]>);
+
+impl CheckMetadataHash {
+    /// Don't check the metadata hash.
+    pub fn disabled() -> Self {
+        CheckMetadataHash(None)
+    }
+
+    /// Check that the metadata hash is the one given.
+    pub fn enabled(metadata_hash: [u8; 32]) -> Self {
+        CheckMetadataHash(Some(metadata_hash))
+    }
+}
+
+impl<Resolver: TypeResolver> TransactionExtension<Resolver> for CheckMetadataHash {
+    const NAME: &str = "CheckMetadataHash";
+
+    fn encode_value_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        let mode = CheckMetadataHashMode(self.0.is_some());
+        Composite::new([(Some("mode"), CompositeField::new(&mode))].into_iter())
+            .encode_composite_as_type_to(type_id, type_resolver, out)
+            .map_err(|e| e.into())
+    }
+
+    fn encode_implicit_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&self.0, type_id, type_resolver, out)
+    }
+}
+
+// The "mode" of the CheckMetadataHash extension; true if Enabled and false if Disabled.
+struct CheckMetadataHashMode(bool);
+
+impl EncodeAsType for CheckMetadataHashMode {
+    fn encode_as_type_to<R: TypeResolver>(
+        &self,
+        type_id: R::TypeId,
+        types: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), scale_encode::Error> {
+        let name = if self.0 { "Enabled" } else { "Disabled" };
+        Variant {
+            name,
+            fields: Composite::new(core::iter::empty()),
+        }
+        .encode_variant_as_type_to(type_id, types, out)
+    }
+}
+
+/// The `CheckMortality` transaction extension, which determines the period of blocks that the
+/// transaction is valid for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckMortality {
+    era: Era,
+    block_hash: [u8; 32],
+}
+
+impl CheckMortality {
+    /// The transaction will be valid forever. The genesis hash of the chain must be given.
+    pub fn immortal(genesis_hash: [u8; 32]) -> Self {
+        CheckMortality {
+            era: Era::Immortal,
+            block_hash: genesis_hash,
+        }
+    }
+
+    /// The transaction will be valid for roughly `period` blocks after the block with the
+    /// number and hash given (see [`Era::mortal`]).
+    pub fn mortal(period: u64, block_number: u64, block_hash: [u8; 32]) -> Self {
+        CheckMortality {
+            era: Era::mortal(period, block_number),
+            block_hash,
+        }
+    }
+
+    /// The era that this transaction will be valid for.
+    pub fn era(&self) -> Era {
+        self.era
+    }
+}
+
+impl<Resolver: TypeResolver> TransactionExtension<Resolver> for CheckMortality {
+    const NAME: &str = "CheckMortality";
+
+    fn encode_value_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&self.era, type_id, type_resolver, out)
+    }
+
+    fn encode_implicit_to(
+        &self,
+        type_id: Resolver::TypeId,
+        type_resolver: &Resolver,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        encode_as_type(&self.block_hash, type_id, type_resolver, out)
+    }
+}
+
+/// The era of a transaction, which determines the period of blocks that it is valid for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Era {
+    /// The transaction is valid forever.
+    Immortal,
+    /// The transaction is valid for `period` blocks, starting from the block whose number
+    /// modulo `period` is `phase`. `period` is a power of two between 4 and 65536, and
+    /// `phase` is less than `period`.
+    Mortal {
+        /// The number of blocks that the transaction is valid for.
+        period: u64,
+        /// The block number of the first block that the transaction is valid in, modulo `period`.
+        phase: u64,
+    },
+}
+
+impl Era {
+    /// Create a mortal era which is valid for roughly `period` blocks, starting from the
+    /// block with the number given. The period will be rounded to a power of two between
+    /// 4 and 65536, and for large periods the starting block may be slightly before the one
+    /// given, as in Substrate.
+    pub fn mortal(period: u64, block_number: u64) -> Self {
+        let period = period
+            .checked_next_power_of_two()
+            .unwrap_or(1 << 16)
+            .clamp(4, 1 << 16);
+        let phase = block_number % period;
+        let quantize_factor = (period >> 12).max(1);
+        let quantized_phase = phase / quantize_factor * quantize_factor;
+
+        Era::Mortal {
+            period,
+            phase: quantized_phase,
+        }
+    }
+
+    /// The SCALE encoded bytes of this era. This is either a single 0 byte for an immortal
+    /// era, or two bytes for a mortal era.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match *self {
+            Era::Immortal => alloc::vec![0],
+            Era::Mortal { period, phase } => {
+                let quantize_factor = (period >> 12).max(1);
+                let encoded = (period.trailing_zeros().saturating_sub(1)).clamp(1, 15) as u16
+                    | ((phase / quantize_factor) << 4) as u16;
+                encoded.to_le_bytes().to_vec()
+            }
+        }
+    }
+
+    /// Decode an era from its SCALE encoded bytes, returning `None` if the bytes do not
+    /// represent a valid era. The cursor is advanced past the bytes that were decoded.
+    pub fn from_bytes(cursor: &mut &[u8]) -> Option<Era> {
+        let (&first, rest) = cursor.split_first()?;
+        if first == 0 {
+            *cursor = rest;
+            return Some(Era::Immortal);
+        }
+
+        let (&second, rest) = rest.split_first()?;
+        let encoded = first as u64 + ((second as u64) << 8);
+        let period = 2 << (encoded % (1 << 4));
+        let quantize_factor = (period >> 12).max(1);
+        let phase = (encoded >> 4) * quantize_factor;
+
+        if period >= 4 && phase < period {
+            *cursor = rest;
+            Some(Era::Mortal { period, phase })
+        } else {
+            None
+        }
+    }
+
+    /// The number of the first block that a transaction with this era is valid in, given the
+    /// number of a block during its lifetime (for instance the block that it was included in).
+    /// This is 0 for immortal eras.
+    pub fn birth(&self, block_number: u64) -> u64 {
+        match *self {
+            Era::Immortal => 0,
+            Era::Mortal { period, phase } => {
+                (block_number.max(phase) - phase) / period.max(1) * period + phase
+            }
+        }
+    }
+
+    /// The number of the first block that a transaction with this era is no longer valid in,
+    /// given the number of a block during its lifetime (for instance the block that it was
+    /// included in). This is [`u64::MAX`] for immortal eras.
+    pub fn death(&self, block_number: u64) -> u64 {
+        match *self {
+            Era::Immortal => u64::MAX,
+            Era::Mortal { period, .. } => self.birth(block_number).saturating_add(period),
+        }
+    }
+
+    /// The range of blocks that a transaction with this era is valid for, given the number
+    /// of a block during its lifetime (for instance the block that it was included in).
+    pub fn mortality(&self, block_number: u64) -> Mortality {
+        Mortality {
+            birth: self.birth(block_number),
+            death: self.death(block_number),
+        }
+    }
+}
+
+/// The range of blocks that a transaction is valid for. See [`Era::mortality`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Mortality {
+    birth: u64,
+    death: u64,
+}
+
+impl Mortality {
+    /// The number of the first block that the transaction is valid in.
+    pub fn birth(&self) -> u64 {
+        self.birth
+    }
+
+    /// The number of the first block that the transaction is no longer valid in.
+    pub fn death(&self) -> u64 {
+        self.death
+    }
+
+    /// Is the transaction valid in the block with the given number?
+    pub fn is_valid_at(&self, block_number: u64) -> bool {
+        self.birth <= block_number && block_number < self.death
+    }
+}
+
+impl EncodeAsType for Era {
+    fn encode_as_type_to<R: TypeResolver>(
+        &self,
+        type_id: R::TypeId,
+        types: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), scale_encode::Error> {
+        // Eras are described in metadata as an enum with the variants `Immortal`, and then
+        // `Mortal1(u8)` to `Mortal255(u8)`, where the variant index is the first byte of the
+        // encoded era and the variant field is the second byte.
+        match *self.to_bytes() {
+            [first, second] => {
+                let name = format!("Mortal{first}");
+                Variant {
+                    name: &name,
+                    fields: Composite::new([(None, CompositeField::new(&second))].into_iter()),
+                }
+                .encode_variant_as_type_to(type_id, types, out)
+            }
+            _ => Variant {
+                name: "Immortal",
+                fields: Composite::new(core::iter::empty()),
+            }
+            .encode_variant_as_type_to(type_id, types, out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use crate::methods::extrinsic_encoder::test_extensions::{
+        ChargeAssetTxPaymentType, PrevalidateAttests,
+    };
+    use crate::methods::extrinsic_encoder::{encode_v4_signed, encode_v4_signer_payload};
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::{Compact, Decode, Encode};
+    use scale_info::{MetaType, PortableRegistry, Registry, TypeInfo, build, meta_type};
+    use scale_value::Value;
+
+    #[test]
+    fn era_encoding_matches_substrate() {
+        assert_eq!(Era::Immortal.to_bytes(), vec![0]);
+        assert_eq!(
+            Era::mortal(64, 42),
+            Era::Mortal {
+                period: 64,
+                phase: 42
+            }
+        );
+        assert_eq!(Era::mortal(64, 42).to_bytes(), vec![0xa5, 0x02]);
+        assert_eq!(
+            Era::mortal(32768, 20000),
+            Era::Mortal {
+                period: 32768,
+                phase: 20000
+            }
+        );
+        assert_eq!(Era::mortal(32768, 20000).to_bytes(), vec![0x4e, 0x9c]);
+        // Periods are rounded up to a power of two and clamped.
+        assert_eq!(
+            Era::mortal(1, 1),
+            Era::Mortal {
+                period: 4,
+                phase: 1
+            }
+        );
+        assert_eq!(
+            Era::mortal(100, 1),
+            Era::Mortal {
+                period: 128,
+                phase: 1
+            }
+        );
+    }
+
+    #[test]
+    fn era_decoding_and_lifetime() {
+        for era in [
+            Era::Immortal,
+            Era::mortal(64, 42),
+            Era::mortal(32768, 20000),
+            Era::mortal(4, 3),
+        ] {
+            let bytes = era.to_bytes();
+            let cursor = &mut &*bytes;
+            assert_eq!(Era::from_bytes(cursor), Some(era));
+            assert!(cursor.is_empty());
+        }
+
+        // Invalid eras (here the phase is not less than the period) fail to decode.
+        assert_eq!(Era::from_bytes(&mut &[0x01, 0x01][..]), None);
+        assert_eq!(Era::from_bytes(&mut &[0x01][..]), None);
+
+        // Transactions are valid for `period` blocks from the birth block.
+        let era = Era::mortal(64, 42);
+        assert_eq!(era.birth(42), 42);
+        assert_eq!(era.birth(100), 42);
+        assert_eq!(era.death(100), 106);
+        assert_eq!(era.birth(106), 106);
+        assert!(era.mortality(100).is_valid_at(100));
+        assert!(!era.mortality(100).is_valid_at(106));
+
+        assert_eq!(Era::Immortal.birth(100), 0);
+        assert_eq!(Era::Immortal.death(100), u64::MAX);
+    }
+
+    #[test]
+    fn standard_extensions_encode_into_extrinsic() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let era = Era::mortal(64, 1000);
+        let exts = (
+            CheckNonZeroSender,
+            CheckSpecVersion::new(9180),
+            CheckTxVersion::new(12),
+            CheckGenesis::new([1; 32]),
+            CheckMortality::mortal(64, 1000, [2; 32]),
+            CheckNonce::new(5),
+            CheckWeight,
+            ChargeTransactionPayment::new(1234),
+            PrevalidateAttests,
+        );
+
+        let call_data = Value::unnamed_composite([Value::from_bytes(b"hello")]);
+        let address = Value::unnamed_variant("Id", [Value::from_bytes([3u8; 32])]);
+        let signature = Value::unnamed_variant("Sr25519", [Value::from_bytes([4u8; 64])]);
+
+        let ext_bytes = encode_v4_signed(
+            "System",
+            "remark",
+            &call_data,
+            &exts,
+            &address,
+            &signature,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+
+        let ext_value = |name: &str| {
+            let ext = ext
+                .transaction_extension_payload()
+                .unwrap()
+                .iter()
+                .find(|e| e.name() == name)
+                .unwrap();
+            ext_bytes[ext.range()].to_vec()
+        };
+
+        assert_eq!(ext_value("CheckMortality"), era.to_bytes());
+        assert_eq!(ext_value("CheckNonce"), Compact(5u32).encode());
+        assert_eq!(
+            ext_value("ChargeTransactionPayment"),
+            Compact(1234u128).encode()
+        );
+
+        // The signer payload ends with the implicit values.
+        let signer_payload = encode_v4_signer_payload(
+            "System",
+            "remark",
+            &call_data,
+            &exts,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let mut implicits = Vec::new();
+        9180u32.encode_to(&mut implicits);
+        12u32.encode_to(&mut implicits);
+        [1u8; 32].encode_to(&mut implicits);
+        [2u8; 32].encode_to(&mut implicits);
+        assert!(signer_payload.ends_with(&implicits));
+    }
+
+    // Test types whose shapes match the FRAME CheckMetadataHash extension, since our
+    // test metadata predates it.
+    struct ModeType;
+    impl TypeInfo for ModeType {
+        type Identity = Self;
+        fn type_info() -> scale_info::Type {
+            scale_info::Type::builder()
+                .path(scale_info::Path::new("Mode", "test"))
+                .variant(
+                    build::Variants::new()
+                        .variant("Disabled", |v| v.index(0))
+                        .variant("Enabled", |v| v.index(1)),
+                )
+        }
+    }
+
+    struct CheckMetadataHashType;
+    impl TypeInfo for CheckMetadataHashType {
+        type Identity = Self;
+        fn type_info() -> scale_info::Type {
+            scale_info::Type::builder()
+                .path(scale_info::Path::new("CheckMetadataHash", "test"))
+                .composite(build::Fields::named().field(|f| f.ty::<ModeType>().name("mode")))
+        }
+    }
+
+    fn registry_with(ty: MetaType) -> (u32, PortableRegistry) {
+        let mut registry = Registry::new();
+        let id = registry.register_type(&ty).id;
+        (id, registry.into())
+    }
+
+    fn encode_value<E: TransactionExtension<PortableRegistry>>(ext: &E, ty: MetaType) -> Vec<u8> {
+        let (id, types) = registry_with(ty);
+        let mut out = Vec::new();
+        ext.encode_value_to(id, &types, &mut out).unwrap();
+        out
+    }
+
+    fn encode_implicit<E: TransactionExtension<PortableRegistry>>(
+        ext: &E,
+        ty: MetaType,
+    ) -> Vec<u8> {
+        let (id, types) = registry_with(ty);
+        let mut out = Vec::new();
+        ext.encode_implicit_to(id, &types, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn charge_asset_tx_payment_encodes_tip_and_asset_id() {
+        let ty = meta_type::<ChargeAssetTxPaymentType>();
+
+        let native = ChargeAssetTxPayment::new(100);
+        let mut expected = Compact(100u128).encode();
+        None::<u32>.encode_to(&mut expected);
+        assert_eq!(encode_value(&native, ty), expected);
+
+        let with_asset = ChargeAssetTxPayment::new(100).with_asset_id(7u32);
+        let mut expected = Compact(100u128).encode();
+        Some(7u32).encode_to(&mut expected);
+        assert_eq!(encode_value(&with_asset, ty), expected);
+    }
+
+    #[test]
+    fn check_metadata_hash_encodes_mode_and_hash() {
+        let ty = meta_type::<CheckMetadataHashType>();
+        let implicit_ty = meta_type::<Option<[u8; 32]>>();
+
+        let disabled = CheckMetadataHash::disabled();
+        assert_eq!(encode_value(&disabled, ty), vec![0]);
+        assert_eq!(
+            encode_implicit(&disabled, implicit_ty),
+            None::<[u8; 32]>.encode()
+        );
+
+        let enabled = CheckMetadataHash::enabled([5; 32]);
+        assert_eq!(encode_value(&enabled, ty), vec![1]);
+        assert_eq!(
+            encode_implicit(&enabled, implicit_ty),
+            Some([5u8; 32]).encode()
+        );
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transaction extensions used when building extrinsics in tests.
+
+use super::transaction_extension::{TransactionExtension, TransactionExtensionError};
+use super::transaction_extensions::{TransactionExtensions, TransactionExtensionsError};
+use alloc::borrow::ToOwned;
+use alloc::vec;
+use alloc::vec::Vec;
+use parity_scale_codec::{Compact, Encode};
+use scale_info::{TypeInfo, build};
+use scale_type_resolver::TypeResolver;
+
+/// Transaction extensions which write out pre-encoded bytes for each extension. Values
+/// in the signer payload are the same as in the extrinsic unless given separately.
+pub(crate) struct RawExtensions {
+    pub values: Vec<(&'static str, Vec<u8>)>,
+    pub signer_payload_values: Vec<(&'static str, Vec<u8>)>,
+    pub implicits: Vec<(&'static str, Vec<u8>)>,
+}
+
+impl RawExtensions {
+    /// The extensions for the Polkadot runtime that our V14 test metadata
+    /// (`metadata_10000000_9180.scale`) is from, with an immortal era.
+    pub fn polkadot(nonce: u32, tip: u128) -> Self {
+        RawExtensions {
+            values: vec![
+                // Immortal era
+                ("CheckMortality", vec![0]),
+                ("CheckNonce", Compact(nonce).encode()),
+                ("ChargeTransactionPayment", Compact(tip).encode()),
+            ],
+            signer_payload_values: vec![],
+            implicits: vec![
+                ("CheckSpecVersion", 9180u32.encode()),
+                ("CheckTxVersion", 12u32.encode()),
+                ("CheckGenesis", [1u8; 32].encode()),
+                ("CheckMortality", [1u8; 32].encode()),
+            ],
+        }
+    }
+}
+
+fn find<'a>(
+    items: &'a [(&'static str, Vec<u8>)],
+    name: &str,
+) -> Result<&'a [u8], TransactionExtensionsError> {
+    items
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, bytes)| &**bytes)
+        .ok_or_else(|| TransactionExtensionsError::NotFound(name.to_owned()))
+}
+
+impl<R: TypeResolver> TransactionExtensions<R> for RawExtensions {
+    fn contains_extension(&self, name: &str) -> bool {
+        self.values.iter().any(|(n, _)| *n == name)
+    }
+
+    fn encode_extension_value_to(
+        &self,
+        name: &str,
+        _type_id: R::TypeId,
+        _type_resolver: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionsError> {
+        out.extend_from_slice(find(&self.values, name)?);
+        Ok(())
+    }
+
+    fn encode_extension_value_for_signer_payload_to(
+        &self,
+        name: &str,
+        _type_id: R::TypeId,
+        _type_resolver: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionsError> {
+        let value =
+            find(&self.signer_payload_values, name).or_else(|_| find(&self.values, name))?;
+        out.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn encode_extension_implicit_to(
+        &self,
+        name: &str,
+        _type_id: R::TypeId,
+        _type_resolver: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionsError> {
+        out.extend_from_slice(find(&self.implicits, name)?);
+        Ok(())
+    }
+}
+
+/// A Polkadot specific extension that we need to provide to build extrinsics
+/// with our V14 test metadata. It has no value and no implicit.
+pub(crate) struct PrevalidateAttests;
+
+impl<R: TypeResolver> TransactionExtension<R> for PrevalidateAttests {
+    const NAME: &str = "PrevalidateAttests";
+
+    fn encode_value_to(
+        &self,
+        _type_id: R::TypeId,
+        _type_resolver: &R,
+        _out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        Ok(())
+    }
+
+    fn encode_implicit_to(
+        &self,
+        _type_id: R::TypeId,
+        _type_resolver: &R,
+        _out: &mut Vec<u8>,
+    ) -> Result<(), TransactionExtensionError> {
+        Ok(())
+    }
+}
+
+/// A type whose shape matches the FRAME `ChargeAssetTxPayment` extension, since our
+/// test metadata predates it.
+pub(crate) struct ChargeAssetTxPaymentType;
+
+impl TypeInfo for ChargeAssetTxPaymentType {
+    type Identity = Self;
+    fn type_info() -> scale_info::Type {
+        scale_info::Type::builder()
+            .path(scale_info::Path::new("ChargeAssetTxPayment", "test"))
+            .composite(
+                build::Fields::named()
+                    .field(|f| f.compact::<u128>().name("tip"))
+                    .field(|f| f.ty::<Option<u32>>().name("asset_id")),
+            )
+    }
+}
@@ -0,0 +1,643 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::error_decoder::{
+    DispatchError, DispatchErrorDecodeError, decode_dispatch_error_at_offset,
+};
+use crate::methods::error_type_info::ErrorTypeInfo;
+use crate::methods::event_records_decoder::{
+    EventRecord, EventRecordsDecodeError, Phase, decode_event_records_with_info,
+};
+use crate::methods::event_type_info::EventTypeInfo;
+use crate::methods::extrinsic_decoder::{
+    Extrinsic, ExtrinsicDecodeError, NamedArg, decode_extrinsic,
+};
+use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
+use crate::methods::storage_type_info::{StorageInfoError, StorageTypeInfo};
+use crate::utils::{DecodeErrorTrace, decode_with_error_tracing};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use scale_decode::IntoVisitor;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode extrinsic receipts.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ExtrinsicReceiptsDecodeError<TypeId> {
+    #[error("Cannot get System.Events storage info:\n\n{0}")]
+    CannotGetEventsInfo(StorageInfoError<'static>),
+    #[error("Cannot decode extrinsic {extrinsic_index}:\n\n{reason}")]
+    CannotDecodeExtrinsic {
+        extrinsic_index: usize,
+        reason: Box<ExtrinsicDecodeError>,
+    },
+    #[error("Cannot decode event records:\n\n{0}")]
+    CannotDecodeEventRecords(Box<EventRecordsDecodeError<TypeId>>),
+    #[error(
+        "Event record {record_index} has phase ApplyExtrinsic({extrinsic_index}), but there are only {num_extrinsics} extrinsics"
+    )]
+    ExtrinsicIndexOutOfBounds {
+        record_index: usize,
+        extrinsic_index: u32,
+        num_extrinsics: usize,
+    },
+    #[error(
+        "The System.ExtrinsicFailed event for extrinsic {extrinsic_index} has no dispatch error field"
+    )]
+    DispatchErrorFieldNotFound { extrinsic_index: usize },
+    #[error("Cannot decode the dispatch error for extrinsic {extrinsic_index}:\n\n{reason}")]
+    CannotDecodeDispatchError {
+        extrinsic_index: usize,
+        reason: Box<DispatchErrorDecodeError>,
+    },
+    #[error("Cannot decode the fee paid for extrinsic {extrinsic_index}:\n\n{reason}")]
+    CannotDecodeFeePaid {
+        extrinsic_index: usize,
+        reason: DecodeErrorTrace,
+    },
+}
+
+/// An owned variant of an [`ExtrinsicReceipt`].
+pub type ExtrinsicReceiptOwned<TypeId> = ExtrinsicReceipt<'static, TypeId>;
+
+/// Everything that happened to a single extrinsic in a block.
+///
+/// The byte ranges in [`ExtrinsicReceipt::extrinsic()`] are relative to the bytes of that
+/// extrinsic, and the byte ranges in [`ExtrinsicReceipt::events()`] and
+/// [`ExtrinsicReceipt::outcome()`] are relative to the `System.Events` bytes.
+#[derive(Clone, Debug)]
+pub struct ExtrinsicReceipt<'info, TypeId> {
+    index: usize,
+    extrinsic: Extrinsic<'info, TypeId>,
+    events: Vec<EventRecord<'info, TypeId>>,
+    outcome: ExtrinsicOutcome<'info, TypeId>,
+    fee_paid: Option<u128>,
+}
+
+impl<'info, TypeId> ExtrinsicReceipt<'info, TypeId> {
+    /// Take ownership of the receipt, so that it no longer references
+    /// the metadata info.
+    pub fn into_owned(self) -> ExtrinsicReceiptOwned<TypeId> {
+        ExtrinsicReceipt {
+            index: self.index,
+            extrinsic: self.extrinsic.into_owned(),
+            events: self.events.into_iter().map(|e| e.into_owned()).collect(),
+            outcome: self.outcome.into_owned(),
+            fee_paid: self.fee_paid,
+        }
+    }
+
+    /// The index of this extrinsic in the block.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The decoded extrinsic.
+    pub fn extrinsic(&self) -> &Extrinsic<'info, TypeId> {
+        &self.extrinsic
+    }
+
+    /// Iterate over the event records whose phase is `ApplyExtrinsic(n)`, where
+    /// `n` is the index of this extrinsic.
+    pub fn events(&self) -> impl ExactSizeIterator<Item = &EventRecord<'info, TypeId>> {
+        self.events.iter()
+    }
+
+    /// Did the extrinsic succeed or fail?
+    pub fn outcome(&self) -> &ExtrinsicOutcome<'info, TypeId> {
+        &self.outcome
+    }
+
+    /// Returns true if a `System.ExtrinsicSuccess` event was emitted for this extrinsic.
+    pub fn is_success(&self) -> bool {
+        matches!(self.outcome, ExtrinsicOutcome::Success)
+    }
+
+    /// The `actual_fee` from a `TransactionPayment.TransactionFeePaid` event for this
+    /// extrinsic, if one was emitted.
+    ///
+    /// The fee is decoded into a `u128`, which any unsigned integer balance type up to 128 bits
+    /// in size can be decoded into. If the balance type cannot be decoded into a `u128`, then
+    /// [`decode_extrinsic_receipts`] returns an
+    /// [`ExtrinsicReceiptsDecodeError::CannotDecodeFeePaid`] error rather than guessing at it.
+    pub fn fee_paid(&self) -> Option<u128> {
+        self.fee_paid
+    }
+
+    /// Map the type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> ExtrinsicReceipt<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        ExtrinsicReceipt {
+            index: self.index,
+            extrinsic: self.extrinsic.map_type_id(&mut f),
+            events: self
+                .events
+                .into_iter()
+                .map(|e| e.map_type_id(&mut f))
+                .collect(),
+            outcome: self.outcome.map_type_id(&mut f),
+            fee_paid: self.fee_paid,
+        }
+    }
+}
+
+/// Whether some extrinsic succeeded or failed.
+#[derive(Clone, Debug)]
+pub enum ExtrinsicOutcome<'info, TypeId> {
+    /// A `System.ExtrinsicSuccess` event was emitted.
+    Success,
+    /// A `System.ExtrinsicFailed` event was emitted, containing this error.
+    Failed(DispatchError<'info, TypeId>),
+    /// Neither a `System.ExtrinsicSuccess` nor a `System.ExtrinsicFailed` event was emitted.
+    Unknown,
+}
+
+impl<'info, TypeId> ExtrinsicOutcome<'info, TypeId> {
+    /// Take ownership of the outcome, so that it no longer references
+    /// the metadata info.
+    pub fn into_owned(self) -> ExtrinsicOutcome<'static, TypeId> {
+        match self {
+            ExtrinsicOutcome::Success => ExtrinsicOutcome::Success,
+            ExtrinsicOutcome::Failed(e) => ExtrinsicOutcome::Failed(e.into_owned()),
+            ExtrinsicOutcome::Unknown => ExtrinsicOutcome::Unknown,
+        }
+    }
+
+    /// Map the type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, f: F) -> ExtrinsicOutcome<'info, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        match self {
+            ExtrinsicOutcome::Success => ExtrinsicOutcome::Success,
+            ExtrinsicOutcome::Failed(e) => ExtrinsicOutcome::Failed(e.map_type_id(f)),
+            ExtrinsicOutcome::Unknown => ExtrinsicOutcome::Unknown,
+        }
+    }
+}
+
+/// Decode the extrinsics in a block alongside the `System.Events` storage value for that block,
+/// returning an [`ExtrinsicReceipt`] for each extrinsic. Each receipt contains the decoded
+/// extrinsic, the events emitted while applying it, whether it succeeded or failed (and if it
+/// failed, the [`DispatchError`]), and the fee paid for it if one was reported.
+///
+/// Each of the extrinsics is expected to be the bytes for a single extrinsic, including its
+/// compact-encoded length prefix, as given to [`crate::extrinsics::decode_extrinsic`].
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::receipts::decode_extrinsic_receipts;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::{Compact, Decode, Encode};
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+/// let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+/// let extrinsics: Vec<Vec<u8>> = extrinsics_hex
+///     .iter()
+///     .map(|hex| hex::decode(hex.trim_start_matches("0x")).unwrap())
+///     .collect();
+///
+/// // We'd normally fetch these from System.Events; here there are no events at all.
+/// let events_bytes = Compact(0u32).encode();
+///
+/// let receipts = decode_extrinsic_receipts(&extrinsics, &events_bytes, &metadata, &metadata.types).unwrap();
+///
+/// assert_eq!(receipts.len(), extrinsics.len());
+/// for receipt in receipts {
+///     println!("Extrinsic {}: {}.{}", receipt.index(), receipt.extrinsic().pallet_name(), receipt.extrinsic().call_name());
+///     assert_eq!(receipt.events().len(), 0);
+/// }
+/// ```
+pub fn decode_extrinsic_receipts<'info, Info, Resolver, Ext>(
+    extrinsics: &[Ext],
+    events_bytes: &[u8],
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<
+    Vec<ExtrinsicReceipt<'info, <Info as ExtrinsicTypeInfo>::TypeId>>,
+    ExtrinsicReceiptsDecodeError<<Info as ExtrinsicTypeInfo>::TypeId>,
+>
+where
+    Ext: AsRef<[u8]>,
+    Info: ExtrinsicTypeInfo
+        + StorageTypeInfo<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>
+        + EventTypeInfo<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>
+        + ErrorTypeInfo<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>,
+    <Info as ExtrinsicTypeInfo>::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>,
+{
+    let events_ty = info
+        .storage_info("System", "Events")
+        .map_err(|e| ExtrinsicReceiptsDecodeError::CannotGetEventsInfo(e.into_owned()))?
+        .value_id;
+
+    decode_extrinsic_receipts_with_info(extrinsics, events_bytes, events_ty, info, type_resolver)
+}
+
+/// Like [`decode_extrinsic_receipts`], but the type ID of the `System.Events` storage value
+/// is provided rather than being looked up.
+pub fn decode_extrinsic_receipts_with_info<'info, Info, Resolver, Ext>(
+    extrinsics: &[Ext],
+    events_bytes: &[u8],
+    events_ty: <Info as ExtrinsicTypeInfo>::TypeId,
+    info: &'info Info,
+    type_resolver: &Resolver,
+) -> Result<
+    Vec<ExtrinsicReceipt<'info, <Info as ExtrinsicTypeInfo>::TypeId>>,
+    ExtrinsicReceiptsDecodeError<<Info as ExtrinsicTypeInfo>::TypeId>,
+>
+where
+    Ext: AsRef<[u8]>,
+    Info: ExtrinsicTypeInfo
+        + EventTypeInfo<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>
+        + ErrorTypeInfo<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>,
+    <Info as ExtrinsicTypeInfo>::TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = <Info as ExtrinsicTypeInfo>::TypeId>,
+{
+    let mut receipts = extrinsics
+        .iter()
+        .enumerate()
+        .map(|(extrinsic_index, ext)| {
+            let extrinsic =
+                decode_extrinsic(&mut ext.as_ref(), info, type_resolver).map_err(|reason| {
+                    ExtrinsicReceiptsDecodeError::CannotDecodeExtrinsic {
+                        extrinsic_index,
+                        reason: Box::new(reason),
+                    }
+                })?;
+            Ok(ExtrinsicReceipt {
+                index: extrinsic_index,
+                extrinsic,
+                events: Vec::new(),
+                outcome: ExtrinsicOutcome::Unknown,
+                fee_paid: None,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let records = decode_event_records_with_info(events_bytes, events_ty, info, type_resolver)
+        .map_err(|e| ExtrinsicReceiptsDecodeError::CannotDecodeEventRecords(Box::new(e)))?;
+
+    for (record_index, record) in records.enumerate() {
+        let record = record
+            .map_err(|e| ExtrinsicReceiptsDecodeError::CannotDecodeEventRecords(Box::new(e)))?;
+
+        let Phase::ApplyExtrinsic(extrinsic_index) = record.phase() else {
+            continue;
+        };
+
+        let num_extrinsics = receipts.len();
+        let receipt = receipts.get_mut(extrinsic_index as usize).ok_or(
+            ExtrinsicReceiptsDecodeError::ExtrinsicIndexOutOfBounds {
+                record_index,
+                extrinsic_index,
+                num_extrinsics,
+            },
+        )?;
+        let extrinsic_index = extrinsic_index as usize;
+
+        match (record.pallet_name(), record.event_name()) {
+            ("System", "ExtrinsicSuccess") => {
+                receipt.outcome = ExtrinsicOutcome::Success;
+            }
+            ("System", "ExtrinsicFailed") => {
+                // ExtrinsicFailed { dispatch_error, dispatch_info }, or
+                // ExtrinsicFailed(DispatchError, DispatchInfo) in older runtimes.
+                let field = find_field(record.event().fields(), "dispatch_error", 0).ok_or(
+                    ExtrinsicReceiptsDecodeError::DispatchErrorFieldNotFound { extrinsic_index },
+                )?;
+                let range = field.range();
+                let dispatch_error = decode_dispatch_error_at_offset(
+                    &mut &events_bytes[range.clone()],
+                    range.start as u32,
+                    field.ty().clone(),
+                    info,
+                    type_resolver,
+                )
+                .map_err(|reason| {
+                    ExtrinsicReceiptsDecodeError::CannotDecodeDispatchError {
+                        extrinsic_index,
+                        reason: Box::new(reason),
+                    }
+                })?;
+                receipt.outcome = ExtrinsicOutcome::Failed(dispatch_error);
+            }
+            ("TransactionPayment", "TransactionFeePaid") => {
+                // TransactionFeePaid { who, actual_fee, tip }
+                if let Some(field) = find_field(record.event().fields(), "actual_fee", 1) {
+                    let fee = decode_with_error_tracing(
+                        &mut &events_bytes[field.range()],
+                        field.ty().clone(),
+                        type_resolver,
+                        u128::into_visitor(),
+                    )
+                    .map_err(|reason| {
+                        ExtrinsicReceiptsDecodeError::CannotDecodeFeePaid {
+                            extrinsic_index,
+                            reason,
+                        }
+                    })?;
+                    receipt.fee_paid = Some(fee);
+                }
+            }
+            _ => {}
+        }
+
+        receipt.events.push(record);
+    }
+
+    Ok(receipts)
+}
+
+// Find a field by name, falling back to its position if the fields are unnamed.
+fn find_field<'a, 'info, TypeId>(
+    fields: impl ExactSizeIterator<Item = &'a NamedArg<'info, TypeId>>,
+    name: &str,
+    position: usize,
+) -> Option<&'a NamedArg<'info, TypeId>>
+where
+    'info: 'a,
+{
+    let fields: Vec<_> = fields.collect();
+    if fields.iter().any(|f| !f.name().is_empty()) {
+        fields.into_iter().find(|f| f.name() == name)
+    } else {
+        fields.into_iter().nth(position)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::error_type_info::ErrorTypeInfo;
+    use frame_metadata::RuntimeMetadata;
+    use frame_metadata::v14::{PalletEventMetadata, RuntimeMetadataV14};
+    use parity_scale_codec::{Compact, Decode, Encode};
+    use scale_info::{Field, Path, PortableType, Type, TypeDef, TypeDefVariant, Variant};
+
+    // The metadata in our artifacts predates the `TransactionFeePaid` event, so this adds it to
+    // the `TransactionPayment` pallet with the same shape that FRAME gives it, using the given
+    // type ID for the `actual_fee` and `tip` balances.
+    fn add_transaction_fee_paid_event(metadata: &mut RuntimeMetadataV14, balance_ty: u32) {
+        let account_id_ty = account_id_ty(metadata);
+        let field = |name: &str, ty: u32| Field {
+            name: Some(name.to_owned()),
+            ty: ty.into(),
+            type_name: None,
+            docs: vec![],
+        };
+
+        let id = metadata.types.types.len() as u32;
+        metadata.types.types.push(PortableType {
+            id,
+            ty: Type {
+                path: Path::from_segments_unchecked([
+                    "pallet_transaction_payment".to_owned(),
+                    "pallet".to_owned(),
+                    "Event".to_owned(),
+                ]),
+                type_params: vec![],
+                type_def: TypeDef::Variant(TypeDefVariant {
+                    variants: vec![Variant {
+                        name: "TransactionFeePaid".to_owned(),
+                        fields: vec![
+                            field("who", account_id_ty),
+                            field("actual_fee", balance_ty),
+                            field("tip", balance_ty),
+                        ],
+                        index: 0,
+                        docs: vec![],
+                    }],
+                }),
+                docs: vec![],
+            },
+        });
+
+        let pallet = metadata
+            .pallets
+            .iter_mut()
+            .find(|p| p.name == "TransactionPayment")
+            .unwrap();
+        assert!(pallet.event.is_none());
+        pallet.event = Some(PalletEventMetadata { ty: id.into() });
+    }
+
+    fn account_id_ty(metadata: &RuntimeMetadataV14) -> u32 {
+        find_type(metadata, |ty| {
+            ty.path.segments.last().map(|s| &**s) == Some("AccountId32")
+        })
+    }
+
+    fn find_type(
+        metadata: &RuntimeMetadataV14,
+        f: impl Fn(&Type<scale_info::form::PortableForm>) -> bool,
+    ) -> u32 {
+        metadata.types.types.iter().find(|t| f(&t.ty)).unwrap().id
+    }
+
+    #[test]
+    fn decode_receipts_with_success_and_failure() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+        let extrinsics: Vec<Vec<u8>> = extrinsics_hex
+            .iter()
+            .map(|hex| hex::decode(hex.trim_start_matches("0x")).unwrap())
+            .collect();
+        assert!(extrinsics.len() >= 2);
+
+        let success = metadata
+            .event_info_by_name("System", "ExtrinsicSuccess")
+            .unwrap();
+        let failed = metadata
+            .event_info_by_name("System", "ExtrinsicFailed")
+            .unwrap();
+        let error = metadata
+            .error_info_by_name("Balances", "InsufficientBalance")
+            .unwrap();
+
+        // DispatchInfo { weight: u64, class: Normal, pays_fee: Yes }
+        let mut dispatch_info = 1000u64.encode();
+        dispatch_info.extend([0, 0]);
+
+        let mut events_bytes = Compact(3u32).encode();
+
+        // Phase::ApplyExtrinsic(0): System.ExtrinsicSuccess
+        events_bytes.extend([0, 0, 0, 0, 0, success.pallet_index, success.event_index]);
+        events_bytes.extend(&dispatch_info);
+        events_bytes.push(0);
+
+        // Phase::ApplyExtrinsic(1): System.ExtrinsicFailed(Module(Balances.InsufficientBalance))
+        events_bytes.extend([0, 1, 0, 0, 0, failed.pallet_index, failed.event_index]);
+        let module_error_start = events_bytes.len() + 1;
+        events_bytes.extend([3, error.pallet_index, error.error_index]);
+        events_bytes.extend(&dispatch_info);
+        events_bytes.push(0);
+
+        // Phase::Finalization: System.ExtrinsicSuccess (not linked to any extrinsic)
+        events_bytes.extend([1, success.pallet_index, success.event_index]);
+        events_bytes.extend(&dispatch_info);
+        events_bytes.push(0);
+
+        let receipts =
+            decode_extrinsic_receipts(&extrinsics, &events_bytes, &metadata, &metadata.types)
+                .unwrap();
+        assert_eq!(receipts.len(), extrinsics.len());
+
+        assert_eq!(receipts[0].index(), 0);
+        assert!(receipts[0].is_success());
+        assert_eq!(receipts[0].events().len(), 1);
+        assert_eq!(receipts[0].fee_paid(), None);
+
+        assert_eq!(receipts[1].events().len(), 1);
+        let ExtrinsicOutcome::Failed(DispatchError::Module(err)) = receipts[1].outcome() else {
+            panic!("Expected extrinsic 1 to fail with a module error");
+        };
+        assert_eq!(err.pallet_name(), "Balances");
+        assert_eq!(err.error_name(), "InsufficientBalance");
+        // Ranges are relative to the events bytes:
+        assert_eq!(
+            err.fields_range(),
+            module_error_start + 2..module_error_start + 2
+        );
+
+        for receipt in &receipts[2..] {
+            assert!(matches!(receipt.outcome(), ExtrinsicOutcome::Unknown));
+            assert_eq!(receipt.events().len(), 0);
+        }
+    }
+
+    #[test]
+    fn extrinsic_index_out_of_bounds() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let success = metadata
+            .event_info_by_name("System", "ExtrinsicSuccess")
+            .unwrap();
+
+        let mut events_bytes = Compact(1u32).encode();
+        events_bytes.extend([0, 0, 0, 0, 0, success.pallet_index, success.event_index]);
+        events_bytes.extend(1000u64.encode());
+        events_bytes.extend([0, 0, 0]);
+
+        let extrinsics: Vec<Vec<u8>> = Vec::new();
+        let err = decode_extrinsic_receipts(&extrinsics, &events_bytes, &metadata, &metadata.types)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ExtrinsicReceiptsDecodeError::ExtrinsicIndexOutOfBounds {
+                record_index: 0,
+                extrinsic_index: 0,
+                num_extrinsics: 0
+            }
+        ));
+    }
+
+    #[test]
+    fn decode_receipts_with_fee_paid() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(original_metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+        let extrinsics: Vec<Vec<u8>> = extrinsics_hex
+            .iter()
+            .map(|hex| hex::decode(hex.trim_start_matches("0x")).unwrap())
+            .collect();
+
+        let primitive_ty = |primitive| {
+            find_type(
+                &original_metadata,
+                |ty| matches!(&ty.type_def, TypeDef::Primitive(p) if *p == primitive),
+            )
+        };
+        let u128_ty = primitive_ty(scale_info::TypeDefPrimitive::U128);
+        let u64_ty = primitive_ty(scale_info::TypeDefPrimitive::U64);
+
+        // Decode TransactionFeePaid { who, actual_fee, tip } followed by ExtrinsicSuccess for
+        // the first extrinsic, given some metadata and the encoded balances.
+        let decode_receipts = |metadata: &RuntimeMetadataV14, balances: &[u8]| {
+            let fee_paid = metadata
+                .event_info_by_name("TransactionPayment", "TransactionFeePaid")
+                .unwrap();
+            let success = metadata
+                .event_info_by_name("System", "ExtrinsicSuccess")
+                .unwrap();
+
+            let mut events_bytes = Compact(2u32).encode();
+            events_bytes.extend([0, 0, 0, 0, 0, fee_paid.pallet_index, fee_paid.event_index]);
+            events_bytes.extend([1u8; 32]);
+            events_bytes.extend(balances);
+            events_bytes.push(0);
+            events_bytes.extend([0, 0, 0, 0, 0, success.pallet_index, success.event_index]);
+            events_bytes.extend(1000u64.encode());
+            events_bytes.extend([0, 0, 0]);
+
+            decode_extrinsic_receipts(&extrinsics, &events_bytes, metadata, &metadata.types).map(
+                |receipts| {
+                    assert!(receipts[0].is_success());
+                    assert_eq!(receipts[0].events().len(), 2);
+                    receipts[0].fee_paid()
+                },
+            )
+        };
+
+        // FRAME balances are u128s:
+        let mut metadata = original_metadata.clone();
+        add_transaction_fee_paid_event(&mut metadata, u128_ty);
+        let balances = (12345u128, 5u128).encode();
+        assert_eq!(decode_receipts(&metadata, &balances).unwrap(), Some(12345));
+
+        // Smaller balance types decode into a u128 too:
+        let mut metadata = original_metadata.clone();
+        add_transaction_fee_paid_event(&mut metadata, u64_ty);
+        let balances = (12345u64, 5u64).encode();
+        assert_eq!(decode_receipts(&metadata, &balances).unwrap(), Some(12345));
+
+        // Balance types which can't be decoded into a u128 lead to an error:
+        let mut metadata = original_metadata.clone();
+        add_transaction_fee_paid_event(&mut metadata, account_id_ty(&original_metadata));
+        let balances = ([2u8; 32], [3u8; 32]).encode();
+        assert!(matches!(
+            decode_receipts(&metadata, &balances),
+            Err(ExtrinsicReceiptsDecodeError::CannotDecodeFeePaid {
+                extrinsic_index: 0,
+                ..
+            })
+        ));
+    }
+}
@@ -13,6 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::deprecation::{Deprecation, DeprecationOf};
+use super::outer_enum_type_info::OuterEnum;
+use crate::utils::{OuterEnumPalletIndexes, find_variant, to_docs};
 use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -66,6 +69,35 @@ pub trait ExtrinsicTypeInfo {
     ) -> Result<impl Iterator<Item = u8>, ExtrinsicInfoError<'_>>;
 }
 
+/// Implementations of this are responsible for handing back the docs for calls, where the
+/// metadata contains them. This is implemented for the same metadata versions as
+/// [`ExtrinsicTypeInfo`].
+pub trait ExtrinsicDocsInfo {
+    /// Get the docs for a given call, or `None` if the call can't be found.
+    fn extrinsic_call_docs(&self, pallet_name: &str, call_name: &str) -> Option<Vec<&str>>;
+
+    /// Get the docs for a given call argument, or `None` if the argument can't be found.
+    /// Legacy (pre-V14) metadata has no docs for call arguments, and so this will return
+    /// no lines for any argument which exists.
+    fn extrinsic_call_arg_docs(
+        &self,
+        pallet_name: &str,
+        call_name: &str,
+        arg_name: &str,
+    ) -> Option<Vec<&str>>;
+}
+
+/// Implementations of this are responsible for handing back the type ID of the outer
+/// `RuntimeCall` enum, which is the type of any call data. This is used to find calls which
+/// are nested inside of the arguments of other calls.
+///
+/// For legacy (pre-V14) metadata, this hands back `builtin::Call`, which is expected to be
+/// provided by [`crate::helpers::type_registry_from_metadata`].
+pub trait ExtrinsicCallTypeInfo: ExtrinsicTypeInfo {
+    /// Get the type ID of the outer `RuntimeCall` enum, or `None` if it can't be found.
+    fn extrinsic_call_type_id(&self) -> Option<Self::TypeId>;
+}
+
 /// An error returned trying to access extrinsic type information.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -92,6 +124,8 @@ pub enum ExtrinsicInfoError<'a> {
         name: Cow<'a, str>,
         reason: scale_info_legacy::lookup_name::ParseError,
     },
+    #[cfg(feature = "legacy")]
+    MetadataNotDecoded,
     CallsTypeNotFound {
         id: u32,
         pallet_index: u8,
@@ -113,6 +147,10 @@ pub enum ExtrinsicInfoError<'a> {
     ExtrinsicExtensionVersionNotFound {
         extension_version: u8,
     },
+    ExtrinsicExtensionNotFound {
+        index: u32,
+        extension_version: u8,
+    },
 }
 
 impl core::error::Error for ExtrinsicInfoError<'_> {}
@@ -150,6 +188,13 @@ impl core::fmt::Display for ExtrinsicInfoError<'_> {
             ExtrinsicInfoError::CannotParseTypeName { name, reason } => {
                 write!(f, "Cannot parse type name '{name}':\n\n{reason}")
             }
+            #[cfg(feature = "legacy")]
+            ExtrinsicInfoError::MetadataNotDecoded => {
+                write!(
+                    f,
+                    "The metadata was not SCALE decoded and so cannot be read"
+                )
+            }
             ExtrinsicInfoError::CallsTypeNotFound {
                 id,
                 pallet_index,
@@ -194,6 +239,15 @@ impl core::fmt::Display for ExtrinsicInfoError<'_> {
                     "Could not find information about extensions with version {extension_version} in the metadata. Note: Metadata <=V15 only supports version 0."
                 )
             }
+            ExtrinsicInfoError::ExtrinsicExtensionNotFound {
+                index,
+                extension_version,
+            } => {
+                write!(
+                    f,
+                    "Extensions with version {extension_version} refer to an extension with index {index}, but no such extension exists in the metadata."
+                )
+            }
         }
     }
 }
@@ -235,6 +289,8 @@ impl ExtrinsicInfoError<'_> {
                     reason,
                 }
             }
+            #[cfg(feature = "legacy")]
+            ExtrinsicInfoError::MetadataNotDecoded => ExtrinsicInfoError::MetadataNotDecoded,
             ExtrinsicInfoError::CallsTypeNotFound {
                 id,
                 pallet_index,
@@ -268,6 +324,13 @@ impl ExtrinsicInfoError<'_> {
             ExtrinsicInfoError::ExtrinsicExtensionVersionNotFound { extension_version } => {
                 ExtrinsicInfoError::ExtrinsicExtensionVersionNotFound { extension_version }
             }
+            ExtrinsicInfoError::ExtrinsicExtensionNotFound {
+                index,
+                extension_version,
+            } => ExtrinsicInfoError::ExtrinsicExtensionNotFound {
+                index,
+                extension_version,
+            },
         }
     }
 }
@@ -296,6 +359,8 @@ pub struct ExtrinsicCallInfo<'info, TypeId> {
     pub call_name: Cow<'info, str>,
     /// Names and types of each of the extrinsic arguments.
     pub args: Vec<ExtrinsicCallInfoArg<'info, TypeId>>,
+    /// Whether the call is deprecated.
+    pub deprecation: Deprecation<'info>,
 }
 
 /// An argument in some extrinsic call data.
@@ -335,7 +400,10 @@ macro_rules! impl_call_info_by_name_body_for_v14_to_v16 {
                 name: Cow::Owned($pallet_name.to_string()),
             })?;
 
-        let pallet_index = pallet.index;
+        let pallet_index = $self
+            .outer_enum_pallet_variants(OuterEnum::Call)
+            .and_then(|variants| variants.iter().find(|v| v.name == pallet.name))
+            .map_or(pallet.index, |v| v.index);
 
         let calls_id = pallet
             .calls
@@ -399,19 +467,23 @@ macro_rules! impl_call_info_by_name_body_for_v14_to_v16 {
             pallet_name: Cow::Borrowed(&pallet.name),
             call_name: Cow::Borrowed(&call_variant.name),
             args,
+            deprecation: pallet.deprecation_of(call_variant.index),
         })
     }};
 }
 
 macro_rules! impl_call_info_by_index_body_for_v14_to_v16 {
     ($self:ident, $pallet_index:ident, $call_index:ident) => {{
-        let pallet = $self
-            .pallets
-            .iter()
-            .find(|p| p.index == $pallet_index)
-            .ok_or_else(|| ExtrinsicInfoError::PalletNotFound {
-                index: $pallet_index,
-            })?;
+        let pallet = match $self.outer_enum_pallet_variants(OuterEnum::Call) {
+            Some(variants) => variants
+                .iter()
+                .find(|v| v.index == $pallet_index)
+                .and_then(|v| $self.pallets.iter().find(|p| p.name == v.name)),
+            None => $self.pallets.iter().find(|p| p.index == $pallet_index),
+        }
+        .ok_or_else(|| ExtrinsicInfoError::PalletNotFound {
+            index: $pallet_index,
+        })?;
 
         let pallet_name = &pallet.name;
 
@@ -477,6 +549,7 @@ macro_rules! impl_call_info_by_index_body_for_v14_to_v16 {
             pallet_name: Cow::Borrowed(pallet_name),
             call_name: Cow::Borrowed(&call_variant.name),
             args,
+            deprecation: pallet.deprecation_of(call_variant.index),
         })
     }};
 }
@@ -632,15 +705,18 @@ impl ExtrinsicTypeInfo for frame_metadata::v16::RuntimeMetadataV16 {
                     .extrinsic
                     .transaction_extensions
                     .get(idx.0 as usize)
-                    .expect("Index in transaction_extensions_by_version should exist in transaction_extensions");
+                    .ok_or(ExtrinsicInfoError::ExtrinsicExtensionNotFound {
+                        index: idx.0,
+                        extension_version,
+                    })?;
 
-                ExtrinsicExtensionInfoArg {
+                Ok(ExtrinsicExtensionInfoArg {
                     id: ext.ty.id,
                     implicit_id: ext.implicit.id,
                     name: Cow::Borrowed(&ext.identifier),
-                }
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         Ok(ExtrinsicExtensionInfo { extension_ids })
     }
@@ -655,6 +731,65 @@ impl ExtrinsicTypeInfo for frame_metadata::v16::RuntimeMetadataV16 {
     }
 }
 
+macro_rules! impl_extrinsic_docs_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl ExtrinsicDocsInfo for $path {
+            fn extrinsic_call_docs(&self, pallet_name: &str, call_name: &str) -> Option<Vec<&str>> {
+                let pallet = self.pallets.iter().find(|p| p.name == pallet_name)?;
+                let call_ty = pallet.calls.as_ref()?.ty.id;
+                let call = find_variant(&self.types, call_ty, call_name)?;
+                Some(to_docs(&call.docs))
+            }
+            fn extrinsic_call_arg_docs(
+                &self,
+                pallet_name: &str,
+                call_name: &str,
+                arg_name: &str,
+            ) -> Option<Vec<&str>> {
+                let pallet = self.pallets.iter().find(|p| p.name == pallet_name)?;
+                let call_ty = pallet.calls.as_ref()?.ty.id;
+                let call = find_variant(&self.types, call_ty, call_name)?;
+                let arg = call.fields.iter().find(|f| {
+                    f.name
+                        .as_ref()
+                        .is_some_and(|n| n.as_ref() as &str == arg_name)
+                })?;
+                Some(to_docs(&arg.docs))
+            }
+        }
+    };
+}
+
+impl_extrinsic_docs_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_extrinsic_docs_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_extrinsic_docs_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
+impl ExtrinsicCallTypeInfo for frame_metadata::v14::RuntimeMetadataV14 {
+    fn extrinsic_call_type_id(&self) -> Option<Self::TypeId> {
+        // V14 metadata only exposes the call type as a type param on the extrinsic type.
+        let extrinsic_ty = self.types.resolve(self.extrinsic.ty.id)?;
+        extrinsic_ty
+            .type_params
+            .iter()
+            .find(|param| param.name == "Call")
+            .and_then(|param| param.ty)
+            .map(|ty| ty.id)
+    }
+}
+
+macro_rules! impl_extrinsic_call_type_info_for_v15_to_v16 {
+    ($path:path) => {
+        impl ExtrinsicCallTypeInfo for $path {
+            fn extrinsic_call_type_id(&self) -> Option<Self::TypeId> {
+                Some(self.outer_enums.call_enum_ty.id)
+            }
+        }
+    };
+}
+
+impl_extrinsic_call_type_info_for_v15_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_extrinsic_call_type_info_for_v15_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
 fn get_v14_extrinsic_parts(
     metadata: &frame_metadata::v14::RuntimeMetadataV14,
 ) -> Result<ExtrinsicParts, ExtrinsicInfoError<'_>> {
@@ -725,13 +860,14 @@ fn err_if_bad_extension_version<'a>(
 
 #[cfg(feature = "legacy")]
 const _: () = {
-    use crate::utils::as_decoded;
+    use crate::utils::as_decoded_str;
     use alloc::format;
+    use frame_metadata::decode_different::DecodeDifferent;
     use scale_info_legacy::LookupName;
 
     macro_rules! impl_extrinsic_info_by_index_body_for_v8_to_v11 {
         ($self:ident, $pallet_index:ident, $call_index:ident) => {{
-            let modules = as_decoded(&$self.modules);
+            let modules = as_decoded(&$self.modules)?;
 
             let m = modules
                 .iter()
@@ -741,9 +877,7 @@ const _: () = {
                     index: $pallet_index,
                 })?;
 
-            // as_ref to work when scale-info returns `&static str`
-            // instead of `String` in no-std mode.
-            let m_name = as_decoded(&m.name).as_ref();
+            let m_name = as_decoded_str(&m.name);
 
             let calls = m
                 .calls
@@ -754,7 +888,7 @@ const _: () = {
                     pallet_name: Cow::Borrowed(m_name),
                 })?;
 
-            let calls = as_decoded(calls);
+            let calls = as_decoded(calls)?;
 
             let call = calls.get($call_index as usize).ok_or_else(|| {
                 ExtrinsicInfoError::CallNotFound {
@@ -764,16 +898,16 @@ const _: () = {
                 }
             })?;
 
-            let c_name = as_decoded(&call.name);
+            let c_name = as_decoded_str(&call.name);
 
-            let args = as_decoded(&call.arguments);
+            let args = as_decoded(&call.arguments)?;
 
             let args = args
                 .iter()
                 .map(|a| {
-                    let ty = as_decoded(&a.ty);
+                    let ty = as_decoded_str(&a.ty);
                     let id = parse_lookup_name(ty)?.in_pallet(m_name);
-                    let name = as_decoded(&a.name);
+                    let name = as_decoded_str(&a.name);
                     Ok(ExtrinsicCallInfoArg {
                         id,
                         name: Cow::Borrowed(name),
@@ -787,6 +921,7 @@ const _: () = {
                 pallet_name: Cow::Borrowed(m_name),
                 call_name: Cow::Borrowed(c_name),
                 args,
+                deprecation: Deprecation::NotDeprecated,
             })
         }};
     }
@@ -795,14 +930,14 @@ const _: () = {
         ($self:ident, $pallet_name_arg:ident, $call_name_arg:ident) => {{
             use alloc::string::ToString;
 
-            let modules = as_decoded(&$self.modules);
+            let modules = as_decoded(&$self.modules)?;
 
             let (pallet_index, m) = modules
                 .iter()
                 .filter(|m| m.calls.is_some())
                 .enumerate()
                 .find(|(_, m)| {
-                    let name: &str = as_decoded(&m.name).as_ref();
+                    let name: &str = as_decoded_str(&m.name);
                     name == $pallet_name_arg
                 })
                 .ok_or_else(|| ExtrinsicInfoError::PalletNotFoundByName {
@@ -811,9 +946,7 @@ const _: () = {
 
             let pallet_index = pallet_index as u8;
 
-            // as_ref to work when scale-info returns `&static str`
-            // instead of `String` in no-std mode.
-            let m_name: &str = as_decoded(&m.name).as_ref();
+            let m_name: &str = as_decoded_str(&m.name);
 
             let calls = m
                 .calls
@@ -824,13 +957,13 @@ const _: () = {
                     pallet_name: Cow::Borrowed(m_name),
                 })?;
 
-            let calls = as_decoded(calls);
+            let calls = as_decoded(calls)?;
 
             let (call_index, call) = calls
                 .iter()
                 .enumerate()
                 .find(|(_, c)| {
-                    let name: &str = as_decoded(&c.name).as_ref();
+                    let name: &str = as_decoded_str(&c.name);
                     name == $call_name_arg
                 })
                 .ok_or_else(|| ExtrinsicInfoError::CallNotFoundByName {
@@ -839,16 +972,16 @@ const _: () = {
                     pallet_name: Cow::Borrowed(m_name),
                 })?;
 
-            let c_name: &str = as_decoded(&call.name).as_ref();
+            let c_name: &str = as_decoded_str(&call.name);
 
-            let args = as_decoded(&call.arguments);
+            let args = as_decoded(&call.arguments)?;
 
             let args = args
                 .iter()
                 .map(|a| {
-                    let ty: &str = as_decoded(&a.ty).as_ref();
+                    let ty: &str = as_decoded_str(&a.ty);
                     let id = parse_lookup_name(ty)?.in_pallet(m_name);
-                    let name: &str = as_decoded(&a.name).as_ref();
+                    let name: &str = as_decoded_str(&a.name);
                     Ok(ExtrinsicCallInfoArg {
                         id,
                         name: Cow::Borrowed(name),
@@ -862,6 +995,7 @@ const _: () = {
                 pallet_name: Cow::Borrowed(m_name),
                 call_name: Cow::Borrowed(c_name),
                 args,
+                deprecation: Deprecation::NotDeprecated,
             })
         }};
     }
@@ -958,7 +1092,7 @@ const _: () = {
                 .signed_extensions
                 .iter()
                 .map(|e| {
-                    let signed_ext_name = as_decoded(e);
+                    let signed_ext_name = as_decoded_str(e);
                     let signed_ext_id = parse_lookup_name(signed_ext_name)?;
                     let signed_ext_implicit_id =
                         parse_lookup_name(&format!("{signed_ext_name}Implicit"))
@@ -990,7 +1124,7 @@ const _: () = {
                     pallet_index: u8,
                     call_index: u8,
                 ) -> Result<ExtrinsicCallInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
-                    let modules = as_decoded(&self.modules);
+                    let modules = as_decoded(&self.modules)?;
 
                     let m = modules
                         .iter()
@@ -999,9 +1133,7 @@ const _: () = {
                             index: pallet_index,
                         })?;
 
-                    // as_ref to work when scale-info returns `&static str`
-                    // instead of `String` in no-std mode.
-                    let m_name = as_decoded(&m.name).as_ref();
+                    let m_name = as_decoded_str(&m.name);
 
                     let calls =
                         m.calls
@@ -1012,7 +1144,7 @@ const _: () = {
                                 pallet_name: Cow::Borrowed(m_name),
                             })?;
 
-                    let calls = as_decoded(calls);
+                    let calls = as_decoded(calls)?;
 
                     let call = calls.get(call_index as usize).ok_or_else(|| {
                         ExtrinsicInfoError::CallNotFound {
@@ -1022,16 +1154,16 @@ const _: () = {
                         }
                     })?;
 
-                    let c_name = as_decoded(&call.name);
+                    let c_name = as_decoded_str(&call.name);
 
-                    let args = as_decoded(&call.arguments);
+                    let args = as_decoded(&call.arguments)?;
 
                     let args = args
                         .iter()
                         .map(|a| {
-                            let ty = as_decoded(&a.ty);
+                            let ty = as_decoded_str(&a.ty);
                             let id = parse_lookup_name(ty)?.in_pallet(m_name);
-                            let name = as_decoded(&a.name);
+                            let name = as_decoded_str(&a.name);
                             Ok(ExtrinsicCallInfoArg {
                                 id,
                                 name: Cow::Borrowed(name),
@@ -1045,6 +1177,7 @@ const _: () = {
                         pallet_name: Cow::Borrowed(m_name),
                         call_name: Cow::Borrowed(c_name),
                         args,
+                        deprecation: Deprecation::NotDeprecated,
                     })
                 }
                 fn extrinsic_call_info_by_name(
@@ -1054,12 +1187,12 @@ const _: () = {
                 ) -> Result<ExtrinsicCallInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
                     use alloc::string::ToString;
 
-                    let modules = as_decoded(&self.modules);
+                    let modules = as_decoded(&self.modules)?;
 
                     let m = modules
                         .iter()
                         .find(|m| {
-                            let name: &str = as_decoded(&m.name).as_ref();
+                            let name: &str = as_decoded_str(&m.name);
                             name == pallet_name
                         })
                         .ok_or_else(|| ExtrinsicInfoError::PalletNotFoundByName {
@@ -1068,9 +1201,7 @@ const _: () = {
 
                     let pallet_index = m.index;
 
-                    // as_ref to work when scale-info returns `&static str`
-                    // instead of `String` in no-std mode.
-                    let m_name: &str = as_decoded(&m.name).as_ref();
+                    let m_name: &str = as_decoded_str(&m.name);
 
                     let calls =
                         m.calls
@@ -1081,13 +1212,13 @@ const _: () = {
                                 pallet_name: Cow::Borrowed(m_name),
                             })?;
 
-                    let calls = as_decoded(calls);
+                    let calls = as_decoded(calls)?;
 
                     let (call_index, call) = calls
                         .iter()
                         .enumerate()
                         .find(|(_, c)| {
-                            let name: &str = as_decoded(&c.name).as_ref();
+                            let name: &str = as_decoded_str(&c.name);
                             name == call_name
                         })
                         .ok_or_else(|| ExtrinsicInfoError::CallNotFoundByName {
@@ -1096,16 +1227,16 @@ const _: () = {
                             pallet_name: Cow::Borrowed(m_name),
                         })?;
 
-                    let c_name: &str = as_decoded(&call.name).as_ref();
+                    let c_name: &str = as_decoded_str(&call.name);
 
-                    let args = as_decoded(&call.arguments);
+                    let args = as_decoded(&call.arguments)?;
 
                     let args = args
                         .iter()
                         .map(|a| {
-                            let ty: &str = as_decoded(&a.ty).as_ref();
+                            let ty: &str = as_decoded_str(&a.ty);
                             let id = parse_lookup_name(ty)?.in_pallet(m_name);
-                            let name: &str = as_decoded(&a.name).as_ref();
+                            let name: &str = as_decoded_str(&a.name);
                             Ok(ExtrinsicCallInfoArg {
                                 id,
                                 name: Cow::Borrowed(name),
@@ -1119,6 +1250,7 @@ const _: () = {
                         pallet_name: Cow::Borrowed(m_name),
                         call_name: Cow::Borrowed(c_name),
                         args,
+                        deprecation: Deprecation::NotDeprecated,
                     })
                 }
                 fn extrinsic_signature_info(
@@ -1142,7 +1274,7 @@ const _: () = {
                         .signed_extensions
                         .iter()
                         .map(|e| {
-                            let signed_ext_name = as_decoded(e);
+                            let signed_ext_name = as_decoded_str(e);
                             let signed_ext_id = parse_lookup_name(signed_ext_name)?;
                             let signed_ext_implicit_id =
                                 parse_lookup_name(&format!("{signed_ext_name}Implicit"))
@@ -1170,10 +1302,79 @@ const _: () = {
     impl_for_v12_to_v13!(frame_metadata::v12::RuntimeMetadataV12);
     impl_for_v12_to_v13!(frame_metadata::v13::RuntimeMetadataV13);
 
+    fn as_decoded<A, B>(item: &DecodeDifferent<A, B>) -> Result<&B, ExtrinsicInfoError<'static>> {
+        crate::utils::as_decoded(item).map_err(|_| ExtrinsicInfoError::MetadataNotDecoded)
+    }
+
     fn parse_lookup_name(name: &str) -> Result<LookupName, ExtrinsicInfoError<'_>> {
         LookupName::parse(name).map_err(|e| ExtrinsicInfoError::CannotParseTypeName {
             name: Cow::Borrowed(name),
             reason: e,
         })
     }
+
+    macro_rules! impl_extrinsic_docs_info_for_v8_to_v13 {
+        ($path:path) => {
+            impl ExtrinsicDocsInfo for $path {
+                fn extrinsic_call_docs(
+                    &self,
+                    pallet_name: &str,
+                    call_name: &str,
+                ) -> Option<Vec<&str>> {
+                    let call = find_legacy_call!(self, pallet_name, call_name);
+                    Some(to_docs(&as_decoded(&call.documentation).ok()?[..]))
+                }
+                fn extrinsic_call_arg_docs(
+                    &self,
+                    pallet_name: &str,
+                    call_name: &str,
+                    arg_name: &str,
+                ) -> Option<Vec<&str>> {
+                    let call = find_legacy_call!(self, pallet_name, call_name);
+                    as_decoded(&call.arguments)
+                        .ok()?
+                        .iter()
+                        .any(|a| as_decoded_str(&a.name) == arg_name)
+                        .then(Vec::new)
+                }
+            }
+        };
+    }
+
+    macro_rules! find_legacy_call {
+        ($self:ident, $pallet_name:ident, $call_name:ident) => {{
+            let m = as_decoded(&$self.modules)
+                .ok()?
+                .iter()
+                .find(|m| as_decoded_str(&m.name) == $pallet_name)?;
+            as_decoded(m.calls.as_ref()?)
+                .ok()?
+                .iter()
+                .find(|c| as_decoded_str(&c.name) == $call_name)?
+        }};
+    }
+
+    impl_extrinsic_docs_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_extrinsic_docs_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_extrinsic_docs_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_extrinsic_docs_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_extrinsic_docs_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12);
+    impl_extrinsic_docs_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13);
+
+    macro_rules! impl_extrinsic_call_type_info_for_v8_to_v13 {
+        ($path:path) => {
+            impl ExtrinsicCallTypeInfo for $path {
+                fn extrinsic_call_type_id(&self) -> Option<Self::TypeId> {
+                    LookupName::parse("builtin::Call").ok()
+                }
+            }
+        };
+    }
+
+    impl_extrinsic_call_type_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_extrinsic_call_type_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_extrinsic_call_type_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_extrinsic_call_type_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_extrinsic_call_type_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12);
+    impl_extrinsic_call_type_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13);
 };
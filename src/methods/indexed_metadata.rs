@@ -0,0 +1,457 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::constant_type_info::{
+    ConstantEntryInfo, ConstantInfo, ConstantInfoError, ConstantTypeInfo,
+};
+use crate::methods::event_type_info::{EventInfo, EventInfoError, EventTypeInfo};
+use crate::methods::extrinsic_type_info::{
+    ExtrinsicCallInfo, ExtrinsicCallTypeInfo, ExtrinsicExtensionInfo, ExtrinsicInfoError,
+    ExtrinsicSignatureInfo, ExtrinsicTypeInfo,
+};
+use crate::methods::storage_type_info::{
+    StorageEntryInfo, StorageInfo, StorageInfoError, StorageTypeInfo,
+};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use scale_type_resolver::{ResolvedTypeVisitor, TypeResolver};
+
+type CallTypeId<Info> = <Info as ExtrinsicTypeInfo>::TypeId;
+type EventTypeId<Info> = <Info as EventTypeInfo>::TypeId;
+type StorageTypeId<Info> = <Info as StorageTypeInfo>::TypeId;
+type ConstantTypeId<Info> = <Info as ConstantTypeInfo>::TypeId;
+
+// Names of items, grouped by the name of the pallet that they are in.
+type ByName<'info, V> = HashMap<Cow<'info, str>, HashMap<Cow<'info, str>, V>>;
+
+/// This wraps some metadata and precomputes the information needed to decode calls, events,
+/// storage entries and constants from it, so that lookups by index or name are `O(1)` rather
+/// than needing to scan over the pallets and items in the metadata each time.
+///
+/// This is worth using when decoding many things with the same metadata. It implements
+/// [`ExtrinsicTypeInfo`], [`EventTypeInfo`], [`StorageTypeInfo`] and [`ConstantTypeInfo`] (and
+/// [`TypeResolver`] if the wrapped metadata does), and so can be used anywhere that the wrapped
+/// metadata could be. Lookups for items which are not found are handed to the wrapped metadata,
+/// so that the same errors are returned.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::extrinsics::decode_extrinsic;
+/// use frame_decode::metadata::IndexedMetadata;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// // Build the index once..
+/// let indexed = IndexedMetadata::new(&metadata);
+///
+/// // ..and then use it in place of the metadata.
+/// let ext_bytes = hex::decode("280403000bf12d93c38a01").unwrap();
+/// let ext = decode_extrinsic(&mut &*ext_bytes, &indexed, &metadata.types).unwrap();
+/// assert_eq!(ext.pallet_name(), "Timestamp");
+/// ```
+pub struct IndexedMetadata<'info, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo,
+{
+    metadata: &'info Info,
+    calls_by_index: HashMap<(u8, u8), ExtrinsicCallInfo<'info, CallTypeId<Info>>>,
+    calls_by_name: ByName<'info, (u8, u8)>,
+    events_by_index: HashMap<(u8, u8), EventInfo<'info, EventTypeId<Info>>>,
+    events_by_name: ByName<'info, (u8, u8)>,
+    storage: ByName<'info, StorageInfo<'info, StorageTypeId<Info>>>,
+    constants: ByName<'info, ConstantInfo<'info, ConstantTypeId<Info>>>,
+    signature_info: Result<ExtrinsicSignatureInfo<CallTypeId<Info>>, ExtrinsicInfoError<'info>>,
+    extension_info: HashMap<Option<u8>, ExtrinsicExtensionInfo<'info, CallTypeId<Info>>>,
+    extension_versions: Result<Vec<u8>, ExtrinsicInfoError<'info>>,
+}
+
+impl<'info, Info> IndexedMetadata<'info, Info>
+where
+    Info: ExtrinsicTypeInfo
+        + EventTypeInfo
+        + StorageTypeInfo
+        + StorageEntryInfo
+        + ConstantTypeInfo
+        + ConstantEntryInfo,
+{
+    /// Index the given metadata.
+    pub fn new(metadata: &'info Info) -> Self {
+        let mut calls_by_index = HashMap::new();
+        let mut calls_by_name: ByName<'info, (u8, u8)> = HashMap::new();
+        let mut events_by_index = HashMap::new();
+        let mut events_by_name: ByName<'info, (u8, u8)> = HashMap::new();
+
+        // There is no iterator over calls or events, and so we look them up by index instead.
+        for pallet_index in 0..=u8::MAX {
+            let has_calls = !matches!(
+                metadata.extrinsic_call_info_by_index(pallet_index, 0),
+                Err(ExtrinsicInfoError::PalletNotFound { .. })
+            );
+            let has_events = !matches!(
+                metadata.event_info_by_index(pallet_index, 0),
+                Err(EventInfoError::PalletNotFound { .. })
+            );
+
+            for index in 0..=u8::MAX {
+                if has_calls
+                    && let Ok(info) = metadata.extrinsic_call_info_by_index(pallet_index, index)
+                {
+                    calls_by_name
+                        .entry(info.pallet_name.clone())
+                        .or_default()
+                        .insert(info.call_name.clone(), (pallet_index, index));
+                    calls_by_index.insert((pallet_index, index), info);
+                }
+                if has_events && let Ok(info) = metadata.event_info_by_index(pallet_index, index) {
+                    events_by_name
+                        .entry(info.pallet_name.clone())
+                        .or_default()
+                        .insert(info.event_name.clone(), (pallet_index, index));
+                    events_by_index.insert((pallet_index, index), info);
+                }
+            }
+        }
+
+        let mut storage: ByName<'info, _> = HashMap::new();
+        for (pallet_name, entry_name) in metadata.storage_tuples() {
+            if let Ok(info) = metadata.storage_info(&pallet_name, &entry_name) {
+                storage
+                    .entry(pallet_name)
+                    .or_default()
+                    .insert(entry_name, info);
+            }
+        }
+
+        let mut constants: ByName<'info, _> = HashMap::new();
+        for (pallet_name, constant_name) in metadata.constant_tuples() {
+            if let Ok(info) = metadata.constant_info(&pallet_name, &constant_name) {
+                constants
+                    .entry(pallet_name)
+                    .or_default()
+                    .insert(constant_name, info);
+            }
+        }
+
+        let extension_versions = metadata
+            .extrinsic_extension_version_info()
+            .map(|versions| versions.collect::<Vec<u8>>());
+
+        let mut extension_info = HashMap::new();
+        let versions = extension_versions.iter().flatten().map(|v| Some(*v));
+        for version in core::iter::once(None).chain(versions) {
+            if let Ok(info) = metadata.extrinsic_extension_info(version) {
+                extension_info.insert(version, info);
+            }
+        }
+
+        IndexedMetadata {
+            metadata,
+            calls_by_index,
+            calls_by_name,
+            events_by_index,
+            events_by_name,
+            storage,
+            constants,
+            signature_info: metadata.extrinsic_signature_info(),
+            extension_info,
+            extension_versions,
+        }
+    }
+}
+
+impl<'info, Info> IndexedMetadata<'info, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo,
+{
+    /// The metadata that has been indexed.
+    pub fn metadata(&self) -> &'info Info {
+        self.metadata
+    }
+}
+
+impl<Info> ExtrinsicTypeInfo for IndexedMetadata<'_, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo,
+    CallTypeId<Info>: Clone,
+{
+    type TypeId = CallTypeId<Info>;
+
+    fn extrinsic_call_info_by_index(
+        &self,
+        pallet_index: u8,
+        call_index: u8,
+    ) -> Result<ExtrinsicCallInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
+        match self.calls_by_index.get(&(pallet_index, call_index)) {
+            Some(info) => Ok(info.clone()),
+            None => self
+                .metadata
+                .extrinsic_call_info_by_index(pallet_index, call_index),
+        }
+    }
+
+    fn extrinsic_call_info_by_name(
+        &self,
+        pallet_name: &str,
+        call_name: &str,
+    ) -> Result<ExtrinsicCallInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
+        let indexes = self
+            .calls_by_name
+            .get(pallet_name)
+            .and_then(|calls| calls.get(call_name));
+        match indexes.and_then(|idx| self.calls_by_index.get(idx)) {
+            Some(info) => Ok(info.clone()),
+            None => self
+                .metadata
+                .extrinsic_call_info_by_name(pallet_name, call_name),
+        }
+    }
+
+    fn extrinsic_signature_info(
+        &self,
+    ) -> Result<ExtrinsicSignatureInfo<Self::TypeId>, ExtrinsicInfoError<'_>> {
+        self.signature_info.clone()
+    }
+
+    fn extrinsic_extension_info(
+        &self,
+        extension_version: Option<u8>,
+    ) -> Result<ExtrinsicExtensionInfo<'_, Self::TypeId>, ExtrinsicInfoError<'_>> {
+        match self.extension_info.get(&extension_version) {
+            Some(info) => Ok(info.clone()),
+            None => self.metadata.extrinsic_extension_info(extension_version),
+        }
+    }
+
+    fn extrinsic_extension_version_info(
+        &self,
+    ) -> Result<impl Iterator<Item = u8>, ExtrinsicInfoError<'_>> {
+        match &self.extension_versions {
+            Ok(versions) => Ok(versions.iter().copied()),
+            Err(e) => Err(e.clone()),
+        }
+    }
+}
+
+impl<Info> ExtrinsicCallTypeInfo for IndexedMetadata<'_, Info>
+where
+    Info: ExtrinsicTypeInfo
+        + ExtrinsicCallTypeInfo
+        + EventTypeInfo
+        + StorageTypeInfo
+        + ConstantTypeInfo,
+    CallTypeId<Info>: Clone,
+{
+    fn extrinsic_call_type_id(&self) -> Option<Self::TypeId> {
+        self.metadata.extrinsic_call_type_id()
+    }
+}
+
+impl<Info> EventTypeInfo for IndexedMetadata<'_, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo,
+    EventTypeId<Info>: Clone,
+{
+    type TypeId = EventTypeId<Info>;
+
+    fn event_info_by_index(
+        &self,
+        pallet_index: u8,
+        event_index: u8,
+    ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+        match self.events_by_index.get(&(pallet_index, event_index)) {
+            Some(info) => Ok(info.clone()),
+            None => self.metadata.event_info_by_index(pallet_index, event_index),
+        }
+    }
+
+    fn event_info_by_name(
+        &self,
+        pallet_name: &str,
+        event_name: &str,
+    ) -> Result<EventInfo<'_, Self::TypeId>, EventInfoError<'_>> {
+        let indexes = self
+            .events_by_name
+            .get(pallet_name)
+            .and_then(|events| events.get(event_name));
+        match indexes.and_then(|idx| self.events_by_index.get(idx)) {
+            Some(info) => Ok(info.clone()),
+            None => self.metadata.event_info_by_name(pallet_name, event_name),
+        }
+    }
+}
+
+impl<Info> StorageTypeInfo for IndexedMetadata<'_, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo,
+{
+    type TypeId = StorageTypeId<Info>;
+
+    fn storage_info(
+        &self,
+        pallet_name: &str,
+        storage_entry: &str,
+    ) -> Result<StorageInfo<'_, Self::TypeId>, StorageInfoError<'_>> {
+        let info = self
+            .storage
+            .get(pallet_name)
+            .and_then(|entries| entries.get(storage_entry));
+        match info {
+            Some(info) => Ok(info.clone()),
+            None => self.metadata.storage_info(pallet_name, storage_entry),
+        }
+    }
+}
+
+impl<Info> ConstantTypeInfo for IndexedMetadata<'_, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo,
+{
+    type TypeId = ConstantTypeId<Info>;
+
+    fn constant_info(
+        &self,
+        pallet_name: &str,
+        constant_name: &str,
+    ) -> Result<ConstantInfo<'_, Self::TypeId>, ConstantInfoError<'_>> {
+        let info = self
+            .constants
+            .get(pallet_name)
+            .and_then(|constants| constants.get(constant_name));
+        match info {
+            Some(info) => Ok(info.clone()),
+            None => self.metadata.constant_info(pallet_name, constant_name),
+        }
+    }
+}
+
+impl<Info> TypeResolver for IndexedMetadata<'_, Info>
+where
+    Info: ExtrinsicTypeInfo + EventTypeInfo + StorageTypeInfo + ConstantTypeInfo + TypeResolver,
+{
+    type TypeId = <Info as TypeResolver>::TypeId;
+    type Error = <Info as TypeResolver>::Error;
+
+    fn resolve_type<'this, V: ResolvedTypeVisitor<'this, TypeId = Self::TypeId>>(
+        &'this self,
+        type_id: Self::TypeId,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.metadata.resolve_type(type_id, visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::any_metadata::AnyMetadata;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Decode;
+
+    fn load_metadata(path: &str) -> RuntimeMetadata {
+        let metadata_bytes = std::fs::read(path).unwrap();
+        RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+    }
+
+    // The indexed metadata should hand back exactly what the underlying metadata does.
+    fn assert_same_as_metadata<Info>(metadata: &Info)
+    where
+        Info: ExtrinsicTypeInfo
+            + EventTypeInfo
+            + StorageTypeInfo
+            + StorageEntryInfo
+            + ConstantTypeInfo
+            + ConstantEntryInfo,
+        CallTypeId<Info>: Clone + PartialEq + core::fmt::Debug,
+        EventTypeId<Info>: Clone + PartialEq + core::fmt::Debug,
+        StorageTypeId<Info>: PartialEq + core::fmt::Debug,
+        ConstantTypeId<Info>: PartialEq + core::fmt::Debug,
+    {
+        let indexed = IndexedMetadata::new(metadata);
+
+        let mut num_calls = 0;
+        for pallet_index in 0..=u8::MAX {
+            for index in 0..=u8::MAX {
+                let expected = metadata.extrinsic_call_info_by_index(pallet_index, index);
+                let actual = indexed.extrinsic_call_info_by_index(pallet_index, index);
+                assert_eq!(expected, actual);
+
+                if let Ok(info) = actual {
+                    num_calls += 1;
+                    let by_name = indexed
+                        .extrinsic_call_info_by_name(&info.pallet_name, &info.call_name)
+                        .unwrap();
+                    assert_eq!(info, by_name);
+                }
+
+                let expected = metadata.event_info_by_index(pallet_index, index);
+                let actual = indexed.event_info_by_index(pallet_index, index);
+                assert_eq!(expected, actual);
+            }
+        }
+        assert!(num_calls > 0);
+
+        for (pallet_name, entry_name) in metadata.storage_tuples() {
+            assert_eq!(
+                metadata.storage_info(&pallet_name, &entry_name),
+                indexed.storage_info(&pallet_name, &entry_name)
+            );
+        }
+        for (pallet_name, constant_name) in metadata.constant_tuples() {
+            assert_eq!(
+                metadata.constant_info(&pallet_name, &constant_name),
+                indexed.constant_info(&pallet_name, &constant_name)
+            );
+        }
+
+        assert_eq!(
+            metadata.extrinsic_signature_info(),
+            indexed.extrinsic_signature_info()
+        );
+        assert_eq!(
+            metadata.extrinsic_extension_info(None),
+            indexed.extrinsic_extension_info(None)
+        );
+
+        // Misses are handed to the metadata to produce the same error:
+        assert_eq!(
+            metadata.extrinsic_call_info_by_name("Nope", "nope"),
+            indexed.extrinsic_call_info_by_name("Nope", "nope")
+        );
+    }
+
+    #[test]
+    fn indexed_metadata_matches_underlying_metadata() {
+        let RuntimeMetadata::V14(modern) = load_metadata("artifacts/metadata_10000000_9180.scale")
+        else {
+            panic!("Expected V14 metadata");
+        };
+        assert_same_as_metadata(&modern);
+
+        let RuntimeMetadata::V12(legacy) = load_metadata("artifacts/metadata_5000000_30.scale")
+        else {
+            panic!("Expected V12 metadata");
+        };
+        assert_same_as_metadata(&legacy);
+
+        let any =
+            AnyMetadata::new(load_metadata("artifacts/metadata_10000000_9180.scale")).unwrap();
+        assert_same_as_metadata(&any);
+    }
+}
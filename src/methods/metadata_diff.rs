@@ -0,0 +1,774 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::Entry;
+use crate::methods::any_metadata::{AnyMetadata, AnyTypeId};
+use crate::methods::constant_type_info::{ConstantEntryInfo, ConstantTypeInfo};
+use crate::methods::event_type_info::{EventInfoError, EventTypeInfo};
+use crate::methods::extrinsic_type_info::{ExtrinsicInfoError, ExtrinsicTypeInfo};
+use crate::methods::runtime_api_type_info::{RuntimeApiEntryInfo, RuntimeApiTypeInfo};
+use crate::methods::storage_type_info::{StorageEntryInfo, StorageHasher, StorageTypeInfo};
+use crate::methods::view_function_type_info::{ViewFunctionEntryInfo, ViewFunctionTypeInfo};
+use crate::utils::{FieldShape, TypeShape, resolve_type_shape};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The differences between two metadatas, as returned from [`diff_metadata`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataDiff {
+    /// Each of the items which differ between the metadatas.
+    pub changes: Vec<ItemDiff>,
+}
+
+impl MetadataDiff {
+    /// Are the metadatas the same?
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Iterate over the changes of a given kind.
+    pub fn changes_of_kind(&self, kind: ItemKind) -> impl Iterator<Item = &ItemDiff> {
+        self.changes.iter().filter(move |c| c.kind == kind)
+    }
+}
+
+impl core::fmt::Display for MetadataDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single item (for instance a call or storage entry) which differs between two metadatas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemDiff {
+    /// The kind of item.
+    pub kind: ItemKind,
+    /// The pallet or Runtime API trait that the item is in.
+    pub container: String,
+    /// The name of the item.
+    pub name: String,
+    /// How the item differs.
+    pub change: ItemChange,
+}
+
+impl core::fmt::Display for ItemDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let ItemDiff {
+            kind,
+            container,
+            name,
+            change,
+        } = self;
+        match change {
+            ItemChange::Added => write!(f, "+ {kind} {container}.{name}"),
+            ItemChange::Removed => write!(f, "- {kind} {container}.{name}"),
+            ItemChange::Modified {
+                index_change,
+                shape_changed,
+            } => {
+                write!(f, "~ {kind} {container}.{name} (")?;
+                if let Some(IndexChange { old, new }) = index_change {
+                    write!(f, "reindexed {old} -> {new}")?;
+                    if *shape_changed {
+                        write!(f, ", ")?;
+                    }
+                }
+                if *shape_changed {
+                    write!(f, "shape changed")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// The kind of item which has changed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemKind {
+    /// A call in some pallet.
+    Call,
+    /// An event in some pallet.
+    Event,
+    /// A storage entry in some pallet.
+    StorageEntry,
+    /// A constant in some pallet.
+    Constant,
+    /// A Runtime API method in some Runtime API trait.
+    RuntimeApi,
+    /// A View Function in some pallet.
+    ViewFunction,
+}
+
+impl core::fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            ItemKind::Call => "call",
+            ItemKind::Event => "event",
+            ItemKind::StorageEntry => "storage entry",
+            ItemKind::Constant => "constant",
+            ItemKind::RuntimeApi => "runtime API",
+            ItemKind::ViewFunction => "view function",
+        };
+        f.write_str(s)
+    }
+}
+
+/// How some item differs between two metadatas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemChange {
+    /// The item exists only in the new metadata.
+    Added,
+    /// The item exists only in the old metadata.
+    Removed,
+    /// The item exists in both metadatas but is different.
+    Modified {
+        /// The item has a different index. Only calls and events are indexed.
+        index_change: Option<IndexChange>,
+        /// The shape of one of the types used in the item has changed.
+        shape_changed: bool,
+    },
+}
+
+/// The old and new index of some call or event.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexChange {
+    /// The index in the old metadata.
+    pub old: ItemIndex,
+    /// The index in the new metadata.
+    pub new: ItemIndex,
+}
+
+/// The index of some call or event.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemIndex {
+    /// The index of the pallet.
+    pub pallet_index: u8,
+    /// The index of the call or event in the pallet.
+    pub index: u8,
+}
+
+impl core::fmt::Display for ItemIndex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}", self.pallet_index, self.index)
+    }
+}
+
+/// Compare two metadatas, returning the calls, events, storage entries, constants, Runtime APIs and
+/// View Functions which were added, removed, re-indexed or which changed shape between them.
+///
+/// Shapes are compared by resolving the types involved in both metadatas and comparing their
+/// structure (ie field names, variant names and indexes, and the shapes of any nested types). Type
+/// names and paths are not compared, and so renaming a type does not count as a change.
+///
+/// Both metadatas must be able to resolve their types in order to properly compare shapes. Where
+/// a type cannot be resolved in both (for instance legacy metadata without legacy types), the
+/// names of the types are compared instead.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::metadata::{AnyMetadata, diff_metadata};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let old = AnyMetadata::new(RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()).unwrap();
+/// let new = AnyMetadata::new(RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()).unwrap();
+///
+/// let diff = diff_metadata(&old, &new);
+/// assert!(diff.is_empty());
+///
+/// // Each change is printed on a new line:
+/// println!("{diff}");
+/// ```
+pub fn diff_metadata(old: &AnyMetadata<'_>, new: &AnyMetadata<'_>) -> MetadataDiff {
+    let mut comparer = ShapeComparer::new(old, new);
+    let mut changes = Vec::new();
+
+    let mut compare_indexed = |(old_index, old_args): &Indexed, (new_index, new_args): &Indexed| {
+        let shape_changed = !comparer.same_args(old_args.clone(), new_args.clone());
+        let index_change = (old_index != new_index).then_some(IndexChange {
+            old: *old_index,
+            new: *new_index,
+        });
+        (index_change, shape_changed)
+    };
+    diff_items(
+        &mut changes,
+        ItemKind::Call,
+        calls(old),
+        calls(new),
+        &mut compare_indexed,
+    );
+    diff_items(
+        &mut changes,
+        ItemKind::Event,
+        events(old),
+        events(new),
+        &mut compare_indexed,
+    );
+
+    diff_items(
+        &mut changes,
+        ItemKind::StorageEntry,
+        storage_entries(old),
+        storage_entries(new),
+        |old, new| {
+            let same = match (old, new) {
+                (Some((old_keys, old_value)), Some((new_keys, new_value))) => {
+                    old_keys.len() == new_keys.len()
+                        && old_keys.iter().zip(new_keys).all(|(a, b)| {
+                            a.0 == b.0 && comparer.same_type(a.1.clone(), b.1.clone())
+                        })
+                        && comparer.same_type(old_value.clone(), new_value.clone())
+                }
+                (None, None) => true,
+                _ => false,
+            };
+            (None, !same)
+        },
+    );
+
+    diff_items(
+        &mut changes,
+        ItemKind::Constant,
+        constants(old),
+        constants(new),
+        |old, new| {
+            let same = match (old, new) {
+                (Some(old), Some(new)) => comparer.same_type(old.clone(), new.clone()),
+                (None, None) => true,
+                _ => false,
+            };
+            (None, !same)
+        },
+    );
+
+    let mut compare_functions = |old: &Option<Function>, new: &Option<Function>| {
+        let same = match (old, new) {
+            (
+                Some((old_query_id, old_inputs, old_output)),
+                Some((new_query_id, new_inputs, new_output)),
+            ) => {
+                old_query_id == new_query_id
+                    && comparer.same_args(old_inputs.clone(), new_inputs.clone())
+                    && comparer.same_type(old_output.clone(), new_output.clone())
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        (None, !same)
+    };
+    diff_items(
+        &mut changes,
+        ItemKind::RuntimeApi,
+        runtime_apis(old),
+        runtime_apis(new),
+        &mut compare_functions,
+    );
+    diff_items(
+        &mut changes,
+        ItemKind::ViewFunction,
+        view_functions(old),
+        view_functions(new),
+        &mut compare_functions,
+    );
+
+    MetadataDiff { changes }
+}
+
+type Key = (String, String);
+
+// Push the items which were added or removed, and any which were modified according to the
+// function provided, onto the list of changes, ordered by container and name.
+fn diff_items<V>(
+    changes: &mut Vec<ItemDiff>,
+    kind: ItemKind,
+    old: BTreeMap<Key, V>,
+    mut new: BTreeMap<Key, V>,
+    mut compare: impl FnMut(&V, &V) -> (Option<IndexChange>, bool),
+) {
+    let mut diffs = Vec::new();
+    for (key, old_value) in old {
+        match new.remove(&key) {
+            None => diffs.push((key, ItemChange::Removed)),
+            Some(new_value) => {
+                let (index_change, shape_changed) = compare(&old_value, &new_value);
+                if index_change.is_some() || shape_changed {
+                    diffs.push((
+                        key,
+                        ItemChange::Modified {
+                            index_change,
+                            shape_changed,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+    diffs.extend(new.into_keys().map(|key| (key, ItemChange::Added)));
+    diffs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    changes.extend(
+        diffs
+            .into_iter()
+            .map(|((container, name), change)| ItemDiff {
+                kind,
+                container,
+                name,
+                change,
+            }),
+    );
+}
+
+type Args = Vec<(String, AnyTypeId)>;
+type Indexed = (ItemIndex, Args);
+type StorageShape = Option<(Vec<(StorageHasher, AnyTypeId)>, AnyTypeId)>;
+// View Functions and Runtime APIs have the same shape. Runtime APIs have no query ID.
+type Function = (Option<[u8; 32]>, Args, AnyTypeId);
+
+fn storage_entries(metadata: &AnyMetadata<'_>) -> BTreeMap<Key, StorageShape> {
+    Entry::tuples_of(metadata.storage_entries())
+        .map(|(pallet, entry)| {
+            let shape = metadata.storage_info(&pallet, &entry).ok().map(|info| {
+                let keys = info
+                    .keys
+                    .iter()
+                    .map(|k| (k.hasher, k.key_id.clone()))
+                    .collect();
+                (keys, info.value_id)
+            });
+            ((pallet.into_owned(), entry.into_owned()), shape)
+        })
+        .collect()
+}
+
+fn constants(metadata: &AnyMetadata<'_>) -> BTreeMap<Key, Option<AnyTypeId>> {
+    Entry::tuples_of(metadata.constant_entries())
+        .map(|(pallet, constant)| {
+            let ty = metadata
+                .constant_info(&pallet, &constant)
+                .ok()
+                .map(|info| info.type_id);
+            ((pallet.into_owned(), constant.into_owned()), ty)
+        })
+        .collect()
+}
+
+fn runtime_apis(metadata: &AnyMetadata<'_>) -> BTreeMap<Key, Option<Function>> {
+    Entry::tuples_of(metadata.runtime_api_entries())
+        .map(|(trait_name, method)| {
+            let function = metadata
+                .runtime_api_info(&trait_name, &method)
+                .ok()
+                .map(|info| {
+                    let inputs = info
+                        .inputs
+                        .iter()
+                        .map(|i| (i.name.to_string(), i.id.clone()))
+                        .collect();
+                    (None, inputs, info.output_id)
+                });
+            ((trait_name.into_owned(), method.into_owned()), function)
+        })
+        .collect()
+}
+
+fn view_functions(metadata: &AnyMetadata<'_>) -> BTreeMap<Key, Option<Function>> {
+    Entry::tuples_of(metadata.view_function_entries())
+        .map(|(pallet, function)| {
+            let function_shape = metadata
+                .view_function_info(&pallet, &function)
+                .ok()
+                .map(|info| {
+                    let inputs = info
+                        .inputs
+                        .iter()
+                        .map(|i| (i.name.to_string(), i.id.clone()))
+                        .collect();
+                    (Some(info.query_id), inputs, info.output_id)
+                });
+            ((pallet.into_owned(), function.into_owned()), function_shape)
+        })
+        .collect()
+}
+
+// There is no iterator over calls, and so we look them up by index instead.
+fn calls(metadata: &AnyMetadata<'_>) -> BTreeMap<Key, Indexed> {
+    let mut calls = BTreeMap::new();
+    for pallet_index in 0..=u8::MAX {
+        if let Err(ExtrinsicInfoError::PalletNotFound { .. }) =
+            metadata.extrinsic_call_info_by_index(pallet_index, 0)
+        {
+            continue;
+        }
+        for index in 0..=u8::MAX {
+            let Ok(info) = metadata.extrinsic_call_info_by_index(pallet_index, index) else {
+                continue;
+            };
+            let args = info
+                .args
+                .into_iter()
+                .map(|a| (a.name.into_owned(), a.id))
+                .collect();
+            calls.insert(
+                (info.pallet_name.into_owned(), info.call_name.into_owned()),
+                (
+                    ItemIndex {
+                        pallet_index,
+                        index,
+                    },
+                    args,
+                ),
+            );
+        }
+    }
+    calls
+}
+
+// There is no iterator over events, and so we look them up by index instead.
+fn events(metadata: &AnyMetadata<'_>) -> BTreeMap<Key, Indexed> {
+    let mut events = BTreeMap::new();
+    for pallet_index in 0..=u8::MAX {
+        if let Err(EventInfoError::PalletNotFound { .. }) =
+            metadata.event_info_by_index(pallet_index, 0)
+        {
+            continue;
+        }
+        for index in 0..=u8::MAX {
+            let Ok(info) = metadata.event_info_by_index(pallet_index, index) else {
+                continue;
+            };
+            let args = info
+                .args
+                .into_iter()
+                .map(|a| (a.name.into_owned(), a.id))
+                .collect();
+            events.insert(
+                (info.pallet_name.into_owned(), info.event_name.into_owned()),
+                (
+                    ItemIndex {
+                        pallet_index,
+                        index,
+                    },
+                    args,
+                ),
+            );
+        }
+    }
+    events
+}
+
+// Compares the shapes of types in two metadatas.
+struct ShapeComparer<'a, 'old, 'new> {
+    old: &'a AnyMetadata<'old>,
+    new: &'a AnyMetadata<'new>,
+    // Results which don't depend on any pairs that are still being compared.
+    cache: BTreeMap<(AnyTypeId, AnyTypeId), bool>,
+    // The pairs of types we are currently in the process of comparing.
+    stack: Vec<(AnyTypeId, AnyTypeId)>,
+    // The lowest position in the stack that the current pair refers back to.
+    lowest_ref: usize,
+}
+
+impl<'a, 'old, 'new> ShapeComparer<'a, 'old, 'new> {
+    fn new(old: &'a AnyMetadata<'old>, new: &'a AnyMetadata<'new>) -> Self {
+        ShapeComparer {
+            old,
+            new,
+            cache: BTreeMap::new(),
+            stack: Vec::new(),
+            lowest_ref: usize::MAX,
+        }
+    }
+
+    fn same_args(&mut self, old: Args, new: Args) -> bool {
+        old.len() == new.len()
+            && old
+                .into_iter()
+                .zip(new)
+                .all(|((a_name, a), (b_name, b))| a_name == b_name && self.same_type(a, b))
+    }
+
+    fn same_fields(
+        &mut self,
+        old: Vec<FieldShape<AnyTypeId>>,
+        new: Vec<FieldShape<AnyTypeId>>,
+    ) -> bool {
+        old.len() == new.len()
+            && old
+                .into_iter()
+                .zip(new)
+                .all(|(a, b)| a.name == b.name && self.same_type(a.id, b.id))
+    }
+
+    fn same_type(&mut self, old: AnyTypeId, new: AnyTypeId) -> bool {
+        let key = (old.clone(), new.clone());
+        if let Some(same) = self.cache.get(&key) {
+            return *same;
+        }
+
+        // Assume types are the same while comparing them, so that recursive types
+        // terminate. Anything that relies on this assumption can't be cached until
+        // the pair it refers back to has been compared.
+        if let Some(pos) = self.stack.iter().position(|s| s == &key) {
+            self.lowest_ref = self.lowest_ref.min(pos);
+            return true;
+        }
+
+        let pos = self.stack.len();
+        let outer_lowest_ref = core::mem::replace(&mut self.lowest_ref, usize::MAX);
+        self.stack.push(key.clone());
+        let same = self.compare_shapes(old, new);
+        self.stack.pop();
+        let lowest_ref = core::mem::replace(&mut self.lowest_ref, outer_lowest_ref);
+
+        // Types that differ do so whatever we assumed about the pairs being compared.
+        if !same || lowest_ref >= pos {
+            self.cache.insert(key, same);
+        } else {
+            self.lowest_ref = self.lowest_ref.min(lowest_ref);
+        }
+        same
+    }
+
+    fn compare_shapes(&mut self, old: AnyTypeId, new: AnyTypeId) -> bool {
+        let old_shape = resolve_type_shape(self.old, old.clone()).ok();
+        let new_shape = resolve_type_shape(self.new, new.clone()).ok();
+
+        match (old_shape, new_shape) {
+            (Some(TypeShape::Composite(a)), Some(TypeShape::Composite(b))) => {
+                self.same_fields(a, b)
+            }
+            (Some(TypeShape::Variant(a)), Some(TypeShape::Variant(b))) => {
+                a.len() == b.len()
+                    && a.into_iter().zip(b).all(|(a, b)| {
+                        a.index == b.index
+                            && a.name == b.name
+                            && self.same_fields(a.fields, b.fields)
+                    })
+            }
+            (Some(TypeShape::Sequence(a)), Some(TypeShape::Sequence(b))) => self.same_type(a, b),
+            (Some(TypeShape::Array(a, a_len)), Some(TypeShape::Array(b, b_len))) => {
+                a_len == b_len && self.same_type(a, b)
+            }
+            (Some(TypeShape::Tuple(a)), Some(TypeShape::Tuple(b))) => {
+                a.len() == b.len() && a.into_iter().zip(b).all(|(a, b)| self.same_type(a, b))
+            }
+            (Some(TypeShape::Primitive(a)), Some(TypeShape::Primitive(b))) => a == b,
+            (Some(TypeShape::Compact(a)), Some(TypeShape::Compact(b))) => self.same_type(a, b),
+            (
+                Some(TypeShape::BitSequence(a_store, a_order)),
+                Some(TypeShape::BitSequence(b_store, b_order)),
+            ) => a_store == b_store && a_order == b_order,
+            // Fall back to comparing the type names if we can't resolve both types.
+            (None | Some(TypeShape::Unhandled(_)), None | Some(TypeShape::Unhandled(_))) => {
+                old.to_string() == new.to_string()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Decode;
+    use scale_info::{TypeDef, TypeDefPrimitive};
+
+    fn load_metadata_v14() -> frame_metadata::v14::RuntimeMetadataV14 {
+        let bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*bytes).unwrap() else {
+            panic!("expected V14 metadata")
+        };
+        metadata
+    }
+
+    fn any(metadata: frame_metadata::v14::RuntimeMetadataV14) -> AnyMetadata<'static> {
+        AnyMetadata::new(RuntimeMetadata::V14(metadata)).unwrap()
+    }
+
+    #[test]
+    fn identical_metadatas_have_no_changes() {
+        let old = any(load_metadata_v14());
+        let new = any(load_metadata_v14());
+        let diff = diff_metadata(&old, &new);
+        assert!(diff.is_empty(), "{diff}");
+    }
+
+    #[test]
+    fn changes_are_found() {
+        let old = load_metadata_v14();
+        let mut new = old.clone();
+
+        let u32_id = new
+            .types
+            .types
+            .iter()
+            .find(|t| t.ty.type_def == TypeDef::Primitive(TypeDefPrimitive::U32))
+            .unwrap()
+            .id;
+
+        for pallet in &mut new.pallets {
+            if pallet.name == "Balances" {
+                // Move the pallet and change the type of one of its constants.
+                pallet.index = 200;
+                let constant = pallet
+                    .constants
+                    .iter_mut()
+                    .find(|c| c.name == "ExistentialDeposit")
+                    .unwrap();
+                constant.ty = u32_id.into();
+            }
+            if pallet.name == "System" {
+                // Remove a storage entry.
+                let storage = pallet.storage.as_mut().unwrap();
+                storage.entries.retain(|e| e.name != "Account");
+            }
+        }
+
+        let diff = diff_metadata(&any(old.clone()), &any(new));
+
+        // Every Balances call and event is re-indexed and nothing else is.
+        let balances_calls = calls(&any(old.clone()))
+            .into_keys()
+            .filter(|(pallet, _)| pallet == "Balances")
+            .count();
+        let reindexed_calls: Vec<_> = diff.changes_of_kind(ItemKind::Call).collect();
+        assert_eq!(reindexed_calls.len(), balances_calls);
+        for call in reindexed_calls {
+            assert_eq!(call.container, "Balances");
+            let ItemChange::Modified {
+                index_change: Some(index_change),
+                shape_changed: false,
+            } = call.change
+            else {
+                panic!("unexpected change {call}")
+            };
+            assert_eq!(index_change.new.pallet_index, 200);
+            assert_eq!(index_change.old.index, index_change.new.index);
+        }
+        assert!(
+            diff.changes_of_kind(ItemKind::Event)
+                .all(|e| e.container == "Balances")
+        );
+
+        let storage: Vec<_> = diff.changes_of_kind(ItemKind::StorageEntry).collect();
+        assert_eq!(
+            storage,
+            vec![&ItemDiff {
+                kind: ItemKind::StorageEntry,
+                container: "System".into(),
+                name: "Account".into(),
+                change: ItemChange::Removed,
+            }]
+        );
+
+        let constants: Vec<_> = diff.changes_of_kind(ItemKind::Constant).collect();
+        assert_eq!(
+            constants,
+            vec![&ItemDiff {
+                kind: ItemKind::Constant,
+                container: "Balances".into(),
+                name: "ExistentialDeposit".into(),
+                change: ItemChange::Modified {
+                    index_change: None,
+                    shape_changed: true,
+                },
+            }]
+        );
+        assert_eq!(
+            constants[0].to_string(),
+            "~ constant Balances.ExistentialDeposit (shape changed)"
+        );
+
+        assert_eq!(diff.changes_of_kind(ItemKind::RuntimeApi).count(), 0);
+        assert_eq!(diff.changes_of_kind(ItemKind::ViewFunction).count(), 0);
+    }
+
+    #[test]
+    fn changes_inside_recursive_types_are_found() {
+        use scale_info::{Field, form::PortableForm};
+        use scale_info::{PortableType, Type, TypeDefSequence, TypeDefVariant, Variant};
+
+        // Add `enum Tree { Node(Forest), Leaf(leaf_ty) }` and `type Forest = Vec<Tree>`,
+        // and constants of each type to the metadata.
+        fn with_tree(
+            mut metadata: frame_metadata::v14::RuntimeMetadataV14,
+            leaf: TypeDefPrimitive,
+        ) -> frame_metadata::v14::RuntimeMetadataV14 {
+            let types = &mut metadata.types.types;
+            let tree_id = types.len() as u32;
+            let forest_id = tree_id + 1;
+            let leaf_id = tree_id + 2;
+
+            let field = |id: u32| Field::<PortableForm>::new(None, id.into(), None, vec![]);
+            let tree = Type::new(
+                Default::default(),
+                [],
+                TypeDefVariant::new([
+                    Variant::new("Node".into(), vec![field(forest_id)], 0, vec![]),
+                    Variant::new("Leaf".into(), vec![field(leaf_id)], 1, vec![]),
+                ]),
+                vec![],
+            );
+            types.push(PortableType::new(tree_id, tree));
+            types.push(PortableType::new(
+                forest_id,
+                TypeDefSequence::new(tree_id.into()).into(),
+            ));
+            types.push(PortableType::new(leaf_id, leaf.into()));
+
+            let pallet = metadata
+                .pallets
+                .iter_mut()
+                .find(|p| p.name == "System")
+                .unwrap();
+            for (name, id) in [("RecursiveA", tree_id), ("RecursiveB", forest_id)] {
+                pallet
+                    .constants
+                    .push(frame_metadata::v14::PalletConstantMetadata {
+                        name: name.into(),
+                        ty: id.into(),
+                        value: vec![],
+                        docs: vec![],
+                    });
+            }
+            metadata
+        }
+
+        let old = with_tree(load_metadata_v14(), TypeDefPrimitive::U32);
+        let new = with_tree(load_metadata_v14(), TypeDefPrimitive::U64);
+        let diff = diff_metadata(&any(old), &any(new));
+
+        // Comparing `RecursiveA` first must not leave `Forest` marked as unchanged.
+        let constants: Vec<_> = diff
+            .changes_of_kind(ItemKind::Constant)
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(constants, vec!["RecursiveA", "RecursiveB"]);
+    }
+}
@@ -0,0 +1,1353 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::extrinsic_type_info::{ExtrinsicCallTypeInfo, ExtrinsicInfoError};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use frame_metadata::RuntimeMetadata;
+use parity_scale_codec::{Compact, Decode, Encode, Output};
+use scale_decode::visitor::{DecodeAsTypeResult, IgnoreVisitor, TypeIdFor};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+
+/// The hash of some node in the merkle tree, or of the metadata digest.
+pub type Hash = [u8; 32];
+
+/// The extrinsic version described by the merkleized metadata.
+const EXTRINSIC_VERSION: u8 = 4;
+
+/// An error returned trying to merkleize metadata or to generate a proof from it.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum MetadataHashError {
+    #[error("Cannot merkleize metadata V{0}: only V14, V15 and V16 metadata is supported.")]
+    UnsupportedMetadataVersion(u32),
+    #[error("Cannot get extrinsic info:\n\n{0}")]
+    CannotGetExtrinsicInfo(ExtrinsicInfoError<'static>),
+    #[error("Cannot find the outer call type in the metadata.")]
+    CallTypeNotFound,
+    #[error("Cannot find type {0} in the type registry.")]
+    TypeNotFound(u32),
+    #[error("Cannot merkleize compact type {type_id}: {reason}")]
+    UnsupportedCompactType { type_id: u32, reason: String },
+    #[error("Cannot merkleize bit sequence type {type_id}: {reason}")]
+    UnsupportedBitSequenceType { type_id: u32, reason: String },
+    #[error("Cannot decode the extrinsic length:\n\n{0}")]
+    CannotDecodeLength(parity_scale_codec::Error),
+    #[error(
+        "Expected the extrinsic to be {expected_len} bytes long, but it was {actual_len} bytes."
+    )]
+    WrongLength {
+        expected_len: usize,
+        actual_len: usize,
+    },
+    #[error("Not enough bytes to decode the extrinsic version.")]
+    NotEnoughBytes,
+    #[error("Cannot generate a proof for a V{0} extrinsic: only V4 extrinsics are supported.")]
+    UnsupportedExtrinsicVersion(u8),
+    #[error("Cannot decode {what}:\n\n{reason}")]
+    CannotDecode { what: String, reason: String },
+    #[error("{num_bytes} bytes were left over after decoding {what}.")]
+    LeftoverBytes {
+        what: &'static str,
+        num_bytes: usize,
+    },
+}
+
+/// Information about the chain which, along with the metadata, is used to compute the metadata
+/// hash. This is the `ExtraInfo` described in RFC-0078.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainInfo {
+    /// The spec version of the runtime.
+    pub spec_version: u32,
+    /// The spec name of the runtime.
+    pub spec_name: String,
+    /// The SS58 (base58) prefix used for addresses on the chain.
+    pub base58_prefix: u16,
+    /// The number of decimals of the primary token.
+    pub decimals: u8,
+    /// The symbol of the primary token.
+    pub token_symbol: String,
+}
+
+impl Encode for ChainInfo {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.spec_version.encode_to(dest);
+        self.spec_name.encode_to(dest);
+        self.base58_prefix.encode_to(dest);
+        self.decimals.encode_to(dest);
+        self.token_symbol.encode_to(dest);
+    }
+}
+
+/// Compute the RFC-0078 metadata hash, which is the value that the `CheckMetadataHash`
+/// transaction extension expects to be given when it is enabled.
+///
+/// See [`MerkleizedMetadata`] to also generate proofs for extrinsics, or to avoid
+/// merkleizing the metadata more than once.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::metadata_hash::{ChainInfo, metadata_hash};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let metadata = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap();
+///
+/// let chain_info = ChainInfo {
+///     spec_version: 9180,
+///     spec_name: "polkadot".into(),
+///     base58_prefix: 0,
+///     decimals: 10,
+///     token_symbol: "DOT".into(),
+/// };
+///
+/// let hash = metadata_hash(&metadata, &chain_info).unwrap();
+/// ```
+pub fn metadata_hash(
+    metadata: &RuntimeMetadata,
+    chain_info: &ChainInfo,
+) -> Result<Hash, MetadataHashError> {
+    MerkleizedMetadata::from_metadata(metadata).map(|m| m.metadata_hash(chain_info))
+}
+
+/// Metadata which has been merkleized according to RFC-0078. This can compute the metadata
+/// hash, and generate proofs containing the type information needed to decode an extrinsic
+/// or signer payload, which a wallet can then check against that hash.
+///
+/// Only the types reachable from the extrinsic (ie the call, address, signature and transaction
+/// extension types) end up in the merkle tree.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::metadata_hash::MerkleizedMetadata;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let merkleized = MerkleizedMetadata::new(&metadata, &metadata.types).unwrap();
+///
+/// // A timestamp.set inherent.
+/// let ext_bytes = hex::decode("280403000b99e04d568001").unwrap();
+/// let proof = merkleized.proof_for_extrinsic(&ext_bytes, None).unwrap();
+///
+/// // The proof contains enough to recompute the root of the merkle tree.
+/// assert_eq!(proof.root(), Some(merkleized.types_root()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MerkleizedMetadata<'info> {
+    types: &'info PortableRegistry,
+    extrinsic: ExtrinsicMetadata,
+    extrinsic_ids: ExtrinsicTypeIds,
+    frame_id_to_id: BTreeMap<u32, u32>,
+    leaves: Vec<Type>,
+    leaf_positions: BTreeMap<LeafId, usize>,
+    // Every node in the tree, in the order described in RFC-0078; the children of
+    // the node at index `i` are at `2i + 1` and `2i + 2`, and the leaves come last.
+    nodes: Vec<Hash>,
+}
+
+impl<'info> MerkleizedMetadata<'info> {
+    /// Merkleize some metadata. V14, V15 and V16 metadata is supported.
+    pub fn from_metadata(metadata: &'info RuntimeMetadata) -> Result<Self, MetadataHashError> {
+        match metadata {
+            RuntimeMetadata::V14(m) => Self::new(m, &m.types),
+            RuntimeMetadata::V15(m) => Self::new(m, &m.types),
+            RuntimeMetadata::V16(m) => Self::new(m, &m.types),
+            _ => Err(MetadataHashError::UnsupportedMetadataVersion(
+                metadata.version(),
+            )),
+        }
+    }
+
+    /// Merkleize the types in the given registry which are reachable from the extrinsic types
+    /// described by `info`. For V16 metadata, the transaction extensions used in V4
+    /// extrinsics (extension version 0) are used.
+    pub fn new<Info>(info: &Info, types: &'info PortableRegistry) -> Result<Self, MetadataHashError>
+    where
+        Info: ExtrinsicCallTypeInfo<TypeId = u32>,
+    {
+        let signature_info = info
+            .extrinsic_signature_info()
+            .map_err(|e| MetadataHashError::CannotGetExtrinsicInfo(e.into_owned()))?;
+        let extension_info = info
+            .extrinsic_extension_info(None)
+            .map_err(|e| MetadataHashError::CannotGetExtrinsicInfo(e.into_owned()))?;
+        let call_id = info
+            .extrinsic_call_type_id()
+            .ok_or(MetadataHashError::CallTypeNotFound)?;
+
+        let extrinsic_ids = ExtrinsicTypeIds {
+            address: signature_info.address_id,
+            call: call_id,
+            signature: signature_info.signature_id,
+            extensions: extension_info
+                .extension_ids
+                .into_iter()
+                .map(|e| (e.name.into_owned(), e.id, e.implicit_id))
+                .collect(),
+        };
+
+        // Only types reachable from the extrinsic are merkleized.
+        let mut accessible_types = BTreeSet::new();
+        for id in extrinsic_ids.all_ids() {
+            collect_accessible_types(types, id, &mut accessible_types)?;
+        }
+
+        let mut frame_id_to_id = BTreeMap::new();
+        for &frame_id in &accessible_types {
+            let ty = resolve(types, frame_id)?;
+            if is_basic_type(&ty.type_def) {
+                let id = frame_id_to_id.len() as u32;
+                frame_id_to_id.insert(frame_id, id);
+            }
+        }
+
+        let ctx = Context {
+            types,
+            frame_id_to_id: &frame_id_to_id,
+        };
+
+        let extrinsic = ExtrinsicMetadata {
+            version: EXTRINSIC_VERSION,
+            address_ty: ctx.type_ref(extrinsic_ids.address)?,
+            call_ty: ctx.type_ref(extrinsic_ids.call)?,
+            signature_ty: ctx.type_ref(extrinsic_ids.signature)?,
+            signed_extensions: extrinsic_ids
+                .extensions
+                .iter()
+                .map(|(name, id, implicit_id)| {
+                    Ok(SignedExtensionMetadata {
+                        identifier: name.clone(),
+                        included_in_extrinsic: ctx.type_ref(*id)?,
+                        included_in_signed_data: ctx.type_ref(*implicit_id)?,
+                    })
+                })
+                .collect::<Result<_, MetadataHashError>>()?,
+        };
+
+        let mut leaves = Vec::new();
+        let mut leaf_positions = BTreeMap::new();
+        for (&frame_id, &id) in &frame_id_to_id {
+            for ty in ctx.leaves(frame_id, id)? {
+                let variant = match &ty.type_def {
+                    RfcTypeDef::Enumeration(v) => Some(v.index.0),
+                    _ => None,
+                };
+                leaf_positions.insert(LeafId { id, variant }, leaves.len());
+                leaves.push(ty);
+            }
+        }
+
+        let nodes = merkle_tree_nodes(leaves.iter().map(|l| l.hash()));
+
+        Ok(MerkleizedMetadata {
+            types,
+            extrinsic,
+            extrinsic_ids,
+            frame_id_to_id,
+            leaves,
+            leaf_positions,
+            nodes,
+        })
+    }
+
+    /// The root of the merkle tree built from the types.
+    pub fn types_root(&self) -> Hash {
+        self.nodes.first().copied().unwrap_or_default()
+    }
+
+    /// The number of leaves (types, or enum variants) in the merkle tree.
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// The SCALE encoded `ExtrinsicMetadata` described in RFC-0078. Wallets are expected to be
+    /// handed this alongside a [`MetadataProof`] and the [`ChainInfo`].
+    pub fn encoded_extrinsic_metadata(&self) -> Vec<u8> {
+        self.extrinsic.encode()
+    }
+
+    /// Compute the metadata hash given some information about the chain. This is the value
+    /// that the `CheckMetadataHash` transaction extension expects when it is enabled.
+    pub fn metadata_hash(&self, chain_info: &ChainInfo) -> Hash {
+        let mut digest = vec![1u8];
+        self.types_root().encode_to(&mut digest);
+        blake3_hash(&self.extrinsic.encode()).encode_to(&mut digest);
+        chain_info.encode_to(&mut digest);
+        blake3_hash(&digest)
+    }
+
+    /// Generate a proof containing the types needed to decode the given V4 extrinsic. The
+    /// extrinsic bytes are expected to begin with the compact encoded length.
+    ///
+    /// If `implicits` is given, it is expected to contain the SCALE encoded implicit data for
+    /// each transaction extension (the "additional signed" data), and the types needed to
+    /// decode it are also included in the proof.
+    pub fn proof_for_extrinsic(
+        &self,
+        extrinsic: &[u8],
+        implicits: Option<&[u8]>,
+    ) -> Result<MetadataProof, MetadataHashError> {
+        let cursor = &mut &*extrinsic;
+        let ext_len = Compact::<u64>::decode(cursor)
+            .map_err(MetadataHashError::CannotDecodeLength)?
+            .0 as usize;
+
+        if cursor.len() != ext_len {
+            return Err(MetadataHashError::WrongLength {
+                expected_len: ext_len,
+                actual_len: cursor.len(),
+            });
+        }
+
+        let Some((&version, rest)) = cursor.split_first() else {
+            return Err(MetadataHashError::NotEnoughBytes);
+        };
+        *cursor = rest;
+
+        let is_signed = version & 0b1000_0000 != 0;
+        let version = version & 0b0111_1111;
+        if version != EXTRINSIC_VERSION {
+            return Err(MetadataHashError::UnsupportedExtrinsicVersion(version));
+        }
+
+        let mut collector = TouchedTypes::default();
+        let ids = &self.extrinsic_ids;
+
+        if is_signed {
+            collector = self.visit(cursor, ids.address, "address", collector)?;
+            collector = self.visit(cursor, ids.signature, "signature", collector)?;
+            for (name, id, _) in &ids.extensions {
+                collector = self.visit(cursor, *id, name, collector)?;
+            }
+        }
+
+        collector = self.visit(cursor, ids.call, "call data", collector)?;
+        if !cursor.is_empty() {
+            return Err(MetadataHashError::LeftoverBytes {
+                what: "the extrinsic",
+                num_bytes: cursor.len(),
+            });
+        }
+
+        if let Some(implicits) = implicits {
+            let cursor = &mut &*implicits;
+            for (name, _, implicit_id) in &ids.extensions {
+                collector = self.visit(cursor, *implicit_id, name, collector)?;
+            }
+            if !cursor.is_empty() {
+                return Err(MetadataHashError::LeftoverBytes {
+                    what: "the implicit data",
+                    num_bytes: cursor.len(),
+                });
+            }
+        }
+
+        Ok(self.build_proof(collector.touched))
+    }
+
+    /// Generate a proof containing the types needed to decode the given V4 signer payload,
+    /// ie the call data, followed by the transaction extension values and then their implicit
+    /// data. The payload must not be hashed, which happens to signer payloads longer than 256
+    /// bytes before they are signed.
+    ///
+    /// Since a wallet will also need to decode the extrinsic built from this payload, every
+    /// type needed to decode the address and signature is also included in the proof.
+    pub fn proof_for_signer_payload(
+        &self,
+        signer_payload: &[u8],
+    ) -> Result<MetadataProof, MetadataHashError> {
+        let cursor = &mut &*signer_payload;
+        let ids = &self.extrinsic_ids;
+
+        let mut collector = self.visit(cursor, ids.call, "call data", TouchedTypes::default())?;
+        for (name, id, _) in &ids.extensions {
+            collector = self.visit(cursor, *id, name, collector)?;
+        }
+        for (name, _, implicit_id) in &ids.extensions {
+            collector = self.visit(cursor, *implicit_id, name, collector)?;
+        }
+        if !cursor.is_empty() {
+            return Err(MetadataHashError::LeftoverBytes {
+                what: "the signer payload",
+                num_bytes: cursor.len(),
+            });
+        }
+
+        for id in [ids.address, ids.signature] {
+            self.touch_all(id, &mut collector.touched);
+        }
+
+        Ok(self.build_proof(collector.touched))
+    }
+
+    // Decode a value, recording the types that we see while doing so.
+    fn visit(
+        &self,
+        cursor: &mut &[u8],
+        type_id: u32,
+        what: &str,
+        collector: TouchedTypes,
+    ) -> Result<TouchedTypes, MetadataHashError> {
+        scale_decode::visitor::decode_with_visitor(cursor, type_id, self.types, collector).map_err(
+            |e| MetadataHashError::CannotDecode {
+                what: what.to_string(),
+                reason: e.to_string(),
+            },
+        )
+    }
+
+    // Record every type which is reachable from the given one, whether or not some
+    // value would touch it.
+    fn touch_all(&self, frame_id: u32, touched: &mut BTreeSet<(u32, Option<u32>)>) {
+        let Some(ty) = self.types.resolve(frame_id) else {
+            return;
+        };
+        let ids: Vec<u32> = match &ty.type_def {
+            TypeDef::Composite(c) => c.fields.iter().map(|f| f.ty.id).collect(),
+            TypeDef::Variant(v) => {
+                let mut ids = Vec::new();
+                for variant in &v.variants {
+                    if touched.insert((frame_id, Some(variant.index as u32))) {
+                        ids.extend(variant.fields.iter().map(|f| f.ty.id));
+                    }
+                }
+                for id in ids {
+                    self.touch_all(id, touched);
+                }
+                return;
+            }
+            TypeDef::Sequence(s) => vec![s.type_param.id],
+            TypeDef::Array(a) => vec![a.type_param.id],
+            TypeDef::Tuple(t) => t.fields.iter().map(|f| f.id).collect(),
+            TypeDef::BitSequence(_) => Vec::new(),
+            TypeDef::Primitive(_) | TypeDef::Compact(_) => return,
+        };
+        if touched.insert((frame_id, None)) {
+            for id in ids {
+                self.touch_all(id, touched);
+            }
+        }
+    }
+
+    fn build_proof(&self, touched: BTreeSet<(u32, Option<u32>)>) -> MetadataProof {
+        let leaf_count = self.leaves.len() as u64;
+
+        // Types which aren't in the tree (like empty composites) are decoded
+        // without needing any type information, so they are ignored here.
+        let mut leaf_indices: Vec<u64> = touched
+            .into_iter()
+            .filter_map(|(frame_id, variant)| {
+                let id = *self.frame_id_to_id.get(&frame_id)?;
+                let pos = *self.leaf_positions.get(&LeafId { id, variant })?;
+                Some(leaf_count - 1 + pos as u64)
+            })
+            .collect();
+
+        // Order the leaves in the order that they'd be seen walking the tree from left
+        // to right, which is how the proof is checked.
+        leaf_indices.sort_by_key(|&idx| (core::cmp::Reverse(level(idx)), idx));
+
+        let mut nodes = Vec::new();
+        if !leaf_indices.is_empty() {
+            self.collect_proof_nodes(0, &leaf_indices, &mut nodes);
+        }
+
+        MetadataProof {
+            leaves: leaf_indices
+                .iter()
+                .map(|&idx| self.leaves[(idx + 1 - leaf_count) as usize].clone())
+                .collect(),
+            leaf_indices: leaf_indices.iter().map(|&idx| idx as u32).collect(),
+            nodes,
+        }
+    }
+
+    fn collect_proof_nodes(&self, node: u64, leaf_indices: &[u64], nodes: &mut Vec<Hash>) {
+        if leaf_indices.contains(&node) {
+            return;
+        }
+        if !leaf_indices.iter().any(|&l| is_descendant(l, node)) {
+            nodes.push(self.nodes[node as usize]);
+            return;
+        }
+        self.collect_proof_nodes(2 * node + 1, leaf_indices, nodes);
+        self.collect_proof_nodes(2 * node + 2, leaf_indices, nodes);
+    }
+}
+
+/// A proof containing the types needed to decode some extrinsic or signer payload, which can
+/// be checked against the types root in the metadata hash. This is the `Proof` described in
+/// RFC-0078, and is handed to wallets (SCALE encoded) alongside the output from
+/// [`MerkleizedMetadata::encoded_extrinsic_metadata`] and the SCALE encoded [`ChainInfo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataProof {
+    leaves: Vec<Type>,
+    leaf_indices: Vec<u32>,
+    nodes: Vec<Hash>,
+}
+
+impl MetadataProof {
+    /// The number of leaves (types, or enum variants) in the proof.
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// The index of each leaf in the merkle tree.
+    pub fn leaf_indices(&self) -> &[u32] {
+        &self.leaf_indices
+    }
+
+    /// The hashes of the nodes needed, alongside the leaves, to compute the root of the tree.
+    pub fn nodes(&self) -> &[Hash] {
+        &self.nodes
+    }
+
+    /// Compute the root of the merkle tree from this proof. This returns `None` if the proof
+    /// is empty or malformed.
+    pub fn root(&self) -> Option<Hash> {
+        if self.leaves.is_empty() {
+            return None;
+        }
+
+        let mut leaves = self
+            .leaf_indices
+            .iter()
+            .copied()
+            .zip(&self.leaves)
+            .peekable();
+        let mut nodes = self.nodes.iter();
+        let root = proof_root(0, &mut leaves, &mut nodes)?;
+
+        (leaves.next().is_none() && nodes.next().is_none()).then_some(root)
+    }
+}
+
+impl Encode for MetadataProof {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.leaves.encode_to(dest);
+        self.leaf_indices.encode_to(dest);
+        self.nodes.encode_to(dest);
+    }
+}
+
+fn proof_root<'a>(
+    node: u64,
+    leaves: &mut core::iter::Peekable<impl Iterator<Item = (u32, &'a Type)>>,
+    nodes: &mut core::slice::Iter<'_, Hash>,
+) -> Option<Hash> {
+    match leaves.peek() {
+        Some(&(idx, leaf)) if idx as u64 == node => {
+            leaves.next();
+            Some(leaf.hash())
+        }
+        Some(&(idx, _)) if is_descendant(idx as u64, node) => {
+            let left = proof_root(2 * node + 1, leaves, nodes)?;
+            let right = proof_root(2 * node + 2, leaves, nodes)?;
+            Some(node_hash(&left, &right))
+        }
+        _ => nodes.next().copied(),
+    }
+}
+
+fn merkle_tree_nodes(leaf_hashes: impl ExactSizeIterator<Item = Hash>) -> Vec<Hash> {
+    let leaf_count = leaf_hashes.len();
+    if leaf_count == 0 {
+        return Vec::new();
+    }
+
+    let mut nodes = vec![Hash::default(); leaf_count - 1];
+    nodes.extend(leaf_hashes);
+    for idx in (0..leaf_count - 1).rev() {
+        nodes[idx] = node_hash(&nodes[2 * idx + 1], &nodes[2 * idx + 2]);
+    }
+    nodes
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(left);
+    bytes[32..].copy_from_slice(right);
+    blake3_hash(&bytes)
+}
+
+fn blake3_hash(bytes: &[u8]) -> Hash {
+    blake3::hash(bytes).into()
+}
+
+fn level(node: u64) -> u32 {
+    (node + 1).ilog2()
+}
+
+fn is_descendant(mut node: u64, ancestor: u64) -> bool {
+    while node > ancestor {
+        node = (node - 1) / 2;
+    }
+    node == ancestor
+}
+
+// The type IDs (in the original type registry) of each part of an extrinsic.
+#[derive(Debug, Clone)]
+struct ExtrinsicTypeIds {
+    address: u32,
+    call: u32,
+    signature: u32,
+    // The name, value type ID and implicit type ID of each transaction extension.
+    extensions: Vec<(String, u32, u32)>,
+}
+
+impl ExtrinsicTypeIds {
+    fn all_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        [self.call, self.address, self.signature].into_iter().chain(
+            self.extensions
+                .iter()
+                .flat_map(|(_, id, implicit_id)| [*id, *implicit_id]),
+        )
+    }
+}
+
+// Identifies a leaf in the tree. Each variant of an enum is a separate leaf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct LeafId {
+    id: u32,
+    variant: Option<u32>,
+}
+
+fn resolve(
+    types: &PortableRegistry,
+    id: u32,
+) -> Result<&scale_info::Type<scale_info::form::PortableForm>, MetadataHashError> {
+    types.resolve(id).ok_or(MetadataHashError::TypeNotFound(id))
+}
+
+// Primitives, compacts and empty types are referred to directly rather than being leaves.
+fn is_basic_type(type_def: &TypeDef<scale_info::form::PortableForm>) -> bool {
+    match type_def {
+        TypeDef::Primitive(_) | TypeDef::Compact(_) => false,
+        TypeDef::Variant(v) => !v.variants.is_empty(),
+        TypeDef::Composite(c) => !c.fields.is_empty(),
+        TypeDef::Tuple(t) => !t.fields.is_empty(),
+        TypeDef::Sequence(_) | TypeDef::Array(_) | TypeDef::BitSequence(_) => true,
+    }
+}
+
+fn collect_accessible_types(
+    types: &PortableRegistry,
+    id: u32,
+    accessible: &mut BTreeSet<u32>,
+) -> Result<(), MetadataHashError> {
+    if !accessible.insert(id) {
+        return Ok(());
+    }
+
+    match &resolve(types, id)?.type_def {
+        TypeDef::Composite(c) => {
+            for f in &c.fields {
+                collect_accessible_types(types, f.ty.id, accessible)?;
+            }
+        }
+        TypeDef::Variant(v) => {
+            for f in v.variants.iter().flat_map(|v| &v.fields) {
+                collect_accessible_types(types, f.ty.id, accessible)?;
+            }
+        }
+        TypeDef::Sequence(s) => collect_accessible_types(types, s.type_param.id, accessible)?,
+        TypeDef::Array(a) => collect_accessible_types(types, a.type_param.id, accessible)?,
+        TypeDef::Tuple(t) => {
+            for f in &t.fields {
+                collect_accessible_types(types, f.id, accessible)?;
+            }
+        }
+        // Compacts are described using primitive like type refs, and bit sequences
+        // by their store size and order, so nothing inside them is needed.
+        TypeDef::Primitive(_) | TypeDef::Compact(_) | TypeDef::BitSequence(_) => {}
+    }
+
+    Ok(())
+}
+
+// Find the distinct primitive types reachable from some type.
+fn collect_primitives(
+    types: &PortableRegistry,
+    id: u32,
+    visited: &mut BTreeSet<u32>,
+    found: &mut Vec<TypeDefPrimitive>,
+) -> Result<(), MetadataHashError> {
+    let mut visit = |id: u32| {
+        if visited.insert(id) {
+            collect_primitives(types, id, visited, found)
+        } else {
+            Ok(())
+        }
+    };
+
+    match &resolve(types, id)?.type_def {
+        TypeDef::Composite(c) => c.fields.iter().try_for_each(|f| visit(f.ty.id)),
+        TypeDef::Variant(v) => v
+            .variants
+            .iter()
+            .flat_map(|v| &v.fields)
+            .try_for_each(|f| visit(f.ty.id)),
+        TypeDef::Sequence(s) => visit(s.type_param.id),
+        TypeDef::Array(a) => visit(a.type_param.id),
+        TypeDef::Tuple(t) => t.fields.iter().try_for_each(|f| visit(f.id)),
+        TypeDef::Compact(c) => visit(c.type_param.id),
+        TypeDef::BitSequence(b) => {
+            visit(b.bit_order_type.id)?;
+            visit(b.bit_store_type.id)
+        }
+        TypeDef::Primitive(p) => {
+            found.push(p.clone());
+            Ok(())
+        }
+    }
+}
+
+// Converts types from the type registry into the types described in RFC-0078.
+struct Context<'a> {
+    types: &'a PortableRegistry,
+    frame_id_to_id: &'a BTreeMap<u32, u32>,
+}
+
+impl Context<'_> {
+    fn type_ref(&self, frame_id: u32) -> Result<TypeRef, MetadataHashError> {
+        let ty = resolve(self.types, frame_id)?;
+        let type_ref = match &ty.type_def {
+            TypeDef::Primitive(p) => match p {
+                TypeDefPrimitive::Bool => TypeRef::Bool,
+                TypeDefPrimitive::Char => TypeRef::Char,
+                TypeDefPrimitive::Str => TypeRef::Str,
+                TypeDefPrimitive::U8 => TypeRef::U8,
+                TypeDefPrimitive::U16 => TypeRef::U16,
+                TypeDefPrimitive::U32 => TypeRef::U32,
+                TypeDefPrimitive::U64 => TypeRef::U64,
+                TypeDefPrimitive::U128 => TypeRef::U128,
+                TypeDefPrimitive::U256 => TypeRef::U256,
+                TypeDefPrimitive::I8 => TypeRef::I8,
+                TypeDefPrimitive::I16 => TypeRef::I16,
+                TypeDefPrimitive::I32 => TypeRef::I32,
+                TypeDefPrimitive::I64 => TypeRef::I64,
+                TypeDefPrimitive::I128 => TypeRef::I128,
+                TypeDefPrimitive::I256 => TypeRef::I256,
+            },
+            TypeDef::Compact(_) => {
+                let mut found = Vec::new();
+                collect_primitives(self.types, frame_id, &mut BTreeSet::new(), &mut found)?;
+                match found.as_slice() {
+                    [] => TypeRef::Void,
+                    [TypeDefPrimitive::U8] => TypeRef::CompactU8,
+                    [TypeDefPrimitive::U16] => TypeRef::CompactU16,
+                    [TypeDefPrimitive::U32] => TypeRef::CompactU32,
+                    [TypeDefPrimitive::U64] => TypeRef::CompactU64,
+                    [TypeDefPrimitive::U128] => TypeRef::CompactU128,
+                    [TypeDefPrimitive::U256] => TypeRef::CompactU256,
+                    [p] => {
+                        return Err(MetadataHashError::UnsupportedCompactType {
+                            type_id: frame_id,
+                            reason: alloc::format!("{p:?} cannot be compact encoded"),
+                        });
+                    }
+                    _ => {
+                        return Err(MetadataHashError::UnsupportedCompactType {
+                            type_id: frame_id,
+                            reason: alloc::format!("it contains several primitives: {found:?}"),
+                        });
+                    }
+                }
+            }
+            _ => match self.frame_id_to_id.get(&frame_id) {
+                Some(&id) => TypeRef::ById(id),
+                // Empty composites, tuples and variants aren't given IDs.
+                None => TypeRef::Void,
+            },
+        };
+        Ok(type_ref)
+    }
+
+    fn fields(
+        &self,
+        fields: &[scale_info::Field<scale_info::form::PortableForm>],
+    ) -> Result<Vec<Field>, MetadataHashError> {
+        fields
+            .iter()
+            .map(|f| {
+                Ok(Field {
+                    name: f.name.as_ref().map(|n| n.to_string()),
+                    ty: self.type_ref(f.ty.id)?,
+                    type_name: f.type_name.as_ref().map(|n| n.to_string()),
+                })
+            })
+            .collect()
+    }
+
+    // Convert a type into leaves. Enums are split so that each variant is its own leaf.
+    fn leaves(&self, frame_id: u32, id: u32) -> Result<Vec<Type>, MetadataHashError> {
+        let ty = resolve(self.types, frame_id)?;
+        let path: Vec<String> = ty.path.segments.iter().map(|s| s.to_string()).collect();
+
+        let type_def = match &ty.type_def {
+            TypeDef::Variant(v) => {
+                let mut variants: Vec<_> = v.variants.iter().collect();
+                variants.sort_by_key(|v| v.index);
+                return variants
+                    .into_iter()
+                    .map(|v| {
+                        Ok(Type {
+                            path: path.clone(),
+                            type_def: RfcTypeDef::Enumeration(EnumerationVariant {
+                                name: v.name.to_string(),
+                                fields: self.fields(&v.fields)?,
+                                index: Compact(v.index as u32),
+                            }),
+                            type_id: Compact(id),
+                        })
+                    })
+                    .collect();
+            }
+            TypeDef::Composite(c) => RfcTypeDef::Composite(self.fields(&c.fields)?),
+            TypeDef::Sequence(s) => RfcTypeDef::Sequence(self.type_ref(s.type_param.id)?),
+            TypeDef::Array(a) => RfcTypeDef::Array {
+                len: a.len,
+                type_param: self.type_ref(a.type_param.id)?,
+            },
+            TypeDef::Tuple(t) => RfcTypeDef::Tuple(
+                t.fields
+                    .iter()
+                    .map(|f| self.type_ref(f.id))
+                    .collect::<Result<_, _>>()?,
+            ),
+            TypeDef::BitSequence(b) => {
+                let bit_sequence_err =
+                    |reason: &str| MetadataHashError::UnsupportedBitSequenceType {
+                        type_id: frame_id,
+                        reason: reason.to_string(),
+                    };
+
+                let mut found = Vec::new();
+                collect_primitives(
+                    self.types,
+                    b.bit_store_type.id,
+                    &mut BTreeSet::new(),
+                    &mut found,
+                )?;
+                let num_bytes = match found.as_slice() {
+                    [TypeDefPrimitive::U8] => 1,
+                    [TypeDefPrimitive::U16] => 2,
+                    [TypeDefPrimitive::U32] => 4,
+                    [TypeDefPrimitive::U64] => 8,
+                    _ => {
+                        return Err(bit_sequence_err(
+                            "the store type must be u8, u16, u32 or u64",
+                        ));
+                    }
+                };
+
+                let order = resolve(self.types, b.bit_order_type.id)?
+                    .path
+                    .segments
+                    .iter()
+                    .map(AsRef::<str>::as_ref)
+                    .find(|s| *s == "Lsb0" || *s == "Msb0")
+                    .ok_or_else(|| bit_sequence_err("the order type must be Lsb0 or Msb0"))?;
+
+                RfcTypeDef::BitSequence {
+                    num_bytes,
+                    least_significant_bit_first: order == "Lsb0",
+                }
+            }
+            TypeDef::Primitive(_) | TypeDef::Compact(_) => return Ok(Vec::new()),
+        };
+
+        Ok(vec![Type {
+            path,
+            type_def,
+            type_id: Compact(id),
+        }])
+    }
+}
+
+// Records the types (and enum variants) that are seen while decoding some value.
+#[derive(Default)]
+struct TouchedTypes {
+    touched: BTreeSet<(u32, Option<u32>)>,
+}
+
+impl scale_decode::Visitor for TouchedTypes {
+    type Value<'scale, 'resolver> = Self;
+    type Error = scale_decode::Error;
+    type TypeResolver = PortableRegistry;
+
+    // Compact values are described using a single type ref, and so we don't want to visit
+    // (and record) any composite types that they are wrapped in.
+    fn unchecked_decode_as_type<'scale, 'resolver>(
+        self,
+        input: &mut &'scale [u8],
+        type_id: TypeIdFor<Self>,
+        types: &'resolver Self::TypeResolver,
+    ) -> DecodeAsTypeResult<Self, Result<Self::Value<'scale, 'resolver>, Self::Error>> {
+        let is_compact = types
+            .resolve(type_id)
+            .is_some_and(|ty| matches!(ty.type_def, TypeDef::Compact(_)));
+        if !is_compact {
+            return DecodeAsTypeResult::Skipped(self);
+        }
+
+        let res =
+            scale_decode::visitor::decode_with_visitor(input, type_id, types, IgnoreVisitor::new());
+        DecodeAsTypeResult::Decoded(res.map(|()| self).map_err(Into::into))
+    }
+
+    // Primitive values don't need recording.
+    fn visit_unexpected<'scale, 'resolver>(
+        self,
+        _unexpected: scale_decode::visitor::Unexpected,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        Ok(self)
+    }
+
+    fn visit_sequence<'scale, 'resolver>(
+        mut self,
+        value: &mut scale_decode::visitor::types::Sequence<'scale, 'resolver, Self::TypeResolver>,
+        type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        self.touched.insert((type_id, None));
+        for item in value {
+            self = item?.decode_with_visitor(self)?;
+        }
+        Ok(self)
+    }
+
+    fn visit_composite<'scale, 'resolver>(
+        mut self,
+        value: &mut scale_decode::visitor::types::Composite<'scale, 'resolver, Self::TypeResolver>,
+        type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        self.touched.insert((type_id, None));
+        for field in value {
+            self = field?.decode_with_visitor(self)?;
+        }
+        Ok(self)
+    }
+
+    fn visit_tuple<'scale, 'resolver>(
+        mut self,
+        value: &mut scale_decode::visitor::types::Tuple<'scale, 'resolver, Self::TypeResolver>,
+        type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        self.touched.insert((type_id, None));
+        for item in value {
+            self = item?.decode_with_visitor(self)?;
+        }
+        Ok(self)
+    }
+
+    fn visit_variant<'scale, 'resolver>(
+        mut self,
+        value: &mut scale_decode::visitor::types::Variant<'scale, 'resolver, Self::TypeResolver>,
+        type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        self.touched.insert((type_id, Some(value.index() as u32)));
+        for field in value.fields() {
+            self = field?.decode_with_visitor(self)?;
+        }
+        Ok(self)
+    }
+
+    fn visit_array<'scale, 'resolver>(
+        mut self,
+        value: &mut scale_decode::visitor::types::Array<'scale, 'resolver, Self::TypeResolver>,
+        type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        self.touched.insert((type_id, None));
+        for item in value {
+            self = item?.decode_with_visitor(self)?;
+        }
+        Ok(self)
+    }
+
+    fn visit_bitsequence<'scale, 'resolver>(
+        mut self,
+        _value: &mut scale_decode::visitor::types::BitSequence<'scale>,
+        type_id: TypeIdFor<Self>,
+    ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+        self.touched.insert((type_id, None));
+        Ok(self)
+    }
+}
+
+// The types below are those described in RFC-0078, and are SCALE encoded in order
+// to be hashed and handed to wallets.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypeRef {
+    Bool,
+    Char,
+    Str,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    I256,
+    CompactU8,
+    CompactU16,
+    CompactU32,
+    CompactU64,
+    CompactU128,
+    CompactU256,
+    Void,
+    ById(u32),
+}
+
+impl Encode for TypeRef {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        let index = match self {
+            TypeRef::Bool => 0u8,
+            TypeRef::Char => 1,
+            TypeRef::Str => 2,
+            TypeRef::U8 => 3,
+            TypeRef::U16 => 4,
+            TypeRef::U32 => 5,
+            TypeRef::U64 => 6,
+            TypeRef::U128 => 7,
+            TypeRef::U256 => 8,
+            TypeRef::I8 => 9,
+            TypeRef::I16 => 10,
+            TypeRef::I32 => 11,
+            TypeRef::I64 => 12,
+            TypeRef::I128 => 13,
+            TypeRef::I256 => 14,
+            TypeRef::CompactU8 => 15,
+            TypeRef::CompactU16 => 16,
+            TypeRef::CompactU32 => 17,
+            TypeRef::CompactU64 => 18,
+            TypeRef::CompactU128 => 19,
+            TypeRef::CompactU256 => 20,
+            TypeRef::Void => 21,
+            TypeRef::ById(id) => {
+                dest.push_byte(22);
+                Compact(*id).encode_to(dest);
+                return;
+            }
+        };
+        dest.push_byte(index);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Type {
+    path: Vec<String>,
+    type_def: RfcTypeDef,
+    type_id: Compact<u32>,
+}
+
+impl Type {
+    fn hash(&self) -> Hash {
+        blake3_hash(&self.encode())
+    }
+}
+
+impl Encode for Type {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.path.encode_to(dest);
+        self.type_def.encode_to(dest);
+        self.type_id.encode_to(dest);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RfcTypeDef {
+    Composite(Vec<Field>),
+    Enumeration(EnumerationVariant),
+    Sequence(TypeRef),
+    Array {
+        len: u32,
+        type_param: TypeRef,
+    },
+    Tuple(Vec<TypeRef>),
+    BitSequence {
+        num_bytes: u8,
+        least_significant_bit_first: bool,
+    },
+}
+
+impl Encode for RfcTypeDef {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        match self {
+            RfcTypeDef::Composite(fields) => {
+                dest.push_byte(0);
+                fields.encode_to(dest);
+            }
+            RfcTypeDef::Enumeration(variant) => {
+                dest.push_byte(1);
+                variant.encode_to(dest);
+            }
+            RfcTypeDef::Sequence(type_param) => {
+                dest.push_byte(2);
+                type_param.encode_to(dest);
+            }
+            RfcTypeDef::Array { len, type_param } => {
+                dest.push_byte(3);
+                len.encode_to(dest);
+                type_param.encode_to(dest);
+            }
+            RfcTypeDef::Tuple(fields) => {
+                dest.push_byte(4);
+                fields.encode_to(dest);
+            }
+            RfcTypeDef::BitSequence {
+                num_bytes,
+                least_significant_bit_first,
+            } => {
+                dest.push_byte(5);
+                num_bytes.encode_to(dest);
+                least_significant_bit_first.encode_to(dest);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Field {
+    name: Option<String>,
+    ty: TypeRef,
+    type_name: Option<String>,
+}
+
+impl Encode for Field {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.name.encode_to(dest);
+        self.ty.encode_to(dest);
+        self.type_name.encode_to(dest);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EnumerationVariant {
+    name: String,
+    fields: Vec<Field>,
+    index: Compact<u32>,
+}
+
+impl Encode for EnumerationVariant {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.name.encode_to(dest);
+        self.fields.encode_to(dest);
+        self.index.encode_to(dest);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExtrinsicMetadata {
+    version: u8,
+    address_ty: TypeRef,
+    call_ty: TypeRef,
+    signature_ty: TypeRef,
+    signed_extensions: Vec<SignedExtensionMetadata>,
+}
+
+impl Encode for ExtrinsicMetadata {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.version.encode_to(dest);
+        self.address_ty.encode_to(dest);
+        self.call_ty.encode_to(dest);
+        self.signature_ty.encode_to(dest);
+        self.signed_extensions.encode_to(dest);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SignedExtensionMetadata {
+    identifier: String,
+    included_in_extrinsic: TypeRef,
+    included_in_signed_data: TypeRef,
+}
+
+impl Encode for SignedExtensionMetadata {
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.identifier.encode_to(dest);
+        self.included_in_extrinsic.encode_to(dest);
+        self.included_in_signed_data.encode_to(dest);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn polkadot_metadata() -> frame_metadata::v14::RuntimeMetadataV14 {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+        metadata
+    }
+
+    fn polkadot_chain_info() -> ChainInfo {
+        ChainInfo {
+            spec_version: 9180,
+            spec_name: "polkadot".into(),
+            base58_prefix: 0,
+            decimals: 10,
+            token_symbol: "DOT".into(),
+        }
+    }
+
+    // A signed Balances.transfer with an immortal era, nonce 1 and no tip.
+    fn signed_transfer() -> (Vec<u8>, Vec<u8>) {
+        let mut call = vec![0x05, 0x00, 0x00];
+        call.extend([0x33; 32]);
+        call.extend([0x02, 0x09, 0x3d, 0x00]);
+
+        let mut body = vec![0x84, 0x00];
+        body.extend([0x11; 32]);
+        body.push(0x01);
+        body.extend([0x22; 64]);
+        body.extend([0x00, 0x04, 0x00]);
+        body.extend(&call);
+
+        let mut ext = Compact(body.len() as u32).encode();
+        ext.extend(body);
+        (ext, call)
+    }
+
+    // Spec version, transaction version, genesis hash and block hash.
+    fn implicits() -> Vec<u8> {
+        let mut implicits = Vec::new();
+        implicits.extend(9180u32.to_le_bytes());
+        implicits.extend(12u32.to_le_bytes());
+        implicits.extend([0x44; 32]);
+        implicits.extend([0x55; 32]);
+        implicits
+    }
+
+    fn proof_hash(proof: &MetadataProof) -> String {
+        hex::encode(blake3_hash(&proof.encode()))
+    }
+
+    // The expected values in these tests were computed using the `merkleized-metadata` crate.
+
+    #[test]
+    fn metadata_hash_matches_reference() {
+        let metadata = polkadot_metadata();
+        let merkleized = MerkleizedMetadata::new(&metadata, &metadata.types).unwrap();
+
+        assert_eq!(merkleized.leaf_count(), 1212);
+        assert_eq!(
+            hex::encode(merkleized.types_root()),
+            "edb82981960bb076df4405a05ee43760769550c0e514b389f274d84de67dd86d"
+        );
+        assert_eq!(
+            hex::encode(blake3_hash(&merkleized.encoded_extrinsic_metadata())),
+            "60c072c6ed5d1003a4e2c72d6d8a342c781ec51711a85baa263f6e8c1196b91f"
+        );
+        assert_eq!(
+            hex::encode(merkleized.metadata_hash(&polkadot_chain_info())),
+            "576ac2599494e97f9d06dec9a641b3ec8a1091cea9b278eeb35890c4ce0cc60a"
+        );
+    }
+
+    #[test]
+    fn proofs_match_reference() {
+        let metadata = polkadot_metadata();
+        let merkleized = MerkleizedMetadata::new(&metadata, &metadata.types).unwrap();
+        let (ext, call) = signed_transfer();
+        let root = Some(merkleized.types_root());
+
+        let proof = merkleized.proof_for_extrinsic(&ext, None).unwrap();
+        assert_eq!(
+            proof.leaf_indices(),
+            [
+                2164, 2165, 2421, 2422, 1211, 1212, 1249, 1250, 1437, 1506, 1512, 2023
+            ]
+        );
+        assert_eq!(proof.nodes().len(), 43);
+        assert_eq!(
+            proof_hash(&proof),
+            "f80ac649c7f8bae99d5c1e05bbd079b36a57395234d6340620702c733a9bf64c"
+        );
+        assert_eq!(proof.root(), root);
+
+        let proof = merkleized
+            .proof_for_extrinsic(&ext, Some(&implicits()))
+            .unwrap();
+        assert_eq!(proof.leaf_count(), 13);
+        assert_eq!(
+            proof_hash(&proof),
+            "729abc6786c24afc9529a720d50dec0bba110b05c29ab3b0496dfee848e38ff0"
+        );
+        assert_eq!(proof.root(), root);
+
+        let mut signer_payload = call;
+        signer_payload.extend([0x00, 0x04, 0x00]);
+        signer_payload.extend(implicits());
+        let proof = merkleized
+            .proof_for_signer_payload(&signer_payload)
+            .unwrap();
+        assert_eq!(proof.leaf_count(), 24);
+        assert_eq!(
+            proof_hash(&proof),
+            "fe70eced6c4cdb4006649b19f775eada1b429a53aec5ca2dc2a93412cf2621f9"
+        );
+        assert_eq!(proof.root(), root);
+
+        let timestamp = hex::decode("280403000b99e04d568001").unwrap();
+        let proof = merkleized.proof_for_extrinsic(&timestamp, None).unwrap();
+        assert_eq!(
+            proof_hash(&proof),
+            "4c5b7b6e92f811c34cb5163084166524acd19310e5558f000c2c8ab2995a1d06"
+        );
+        assert_eq!(proof.root(), root);
+    }
+
+    #[test]
+    fn proofs_reject_bad_input() {
+        let metadata = polkadot_metadata();
+        let merkleized = MerkleizedMetadata::new(&metadata, &metadata.types).unwrap();
+        let (mut ext, _) = signed_transfer();
+
+        let err = merkleized
+            .proof_for_extrinsic(&ext, Some(&implicits()[..70]))
+            .unwrap_err();
+        assert!(matches!(err, MetadataHashError::CannotDecode { .. }));
+
+        ext.push(0);
+        let err = merkleized.proof_for_extrinsic(&ext, None).unwrap_err();
+        assert!(matches!(err, MetadataHashError::WrongLength { .. }));
+
+        let v5_bare = [0x0c, 0x05, 0x03, 0x00];
+        let err = merkleized.proof_for_extrinsic(&v5_bare, None).unwrap_err();
+        assert!(matches!(
+            err,
+            MetadataHashError::UnsupportedExtrinsicVersion(5)
+        ));
+    }
+}
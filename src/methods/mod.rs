@@ -13,19 +13,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod any_metadata;
+pub mod call_data_decoder;
+pub mod call_tree_decoder;
 pub mod constant_decoder;
 pub mod constant_type_info;
+#[cfg(feature = "crypto")]
+pub mod crypto;
 pub mod custom_value_decoder;
 pub mod custom_value_type_info;
+pub mod deprecation;
+pub mod error_decoder;
+pub mod error_type_info;
+pub mod event_decoder;
+pub mod event_records_decoder;
+pub mod event_type_info;
 pub mod extrinsic_decoder;
 pub mod extrinsic_encoder;
+pub mod extrinsic_receipts_decoder;
 pub mod extrinsic_type_info;
+pub mod indexed_metadata;
+pub mod metadata_diff;
+#[cfg(feature = "metadata-hash")]
+pub mod metadata_hash;
+pub mod outer_enum_decoder;
+pub mod outer_enum_type_info;
 pub mod runtime_api_decoder;
 pub mod runtime_api_encoder;
 pub mod runtime_api_type_info;
+pub mod shape_hash;
+pub mod standard_extensions_decoder;
 pub mod storage_decoder;
 pub mod storage_encoder;
+pub mod storage_key_lookup;
+pub mod storage_prefix_index;
+pub mod storage_proof;
 pub mod storage_type_info;
+pub mod type_docs_info;
 pub mod view_function_decoder;
 pub mod view_function_encoder;
 pub mod view_function_type_info;
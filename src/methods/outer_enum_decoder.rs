@@ -0,0 +1,612 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::extrinsic_decoder::NamedArg;
+use crate::methods::outer_enum_type_info::{OuterEnum, OuterEnumInfoError, OuterEnumTypeInfo};
+use crate::utils::{
+    DecodeErrorTrace, TypeShape, VariantShape, decode_with_error_tracing, resolve_type_shape,
+};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+use parity_scale_codec::Decode;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode the bytes of an outer enum.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum OuterEnumDecodeError {
+    #[error("Cannot get outer enum info:\n\n{0}")]
+    CannotGetInfo(OuterEnumInfoError),
+    #[error("Cannot resolve type {type_id}:\n\n{reason}")]
+    CannotResolveType { type_id: String, reason: String },
+    #[error("Type {type_id} should be a variant type, but it is not.")]
+    TypeShouldBeVariant { type_id: String },
+    #[error("Cannot decode pallet index byte:\n\n{0}")]
+    CannotDecodePalletIndex(parity_scale_codec::Error),
+    #[error("Pallet with index {index} not found in the outer enum.")]
+    PalletNotFound { index: u8 },
+    #[error(
+        "The outer enum variant for pallet '{pallet_name}' should have exactly one field, but has {num_fields}."
+    )]
+    UnexpectedPalletVariantFields {
+        pallet_name: String,
+        num_fields: usize,
+    },
+    #[error("Cannot decode variant index byte:\n\n{0}")]
+    CannotDecodeVariantIndex(parity_scale_codec::Error),
+    #[error("Variant with index {index} not found in pallet '{pallet_name}'.")]
+    VariantNotFound { pallet_name: String, index: u8 },
+    #[error("Cannot decode field {field_name} in {pallet_name}.{variant_name}:\n\n{reason}")]
+    CannotDecodeField {
+        pallet_name: String,
+        variant_name: String,
+        field_name: String,
+        reason: DecodeErrorTrace,
+    },
+}
+
+/// Information about some decoded outer enum (eg a `RuntimeCall`, `RuntimeEvent` or
+/// `RuntimeError`).
+#[derive(Clone, Debug)]
+pub struct OuterEnumVariant<TypeId> {
+    pallet_name: String,
+    pallet_index: u8,
+    pallet_index_idx: u32,
+    variant_name: String,
+    variant_index: u8,
+    fields: Vec<NamedArg<'static, TypeId>>,
+}
+
+impl<TypeId> OuterEnumVariant<TypeId> {
+    /// The name of the pallet.
+    pub fn pallet_name(&self) -> &str {
+        &self.pallet_name
+    }
+
+    /// The index of the pallet.
+    pub fn pallet_index(&self) -> u8 {
+        self.pallet_index
+    }
+
+    /// The name of the call, event or error within the pallet.
+    pub fn variant_name(&self) -> &str {
+        &self.variant_name
+    }
+
+    /// The index of the call, event or error within the pallet.
+    pub fn variant_index(&self) -> u8 {
+        self.variant_index
+    }
+
+    /// Iterate over the field names and types.
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = &NamedArg<'static, TypeId>> {
+        self.fields.iter()
+    }
+
+    /// Return a range denoting the outer enum bytes. This includes the pallet index and
+    /// variant index bytes and then any encoded fields.
+    pub fn range(&self) -> Range<usize> {
+        let start = self.pallet_index_idx as usize;
+        let end = self.fields_range().end;
+        Range { start, end }
+    }
+
+    /// Return a range denoting the fields. This does *not* include the pallet index and
+    /// variant index bytes.
+    pub fn fields_range(&self) -> Range<usize> {
+        let start = (self.pallet_index_idx + 2) as usize;
+        let end = self
+            .fields()
+            .map(|a| a.range.end as usize)
+            .max()
+            .unwrap_or(start);
+
+        Range { start, end }
+    }
+
+    /// Map the field type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> OuterEnumVariant<NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        OuterEnumVariant {
+            pallet_name: self.pallet_name,
+            pallet_index: self.pallet_index,
+            pallet_index_idx: self.pallet_index_idx,
+            variant_name: self.variant_name,
+            variant_index: self.variant_index,
+            fields: self
+                .fields
+                .into_iter()
+                .map(|s| s.map_type_id(&mut f))
+                .collect(),
+        }
+    }
+}
+
+/// Decode some bytes as the `RuntimeCall` outer enum. This is the same as the call data
+/// in an extrinsic, and is also what is found in values which embed calls, such as the
+/// arguments given to `Proxy.proxy`, `Multisig.as_multi` or `Sudo.sudo`.
+///
+/// See [`decode_outer_enum`] for more information.
+pub fn decode_runtime_call<Info, Resolver>(
+    cursor: &mut &[u8],
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<OuterEnumVariant<Info::TypeId>, OuterEnumDecodeError>
+where
+    Info: OuterEnumTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone + 'static,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_outer_enum(cursor, OuterEnum::Call, info, type_resolver)
+}
+
+/// Decode some bytes as the `RuntimeEvent` outer enum.
+///
+/// See [`decode_outer_enum`] for more information.
+pub fn decode_runtime_event<Info, Resolver>(
+    cursor: &mut &[u8],
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<OuterEnumVariant<Info::TypeId>, OuterEnumDecodeError>
+where
+    Info: OuterEnumTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone + 'static,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_outer_enum(cursor, OuterEnum::Event, info, type_resolver)
+}
+
+/// Decode some bytes as the `RuntimeError` outer enum.
+///
+/// See [`decode_outer_enum`] for more information.
+pub fn decode_runtime_error<Info, Resolver>(
+    cursor: &mut &[u8],
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<OuterEnumVariant<Info::TypeId>, OuterEnumDecodeError>
+where
+    Info: OuterEnumTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone + 'static,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_outer_enum(cursor, OuterEnum::Error, info, type_resolver)
+}
+
+/// Decode some bytes as one of the outer enums, returning information about the pallet and
+/// variant found, and the byte ranges of each field. The byte ranges handed back are relative
+/// to the start of the bytes that the cursor points to when this is called.
+///
+/// This information can be used to then decode each of the fields to concrete types.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::outer_enums::{decode_outer_enum, OuterEnum};
+/// use frame_decode::helpers::decode_with_visitor;
+/// use frame_metadata::v15::RuntimeMetadataV15;
+/// use scale_value::scale::ValueVisitor;
+///
+/// fn print_call(call_bytes: &[u8], metadata: &RuntimeMetadataV15) {
+///     // Decode the bytes as a `RuntimeCall`, returning information about it:
+///     let call = decode_outer_enum(
+///         &mut &*call_bytes,
+///         OuterEnum::Call,
+///         metadata,
+///         &metadata.types
+///     ).unwrap();
+///
+///     println!("{}.{}", call.pallet_name(), call.variant_name());
+///
+///     // Decode the call arguments to scale_value::Value's.
+///     for field in call.fields() {
+///         let field_bytes = &call_bytes[field.range()];
+///         let field_value = decode_with_visitor(
+///             &mut &*field_bytes,
+///             *field.ty(),
+///             &metadata.types,
+///             ValueVisitor::new()
+///         ).unwrap();
+///         println!("  {}: {field_value}", field.name());
+///     }
+/// }
+/// ```
+pub fn decode_outer_enum<Info, Resolver>(
+    cursor: &mut &[u8],
+    outer_enum: OuterEnum,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<OuterEnumVariant<Info::TypeId>, OuterEnumDecodeError>
+where
+    Info: OuterEnumTypeInfo,
+    Info::TypeId: core::fmt::Debug + Clone + 'static,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let outer_enum_info = info
+        .outer_enum_info()
+        .map_err(OuterEnumDecodeError::CannotGetInfo)?;
+    let type_id = outer_enum_info.type_id(outer_enum).clone();
+    decode_outer_enum_with_type_id(cursor, type_id, type_resolver)
+}
+
+/// Decode some bytes as an outer enum with the given type ID. This is useful when the type ID
+/// is known from elsewhere, for instance when some argument or storage value has the type
+/// `RuntimeCall`, or when working with V14 metadata which does not expose the outer enums.
+///
+/// See [`decode_outer_enum`] for more information.
+pub fn decode_outer_enum_with_type_id<Resolver>(
+    cursor: &mut &[u8],
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<OuterEnumVariant<Resolver::TypeId>, OuterEnumDecodeError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone + 'static,
+{
+    let bytes = *cursor;
+    let curr_idx = |cursor: &mut &[u8]| (bytes.len() - cursor.len()) as u32;
+
+    // The outer enum has one variant per pallet, each containing the pallet's own enum.
+    let pallets = resolve_variants(type_resolver, type_id)?;
+    let pallet_index_idx = curr_idx(cursor);
+    let pallet_index: u8 =
+        Decode::decode(cursor).map_err(OuterEnumDecodeError::CannotDecodePalletIndex)?;
+    let pallet = pallets
+        .into_iter()
+        .find(|v| v.index == pallet_index)
+        .ok_or(OuterEnumDecodeError::PalletNotFound {
+            index: pallet_index,
+        })?;
+
+    let num_fields = pallet.fields.len();
+    let Some(pallet_field) = pallet.fields.into_iter().next().filter(|_| num_fields == 1) else {
+        return Err(OuterEnumDecodeError::UnexpectedPalletVariantFields {
+            pallet_name: pallet.name,
+            num_fields,
+        });
+    };
+
+    let variants = resolve_variants(type_resolver, pallet_field.id)?;
+    let variant_index: u8 =
+        Decode::decode(cursor).map_err(OuterEnumDecodeError::CannotDecodeVariantIndex)?;
+    let variant = variants
+        .into_iter()
+        .find(|v| v.index == variant_index)
+        .ok_or_else(|| OuterEnumDecodeError::VariantNotFound {
+            pallet_name: pallet.name.clone(),
+            index: variant_index,
+        })?;
+
+    let mut fields = Vec::with_capacity(variant.fields.len());
+    for field in variant.fields {
+        let field_name = field.name.unwrap_or_default();
+        let start_idx = curr_idx(cursor);
+        decode_with_error_tracing(
+            cursor,
+            field.id.clone(),
+            type_resolver,
+            scale_decode::visitor::IgnoreVisitor::new(),
+        )
+        .map_err(|e| OuterEnumDecodeError::CannotDecodeField {
+            pallet_name: pallet.name.clone(),
+            variant_name: variant.name.clone(),
+            field_name: field_name.clone(),
+            reason: e,
+        })?;
+        let end_idx = curr_idx(cursor);
+
+        fields.push(NamedArg {
+            name: Cow::Owned(field_name),
+            range: Range {
+                start: start_idx,
+                end: end_idx,
+            },
+            ty: field.id,
+        })
+    }
+
+    Ok(OuterEnumVariant {
+        pallet_name: pallet.name,
+        pallet_index,
+        pallet_index_idx,
+        variant_name: variant.name,
+        variant_index,
+        fields,
+    })
+}
+
+fn resolve_variants<Resolver>(
+    type_resolver: &Resolver,
+    type_id: Resolver::TypeId,
+) -> Result<Vec<VariantShape<Resolver::TypeId>>, OuterEnumDecodeError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone + 'static,
+{
+    let shape = resolve_type_shape(type_resolver, type_id.clone()).map_err(|e| {
+        OuterEnumDecodeError::CannotResolveType {
+            type_id: format!("{type_id:?}"),
+            reason: e.to_string(),
+        }
+    })?;
+
+    match shape {
+        TypeShape::Variant(variants) => Ok(variants),
+        _ => Err(OuterEnumDecodeError::TypeShouldBeVariant {
+            type_id: format!("{type_id:?}"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::error_type_info::ErrorTypeInfo;
+    use crate::methods::event_type_info::EventTypeInfo;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use crate::methods::outer_enum_type_info::OuterEnumInfo;
+    use frame_metadata::RuntimeMetadata;
+    use frame_metadata::v14::RuntimeMetadataV14;
+    use frame_metadata::v15::{self, RuntimeMetadataV15};
+    use parity_scale_codec::Encode;
+    use scale_info::form::PortableForm;
+    use scale_info::{Field, Path, PortableType, Type, TypeDef, TypeDefVariant, Variant};
+
+    fn metadata() -> RuntimeMetadataV14 {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(mut metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        // This runtime predates the `RuntimeError` outer enum, so add one in the shape that
+        // FRAME generates: a variant for each pallet with errors, at the index of the pallet,
+        // which wraps that pallet's error enum.
+        let variants = metadata
+            .pallets
+            .iter()
+            .filter_map(|p| {
+                let error = p.error.as_ref()?;
+                Some(Variant {
+                    name: p.name.clone(),
+                    fields: vec![Field {
+                        name: None,
+                        ty: error.ty,
+                        type_name: None,
+                        docs: vec![],
+                    }],
+                    index: p.index,
+                    docs: vec![],
+                })
+            })
+            .collect();
+        let id = metadata.types.types.len() as u32;
+        metadata.types.types.push(PortableType {
+            id,
+            ty: Type {
+                path: Path::from_segments_unchecked([
+                    "polkadot_runtime".to_owned(),
+                    "RuntimeError".to_owned(),
+                ]),
+                type_params: vec![],
+                type_def: TypeDef::Variant(TypeDefVariant { variants }),
+                docs: vec![],
+            },
+        });
+
+        metadata
+    }
+
+    // The same metadata as above, converted to V15 so that the outer enums are available.
+    fn metadata_v15() -> RuntimeMetadataV15 {
+        let metadata = metadata();
+        let outer_enums = V14OuterEnums(&metadata).outer_enum_info().unwrap();
+        let extrinsic_type_param = |name: &str| {
+            let extrinsic_ty = metadata.types.resolve(metadata.extrinsic.ty.id).unwrap();
+            extrinsic_ty
+                .type_params
+                .iter()
+                .find(|p| p.name == name)
+                .and_then(|p| p.ty)
+                .unwrap()
+        };
+
+        RuntimeMetadataV15 {
+            pallets: metadata
+                .pallets
+                .iter()
+                .map(|p| v15::PalletMetadata {
+                    name: p.name.clone(),
+                    storage: p.storage.clone(),
+                    calls: p.calls.clone(),
+                    event: p.event.clone(),
+                    constants: p.constants.clone(),
+                    error: p.error.clone(),
+                    index: p.index,
+                    docs: vec![],
+                })
+                .collect(),
+            extrinsic: v15::ExtrinsicMetadata {
+                version: metadata.extrinsic.version,
+                address_ty: extrinsic_type_param("Address"),
+                call_ty: extrinsic_type_param("Call"),
+                signature_ty: extrinsic_type_param("Signature"),
+                extra_ty: extrinsic_type_param("Extra"),
+                signed_extensions: metadata
+                    .extrinsic
+                    .signed_extensions
+                    .iter()
+                    .map(|e| v15::SignedExtensionMetadata {
+                        identifier: e.identifier.clone(),
+                        ty: e.ty,
+                        additional_signed: e.additional_signed,
+                    })
+                    .collect(),
+            },
+            ty: metadata.ty,
+            apis: vec![],
+            outer_enums: v15::OuterEnums::<PortableForm> {
+                call_enum_ty: outer_enums.call_enum_ty.into(),
+                event_enum_ty: outer_enums.event_enum_ty.into(),
+                error_enum_ty: outer_enums.error_enum_ty.into(),
+            },
+            custom: v15::CustomMetadata {
+                map: Default::default(),
+            },
+            types: metadata.types,
+        }
+    }
+
+    // V14 doesn't expose the outer enums, so we find them by name for testing.
+    struct V14OuterEnums<'a>(&'a RuntimeMetadataV14);
+
+    impl OuterEnumTypeInfo for V14OuterEnums<'_> {
+        type TypeId = u32;
+        fn outer_enum_info(&self) -> Result<OuterEnumInfo<u32>, OuterEnumInfoError> {
+            let type_id = |name: &str| {
+                self.0
+                    .types
+                    .types
+                    .iter()
+                    .find(|ty| {
+                        ty.ty.path.segments.first().map(|s| &**s) == Some("polkadot_runtime")
+                            && ty.ty.path.segments.last().map(|s| &**s) == Some(name)
+                    })
+                    .unwrap()
+                    .id
+            };
+            Ok(OuterEnumInfo {
+                call_enum_ty: type_id("Call"),
+                event_enum_ty: type_id("Event"),
+                error_enum_ty: type_id("RuntimeError"),
+            })
+        }
+    }
+
+    #[test]
+    fn runtime_call_matches_extrinsic_call_data() {
+        let metadata = metadata();
+        let info = V14OuterEnums(&metadata);
+
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+
+        for ext_hex in extrinsics_hex {
+            let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+
+            let call_bytes = &ext_bytes[ext.call_data_range()];
+            let cursor = &mut &*call_bytes;
+            let call = decode_runtime_call(cursor, &info, &metadata.types).unwrap();
+
+            assert!(cursor.is_empty());
+            assert_eq!(call.pallet_name(), ext.pallet_name());
+            assert_eq!(call.pallet_index(), ext.pallet_index());
+            assert_eq!(call.variant_name(), ext.call_name());
+            assert_eq!(call.variant_index(), ext.call_index());
+            assert_eq!(call.range(), 0..call_bytes.len());
+
+            let offset = ext.call_data_range().start;
+            let call_fields: Vec<_> = call
+                .fields()
+                .map(|f| (f.name().to_owned(), f.range(), *f.ty()))
+                .collect();
+            let ext_fields: Vec<_> = ext
+                .call_data()
+                .map(|f| {
+                    let range = f.range();
+                    (
+                        f.name().to_owned(),
+                        range.start - offset..range.end - offset,
+                        *f.ty(),
+                    )
+                })
+                .collect();
+            assert_eq!(call_fields, ext_fields);
+        }
+    }
+
+    #[test]
+    fn runtime_event_can_be_decoded() {
+        let metadata = metadata();
+        let info = V14OuterEnums(&metadata);
+
+        let deposit_info = metadata.event_info_by_name("Balances", "Deposit").unwrap();
+        let mut event_bytes = vec![deposit_info.pallet_index, deposit_info.event_index];
+        [1u8; 32].encode_to(&mut event_bytes);
+        12345u128.encode_to(&mut event_bytes);
+
+        let event = decode_runtime_event(&mut &*event_bytes, &info, &metadata.types).unwrap();
+        assert_eq!(event.pallet_name(), "Balances");
+        assert_eq!(event.variant_name(), "Deposit");
+        assert_eq!(event.fields_range(), 2..event_bytes.len());
+        assert_eq!(
+            event.fields().map(|f| f.name()).collect::<Vec<_>>(),
+            ["who", "amount"]
+        );
+
+        // Unknown pallets are reported:
+        let err = decode_runtime_event(&mut &[255u8, 0][..], &info, &metadata.types).unwrap_err();
+        assert!(matches!(
+            err,
+            OuterEnumDecodeError::PalletNotFound { index: 255 }
+        ));
+    }
+
+    #[test]
+    fn runtime_error_can_be_decoded() {
+        let v15_metadata = metadata_v15();
+
+        let error_info = v15_metadata
+            .error_info_by_name("Balances", "InsufficientBalance")
+            .unwrap();
+        let error_bytes = [error_info.pallet_index, error_info.error_index];
+
+        let cursor = &mut &error_bytes[..];
+        let error = decode_runtime_error(cursor, &v15_metadata, &v15_metadata.types).unwrap();
+        assert!(cursor.is_empty());
+        assert_eq!(error.pallet_name(), "Balances");
+        assert_eq!(error.pallet_index(), error_info.pallet_index);
+        assert_eq!(error.variant_name(), "InsufficientBalance");
+        assert_eq!(error.variant_index(), error_info.error_index);
+        assert_eq!(error.range(), 0..2);
+        assert_eq!(error.fields().count(), 0);
+
+        // The V14 metadata, given the same outer enums, decodes the error identically.
+        let v14_metadata = metadata();
+        let v14_error = decode_runtime_error(
+            &mut &error_bytes[..],
+            &V14OuterEnums(&v14_metadata),
+            &v14_metadata.types,
+        )
+        .unwrap();
+        assert_eq!(v14_error.pallet_name(), "Balances");
+        assert_eq!(v14_error.variant_name(), "InsufficientBalance");
+
+        // Pallets without errors are not a part of the enum. Pallet 3 is Timestamp:
+        let err = decode_runtime_error(&mut &[3u8, 0][..], &v15_metadata, &v15_metadata.types)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OuterEnumDecodeError::PalletNotFound { index: 3 }
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// This is implemented for anything which can hand back the type IDs of the outer enums;
+/// the aggregated `RuntimeCall`, `RuntimeEvent` and `RuntimeError` types. These are only
+/// available in V15 and later metadata.
+pub trait OuterEnumTypeInfo {
+    /// The type of type IDs that we are using to obtain type information.
+    type TypeId;
+    /// Get the type IDs of the outer enums.
+    fn outer_enum_info(&self) -> Result<OuterEnumInfo<Self::TypeId>, OuterEnumInfoError>;
+}
+
+/// An error returned trying to access outer enum information.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OuterEnumInfoError {
+    #[error(
+        "Outer enums are only available in V15 and later metadata, but got V{metadata_version} metadata."
+    )]
+    NotAvailable { metadata_version: u32 },
+}
+
+/// One of the outer enums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OuterEnum {
+    /// The `RuntimeCall` enum, which is the type of the call data in an extrinsic.
+    Call,
+    /// The `RuntimeEvent` enum, which contains every event that can be emitted.
+    Event,
+    /// The `RuntimeError` enum, which contains every pallet error.
+    Error,
+}
+
+/// The type IDs of the outer enums.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OuterEnumInfo<TypeId> {
+    /// The `RuntimeCall` type ID.
+    pub call_enum_ty: TypeId,
+    /// The `RuntimeEvent` type ID.
+    pub event_enum_ty: TypeId,
+    /// The `RuntimeError` type ID.
+    pub error_enum_ty: TypeId,
+}
+
+impl<TypeId> OuterEnumInfo<TypeId> {
+    /// Return the type ID of the given outer enum.
+    pub fn type_id(&self, outer_enum: OuterEnum) -> &TypeId {
+        match outer_enum {
+            OuterEnum::Call => &self.call_enum_ty,
+            OuterEnum::Event => &self.event_enum_ty,
+            OuterEnum::Error => &self.error_enum_ty,
+        }
+    }
+
+    /// Map the type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> OuterEnumInfo<NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        OuterEnumInfo {
+            call_enum_ty: f(self.call_enum_ty),
+            event_enum_ty: f(self.event_enum_ty),
+            error_enum_ty: f(self.error_enum_ty),
+        }
+    }
+}
+
+macro_rules! impl_outer_enum_type_info_for_v15_to_v16 {
+    ($path:path) => {
+        impl OuterEnumTypeInfo for $path {
+            type TypeId = u32;
+
+            fn outer_enum_info(&self) -> Result<OuterEnumInfo<Self::TypeId>, OuterEnumInfoError> {
+                Ok(OuterEnumInfo {
+                    call_enum_ty: self.outer_enums.call_enum_ty.id,
+                    event_enum_ty: self.outer_enums.event_enum_ty.id,
+                    error_enum_ty: self.outer_enums.error_enum_ty.id,
+                })
+            }
+        }
+    };
+}
+
+impl_outer_enum_type_info_for_v15_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_outer_enum_type_info_for_v15_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
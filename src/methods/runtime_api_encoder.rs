@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::deprecation::{DeprecationPolicy, DeprecationWarning, check_deprecation};
 use super::runtime_api_type_info::{RuntimeApiInfo, RuntimeApiInfoError, RuntimeApiTypeInfo};
 use crate::utils::{EncodableValues, IntoEncodableValues};
 use alloc::format;
@@ -34,6 +35,8 @@ pub enum RuntimeApiInputsEncodeError {
         /// The number of input parameters that were expected.
         num_inputs_expected: usize,
     },
+    #[error("Cannot encode Runtime API inputs: {0}")]
+    Deprecated(DeprecationWarning),
 }
 
 /// Encode the name/ID of a Runtime API used in RPC methods given the trait name and method name.
@@ -82,6 +85,44 @@ where
     encode_runtime_api_inputs_with_info_to(keys, &runtime_api_info, type_resolver, out)
 }
 
+/// Encode the inputs to a Runtime API, applying the given [`DeprecationPolicy`] if the
+/// Runtime API is marked as deprecated in the metadata.
+///
+/// This is like [`encode_runtime_api_inputs`], but also hands back a [`DeprecationWarning`]
+/// if the Runtime API is deprecated and the policy is [`DeprecationPolicy::Warn`], and returns
+/// [`RuntimeApiInputsEncodeError::Deprecated`] if the Runtime API is deprecated and the policy
+/// is [`DeprecationPolicy::Deny`].
+pub fn encode_runtime_api_inputs_checked<Info, Resolver, Inputs>(
+    trait_name: &str,
+    method_name: &str,
+    keys: Inputs,
+    info: &Info,
+    type_resolver: &Resolver,
+    policy: DeprecationPolicy,
+) -> Result<(Vec<u8>, Option<DeprecationWarning>), RuntimeApiInputsEncodeError>
+where
+    Inputs: IntoEncodableValues,
+    Info: RuntimeApiTypeInfo,
+    Info::TypeId: Clone + core::fmt::Debug,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let runtime_api_info = info
+        .runtime_api_info(trait_name, method_name)
+        .map_err(|e| RuntimeApiInputsEncodeError::CannotGetInfo(e.into_owned()))?;
+
+    let warning = check_deprecation(
+        policy,
+        trait_name,
+        method_name,
+        &runtime_api_info.deprecation,
+    )
+    .map_err(RuntimeApiInputsEncodeError::Deprecated)?;
+
+    let mut out = Vec::new();
+    encode_runtime_api_inputs_with_info_to(keys, &runtime_api_info, type_resolver, &mut out)?;
+    Ok((out, warning))
+}
+
 /// Encode the inputs to a Runtime API to a provided output `Vec`.
 ///
 /// Unlike [`encode_runtime_api_inputs_to`], which obtains the Runtime API info internally given trait and method names,
@@ -14,7 +14,8 @@
 // limitations under the License.
 
 use super::Entry;
-use crate::utils::Either;
+use super::deprecation::{Deprecation, DeprecationOf};
+use crate::utils::{Either, to_docs};
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
 use alloc::string::{String, ToString};
@@ -33,6 +34,15 @@ pub trait RuntimeApiTypeInfo {
     ) -> Result<RuntimeApiInfo<'_, Self::TypeId>, RuntimeApiInfoError<'_>>;
 }
 
+/// Implementations of this are responsible for handing back the docs for Runtime APIs. This
+/// is implemented for newer versions of frame-metadata (V15 and above).
+pub trait RuntimeApiDocsInfo {
+    /// Get the docs for a given Runtime API trait, or `None` if the trait can't be found.
+    fn runtime_api_trait_docs(&self, trait_name: &str) -> Option<Vec<&str>>;
+    /// Get the docs for a given Runtime API method, or `None` if the method can't be found.
+    fn runtime_api_docs(&self, trait_name: &str, method_name: &str) -> Option<Vec<&str>>;
+}
+
 /// This can be implemented for anything capable of providing information about the available Runtime Apis
 pub trait RuntimeApiEntryInfo {
     /// Iterate over all of the available Runtime Apis, returning [`Entry`] as we go.
@@ -89,6 +99,8 @@ pub struct RuntimeApiInfo<'info, TypeId: Clone> {
     pub inputs: Cow<'info, [RuntimeApiInput<'info, TypeId>]>,
     /// The output type returned from the runtime API.
     pub output_id: TypeId,
+    /// Whether the Runtime API method is deprecated.
+    pub deprecation: Deprecation<'info>,
 }
 
 impl<'info, TypeId: Clone + 'static> RuntimeApiInfo<'info, TypeId> {
@@ -103,6 +115,7 @@ impl<'info, TypeId: Clone + 'static> RuntimeApiInfo<'info, TypeId> {
         RuntimeApiInfo {
             inputs: Cow::Owned(inputs),
             output_id: self.output_id,
+            deprecation: self.deprecation.into_owned(),
         }
     }
 
@@ -139,6 +152,7 @@ impl<'info, TypeId: Clone + 'static> RuntimeApiInfo<'info, TypeId> {
         Ok(RuntimeApiInfo {
             inputs: Cow::Owned(new_inputs),
             output_id: new_output_id,
+            deprecation: self.deprecation.into_owned(),
         })
     }
 }
@@ -200,6 +214,7 @@ macro_rules! impl_runtime_api_info_for_v15_to_v16 {
                     Ok(RuntimeApiInfo {
                         inputs,
                         output_id: method.output.id,
+                        deprecation: api.deprecation_of(method),
                     })
                 }
             }
@@ -237,6 +252,25 @@ macro_rules! impl_runtime_api_info_for_v15_to_v16 {
 impl_runtime_api_info_for_v15_to_v16!(frame_metadata::v15, RuntimeMetadataV15);
 impl_runtime_api_info_for_v15_to_v16!(frame_metadata::v16, RuntimeMetadataV16);
 
+macro_rules! impl_runtime_api_docs_info_for_v15_to_v16 {
+    ($path:path) => {
+        impl RuntimeApiDocsInfo for $path {
+            fn runtime_api_trait_docs(&self, trait_name: &str) -> Option<Vec<&str>> {
+                let api = self.apis.iter().find(|api| api.name == trait_name)?;
+                Some(to_docs(&api.docs))
+            }
+            fn runtime_api_docs(&self, trait_name: &str, method_name: &str) -> Option<Vec<&str>> {
+                let api = self.apis.iter().find(|api| api.name == trait_name)?;
+                let method = api.methods.iter().find(|m| m.name == method_name)?;
+                Some(to_docs(&method.docs))
+            }
+        }
+    };
+}
+
+impl_runtime_api_docs_info_for_v15_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_runtime_api_docs_info_for_v15_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
 #[cfg(feature = "legacy")]
 mod legacy {
     use super::*;
@@ -270,6 +304,7 @@ mod legacy {
             Ok(RuntimeApiInfo {
                 inputs,
                 output_id: api.output.clone(),
+                deprecation: Deprecation::NotDeprecated,
             })
         }
     }
@@ -312,6 +347,7 @@ mod legacy {
             Ok(RuntimeApiInfo {
                 inputs,
                 output_id: api.output.clone(),
+                deprecation: Deprecation::NotDeprecated,
             })
         }
     }
@@ -0,0 +1,524 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::constant_type_info::{ConstantInfoError, ConstantTypeInfo};
+use crate::methods::extrinsic_type_info::{ExtrinsicInfoError, ExtrinsicTypeInfo};
+use crate::methods::runtime_api_type_info::{RuntimeApiInfoError, RuntimeApiTypeInfo};
+use crate::methods::storage_type_info::{StorageHasher, StorageInfoError, StorageTypeInfo};
+use crate::methods::view_function_type_info::{ViewFunctionInfoError, ViewFunctionTypeInfo};
+use crate::utils::{FieldShape, TypeShape, resolve_type_shape};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use parity_scale_codec::Encode;
+use scale_type_resolver::{
+    BitsOrderFormat, BitsStoreFormat, Primitive, TypeResolver, UnhandledKind,
+};
+
+/// A structural hash of some type or item in the metadata.
+pub type ShapeHash = [u8; 32];
+
+/// An error returned trying to compute a shape hash.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ShapeHashError {
+    #[error("Cannot get call info:\n\n{0}")]
+    CannotGetCallInfo(ExtrinsicInfoError<'static>),
+    #[error("Cannot get storage info:\n\n{0}")]
+    CannotGetStorageInfo(StorageInfoError<'static>),
+    #[error("Cannot get constant info:\n\n{0}")]
+    CannotGetConstantInfo(ConstantInfoError<'static>),
+    #[error("Cannot get Runtime API info:\n\n{0}")]
+    CannotGetRuntimeApiInfo(RuntimeApiInfoError<'static>),
+    #[error("Cannot get View Function info:\n\n{0}")]
+    CannotGetViewFunctionInfo(ViewFunctionInfoError<'static>),
+    #[error("Cannot resolve type {type_id}:\n\n{reason}")]
+    CannotResolveType { type_id: String, reason: String },
+    #[error("Cannot hash type {type_id}: {kind:?} is not supported")]
+    UnhandledType {
+        type_id: String,
+        kind: UnhandledKind,
+    },
+}
+
+/// Compute a structural hash of the arguments to some call.
+///
+/// Shape hashes are computed by walking each type through the [`TypeResolver`]. Type IDs, paths
+/// and docs are ignored, and so two items which have the same hash will be SCALE encoded in the
+/// same way, regardless of which metadata (or whether modern or legacy type information) was used
+/// to compute them. This makes them useful for checking whether some code written against one
+/// runtime is compatible with another.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::metadata::call_shape_hash;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let hash = call_shape_hash("Balances", "transfer", &metadata, &metadata.types).unwrap();
+///
+/// // Comparing this against a hash computed from some other runtime tells us
+/// // whether calls to Balances.transfer are encoded in the same way.
+/// # let _ = hash;
+/// ```
+pub fn call_shape_hash<Info, Resolver>(
+    pallet_name: &str,
+    call_name: &str,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<ShapeHash, ShapeHashError>
+where
+    Info: ExtrinsicTypeInfo,
+    Info::TypeId: Clone + Ord,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let call_info = info
+        .extrinsic_call_info_by_name(pallet_name, call_name)
+        .map_err(|e| ShapeHashError::CannotGetCallInfo(e.into_owned()))?;
+
+    let mut hasher = ShapeHasher::new(type_resolver);
+    let args = call_info.args.iter().map(|a| (&*a.name, a.id.clone()));
+    hasher.hash_named_types(args)
+}
+
+/// Compute a structural hash of the keys and value of some storage entry. See
+/// [`call_shape_hash`] for more details.
+pub fn storage_shape_hash<Info, Resolver>(
+    pallet_name: &str,
+    storage_entry: &str,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<ShapeHash, ShapeHashError>
+where
+    Info: StorageTypeInfo,
+    Info::TypeId: Clone + Ord,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let storage_info = info
+        .storage_info(pallet_name, storage_entry)
+        .map_err(|e| ShapeHashError::CannotGetStorageInfo(e.into_owned()))?;
+
+    let mut hasher = ShapeHasher::new(type_resolver);
+    let mut bytes = Vec::new();
+    for key in storage_info.keys.iter() {
+        bytes.push(storage_hasher_tag(key.hasher));
+        bytes.extend(hasher.type_hash(key.key_id.clone())?);
+    }
+    bytes.extend(hasher.type_hash(storage_info.value_id)?);
+    Ok(hash(&bytes))
+}
+
+/// Compute a structural hash of the type of some constant. See [`call_shape_hash`] for more
+/// details.
+pub fn constant_shape_hash<Info, Resolver>(
+    pallet_name: &str,
+    constant_name: &str,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<ShapeHash, ShapeHashError>
+where
+    Info: ConstantTypeInfo,
+    Info::TypeId: Clone + Ord,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let constant_info = info
+        .constant_info(pallet_name, constant_name)
+        .map_err(|e| ShapeHashError::CannotGetConstantInfo(e.into_owned()))?;
+
+    ShapeHasher::new(type_resolver).type_hash(constant_info.type_id)
+}
+
+/// Compute a structural hash of the inputs and output of some Runtime API method. See
+/// [`call_shape_hash`] for more details.
+pub fn runtime_api_shape_hash<Info, Resolver>(
+    trait_name: &str,
+    method_name: &str,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<ShapeHash, ShapeHashError>
+where
+    Info: RuntimeApiTypeInfo,
+    Info::TypeId: Clone + Ord,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let api_info = info
+        .runtime_api_info(trait_name, method_name)
+        .map_err(|e| ShapeHashError::CannotGetRuntimeApiInfo(e.into_owned()))?;
+
+    let mut hasher = ShapeHasher::new(type_resolver);
+    let inputs = api_info.inputs.iter().map(|i| (&*i.name, i.id.clone()));
+    let mut bytes = hasher.hash_named_types(inputs)?.to_vec();
+    bytes.extend(hasher.type_hash(api_info.output_id)?);
+    Ok(hash(&bytes))
+}
+
+/// Compute a structural hash of the query ID, inputs and output of some View Function. See
+/// [`call_shape_hash`] for more details.
+pub fn view_function_shape_hash<Info, Resolver>(
+    pallet_name: &str,
+    function_name: &str,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<ShapeHash, ShapeHashError>
+where
+    Info: ViewFunctionTypeInfo,
+    Info::TypeId: Clone + Ord,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let function_info = info
+        .view_function_info(pallet_name, function_name)
+        .map_err(|e| ShapeHashError::CannotGetViewFunctionInfo(e.into_owned()))?;
+
+    let mut hasher = ShapeHasher::new(type_resolver);
+    let inputs = function_info
+        .inputs
+        .iter()
+        .map(|i| (&*i.name, i.id.clone()));
+    let mut bytes = function_info.query_id.to_vec();
+    bytes.extend(hasher.hash_named_types(inputs)?);
+    bytes.extend(hasher.type_hash(function_info.output_id)?);
+    Ok(hash(&bytes))
+}
+
+/// Compute a structural hash of a single type. See [`call_shape_hash`] for more details.
+pub fn type_shape_hash<Resolver>(
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<ShapeHash, ShapeHashError>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: Ord,
+{
+    ShapeHasher::new(type_resolver).type_hash(type_id)
+}
+
+// Tags to distinguish each kind of type shape in the bytes that we hash.
+const COMPOSITE: u8 = 0;
+const VARIANT: u8 = 1;
+const SEQUENCE: u8 = 2;
+const ARRAY: u8 = 3;
+const TUPLE: u8 = 4;
+const PRIMITIVE: u8 = 5;
+const COMPACT: u8 = 6;
+const BIT_SEQUENCE: u8 = 7;
+const RECURSIVE: u8 = 8;
+
+struct ShapeHasher<'r, Resolver: TypeResolver> {
+    resolver: &'r Resolver,
+    // Hashes which don't depend on where we started hashing from.
+    cache: BTreeMap<Resolver::TypeId, ShapeHash>,
+    // The types we are currently in the process of hashing.
+    stack: Vec<Resolver::TypeId>,
+    // The lowest position in the stack that the current type refers back to.
+    lowest_ref: usize,
+}
+
+impl<'r, Resolver> ShapeHasher<'r, Resolver>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: Ord,
+{
+    fn new(resolver: &'r Resolver) -> Self {
+        ShapeHasher {
+            resolver,
+            cache: BTreeMap::new(),
+            stack: Vec::new(),
+            lowest_ref: usize::MAX,
+        }
+    }
+
+    fn hash_named_types<'a>(
+        &mut self,
+        types: impl Iterator<Item = (&'a str, Resolver::TypeId)>,
+    ) -> Result<ShapeHash, ShapeHashError> {
+        let mut bytes = Vec::new();
+        for (name, id) in types {
+            name.encode_to(&mut bytes);
+            bytes.extend(self.type_hash(id)?);
+        }
+        Ok(hash(&bytes))
+    }
+
+    fn hash_fields(
+        &mut self,
+        bytes: &mut Vec<u8>,
+        fields: Vec<FieldShape<Resolver::TypeId>>,
+    ) -> Result<(), ShapeHashError> {
+        (fields.len() as u32).encode_to(bytes);
+        for field in fields {
+            field.name.encode_to(bytes);
+            bytes.extend(self.type_hash(field.id)?);
+        }
+        Ok(())
+    }
+
+    fn type_hash(&mut self, id: Resolver::TypeId) -> Result<ShapeHash, ShapeHashError> {
+        if let Some(hash) = self.cache.get(&id) {
+            return Ok(*hash);
+        }
+
+        // Recursive types are hashed according to how far back up the stack they point,
+        // so that the hash doesn't depend on the type ID.
+        if let Some(pos) = self.stack.iter().position(|s| s == &id) {
+            self.lowest_ref = self.lowest_ref.min(pos);
+            let depth = (self.stack.len() - pos) as u32;
+            let mut bytes = alloc::vec![RECURSIVE];
+            depth.encode_to(&mut bytes);
+            return Ok(hash(&bytes));
+        }
+
+        let pos = self.stack.len();
+        let outer_lowest_ref = core::mem::replace(&mut self.lowest_ref, usize::MAX);
+        self.stack.push(id.clone());
+        let res = self.shape_hash(id.clone());
+        self.stack.pop();
+        let lowest_ref = core::mem::replace(&mut self.lowest_ref, outer_lowest_ref);
+
+        let hash = res?;
+        if lowest_ref >= pos {
+            // This type doesn't point to any of the types currently being hashed,
+            // so its hash will be the same wherever it's seen.
+            self.cache.insert(id, hash);
+        } else {
+            self.lowest_ref = self.lowest_ref.min(lowest_ref);
+        }
+        Ok(hash)
+    }
+
+    fn shape_hash(&mut self, id: Resolver::TypeId) -> Result<ShapeHash, ShapeHashError> {
+        let shape = resolve_type_shape(self.resolver, id.clone()).map_err(|e| {
+            ShapeHashError::CannotResolveType {
+                type_id: format!("{id:?}"),
+                reason: e.to_string(),
+            }
+        })?;
+
+        let mut bytes = Vec::new();
+        match shape {
+            TypeShape::Composite(fields) => {
+                bytes.push(COMPOSITE);
+                self.hash_fields(&mut bytes, fields)?;
+            }
+            TypeShape::Variant(variants) => {
+                bytes.push(VARIANT);
+                (variants.len() as u32).encode_to(&mut bytes);
+                for variant in variants {
+                    bytes.push(variant.index);
+                    variant.name.encode_to(&mut bytes);
+                    self.hash_fields(&mut bytes, variant.fields)?;
+                }
+            }
+            TypeShape::Sequence(id) => {
+                bytes.push(SEQUENCE);
+                bytes.extend(self.type_hash(id)?);
+            }
+            TypeShape::Array(id, len) => {
+                bytes.push(ARRAY);
+                (len as u64).encode_to(&mut bytes);
+                bytes.extend(self.type_hash(id)?);
+            }
+            TypeShape::Tuple(ids) => {
+                bytes.push(TUPLE);
+                (ids.len() as u32).encode_to(&mut bytes);
+                for id in ids {
+                    bytes.extend(self.type_hash(id)?);
+                }
+            }
+            TypeShape::Primitive(primitive) => {
+                bytes.push(PRIMITIVE);
+                bytes.push(primitive_tag(primitive));
+            }
+            TypeShape::Compact(id) => {
+                bytes.push(COMPACT);
+                bytes.extend(self.type_hash(id)?);
+            }
+            TypeShape::BitSequence(store, order) => {
+                bytes.push(BIT_SEQUENCE);
+                bytes.push(match store {
+                    BitsStoreFormat::U8 => 0,
+                    BitsStoreFormat::U16 => 1,
+                    BitsStoreFormat::U32 => 2,
+                    BitsStoreFormat::U64 => 3,
+                });
+                bytes.push(match order {
+                    BitsOrderFormat::Lsb0 => 0,
+                    BitsOrderFormat::Msb0 => 1,
+                });
+            }
+            TypeShape::Unhandled(kind) => {
+                return Err(ShapeHashError::UnhandledType {
+                    type_id: format!("{id:?}"),
+                    kind,
+                });
+            }
+        }
+        Ok(hash(&bytes))
+    }
+}
+
+fn primitive_tag(primitive: Primitive) -> u8 {
+    match primitive {
+        Primitive::Bool => 0,
+        Primitive::Char => 1,
+        Primitive::Str => 2,
+        Primitive::U8 => 3,
+        Primitive::U16 => 4,
+        Primitive::U32 => 5,
+        Primitive::U64 => 6,
+        Primitive::U128 => 7,
+        Primitive::U256 => 8,
+        Primitive::I8 => 9,
+        Primitive::I16 => 10,
+        Primitive::I32 => 11,
+        Primitive::I64 => 12,
+        Primitive::I128 => 13,
+        Primitive::I256 => 14,
+    }
+}
+
+fn storage_hasher_tag(hasher: StorageHasher) -> u8 {
+    match hasher {
+        StorageHasher::Blake2_128 => 0,
+        StorageHasher::Blake2_256 => 1,
+        StorageHasher::Blake2_128Concat => 2,
+        StorageHasher::Twox128 => 3,
+        StorageHasher::Twox256 => 4,
+        StorageHasher::Twox64Concat => 5,
+        StorageHasher::Identity => 6,
+    }
+}
+
+fn hash(bytes: &[u8]) -> ShapeHash {
+    sp_crypto_hashing::blake2_256(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frame_metadata::RuntimeMetadata;
+    use frame_metadata::v14::RuntimeMetadataV14;
+    use parity_scale_codec::Decode;
+    use scale_info_legacy::TypeRegistrySet;
+
+    fn modern_metadata() -> RuntimeMetadataV14 {
+        let bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*bytes).unwrap() else {
+            panic!("expected V14 metadata")
+        };
+        metadata
+    }
+
+    fn legacy_metadata() -> frame_metadata::v12::RuntimeMetadataV12 {
+        let bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) = RuntimeMetadata::decode(&mut &*bytes).unwrap() else {
+            panic!("expected V12 metadata")
+        };
+        metadata
+    }
+
+    fn legacy_types(
+        metadata: &frame_metadata::v12::RuntimeMetadataV12,
+    ) -> TypeRegistrySet<'static> {
+        let mut types = crate::legacy_types::polkadot::relay_chain()
+            .for_spec_version(30)
+            .to_owned();
+        types.prepend(crate::helpers::type_registry_from_metadata(metadata).unwrap());
+        types
+    }
+
+    #[test]
+    fn hashes_are_the_same_for_modern_and_legacy_types() {
+        let modern = modern_metadata();
+        let legacy = legacy_metadata();
+        let legacy_types = legacy_types(&legacy);
+
+        // Timestamp.set takes a Compact<u64> in both runtimes.
+        let modern_hash = call_shape_hash("Timestamp", "set", &modern, &modern.types).unwrap();
+        let legacy_hash = call_shape_hash("Timestamp", "set", &legacy, &legacy_types).unwrap();
+        assert_eq!(modern_hash, legacy_hash);
+
+        // Balances.ExistentialDeposit is a u128 in both runtimes.
+        let modern_hash =
+            constant_shape_hash("Balances", "ExistentialDeposit", &modern, &modern.types).unwrap();
+        let legacy_hash =
+            constant_shape_hash("Balances", "ExistentialDeposit", &legacy, &legacy_types).unwrap();
+        assert_eq!(modern_hash, legacy_hash);
+
+        // System.Account has the same shape in both runtimes, too.
+        let modern_hash = storage_shape_hash("System", "Account", &modern, &modern.types).unwrap();
+        let legacy_hash = storage_shape_hash("System", "Account", &legacy, &legacy_types).unwrap();
+        assert_eq!(modern_hash, legacy_hash);
+
+        // The MultiAddress passed to Balances.transfer has a u32 index in the old runtime and a
+        // Compact<()> index in the new one.
+        let modern_hash = call_shape_hash("Balances", "transfer", &modern, &modern.types).unwrap();
+        let legacy_hash = call_shape_hash("Balances", "transfer", &legacy, &legacy_types).unwrap();
+        assert_ne!(modern_hash, legacy_hash);
+    }
+
+    #[test]
+    fn hashes_ignore_type_ids() {
+        let metadata = modern_metadata();
+        let original_hash =
+            constant_shape_hash("System", "BlockWeights", &metadata, &metadata.types).unwrap();
+
+        // Append a copy of a type to the registry and point to that instead.
+        let mut modified = metadata.clone();
+        let pallet = modified
+            .pallets
+            .iter_mut()
+            .find(|p| p.name == "System")
+            .unwrap();
+        let constant = pallet
+            .constants
+            .iter_mut()
+            .find(|c| c.name == "BlockWeights")
+            .unwrap();
+        let new_id = modified.types.types.len() as u32;
+        let ty = modified.types.types[constant.ty.id as usize].ty.clone();
+        modified
+            .types
+            .types
+            .push(scale_info::PortableType::new(new_id, ty));
+        constant.ty = new_id.into();
+
+        let modified_hash =
+            constant_shape_hash("System", "BlockWeights", &modified, &modified.types).unwrap();
+        assert_eq!(original_hash, modified_hash);
+
+        // But the shape does matter.
+        let other_hash =
+            constant_shape_hash("System", "BlockLength", &metadata, &metadata.types).unwrap();
+        assert_ne!(original_hash, other_hash);
+    }
+
+    #[test]
+    fn recursive_types_can_be_hashed() {
+        let metadata = modern_metadata();
+
+        // Utility.batch takes a Vec<RuntimeCall>, which contains Utility.batch.
+        let hash1 = call_shape_hash("Utility", "batch", &metadata, &metadata.types).unwrap();
+        let hash2 = call_shape_hash("Utility", "batch", &metadata, &metadata.types).unwrap();
+        let other = call_shape_hash("Utility", "batch_all", &metadata, &metadata.types).unwrap();
+        assert_eq!(hash1, hash2);
+        assert_eq!(hash1, other, "batch and batch_all take the same arguments");
+    }
+}
@@ -0,0 +1,589 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::extrinsic_decoder::{Extrinsic, NamedArg};
+use crate::methods::extrinsic_encoder::{Era, Mortality};
+use crate::utils::{TypeShape, resolve_type_shape};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::ops::Range;
+use scale_decode::DecodeAsType;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode the standard transaction extensions of an extrinsic.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StandardExtensionsDecodeError {
+    #[error("Cannot decode the {extension_name} transaction extension: {reason}")]
+    CannotDecodeExtension {
+        extension_name: String,
+        reason: String,
+    },
+}
+
+/// A summary of the values given to the standard FRAME transaction extensions in an extrinsic.
+/// Each value is `None` if the relevant transaction extension was not present.
+#[derive(Clone, Debug)]
+pub struct StandardExtensions<TypeId> {
+    nonce: Option<u64>,
+    tip: Option<u128>,
+    era: Option<Era>,
+    asset_id: Option<NamedArg<'static, TypeId>>,
+}
+
+impl<TypeId> StandardExtensions<TypeId> {
+    /// The account nonce given in the `CheckNonce` extension.
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    /// The tip given in the `ChargeTransactionPayment` or `ChargeAssetTxPayment` extension.
+    pub fn tip(&self) -> Option<u128> {
+        self.tip
+    }
+
+    /// The era given in the `CheckMortality` extension.
+    pub fn era(&self) -> Option<Era> {
+        self.era
+    }
+
+    /// The range of blocks that the extrinsic is valid for, given the number of a block
+    /// during its lifetime (for instance the block that it was included in). This is derived
+    /// from the era given in the `CheckMortality` extension.
+    pub fn mortality(&self, block_number: u64) -> Option<Mortality> {
+        self.era.map(|era| era.mortality(block_number))
+    }
+
+    /// The asset ID given in the `ChargeAssetTxPayment` extension, if fees are being paid in
+    /// some asset other than the native currency. Since the shape of this differs between
+    /// chains, we hand back the range of bytes that it occupies and its type ID so that it
+    /// can be decoded.
+    pub fn asset_id(&self) -> Option<&NamedArg<'static, TypeId>> {
+        self.asset_id.as_ref()
+    }
+
+    /// Map the type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, f: F) -> StandardExtensions<NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        StandardExtensions {
+            nonce: self.nonce,
+            tip: self.tip,
+            era: self.era,
+            asset_id: self.asset_id.map(|a| a.map_type_id(f)),
+        }
+    }
+}
+
+/// Decode the values given to the standard FRAME transaction extensions (`CheckNonce`,
+/// `ChargeTransactionPayment`, `ChargeAssetTxPayment` and `CheckMortality`) in some V4 signed
+/// or V5 general extrinsic. Bare extrinsics have no transaction extensions, and so every value
+/// in the returned summary will be `None`.
+///
+/// `bytes` must be the bytes that the extrinsic was decoded from.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::extrinsics::{decode_extrinsic, decode_standard_extensions};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+/// let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+///
+/// for ext_hex in extrinsics_hex {
+///     let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+///     let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+///     let summary = decode_standard_extensions(&ext, &ext_bytes, &metadata.types).unwrap();
+///
+///     if let Some(mortality) = summary.mortality(10_000_000) {
+///         // These extrinsics were included in block 10,000,000:
+///         assert!(mortality.is_valid_at(10_000_000));
+///     }
+/// }
+/// ```
+pub fn decode_standard_extensions<TypeId, Resolver>(
+    extrinsic: &Extrinsic<'_, TypeId>,
+    bytes: &[u8],
+    type_resolver: &Resolver,
+) -> Result<StandardExtensions<TypeId>, StandardExtensionsDecodeError>
+where
+    TypeId: core::fmt::Debug + Clone,
+    Resolver: TypeResolver<TypeId = TypeId>,
+{
+    let mut summary = StandardExtensions {
+        nonce: None,
+        tip: None,
+        era: None,
+        asset_id: None,
+    };
+
+    let Some(extensions) = extrinsic.transaction_extension_payload() else {
+        return Ok(summary);
+    };
+
+    for ext in extensions.iter() {
+        let name = ext.name();
+        let range = ext.range();
+        let err = |reason: String| StandardExtensionsDecodeError::CannotDecodeExtension {
+            extension_name: name.to_string(),
+            reason,
+        };
+        let ext_bytes = bytes.get(range.clone()).ok_or_else(|| {
+            err("The bytes given do not line up with the extrinsic that was decoded".to_string())
+        })?;
+
+        match name {
+            "CheckNonce" => {
+                let nonce =
+                    decode_as_type(ext_bytes, ext.ty().clone(), type_resolver).map_err(err)?;
+                summary.nonce = Some(nonce);
+            }
+            "ChargeTransactionPayment" => {
+                let tip =
+                    decode_as_type(ext_bytes, ext.ty().clone(), type_resolver).map_err(err)?;
+                summary.tip = Some(tip);
+            }
+            "ChargeAssetTxPayment" => {
+                let (tip, asset_id) =
+                    decode_charge_asset_tx_payment(bytes, range, ext.ty().clone(), type_resolver)
+                        .map_err(err)?;
+                summary.tip = Some(tip);
+                summary.asset_id = asset_id;
+            }
+            "CheckMortality" => {
+                let era = Era::from_bytes(&mut &*ext_bytes)
+                    .ok_or_else(|| err("The bytes do not represent a valid era".to_string()))?;
+                summary.era = Some(era);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(summary)
+}
+
+// The tip, and the location and type of the asset ID if one is given.
+type TipAndAssetId<TypeId> = (u128, Option<NamedArg<'static, TypeId>>);
+
+// Decode the tip and locate the asset ID (if one is given) from the ChargeAssetTxPayment bytes
+// found at `range` in the extrinsic `bytes`. The asset ID range is relative to `bytes`.
+fn decode_charge_asset_tx_payment<Resolver>(
+    bytes: &[u8],
+    range: Range<usize>,
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<TipAndAssetId<Resolver::TypeId>, String>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone,
+{
+    let TypeShape::Composite(fields) = resolve_shape(type_id, type_resolver)? else {
+        return Err("Expected a composite type".to_string());
+    };
+
+    let cursor = &mut bytes
+        .get(range.clone())
+        .ok_or_else(|| "The range given is out of bounds".to_string())?;
+    let curr_idx = |cursor: &mut &[u8]| range.end - cursor.len();
+
+    let mut tip = None;
+    let mut asset_id = None;
+    for field in fields {
+        match field.name.as_deref() {
+            Some("tip") => {
+                tip = Some(
+                    u128::decode_as_type(cursor, field.id, type_resolver)
+                        .map_err(|e| e.to_string())?,
+                );
+            }
+            Some("asset_id") => {
+                // The asset ID is expected to be an Option<AssetId>.
+                let TypeShape::Variant(variants) = resolve_shape(field.id, type_resolver)? else {
+                    return Err("Expected asset_id to be an Option".to_string());
+                };
+                let (&index, rest) = cursor
+                    .split_first()
+                    .ok_or_else(|| "Not enough bytes to decode asset_id".to_string())?;
+                *cursor = rest;
+
+                let variant = variants
+                    .into_iter()
+                    .find(|v| v.index == index)
+                    .ok_or_else(|| format!("Variant index {index} not found in asset_id"))?;
+                for variant_field in variant.fields {
+                    let start_idx = curr_idx(cursor);
+                    skip_type(cursor, variant_field.id.clone(), type_resolver)?;
+                    let end_idx = curr_idx(cursor);
+
+                    if variant.name == "Some" {
+                        asset_id = Some(NamedArg {
+                            name: Cow::Borrowed("asset_id"),
+                            range: Range {
+                                start: start_idx as u32,
+                                end: end_idx as u32,
+                            },
+                            ty: variant_field.id,
+                        });
+                    }
+                }
+            }
+            _ => skip_type(cursor, field.id, type_resolver)?,
+        }
+    }
+
+    let tip = tip.ok_or_else(|| "No tip field found".to_string())?;
+    Ok((tip, asset_id))
+}
+
+fn decode_as_type<T, Resolver>(
+    mut bytes: &[u8],
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<T, String>
+where
+    T: DecodeAsType,
+    Resolver: TypeResolver,
+{
+    T::decode_as_type(&mut bytes, type_id, type_resolver).map_err(|e| e.to_string())
+}
+
+fn skip_type<Resolver>(
+    cursor: &mut &[u8],
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<(), String>
+where
+    Resolver: TypeResolver,
+{
+    scale_decode::visitor::decode_with_visitor(
+        cursor,
+        type_id,
+        type_resolver,
+        scale_decode::visitor::IgnoreVisitor::new(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn resolve_shape<Resolver>(
+    type_id: Resolver::TypeId,
+    type_resolver: &Resolver,
+) -> Result<TypeShape<Resolver::TypeId>, String>
+where
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone,
+{
+    resolve_type_shape(type_resolver, type_id.clone())
+        .map_err(|e| format!("Cannot resolve type {type_id:?}: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use crate::methods::extrinsic_encoder::test_extensions::{
+        ChargeAssetTxPaymentType, PrevalidateAttests,
+    };
+    use crate::methods::extrinsic_encoder::{
+        ChargeAssetTxPayment, ChargeTransactionPayment, CheckGenesis, CheckMortality,
+        CheckNonZeroSender, CheckNonce, CheckSpecVersion, CheckTxVersion, CheckWeight,
+        encode_v4_signed, encode_v5_general,
+    };
+    use alloc::vec::Vec;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::{Compact, Decode, Encode};
+    use scale_info::{PortableRegistry, Registry, TypeInfo, build, meta_type};
+    use scale_value::Value;
+
+    #[test]
+    fn decode_standard_extensions_from_signed_extrinsic() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let exts = (
+            CheckNonZeroSender,
+            CheckSpecVersion::new(9180),
+            CheckTxVersion::new(12),
+            CheckGenesis::new([1; 32]),
+            CheckMortality::mortal(64, 1000, [2; 32]),
+            CheckNonce::new(5),
+            CheckWeight,
+            ChargeTransactionPayment::new(1234),
+            PrevalidateAttests,
+        );
+        let call_data = Value::unnamed_composite([Value::from_bytes(b"hello")]);
+        let address = Value::unnamed_variant("Id", [Value::from_bytes([3u8; 32])]);
+        let signature = Value::unnamed_variant("Sr25519", [Value::from_bytes([4u8; 64])]);
+
+        let ext_bytes = encode_v4_signed(
+            "System",
+            "remark",
+            &call_data,
+            &exts,
+            &address,
+            &signature,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+        let summary = decode_standard_extensions(&ext, &ext_bytes, &metadata.types).unwrap();
+
+        assert_eq!(summary.nonce(), Some(5));
+        assert_eq!(summary.tip(), Some(1234));
+        assert_eq!(summary.era(), Some(Era::mortal(64, 1000)));
+        assert!(summary.asset_id().is_none());
+
+        let mortality = summary.mortality(1010).unwrap();
+        assert_eq!(mortality.birth(), 1000);
+        assert_eq!(mortality.death(), 1064);
+        assert!(!mortality.is_valid_at(999));
+        assert!(mortality.is_valid_at(1000));
+        assert!(mortality.is_valid_at(1063));
+        assert!(!mortality.is_valid_at(1064));
+    }
+
+    #[test]
+    fn decode_standard_extensions_from_general_extrinsic() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let exts = (
+            CheckNonZeroSender,
+            CheckSpecVersion::new(9180),
+            CheckTxVersion::new(12),
+            CheckGenesis::new([1; 32]),
+            CheckMortality::mortal(64, 1000, [2; 32]),
+            CheckNonce::new(5),
+            CheckWeight,
+            ChargeTransactionPayment::new(1234),
+            PrevalidateAttests,
+        );
+        let call_data = Value::unnamed_composite([Value::from_bytes(b"hello")]);
+
+        // V5 general extrinsics have no address or signature, so the extensions
+        // are found at different offsets than in V4 signed extrinsics.
+        let ext_bytes = encode_v5_general(
+            "System",
+            "remark",
+            &call_data,
+            0,
+            &exts,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+        assert!(!ext.is_signed());
+
+        let summary = decode_standard_extensions(&ext, &ext_bytes, &metadata.types).unwrap();
+        assert_eq!(summary.nonce(), Some(5));
+        assert_eq!(summary.tip(), Some(1234));
+        assert_eq!(summary.era(), Some(Era::mortal(64, 1000)));
+        assert!(summary.asset_id().is_none());
+    }
+
+    #[test]
+    fn decode_standard_extensions_from_real_extrinsics() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+
+        for ext_hex in extrinsics_hex {
+            let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+            let summary = decode_standard_extensions(&ext, &ext_bytes, &metadata.types).unwrap();
+
+            if ext.is_signed() {
+                assert!(summary.nonce().is_some());
+                assert!(summary.tip().is_some());
+                // These extrinsics were all included in block 10,000,000.
+                let mortality = summary.mortality(10_000_000).unwrap();
+                assert!(mortality.is_valid_at(10_000_000));
+            } else {
+                assert!(summary.nonce().is_none());
+                assert!(summary.tip().is_none());
+                assert!(summary.era().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn decode_charge_asset_tx_payment_asset_id() {
+        let mut registry = Registry::new();
+        let id = registry
+            .register_type(&meta_type::<ChargeAssetTxPaymentType>())
+            .id;
+        let types: PortableRegistry = registry.into();
+
+        // Some prefix bytes, to check that ranges are correct.
+        let mut bytes = vec![1, 2, 3];
+        Compact(100u128).encode_to(&mut bytes);
+        Some(7u32).encode_to(&mut bytes);
+
+        let (tip, asset_id) =
+            decode_charge_asset_tx_payment(&bytes, 3..bytes.len(), id, &types).unwrap();
+        let asset_id = asset_id.unwrap();
+        assert_eq!(tip, 100);
+        assert_eq!(asset_id.range(), bytes.len() - 4..bytes.len());
+        assert_eq!(u32::decode(&mut &bytes[asset_id.range()]).unwrap(), 7);
+
+        let mut bytes = Compact(100u128).encode();
+        None::<u32>.encode_to(&mut bytes);
+
+        let (tip, asset_id) =
+            decode_charge_asset_tx_payment(&bytes, 0..bytes.len(), id, &types).unwrap();
+        assert_eq!(tip, 100);
+        assert!(asset_id.is_none());
+    }
+
+    // A pallet call enum with a single call taking no arguments.
+    struct FooCall;
+    impl TypeInfo for FooCall {
+        type Identity = Self;
+        fn type_info() -> scale_info::Type {
+            scale_info::Type::builder()
+                .path(scale_info::Path::new("Call", "test"))
+                .variant(build::Variants::new().variant("bar", |v| v.index(0)))
+        }
+    }
+
+    // Metadata for a chain whose only transaction extensions are CheckNonce
+    // and ChargeAssetTxPayment.
+    fn metadata_with_charge_asset_tx_payment() -> frame_metadata::v16::RuntimeMetadataV16 {
+        use frame_metadata::v16::*;
+
+        let pallet = PalletMetadata {
+            name: "Foo",
+            storage: None,
+            calls: Some(PalletCallMetadata {
+                ty: meta_type::<FooCall>(),
+                deprecation_info: EnumDeprecationInfo::nothing_deprecated(),
+            }),
+            event: None,
+            constants: vec![],
+            error: None,
+            associated_types: vec![],
+            view_functions: vec![],
+            index: 0,
+            docs: vec![],
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+
+        RuntimeMetadataV16::new(
+            vec![pallet],
+            ExtrinsicMetadata {
+                versions: vec![4],
+                address_ty: meta_type::<[u8; 32]>(),
+                call_ty: meta_type::<u32>(),
+                signature_ty: meta_type::<[u8; 64]>(),
+                transaction_extensions_by_version: [(0, vec![Compact(0), Compact(1)])]
+                    .into_iter()
+                    .collect(),
+                transaction_extensions: vec![
+                    TransactionExtensionMetadata {
+                        identifier: "CheckNonce",
+                        ty: meta_type::<Compact<u32>>(),
+                        implicit: meta_type::<()>(),
+                    },
+                    TransactionExtensionMetadata {
+                        identifier: "ChargeAssetTxPayment",
+                        ty: meta_type::<ChargeAssetTxPaymentType>(),
+                        implicit: meta_type::<()>(),
+                    },
+                ],
+            },
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<u32>(),
+                event_enum_ty: meta_type::<u32>(),
+                error_enum_ty: meta_type::<u32>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        )
+    }
+
+    #[test]
+    fn decode_standard_extensions_with_charge_asset_tx_payment() {
+        let metadata = metadata_with_charge_asset_tx_payment();
+        let call_data = Value::unnamed_composite([]);
+        let address = Value::from_bytes([3u8; 32]);
+        let signature = Value::from_bytes([4u8; 64]);
+
+        let exts = (
+            CheckNonce::new(5),
+            ChargeAssetTxPayment::new(100).with_asset_id(7u32),
+        );
+        let ext_bytes = encode_v4_signed(
+            "Foo",
+            "bar",
+            &call_data,
+            &exts,
+            &address,
+            &signature,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+        let summary = decode_standard_extensions(&ext, &ext_bytes, &metadata.types).unwrap();
+
+        assert_eq!(summary.nonce(), Some(5));
+        assert_eq!(summary.tip(), Some(100));
+        let asset_id = summary.asset_id().expect("asset ID should be given");
+        assert_eq!(u32::decode(&mut &ext_bytes[asset_id.range()]).unwrap(), 7);
+
+        let exts = (CheckNonce::new(5), ChargeAssetTxPayment::new(100));
+        let ext_bytes = encode_v4_signed(
+            "Foo",
+            "bar",
+            &call_data,
+            &exts,
+            &address,
+            &signature,
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+        let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+        let summary = decode_standard_extensions(&ext, &ext_bytes, &metadata.types).unwrap();
+
+        assert_eq!(summary.tip(), Some(100));
+        assert!(summary.asset_id().is_none());
+    }
+}
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use super::storage_encoder::encode_storage_key_prefix;
+use super::storage_key_lookup::{KeyCandidates, StorageKeyLookup};
 use super::storage_type_info::{StorageHasher, StorageInfo, StorageTypeInfo};
 use crate::methods::storage_type_info::StorageInfoError;
 use crate::utils::{
@@ -45,6 +46,10 @@ pub enum StorageKeyDecodeError<TypeId> {
         reason: DecodeErrorTrace,
         decoded_so_far: StorageKey<TypeId>,
     },
+    #[error(
+        "The hash in storage key part {index} does not match the hash of the key value that follows it."
+    )]
+    HashMismatch { index: usize },
 }
 
 impl<TypeId> StorageKeyDecodeError<TypeId> {
@@ -68,6 +73,9 @@ impl<TypeId> StorageKeyDecodeError<TypeId> {
                 reason,
                 decoded_so_far: decoded_so_far.map_type_id(f),
             },
+            StorageKeyDecodeError::HashMismatch { index } => {
+                StorageKeyDecodeError::HashMismatch { index }
+            }
         }
     }
 }
@@ -92,6 +100,8 @@ pub enum StorageKeyValueDecodeError {
         "An invalid byte range was asked for from the key bytes, implying that the key bytes are not those that the information is about"
     )]
     InvalidRange,
+    #[error("Cannot return the decoded storage key values: {0}")]
+    CannotReturnValues(scale_decode::Error),
 }
 
 /// An error returned trying to decode storage bytes.
@@ -167,6 +177,9 @@ impl<TypeId: core::fmt::Debug> core::fmt::Display for StorageKey<TypeId> {
                 writeln!(f, "Value type: {:?}", v.ty)?;
                 writeln!(f, "Value range: {}..{}", v.range.start, v.range.end)?;
             }
+            if let Some(v) = &key.looked_up_value {
+                writeln!(f, "Looked up value type: {:?}", v.ty)?;
+            }
 
             writeln!(f)?;
         }
@@ -209,6 +222,7 @@ pub struct StorageKeyPart<TypeId> {
     hash_range: Range<u32>,
     value: Option<StorageKeyPartValue<TypeId>>,
     hasher: StorageHasher,
+    looked_up_value: Option<StorageKeyPartLookedUpValue<TypeId>>,
 }
 
 impl<TypeId> StorageKeyPart<TypeId> {
@@ -231,15 +245,24 @@ impl<TypeId> StorageKeyPart<TypeId> {
         self.value.as_ref()
     }
 
+    /// If this part uses an opaque hasher (ie one which does not append the key value to the
+    /// hash), and the key was decoded using a [`StorageKeyLookup`] (see
+    /// [`DecodeStorageKeyOptions::lookup()`]) containing a key value which hashes to this part,
+    /// return that value.
+    pub fn looked_up_value(&self) -> Option<&StorageKeyPartLookedUpValue<TypeId>> {
+        self.looked_up_value.as_ref()
+    }
+
     /// Map the storage part type ID to something else.
-    pub fn map_type_id<NewTypeId, F>(self, f: F) -> StorageKeyPart<NewTypeId>
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> StorageKeyPart<NewTypeId>
     where
         F: FnMut(TypeId) -> NewTypeId,
     {
         StorageKeyPart {
             hash_range: self.hash_range,
-            value: self.value.map(|v| v.map_type_id(f)),
+            value: self.value.map(|v| v.map_type_id(&mut f)),
             hasher: self.hasher,
+            looked_up_value: self.looked_up_value.map(|v| v.map_type_id(f)),
         }
     }
 }
@@ -277,6 +300,36 @@ impl<TypeId> StorageKeyPartValue<TypeId> {
     }
 }
 
+/// A key value, found in a [`StorageKeyLookup`], whose hash matches a storage key part.
+#[derive(Clone, Debug)]
+pub struct StorageKeyPartLookedUpValue<TypeId> {
+    bytes: Vec<u8>,
+    ty: TypeId,
+}
+
+impl<TypeId> StorageKeyPartLookedUpValue<TypeId> {
+    /// The SCALE encoded bytes of the key value.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The type ID for this value.
+    pub fn ty(&self) -> &TypeId {
+        &self.ty
+    }
+
+    /// Map the type ID to something else.
+    pub fn map_type_id<NewTypeId, F>(self, mut f: F) -> StorageKeyPartLookedUpValue<NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        StorageKeyPartLookedUpValue {
+            bytes: self.bytes,
+            ty: f(self.ty),
+        }
+    }
+}
+
 /// Decode a storage key, returning information about it.
 ///
 /// This information can be used to identify and, where possible, decode the parts of the storage key.
@@ -336,6 +389,113 @@ pub fn decode_storage_key<Info, Resolver>(
     info: &Info,
     type_resolver: &Resolver,
 ) -> Result<StorageKey<Info::TypeId>, StorageKeyDecodeError<Info::TypeId>>
+where
+    Info: StorageTypeInfo,
+    Info::TypeId: Clone + core::fmt::Debug,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_storage_key_inner(
+        pallet_name,
+        storage_entry,
+        cursor,
+        info,
+        type_resolver,
+        DecodeStorageKeyOptions::new(),
+    )
+}
+
+/// Options to configure how a storage key is decoded, for use with
+/// [`decode_storage_key_with_options`] and [`decode_storage_key_with_info_and_options`].
+///
+/// By default, these decode storage keys in the same way as [`decode_storage_key`].
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::storage::{DecodeStorageKeyOptions, StorageKeyLookup};
+///
+/// let mut lookup = StorageKeyLookup::new();
+/// lookup.register_candidate("Pallet", "Entry", &[1u8; 32]);
+///
+/// let options = DecodeStorageKeyOptions::new()
+///     .verify_hashes(true)
+///     .lookup(&lookup);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecodeStorageKeyOptions<'lookup> {
+    verify_hashes: bool,
+    lookup: Option<&'lookup StorageKeyLookup>,
+}
+
+impl<'lookup> DecodeStorageKeyOptions<'lookup> {
+    /// Create some new options, which decode storage keys in the same way as
+    /// [`decode_storage_key`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check that the hash in each [`StorageHasher::Blake2_128Concat`] and
+    /// [`StorageHasher::Twox64Concat`] part of the key matches the key value that follows it.
+    ///
+    /// If any of these hashes are wrong, for instance because the key was corrupted or spoofed,
+    /// then [`StorageKeyDecodeError::HashMismatch`] is returned. Opaque hashers like
+    /// [`StorageHasher::Blake2_128`] do not include the key value, and so cannot be checked.
+    pub fn verify_hashes(mut self, verify_hashes: bool) -> Self {
+        self.verify_hashes = verify_hashes;
+        self
+    }
+
+    /// Use the given [`StorageKeyLookup`] to find the key values for any parts of the key which
+    /// use an opaque hasher.
+    ///
+    /// Any [`StorageHasher::Blake2_128`], [`StorageHasher::Blake2_256`],
+    /// [`StorageHasher::Twox128`] or [`StorageHasher::Twox256`] parts whose hash matches a
+    /// candidate value registered for the storage entry will have a
+    /// [`StorageKeyPart::looked_up_value()`]. The hashes are computed in the same way as
+    /// [`crate::storage::encode_storage_key`] computes them.
+    pub fn lookup(mut self, lookup: &'lookup StorageKeyLookup) -> Self {
+        self.lookup = Some(lookup);
+        self
+    }
+}
+
+/// Decode a storage key, returning information about it, using the given
+/// [`DecodeStorageKeyOptions`] to verify the hashes in the key or look up the values behind
+/// opaque hashes.
+///
+/// This is like [`decode_storage_key`], which is equivalent to calling this with
+/// [`DecodeStorageKeyOptions::new()`].
+pub fn decode_storage_key_with_options<Info, Resolver>(
+    pallet_name: &str,
+    storage_entry: &str,
+    cursor: &mut &[u8],
+    info: &Info,
+    type_resolver: &Resolver,
+    options: DecodeStorageKeyOptions<'_>,
+) -> Result<StorageKey<Info::TypeId>, StorageKeyDecodeError<Info::TypeId>>
+where
+    Info: StorageTypeInfo,
+    Info::TypeId: Clone + core::fmt::Debug,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    decode_storage_key_inner(
+        pallet_name,
+        storage_entry,
+        cursor,
+        info,
+        type_resolver,
+        options,
+    )
+}
+
+fn decode_storage_key_inner<Info, Resolver>(
+    pallet_name: &str,
+    storage_entry: &str,
+    cursor: &mut &[u8],
+    info: &Info,
+    type_resolver: &Resolver,
+    options: DecodeStorageKeyOptions<'_>,
+) -> Result<StorageKey<Info::TypeId>, StorageKeyDecodeError<Info::TypeId>>
 where
     Info: StorageTypeInfo,
     Info::TypeId: Clone + core::fmt::Debug,
@@ -347,11 +507,26 @@ where
 
     // Sanity check that the storage key prefix is what we expect:
     let expected_prefix = encode_storage_key_prefix(pallet_name, storage_entry);
-    if cursor[..32] != expected_prefix {
+    let Some(prefix) = cursor.get(..32) else {
+        return Err(StorageKeyDecodeError::NotEnoughBytes {
+            needed: 32,
+            have: cursor.len(),
+        });
+    };
+    if prefix != expected_prefix {
         return Err(StorageKeyDecodeError::PrefixMismatch);
     }
 
-    decode_storage_key_with_info(cursor, &storage_info, type_resolver)
+    let candidates = options
+        .lookup
+        .and_then(|l| l.candidates(pallet_name, storage_entry));
+    decode_storage_key_with_info_inner(
+        cursor,
+        &storage_info,
+        type_resolver,
+        options.verify_hashes,
+        candidates,
+    )
 }
 
 /// Decode a storage key, returning information about it.
@@ -372,6 +547,63 @@ pub fn decode_storage_key_with_info<Resolver>(
     StorageKey<<Resolver as TypeResolver>::TypeId>,
     StorageKeyDecodeError<<Resolver as TypeResolver>::TypeId>,
 >
+where
+    Resolver: TypeResolver,
+    <Resolver as TypeResolver>::TypeId: Clone + core::fmt::Debug,
+{
+    decode_storage_key_with_info_inner(cursor, storage_info, type_resolver, false, None)
+}
+
+/// Decode a storage key given the storage info, returning information about it, using the given
+/// [`DecodeStorageKeyOptions`] to verify the hashes in the key or look up the values behind
+/// opaque hashes.
+///
+/// This is like [`decode_storage_key_with_info`], which is equivalent to calling this with
+/// [`DecodeStorageKeyOptions::new()`]. The pallet and storage entry names are used to find the
+/// candidate values registered for the storage entry in any [`DecodeStorageKeyOptions::lookup()`].
+///
+/// # Warning
+///
+/// Like [`decode_storage_key_with_info`], this does not check that the bytes start with the
+/// expected prefix; ensuring that the storage information lines up with the bytes is the caller's
+/// responsibility.
+pub fn decode_storage_key_with_info_and_options<Resolver>(
+    pallet_name: &str,
+    storage_entry: &str,
+    cursor: &mut &[u8],
+    storage_info: &StorageInfo<<Resolver as TypeResolver>::TypeId>,
+    type_resolver: &Resolver,
+    options: DecodeStorageKeyOptions<'_>,
+) -> Result<
+    StorageKey<<Resolver as TypeResolver>::TypeId>,
+    StorageKeyDecodeError<<Resolver as TypeResolver>::TypeId>,
+>
+where
+    Resolver: TypeResolver,
+    <Resolver as TypeResolver>::TypeId: Clone + core::fmt::Debug,
+{
+    let candidates = options
+        .lookup
+        .and_then(|l| l.candidates(pallet_name, storage_entry));
+    decode_storage_key_with_info_inner(
+        cursor,
+        storage_info,
+        type_resolver,
+        options.verify_hashes,
+        candidates,
+    )
+}
+
+fn decode_storage_key_with_info_inner<Resolver>(
+    cursor: &mut &[u8],
+    storage_info: &StorageInfo<<Resolver as TypeResolver>::TypeId>,
+    type_resolver: &Resolver,
+    verify_hashes: bool,
+    candidates: Option<&KeyCandidates>,
+) -> Result<
+    StorageKey<<Resolver as TypeResolver>::TypeId>,
+    StorageKeyDecodeError<<Resolver as TypeResolver>::TypeId>,
+>
 where
     Resolver: TypeResolver,
     <Resolver as TypeResolver>::TypeId: Clone + core::fmt::Debug,
@@ -390,7 +622,7 @@ where
         };
 
         let start_idx = curr_idx(cursor);
-        let part = match &hasher {
+        let mut part = match &hasher {
             StorageHasher::Blake2_128 | StorageHasher::Twox128 => {
                 strip_bytes(cursor, 16)?;
                 StorageKeyPart {
@@ -400,6 +632,7 @@ where
                     },
                     value: None,
                     hasher,
+                    looked_up_value: None,
                 }
             }
             StorageHasher::Blake2_256 | StorageHasher::Twox256 => {
@@ -411,6 +644,7 @@ where
                     },
                     value: None,
                     hasher,
+                    looked_up_value: None,
                 }
             }
             StorageHasher::Blake2_128Concat => {
@@ -442,6 +676,7 @@ where
                         ty: key.key_id.clone(),
                     }),
                     hasher,
+                    looked_up_value: None,
                 }
             }
             StorageHasher::Twox64Concat => {
@@ -473,6 +708,7 @@ where
                         ty: key.key_id.clone(),
                     }),
                     hasher,
+                    looked_up_value: None,
                 }
             }
             StorageHasher::Identity => {
@@ -502,9 +738,30 @@ where
                         ty: key.key_id.clone(),
                     }),
                     hasher,
+                    looked_up_value: None,
                 }
             }
         };
+
+        // Check that the hash was derived from the key value that follows it:
+        if verify_hashes && let Some(value) = &part.value {
+            let hash_len = hasher.len_excluding_key();
+            let expected_hash = hasher.hash_excluding_key(&bytes[value.range()]);
+            if expected_hash[..hash_len] != bytes[part.hash_range()] {
+                return Err(StorageKeyDecodeError::HashMismatch { index: parts.len() });
+            }
+        }
+
+        // Find the key value that an opaque hash was derived from, if we can:
+        if part.value.is_none()
+            && let Some(value) = candidates.and_then(|c| c.find(hasher, &bytes[part.hash_range()]))
+        {
+            part.looked_up_value = Some(StorageKeyPartLookedUpValue {
+                bytes: value.to_vec(),
+                ty: key.key_id.clone(),
+            });
+        }
+
         parts.push(part)
     }
 
@@ -605,7 +862,7 @@ where
         (0..needed_values.unwrap_or(usize::MAX)).zip(decoded_key.parts().filter_map(|p| p.value()));
 
     for (idx, value_info) in value_info_iter {
-        // This will panic if the key bytes provided don't line up with the information.
+        // This will error if the key bytes provided don't line up with the information.
         let value_bytes = &mut key_bytes
             .get(value_info.range())
             .ok_or(StorageKeyValueDecodeError::InvalidRange)?;
@@ -625,7 +882,9 @@ where
         }
     }
 
-    Ok(decode_target.decoded_target())
+    decode_target
+        .decoded_target()
+        .map_err(StorageKeyValueDecodeError::CannotReturnValues)
 }
 
 /// Decode a storage value.
@@ -790,4 +1049,108 @@ mod test {
         assert_eq!(stripped, &[0, 1, 2, 3]);
         assert_eq!(cursor, &[4, 5, 6, 7, 8]);
     }
+
+    fn storage_keys(path: &str) -> Vec<Vec<u8>> {
+        let storage_keyval_bytes = std::fs::read(path).unwrap();
+        let storage_keyval_hex: Vec<(String, String)> =
+            serde_json::from_slice(&storage_keyval_bytes).unwrap();
+        storage_keyval_hex
+            .into_iter()
+            .map(|(key, _)| hex::decode(key.trim_start_matches("0x")).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_storage_key_verified() {
+        use frame_metadata::RuntimeMetadata;
+        use parity_scale_codec::Decode;
+
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let decode = |key_bytes: &[u8]| {
+            decode_storage_key_with_options(
+                "System",
+                "Account",
+                &mut &*key_bytes,
+                &metadata,
+                &metadata.types,
+                DecodeStorageKeyOptions::new().verify_hashes(true),
+            )
+        };
+
+        for key_bytes in storage_keys("artifacts/storage_10000000_9180_system_account.json") {
+            decode(&key_bytes).unwrap();
+
+            // Corrupting the hash or the account ID that it was derived from is spotted:
+            for idx in [32, 47, 48, key_bytes.len() - 1] {
+                let mut corrupted = key_bytes.clone();
+                corrupted[idx] ^= 1;
+                assert!(matches!(
+                    decode(&corrupted),
+                    Err(StorageKeyDecodeError::HashMismatch { index: 0 })
+                ));
+
+                // But the unverified decoding doesn't care:
+                decode_storage_key(
+                    "System",
+                    "Account",
+                    &mut &*corrupted,
+                    &metadata,
+                    &metadata.types,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_legacy_storage_key_verified() {
+        use frame_metadata::RuntimeMetadata;
+        use parity_scale_codec::Decode;
+
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        types.prepend(crate::helpers::type_registry_from_metadata(&metadata).unwrap());
+
+        let keys = storage_keys("artifacts/storage_5000000_30_staking_validators.json");
+        assert!(!keys.is_empty());
+        let options = DecodeStorageKeyOptions::new().verify_hashes(true);
+        for key_bytes in keys {
+            decode_storage_key_with_options(
+                "Staking",
+                "Validators",
+                &mut &*key_bytes,
+                &metadata,
+                &types,
+                options,
+            )
+            .unwrap();
+
+            let mut corrupted = key_bytes.clone();
+            *corrupted.last_mut().unwrap() ^= 1;
+            assert!(matches!(
+                decode_storage_key_with_options(
+                    "Staking",
+                    "Validators",
+                    &mut &*corrupted,
+                    &metadata,
+                    &types,
+                    options,
+                ),
+                Err(StorageKeyDecodeError::HashMismatch { index: 0 })
+            ));
+        }
+    }
 }
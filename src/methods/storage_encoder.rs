@@ -13,7 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::storage_type_info::{StorageHasher, StorageInfo, StorageTypeInfo};
+use super::deprecation::{DeprecationPolicy, DeprecationWarning, check_deprecation};
+use super::storage_type_info::{StorageInfo, StorageTypeInfo};
 use crate::methods::storage_type_info::StorageInfoError;
 use crate::utils::{EncodableValues, IntoEncodableValues};
 use alloc::vec::Vec;
@@ -33,6 +34,8 @@ pub enum StorageKeyEncodeError {
         /// The maximum number of keys that were expected.
         max_keys_expected: usize,
     },
+    #[error("Cannot encode storage key: {0}")]
+    Deprecated(DeprecationWarning),
 }
 
 /// Encode a storage key prefix from a pallet name and storage entry name. This prefix
@@ -163,6 +166,51 @@ where
     )
 }
 
+/// Encode a complete storage key for a given pallet and storage entry, applying the given
+/// [`DeprecationPolicy`] if the storage entry is marked as deprecated in the metadata.
+///
+/// This is like [`encode_storage_key`], but also hands back a [`DeprecationWarning`] if the
+/// entry is deprecated and the policy is [`DeprecationPolicy::Warn`], and returns
+/// [`StorageKeyEncodeError::Deprecated`] if the entry is deprecated and the policy is
+/// [`DeprecationPolicy::Deny`].
+pub fn encode_storage_key_checked<Info, Resolver, Keys>(
+    pallet_name: &str,
+    storage_entry: &str,
+    keys: Keys,
+    info: &Info,
+    type_resolver: &Resolver,
+    policy: DeprecationPolicy,
+) -> Result<(Vec<u8>, Option<DeprecationWarning>), StorageKeyEncodeError>
+where
+    Keys: IntoEncodableValues,
+    Info: StorageTypeInfo,
+    Info::TypeId: Clone + core::fmt::Debug,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let storage_info = info
+        .storage_info(pallet_name, storage_entry)
+        .map_err(|e| StorageKeyEncodeError::CannotGetInfo(e.into_owned()))?;
+
+    let warning = check_deprecation(
+        policy,
+        pallet_name,
+        storage_entry,
+        &storage_info.deprecation,
+    )
+    .map_err(StorageKeyEncodeError::Deprecated)?;
+
+    let mut out = Vec::with_capacity(32);
+    encode_storage_key_with_info_to(
+        pallet_name,
+        storage_entry,
+        keys,
+        &storage_info,
+        type_resolver,
+        &mut out,
+    )?;
+    Ok((out, warning))
+}
+
 /// Encode a complete storage key for a given pallet and storage entry and a set of keys that
 /// are each able to be encoded via [`scale_encode::EncodeAsType`].
 ///
@@ -238,37 +286,7 @@ where
         keys.encode_next_value_to(key_info.key_id.clone(), type_resolver, &mut temp)
             .map_err(StorageKeyEncodeError::EncodeError)?;
 
-        match key_info.hasher {
-            StorageHasher::Blake2_128 => {
-                let hash = sp_crypto_hashing::blake2_128(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Blake2_256 => {
-                let hash = sp_crypto_hashing::blake2_256(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Blake2_128Concat => {
-                let hash = sp_crypto_hashing::blake2_128(&temp);
-                out.extend_from_slice(&hash);
-                out.extend_from_slice(&temp);
-            }
-            StorageHasher::Twox128 => {
-                let hash = sp_crypto_hashing::twox_128(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Twox256 => {
-                let hash = sp_crypto_hashing::twox_256(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Twox64Concat => {
-                let hash = sp_crypto_hashing::twox_64(&temp);
-                out.extend_from_slice(&hash);
-                out.extend_from_slice(&temp);
-            }
-            StorageHasher::Identity => {
-                out.extend_from_slice(&temp);
-            }
-        }
+        key_info.hasher.hash_to(&temp, out);
 
         // Clear our temp space ready for the next key.
         temp.clear();
@@ -432,37 +450,7 @@ where
         keys.encode_next_value_to(key_info.key_id.clone(), type_resolver, &mut temp)
             .map_err(StorageKeyEncodeError::EncodeError)?;
 
-        match key_info.hasher {
-            StorageHasher::Blake2_128 => {
-                let hash = sp_crypto_hashing::blake2_128(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Blake2_256 => {
-                let hash = sp_crypto_hashing::blake2_256(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Blake2_128Concat => {
-                let hash = sp_crypto_hashing::blake2_128(&temp);
-                out.extend_from_slice(&hash);
-                out.extend_from_slice(&temp);
-            }
-            StorageHasher::Twox128 => {
-                let hash = sp_crypto_hashing::twox_128(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Twox256 => {
-                let hash = sp_crypto_hashing::twox_256(&temp);
-                out.extend_from_slice(&hash);
-            }
-            StorageHasher::Twox64Concat => {
-                let hash = sp_crypto_hashing::twox_64(&temp);
-                out.extend_from_slice(&hash);
-                out.extend_from_slice(&temp);
-            }
-            StorageHasher::Identity => {
-                out.extend_from_slice(&temp);
-            }
-        }
+        key_info.hasher.hash_to(&temp, out);
 
         // Clear our temp space ready for the next key.
         temp.clear();
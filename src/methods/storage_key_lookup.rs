@@ -0,0 +1,411 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::storage_type_info::StorageHasher;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use parity_scale_codec::Encode;
+
+/// The hashers which do not append the key value to the hash, and so need looking up.
+const OPAQUE_HASHERS: [StorageHasher; 4] = [
+    StorageHasher::Blake2_128,
+    StorageHasher::Blake2_256,
+    StorageHasher::Twox128,
+    StorageHasher::Twox256,
+];
+
+/// A reverse lookup table from the opaque hashes in storage keys back to the key values that
+/// they were produced from.
+///
+/// Storage keys hashed with [`StorageHasher::Blake2_128`], [`StorageHasher::Blake2_256`],
+/// [`StorageHasher::Twox128`] or [`StorageHasher::Twox256`] do not contain the key value, and so
+/// it cannot be decoded from them. If you know the values that such keys may have (for example
+/// all known account IDs), register them here and pass this to
+/// [`crate::storage::DecodeStorageKeyOptions::lookup()`] when decoding storage keys. Any storage
+/// key parts whose hash matches
+/// one of these values will then have a [`crate::storage::StorageKeyPart::looked_up_value()`].
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::storage::StorageKeyLookup;
+///
+/// let mut lookup = StorageKeyLookup::new();
+///
+/// // Register some values that the keys for a storage entry may have:
+/// for account_id in [[1u8; 32], [2u8; 32]] {
+///     lookup.register_candidate("Pallet", "Entry", &account_id);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StorageKeyLookup {
+    entries: HashMap<String, HashMap<String, KeyCandidates>>,
+}
+
+impl StorageKeyLookup {
+    /// Create a new, empty lookup table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a candidate key value for the given storage entry. The value will be SCALE
+    /// encoded and then hashed with each opaque hasher.
+    ///
+    /// Candidates are matched against each key part individually, so the value should have the
+    /// shape of a single key part (for instance an account ID) and not of the whole storage
+    /// entry key. For a storage entry with multiple keys, like a double map, registering a tuple
+    /// of all of the key values will never match anything; instead, register candidates for each
+    /// key part separately. Candidates for any of the key parts can be registered against the
+    /// same storage entry.
+    pub fn register_candidate<K: Encode>(
+        &mut self,
+        pallet_name: &str,
+        storage_entry: &str,
+        key: &K,
+    ) {
+        self.register_encoded_candidate(pallet_name, storage_entry, key.encode());
+    }
+
+    /// Register an already SCALE encoded candidate key value for the given storage entry.
+    pub fn register_encoded_candidate(
+        &mut self,
+        pallet_name: &str,
+        storage_entry: &str,
+        encoded_key: Vec<u8>,
+    ) {
+        self.entries
+            .entry(pallet_name.to_string())
+            .or_default()
+            .entry(storage_entry.to_string())
+            .or_default()
+            .insert(encoded_key);
+    }
+
+    /// Return the encoded candidate key value for the given storage entry whose hash, using the
+    /// given hasher, is the one provided. Only opaque hashers are supported; for others, the key
+    /// value is already a part of the storage key.
+    pub fn find(
+        &self,
+        pallet_name: &str,
+        storage_entry: &str,
+        hasher: StorageHasher,
+        hash: &[u8],
+    ) -> Option<&[u8]> {
+        self.candidates(pallet_name, storage_entry)?
+            .find(hasher, hash)
+    }
+
+    /// The number of candidate key values registered for the given storage entry.
+    pub fn num_candidates(&self, pallet_name: &str, storage_entry: &str) -> usize {
+        self.candidates(pallet_name, storage_entry)
+            .map_or(0, |c| c.values.len())
+    }
+
+    pub(crate) fn candidates(
+        &self,
+        pallet_name: &str,
+        storage_entry: &str,
+    ) -> Option<&KeyCandidates> {
+        self.entries.get(pallet_name)?.get(storage_entry)
+    }
+}
+
+/// The candidate key values for a single storage entry, indexed by each of their opaque hashes.
+/// The hashes are stored in fixed size arrays to avoid an allocation per hash.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KeyCandidates {
+    values: Vec<Vec<u8>>,
+    by_hash_128: HashMap<(StorageHasher, [u8; 16]), usize>,
+    by_hash_256: HashMap<(StorageHasher, [u8; 32]), usize>,
+}
+
+impl KeyCandidates {
+    fn insert(&mut self, encoded_key: Vec<u8>) {
+        let idx = self.values.len();
+        let mut is_new = false;
+        for hasher in OPAQUE_HASHERS {
+            let hash = hasher.hash_excluding_key(&encoded_key);
+            let inserted = if hasher.len_excluding_key() == 16 {
+                let hash: [u8; 16] = hash[..16].try_into().expect("16 bytes; qed");
+                try_insert(&mut self.by_hash_128, (hasher, hash), idx)
+            } else {
+                try_insert(&mut self.by_hash_256, (hasher, hash), idx)
+            };
+            is_new |= inserted;
+        }
+        // Don't store the same value twice.
+        if is_new {
+            self.values.push(encoded_key);
+        }
+    }
+
+    pub(crate) fn find(&self, hasher: StorageHasher, hash: &[u8]) -> Option<&[u8]> {
+        let idx = match hash.len() {
+            16 => *self.by_hash_128.get(&(hasher, hash.try_into().ok()?))?,
+            32 => *self.by_hash_256.get(&(hasher, hash.try_into().ok()?))?,
+            _ => return None,
+        };
+        Some(&self.values[idx])
+    }
+}
+
+/// Insert the value if the key isn't already present, returning whether it was inserted.
+fn try_insert<K: core::hash::Hash + Eq>(map: &mut HashMap<K, usize>, key: K, idx: usize) -> bool {
+    match map.entry(key) {
+        hashbrown::hash_map::Entry::Vacant(e) => {
+            e.insert(idx);
+            true
+        }
+        hashbrown::hash_map::Entry::Occupied(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::StorageHasher;
+    use crate::storage::{
+        DecodeStorageKeyOptions, StorageTypeInfo, decode_storage_key,
+        decode_storage_key_with_info_and_options, decode_storage_key_with_options,
+        encode_storage_key,
+    };
+    use frame_metadata::v16::StorageHasher as MetadataHasher;
+    use frame_metadata::v16::*;
+    use scale_info::meta_type;
+
+    fn storage_entry(
+        name: &'static str,
+        hashers: Vec<MetadataHasher>,
+        key: scale_info::MetaType,
+    ) -> StorageEntryMetadata {
+        StorageEntryMetadata {
+            name,
+            modifier: StorageEntryModifier::Optional,
+            ty: StorageEntryType::Map {
+                hashers,
+                key,
+                value: meta_type::<u32>(),
+            },
+            default: vec![],
+            docs: vec![],
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        }
+    }
+
+    fn metadata() -> RuntimeMetadataV16 {
+        let pallet = PalletMetadata {
+            name: "Foo",
+            storage: Some(PalletStorageMetadata {
+                prefix: "Foo",
+                entries: vec![
+                    storage_entry(
+                        "Accounts",
+                        vec![MetadataHasher::Blake2_128],
+                        meta_type::<[u8; 32]>(),
+                    ),
+                    storage_entry(
+                        "DoubleMap",
+                        vec![MetadataHasher::Twox256, MetadataHasher::Blake2_256],
+                        meta_type::<(u32, u64)>(),
+                    ),
+                ],
+            }),
+            calls: None,
+            event: None,
+            constants: vec![],
+            error: None,
+            associated_types: vec![],
+            view_functions: vec![],
+            index: 0,
+            docs: vec![],
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+
+        RuntimeMetadataV16::new(
+            vec![pallet],
+            ExtrinsicMetadata {
+                versions: vec![4],
+                address_ty: meta_type::<u32>(),
+                call_ty: meta_type::<u32>(),
+                signature_ty: meta_type::<u32>(),
+                transaction_extensions_by_version: Default::default(),
+                transaction_extensions: vec![],
+            },
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<u32>(),
+                event_enum_ty: meta_type::<u32>(),
+                error_enum_ty: meta_type::<u32>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        )
+    }
+
+    #[test]
+    fn opaque_key_values_are_looked_up() {
+        let metadata = metadata();
+        let key = encode_storage_key("Foo", "Accounts", ([7u8; 32],), &metadata, &metadata.types)
+            .unwrap();
+
+        let mut lookup = StorageKeyLookup::new();
+        for account_id in [[1u8; 32], [7u8; 32], [7u8; 32]] {
+            lookup.register_candidate("Foo", "Accounts", &account_id);
+        }
+        assert_eq!(lookup.num_candidates("Foo", "Accounts"), 2);
+
+        let decoded = decode_storage_key_with_options(
+            "Foo",
+            "Accounts",
+            &mut &*key,
+            &metadata,
+            &metadata.types,
+            DecodeStorageKeyOptions::new().lookup(&lookup),
+        )
+        .unwrap();
+        let looked_up = decoded[0].looked_up_value().expect("value should be found");
+        assert_eq!(looked_up.bytes(), &[7u8; 32]);
+        assert!(decoded[0].value().is_none());
+
+        // Without the lookup, we don't know what the value is:
+        let decoded =
+            decode_storage_key("Foo", "Accounts", &mut &*key, &metadata, &metadata.types).unwrap();
+        assert!(decoded[0].looked_up_value().is_none());
+
+        // Candidates for other entries aren't used:
+        let mut lookup = StorageKeyLookup::new();
+        lookup.register_candidate("Foo", "Other", &[7u8; 32]);
+        let decoded = decode_storage_key_with_options(
+            "Foo",
+            "Accounts",
+            &mut &*key,
+            &metadata,
+            &metadata.types,
+            DecodeStorageKeyOptions::new().lookup(&lookup),
+        )
+        .unwrap();
+        assert!(decoded[0].looked_up_value().is_none());
+    }
+
+    #[test]
+    fn each_key_part_is_looked_up() {
+        let metadata = metadata();
+        let key = encode_storage_key(
+            "Foo",
+            "DoubleMap",
+            (42u32, 1234u64),
+            &metadata,
+            &metadata.types,
+        )
+        .unwrap();
+
+        let mut lookup = StorageKeyLookup::new();
+        lookup.register_candidate("Foo", "DoubleMap", &42u32);
+
+        let decoded = decode_storage_key_with_options(
+            "Foo",
+            "DoubleMap",
+            &mut &*key,
+            &metadata,
+            &metadata.types,
+            DecodeStorageKeyOptions::new().lookup(&lookup),
+        )
+        .unwrap();
+        assert_eq!(
+            decoded[0].looked_up_value().map(|v| v.bytes()),
+            Some(&42u32.encode()[..])
+        );
+        assert!(decoded[1].looked_up_value().is_none());
+
+        lookup.register_encoded_candidate("Foo", "DoubleMap", 1234u64.encode());
+
+        let decoded = decode_storage_key_with_options(
+            "Foo",
+            "DoubleMap",
+            &mut &*key,
+            &metadata,
+            &metadata.types,
+            DecodeStorageKeyOptions::new().lookup(&lookup),
+        )
+        .unwrap();
+        assert_eq!(
+            decoded[0].looked_up_value().map(|v| v.bytes()),
+            Some(&42u32.encode()[..])
+        );
+        assert_eq!(
+            decoded[1].looked_up_value().map(|v| v.bytes()),
+            Some(&1234u64.encode()[..])
+        );
+        assert_eq!(
+            lookup.find(
+                "Foo",
+                "DoubleMap",
+                StorageHasher::Blake2_256,
+                &key[decoded[1].hash_range()]
+            ),
+            Some(&1234u64.encode()[..])
+        );
+    }
+
+    #[test]
+    fn values_are_looked_up_when_verifying_or_given_info() {
+        let metadata = metadata();
+        let key = encode_storage_key("Foo", "Accounts", ([7u8; 32],), &metadata, &metadata.types)
+            .unwrap();
+        let info = metadata.storage_info("Foo", "Accounts").unwrap();
+
+        let mut lookup = StorageKeyLookup::new();
+        lookup.register_candidate("Foo", "Accounts", &[7u8; 32]);
+
+        let verified = DecodeStorageKeyOptions::new()
+            .verify_hashes(true)
+            .lookup(&lookup);
+        let not_verified = DecodeStorageKeyOptions::new().lookup(&lookup);
+
+        let decoded = [
+            decode_storage_key_with_options(
+                "Foo",
+                "Accounts",
+                &mut &*key,
+                &metadata,
+                &metadata.types,
+                verified,
+            ),
+            decode_storage_key_with_info_and_options(
+                "Foo",
+                "Accounts",
+                &mut &*key,
+                &info,
+                &metadata.types,
+                not_verified,
+            ),
+            decode_storage_key_with_info_and_options(
+                "Foo",
+                "Accounts",
+                &mut &*key,
+                &info,
+                &metadata.types,
+                verified,
+            ),
+        ];
+        for decoded in decoded {
+            let decoded = decoded.unwrap();
+            let looked_up = decoded[0].looked_up_value().expect("value should be found");
+            assert_eq!(looked_up.bytes(), &[7u8; 32]);
+        }
+    }
+}
@@ -0,0 +1,428 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::storage_decoder::{StorageKey, StorageKeyDecodeError, decode_storage_key};
+use super::storage_encoder::encode_storage_key_prefix;
+use super::storage_type_info::{StorageEntryInfo, StorageTypeInfo};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use hashbrown::HashMap;
+use scale_type_resolver::TypeResolver;
+
+/// An error returned trying to decode a storage key without knowing which storage entry it
+/// belongs to.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum AnyStorageKeyDecodeError<TypeId> {
+    #[error(
+        "The storage key is not a well known key, and does not begin with the prefix of any known storage entry."
+    )]
+    UnknownPrefix,
+    #[error("Cannot decode storage key for {pallet_name}.{storage_entry}:\n\n{error}")]
+    CannotDecodeKey {
+        pallet_name: String,
+        storage_entry: String,
+        error: StorageKeyDecodeError<TypeId>,
+    },
+}
+
+impl<TypeId> AnyStorageKeyDecodeError<TypeId> {
+    /// Map the storage key error type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, f: F) -> AnyStorageKeyDecodeError<NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        match self {
+            AnyStorageKeyDecodeError::UnknownPrefix => AnyStorageKeyDecodeError::UnknownPrefix,
+            AnyStorageKeyDecodeError::CannotDecodeKey {
+                pallet_name,
+                storage_entry,
+                error,
+            } => AnyStorageKeyDecodeError::CannotDecodeKey {
+                pallet_name,
+                storage_entry,
+                error: error.map_type_id(f),
+            },
+        }
+    }
+}
+
+/// This maps the 32 byte `twox_128(pallet_name) ++ twox_128(storage_entry)` prefix that every
+/// storage key begins with back to the pallet and storage entry names, so that storage keys can
+/// be identified without knowing in advance which storage entry they belong to.
+///
+/// This can be built from modern or legacy metadata, or anything else which implements
+/// [`StorageEntryInfo`]. See [`decode_any_storage_key`] to decode keys using it.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::storage::StoragePrefixIndex;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let index = StoragePrefixIndex::new(&metadata);
+///
+/// // The key for System.Number (which has no further parts):
+/// let key = hex::decode("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").unwrap();
+/// assert_eq!(index.storage_entry(&key), Some(("System", "Number")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct StoragePrefixIndex<'info> {
+    entries: HashMap<[u8; 32], (Cow<'info, str>, Cow<'info, str>)>,
+}
+
+impl<'info> StoragePrefixIndex<'info> {
+    /// Build an index of the storage entries in the given metadata.
+    pub fn new<Info: StorageEntryInfo>(info: &'info Info) -> Self {
+        let entries = info
+            .storage_tuples()
+            .map(|(pallet_name, entry_name)| {
+                let prefix = encode_storage_key_prefix(&pallet_name, &entry_name);
+                (prefix, (pallet_name, entry_name))
+            })
+            .collect();
+
+        StoragePrefixIndex { entries }
+    }
+
+    /// Take ownership of the names in this index, so that it no longer borrows from the metadata.
+    pub fn into_owned(self) -> StoragePrefixIndex<'static> {
+        StoragePrefixIndex {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|(prefix, (pallet_name, entry_name))| {
+                    let pallet_name = Cow::Owned(pallet_name.into_owned());
+                    let entry_name = Cow::Owned(entry_name.into_owned());
+                    (prefix, (pallet_name, entry_name))
+                })
+                .collect(),
+        }
+    }
+
+    /// Return the pallet and storage entry names for the given storage key, or `None` if the
+    /// key does not begin with the prefix of a known storage entry. Only the first 32 bytes of
+    /// the key are looked at.
+    pub fn storage_entry(&self, key: &[u8]) -> Option<(&str, &str)> {
+        let prefix: &[u8; 32] = key.get(..32)?.try_into().ok()?;
+        self.entries
+            .get(prefix)
+            .map(|(pallet_name, entry_name)| (&**pallet_name, &**entry_name))
+    }
+
+    /// The number of storage entries in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Is the index empty?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Keys which aren't a part of any pallet's storage, but which have some well known meaning
+/// in every Substrate based chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WellKnownKey {
+    /// `:code`, the key under which the runtime WASM blob is stored.
+    Code,
+    /// `:heappages`, the key under which the number of heap pages the runtime can use is stored.
+    HeapPages,
+    /// `:extrinsic_index`, the key under which the index of the current extrinsic is stored
+    /// during block execution.
+    ExtrinsicIndex,
+    /// `:intrablock_entropy`, the key under which some entropy is stored during block execution.
+    IntrablockEntropy,
+    /// A key beginning with `:child_storage:default:`, under which the root of a child trie is
+    /// stored. The rest of the key identifies the child trie.
+    DefaultChildStorage,
+}
+
+impl WellKnownKey {
+    const ALL: [WellKnownKey; 5] = [
+        WellKnownKey::Code,
+        WellKnownKey::HeapPages,
+        WellKnownKey::ExtrinsicIndex,
+        WellKnownKey::IntrablockEntropy,
+        WellKnownKey::DefaultChildStorage,
+    ];
+
+    /// Identify a well known key from the given storage key bytes.
+    pub fn from_key(key: &[u8]) -> Option<WellKnownKey> {
+        WellKnownKey::ALL.into_iter().find(|k| match k {
+            WellKnownKey::DefaultChildStorage => key.starts_with(k.as_bytes()),
+            _ => key == k.as_bytes(),
+        })
+    }
+
+    /// The bytes of this key. For [`WellKnownKey::DefaultChildStorage`], this is the prefix
+    /// that such keys begin with.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            WellKnownKey::Code => b":code",
+            WellKnownKey::HeapPages => b":heappages",
+            WellKnownKey::ExtrinsicIndex => b":extrinsic_index",
+            WellKnownKey::IntrablockEntropy => b":intrablock_entropy",
+            WellKnownKey::DefaultChildStorage => b":child_storage:default:",
+        }
+    }
+}
+
+/// A storage key which has been identified and decoded by [`decode_any_storage_key`].
+#[derive(Clone, Debug)]
+pub enum AnyStorageKey<'index, TypeId> {
+    /// One of the [`WellKnownKey`]s.
+    WellKnown(WellKnownKey),
+    /// A key belonging to some pallet's storage entry.
+    Entry {
+        /// The name of the pallet.
+        pallet_name: &'index str,
+        /// The name of the storage entry.
+        storage_entry: &'index str,
+        /// Information about the parts of the key.
+        key: StorageKey<TypeId>,
+    },
+}
+
+impl<'index, TypeId> AnyStorageKey<'index, TypeId> {
+    /// Map the storage key type IDs to something else.
+    pub fn map_type_id<NewTypeId, F>(self, f: F) -> AnyStorageKey<'index, NewTypeId>
+    where
+        F: FnMut(TypeId) -> NewTypeId,
+    {
+        match self {
+            AnyStorageKey::WellKnown(k) => AnyStorageKey::WellKnown(k),
+            AnyStorageKey::Entry {
+                pallet_name,
+                storage_entry,
+                key,
+            } => AnyStorageKey::Entry {
+                pallet_name,
+                storage_entry,
+                key: key.map_type_id(f),
+            },
+        }
+    }
+}
+
+/// Decode a storage key without knowing in advance which storage entry it belongs to. This is
+/// useful when handed raw keys, for instance from `state_getKeysPaged`, a trie diff or a
+/// snapshot.
+///
+/// [`WellKnownKey`]s such as `:code` are recognised first. Otherwise, the pallet and storage
+/// entry are looked up in the given [`StoragePrefixIndex`], and the key is decoded as with
+/// [`decode_storage_key`].
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::storage::{AnyStorageKey, StoragePrefixIndex, decode_any_storage_key};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let storage_keyval_bytes = std::fs::read("artifacts/storage_10000000_9180_system_account.json").unwrap();
+/// let storage_keyval_hex: Vec<(String, String)> = serde_json::from_slice(&storage_keyval_bytes).unwrap();
+///
+/// let index = StoragePrefixIndex::new(&metadata);
+///
+/// for (key, _val) in storage_keyval_hex {
+///     let key_bytes = hex::decode(key.trim_start_matches("0x")).unwrap();
+///
+///     let key = decode_any_storage_key(
+///         &mut &*key_bytes,
+///         &index,
+///         &metadata,
+///         &metadata.types,
+///     ).unwrap();
+///
+///     let AnyStorageKey::Entry { pallet_name, storage_entry, .. } = key else { panic!() };
+///     assert_eq!((pallet_name, storage_entry), ("System", "Account"));
+/// }
+/// ```
+pub fn decode_any_storage_key<'index, Info, Resolver>(
+    cursor: &mut &[u8],
+    index: &'index StoragePrefixIndex<'_>,
+    info: &Info,
+    type_resolver: &Resolver,
+) -> Result<AnyStorageKey<'index, Info::TypeId>, AnyStorageKeyDecodeError<Info::TypeId>>
+where
+    Info: StorageTypeInfo,
+    Info::TypeId: Clone + core::fmt::Debug,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    if let Some(well_known) = WellKnownKey::from_key(cursor) {
+        *cursor = &[];
+        return Ok(AnyStorageKey::WellKnown(well_known));
+    }
+
+    let (pallet_name, storage_entry) = index
+        .storage_entry(cursor)
+        .ok_or(AnyStorageKeyDecodeError::UnknownPrefix)?;
+
+    let key = decode_storage_key(pallet_name, storage_entry, cursor, info, type_resolver).map_err(
+        |error| AnyStorageKeyDecodeError::CannotDecodeKey {
+            pallet_name: pallet_name.to_string(),
+            storage_entry: storage_entry.to_string(),
+            error,
+        },
+    )?;
+
+    Ok(AnyStorageKey::Entry {
+        pallet_name,
+        storage_entry,
+        key,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Decode;
+
+    fn storage_keys(path: &str) -> Vec<Vec<u8>> {
+        let bytes = std::fs::read(path).unwrap();
+        let keyvals: Vec<(String, String)> = serde_json::from_slice(&bytes).unwrap();
+        keyvals
+            .into_iter()
+            .map(|(key, _)| hex::decode(key.trim_start_matches("0x")).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn modern_keys_are_identified_and_decoded() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let index = StoragePrefixIndex::new(&metadata);
+        assert_eq!(index.len(), metadata.storage_tuples().count());
+
+        let keys = storage_keys("artifacts/storage_10000000_9180_system_account.json");
+        assert!(!keys.is_empty());
+        for key_bytes in keys {
+            let cursor = &mut &*key_bytes;
+            let key = decode_any_storage_key(cursor, &index, &metadata, &metadata.types).unwrap();
+            assert!(cursor.is_empty());
+
+            let AnyStorageKey::Entry {
+                pallet_name,
+                storage_entry,
+                key,
+            } = key
+            else {
+                panic!("Expected a System.Account key");
+            };
+            assert_eq!((pallet_name, storage_entry), ("System", "Account"));
+            assert_eq!(key.parts().len(), 1);
+        }
+
+        // Once owned, the index no longer borrows from the metadata.
+        let index = index.into_owned();
+        drop(metadata);
+        let prefix = encode_storage_key_prefix("Staking", "Validators");
+        assert_eq!(
+            index.storage_entry(&prefix),
+            Some(("Staking", "Validators"))
+        );
+    }
+
+    #[test]
+    fn legacy_keys_are_identified_and_decoded() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        let metadata_types = crate::helpers::type_registry_from_metadata(&metadata).unwrap();
+        types.prepend(metadata_types);
+
+        let index = StoragePrefixIndex::new(&metadata);
+
+        let keys = storage_keys("artifacts/storage_5000000_30_staking_validators.json");
+        assert!(!keys.is_empty());
+        for key_bytes in keys {
+            let key = decode_any_storage_key(&mut &*key_bytes, &index, &metadata, &types).unwrap();
+            assert!(matches!(
+                key,
+                AnyStorageKey::Entry {
+                    pallet_name: "Staking",
+                    storage_entry: "Validators",
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn well_known_and_unknown_keys() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+        let index = StoragePrefixIndex::new(&metadata);
+
+        // Returns the well known key found, or the name of the error variant.
+        let decode = |key: &[u8]| match decode_any_storage_key(
+            &mut &*key,
+            &index,
+            &metadata,
+            &metadata.types,
+        ) {
+            Ok(AnyStorageKey::WellKnown(k)) => Ok(Some(k)),
+            Ok(AnyStorageKey::Entry { .. }) => Ok(None),
+            Err(AnyStorageKeyDecodeError::UnknownPrefix) => Err("UnknownPrefix"),
+            Err(AnyStorageKeyDecodeError::CannotDecodeKey { .. }) => Err("CannotDecodeKey"),
+        };
+
+        assert_eq!(decode(b":code"), Ok(Some(WellKnownKey::Code)));
+        assert_eq!(decode(b":heappages"), Ok(Some(WellKnownKey::HeapPages)));
+        assert_eq!(
+            decode(b":extrinsic_index"),
+            Ok(Some(WellKnownKey::ExtrinsicIndex))
+        );
+        assert_eq!(
+            decode(b":child_storage:default:crowdloan"),
+            Ok(Some(WellKnownKey::DefaultChildStorage))
+        );
+
+        // Keys which merely begin with a well known key aren't mistaken for one.
+        assert_eq!(decode(b":codex"), Err("UnknownPrefix"));
+        assert_eq!(decode(&[0u8; 40]), Err("UnknownPrefix"));
+
+        // A known prefix which is missing the rest of the key fails to decode.
+        let prefix = encode_storage_key_prefix("System", "Account");
+        assert_eq!(decode(&prefix), Err("CannotDecodeKey"));
+    }
+}
@@ -0,0 +1,844 @@
+// Copyright (C) 2022-2026 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use parity_scale_codec::{Compact, Decode, Encode};
+use sp_crypto_hashing::blake2_256;
+
+/// The prefix of the keys in the main trie whose values are the roots of default child tries.
+const DEFAULT_CHILD_STORAGE_KEY_PREFIX: &[u8] = b":child_storage:default:";
+
+// The node header prefixes used by the Substrate trie layout.
+const EMPTY_TRIE: u8 = 0b0000_0000;
+const LEAF_PREFIX_MASK: u8 = 0b0100_0000;
+const BRANCH_WITHOUT_VALUE_MASK: u8 = 0b1000_0000;
+const BRANCH_WITH_VALUE_MASK: u8 = 0b1100_0000;
+const HASHED_VALUE_LEAF_PREFIX_MASK: u8 = 0b0010_0000;
+const HASHED_VALUE_BRANCH_PREFIX_MASK: u8 = 0b0001_0000;
+// Prepended to nodes in a compact proof whose value has been omitted.
+const ESCAPE_COMPACT_HEADER: u8 = 0b0000_0001;
+// The maximum number of nibbles that a partial key can be encoded with.
+const NIBBLE_SIZE_BOUND: usize = u16::MAX as usize;
+
+/// An error returned trying to verify a storage proof.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StorageProofError {
+    #[error("The proof does not contain the node for the given state root.")]
+    MissingRootNode,
+    #[error("Cannot decode a trie node in the proof:\n\n{0}")]
+    CannotDecodeNode(parity_scale_codec::Error),
+    #[error("The root computed from the compact proof does not match the given state root.")]
+    RootMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+    #[error("The compact proof ended before all of the omitted nodes were provided.")]
+    IncompleteCompactProof,
+    #[error("The compact proof omits a value but does not provide it.")]
+    MissingCompactValue,
+    #[error("The compact proof provides a value for a node which has no omitted value.")]
+    UnexpectedCompactValue,
+    #[error("The compact proof contains a trie which is not a child trie of the proven state.")]
+    ExtraneousChildTrie { root: [u8; 32] },
+    #[error("The proof contains a value at a key which is not a whole number of bytes long.")]
+    OddLengthKey,
+    #[error(
+        "The child trie root stored at key {key:?} should be 32 bytes long, but is {len} bytes."
+    )]
+    InvalidChildTrieRoot { key: Vec<u8>, len: usize },
+}
+
+/// The key/value pairs that a storage proof has been verified to contain.
+///
+/// A storage proof typically covers only a handful of keys, and so this will not contain every
+/// entry in the state; the absence of a key here does not mean that it is absent from the state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedStorage {
+    root: [u8; 32],
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+    child_tries: BTreeMap<Vec<u8>, VerifiedStorage>,
+}
+
+impl VerifiedStorage {
+    /// The root of the trie that these entries were verified against.
+    pub fn root(&self) -> &[u8; 32] {
+        &self.root
+    }
+
+    /// Return the value stored at the given key, if the proof contains it.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key).map(|v| &**v)
+    }
+
+    /// Iterate over the proven key/value pairs, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.entries.iter().map(|(k, v)| (&**k, &**v))
+    }
+
+    /// Iterate over the proven key/value pairs whose keys begin with the given prefix, ordered by
+    /// key. Use this with [`crate::storage::encode_storage_key_prefix`] to find all of the proven
+    /// entries for some storage item.
+    pub fn iter_prefix<'a>(
+        &'a self,
+        prefix: &'a [u8],
+    ) -> impl Iterator<Item = (&'a [u8], &'a [u8])> {
+        self.entries
+            .range::<[u8], _>((
+                core::ops::Bound::Included(prefix),
+                core::ops::Bound::Unbounded,
+            ))
+            .take_while(move |(k, _)| k.starts_with(prefix))
+            .map(|(k, v)| (&**k, &**v))
+    }
+
+    /// The number of proven key/value pairs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Does the proof contain no key/value pairs?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the proven entries of the default child trie with the given storage key (that is,
+    /// the key without the `:child_storage:default:` prefix), if the proof contains it.
+    pub fn child_trie(&self, storage_key: &[u8]) -> Option<&VerifiedStorage> {
+        self.child_tries.get(storage_key)
+    }
+
+    /// Iterate over the default child tries that the proof contains, along with their storage
+    /// keys.
+    pub fn child_tries(&self) -> impl Iterator<Item = (&[u8], &VerifiedStorage)> {
+        self.child_tries.iter().map(|(k, v)| (&**k, v))
+    }
+}
+
+/// Verify a plain storage proof, as returned from `state_getReadProof`, against the given state
+/// root. A plain proof is an unordered set of encoded trie nodes.
+///
+/// This returns every key/value pair which can be reached from the root using the nodes in the
+/// proof, including the entries of any default child tries which the proof contains. Both
+/// state versions are supported; with state version 1, larger values are stored in separate
+/// nodes which must also be present in the proof for the value to be returned.
+///
+/// # Example
+///
+/// Here, we verify a proof containing some `System.Account` entries, and then decode them.
+///
+/// ```rust
+/// use frame_decode::storage::{
+///     decode_storage_key, decode_storage_value, encode_storage_key_prefix, verify_storage_proof,
+/// };
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+/// use scale_value::scale::ValueVisitor;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let proofs_bytes = std::fs::read("artifacts/storage_proof_10000000_9180.json").unwrap();
+/// let proofs: serde_json::Value = serde_json::from_slice(&proofs_bytes).unwrap();
+/// let proof = &proofs[0];
+///
+/// let from_hex = |s: &serde_json::Value| hex::decode(s.as_str().unwrap().trim_start_matches("0x")).unwrap();
+/// let root: [u8; 32] = from_hex(&proof["root"]).try_into().unwrap();
+/// let nodes: Vec<Vec<u8>> = proof["proof"].as_array().unwrap().iter().map(from_hex).collect();
+///
+/// // Verify the proof against the state root:
+/// let verified = verify_storage_proof(&root, &nodes).unwrap();
+///
+/// // Decode the verified System.Account entries:
+/// let prefix = encode_storage_key_prefix("System", "Account");
+/// for (key, value) in verified.iter_prefix(&prefix) {
+///     let key = decode_storage_key(
+///         "System",
+///         "Account",
+///         &mut &*key,
+///         &metadata,
+///         &metadata.types,
+///     ).unwrap();
+///
+///     let value = decode_storage_value(
+///         "System",
+///         "Account",
+///         &mut &*value,
+///         &metadata,
+///         &metadata.types,
+///         ValueVisitor::new(),
+///     ).unwrap();
+/// }
+/// ```
+pub fn verify_storage_proof<Node: AsRef<[u8]>>(
+    root: &[u8; 32],
+    proof: impl IntoIterator<Item = Node>,
+) -> Result<VerifiedStorage, StorageProofError> {
+    let proof: Vec<Node> = proof.into_iter().collect();
+    let nodes: HashMap<[u8; 32], &[u8]> = proof
+        .iter()
+        .map(|node| (blake2_256(node.as_ref()), node.as_ref()))
+        .collect();
+
+    verify_storage(root, &nodes)
+}
+
+/// Verify a compact storage proof against the given state root. A compact proof is an ordered
+/// list of encoded trie nodes, from which any hashes that can be computed from the other nodes
+/// have been omitted. The nodes of the main trie come first, followed by the nodes of any
+/// default child tries.
+///
+/// This returns the same entries as [`verify_storage_proof`] would for the equivalent plain
+/// proof.
+pub fn verify_compact_storage_proof<Node: AsRef<[u8]>>(
+    root: &[u8; 32],
+    proof: impl IntoIterator<Item = Node>,
+) -> Result<VerifiedStorage, StorageProofError> {
+    let proof: Vec<Node> = proof.into_iter().collect();
+    let mut encoded_nodes = proof.iter().map(|n| n.as_ref()).peekable();
+    let mut nodes = HashMap::new();
+
+    let actual = decode_compact_trie(&mut encoded_nodes, &mut nodes)?;
+    if &actual != root {
+        return Err(StorageProofError::RootMismatch {
+            expected: *root,
+            actual,
+        });
+    }
+
+    let mut child_roots = Vec::new();
+    while encoded_nodes.peek().is_some() {
+        child_roots.push(decode_compact_trie(&mut encoded_nodes, &mut nodes)?);
+    }
+
+    let verified = verify_storage(root, &nodes)?;
+
+    // Every trie after the first must be one of the proven child tries.
+    for child_root in child_roots {
+        if !verified.child_tries.values().any(|c| c.root == child_root) {
+            return Err(StorageProofError::ExtraneousChildTrie { root: child_root });
+        }
+    }
+
+    Ok(verified)
+}
+
+// Collect the entries of the main trie and of any child tries that we have the roots for.
+fn verify_storage<Bytes: AsRef<[u8]>>(
+    root: &[u8; 32],
+    nodes: &HashMap<[u8; 32], Bytes>,
+) -> Result<VerifiedStorage, StorageProofError> {
+    let entries = collect_entries(root, nodes)?;
+
+    let mut child_tries = BTreeMap::new();
+    for (key, value) in entries.range::<[u8], _>((
+        core::ops::Bound::Included(DEFAULT_CHILD_STORAGE_KEY_PREFIX),
+        core::ops::Bound::Unbounded,
+    )) {
+        let Some(storage_key) = key.strip_prefix(DEFAULT_CHILD_STORAGE_KEY_PREFIX) else {
+            break;
+        };
+        let child_root: [u8; 32] =
+            value
+                .as_slice()
+                .try_into()
+                .map_err(|_| StorageProofError::InvalidChildTrieRoot {
+                    key: key.clone(),
+                    len: value.len(),
+                })?;
+
+        // The proof need not contain every child trie.
+        if !nodes.contains_key(&child_root) {
+            continue;
+        }
+
+        let child_entries = collect_entries(&child_root, nodes)?;
+        child_tries.insert(
+            storage_key.to_vec(),
+            VerifiedStorage {
+                root: child_root,
+                entries: child_entries,
+                child_tries: BTreeMap::new(),
+            },
+        );
+    }
+
+    Ok(VerifiedStorage {
+        root: *root,
+        entries,
+        child_tries,
+    })
+}
+
+// Walk the trie from the given root, collecting every key/value pair that we have the nodes for.
+fn collect_entries<Bytes: AsRef<[u8]>>(
+    root: &[u8; 32],
+    nodes: &HashMap<[u8; 32], Bytes>,
+) -> Result<BTreeMap<Vec<u8>, Vec<u8>>, StorageProofError> {
+    let root_node = nodes.get(root).ok_or(StorageProofError::MissingRootNode)?;
+
+    let mut entries = BTreeMap::new();
+    let mut insert = |nibbles: &[u8], value: &NodeValue<'_>| {
+        let value = match value {
+            NodeValue::Inline(value) => *value,
+            NodeValue::Hashed(hash) => match nodes.get(hash) {
+                Some(value) => value.as_ref(),
+                // The key exists, but its value isn't in the proof.
+                None => return Ok(()),
+            },
+        };
+        entries.insert(nibbles_to_key(nibbles)?, value.to_vec());
+        Ok(())
+    };
+
+    // Walk the nodes depth first, keeping track of the nibbles leading to each one.
+    let mut stack: Vec<(Vec<u8>, &[u8])> = Vec::from([(Vec::new(), root_node.as_ref())]);
+    while let Some((mut nibbles, node_bytes)) = stack.pop() {
+        match decode_node(node_bytes)? {
+            Node::Empty => {}
+            Node::Leaf { partial, value } => {
+                nibbles.extend(partial.iter());
+                insert(&nibbles, &value)?;
+            }
+            Node::Branch {
+                partial,
+                value,
+                children,
+            } => {
+                nibbles.extend(partial.iter());
+                if let Some(value) = &value {
+                    insert(&nibbles, value)?;
+                }
+                for (idx, child) in children.iter().enumerate() {
+                    let child_bytes = match child {
+                        Some(ChildRef::Inline(bytes)) => *bytes,
+                        Some(ChildRef::Hash(hash)) => match nodes.get(hash) {
+                            Some(bytes) => bytes.as_ref(),
+                            // This part of the trie isn't in the proof.
+                            None => continue,
+                        },
+                        None => continue,
+                    };
+                    let mut child_nibbles = nibbles.clone();
+                    child_nibbles.push(idx as u8);
+                    stack.push((child_nibbles, child_bytes));
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+// Decode a single trie from the compact proof, adding its nodes (with any omitted hashes
+// restored) to the given map. Returns the root of the trie.
+fn decode_compact_trie<'a>(
+    encoded_nodes: &mut impl Iterator<Item = &'a [u8]>,
+    nodes: &mut HashMap<[u8; 32], Vec<u8>>,
+) -> Result<[u8; 32], StorageProofError> {
+    // Nodes that are waiting for the child at `child_index` to be decoded.
+    let mut stack: Vec<(Node<'a>, usize)> = Vec::new();
+
+    loop {
+        let encoded = encoded_nodes
+            .next()
+            .ok_or(StorageProofError::IncompleteCompactProof)?;
+
+        let mut node = match encoded.split_first() {
+            Some((&ESCAPE_COMPACT_HEADER, rest)) => {
+                // The value was omitted and is provided as the next item.
+                let value = encoded_nodes
+                    .next()
+                    .ok_or(StorageProofError::MissingCompactValue)?;
+                let mut node = decode_node(rest)?;
+                match node.value_mut() {
+                    Some(v @ NodeValue::Inline([])) => {
+                        let hash = blake2_256(value);
+                        nodes.insert(hash, value.to_vec());
+                        *v = NodeValue::Hashed(hash);
+                    }
+                    _ => return Err(StorageProofError::UnexpectedCompactValue),
+                }
+                node
+            }
+            _ => decode_node(encoded)?,
+        };
+
+        let mut child_index = 0;
+        loop {
+            // Omitted children are empty inline nodes, and come next in the proof.
+            if let Some(idx) = node.next_omitted_child(child_index) {
+                stack.push((node, idx));
+                break;
+            }
+
+            let encoded = encode_node(&node);
+            let hash = blake2_256(&encoded);
+            nodes.insert(hash, encoded);
+
+            let Some((mut parent, parent_index)) = stack.pop() else {
+                return Ok(hash);
+            };
+            if let Node::Branch { children, .. } = &mut parent {
+                children[parent_index] = Some(ChildRef::Hash(hash));
+            }
+            node = parent;
+            child_index = parent_index + 1;
+        }
+    }
+}
+
+fn nibbles_to_key(nibbles: &[u8]) -> Result<Vec<u8>, StorageProofError> {
+    if !nibbles.len().is_multiple_of(2) {
+        return Err(StorageProofError::OddLengthKey);
+    }
+    Ok(nibbles.chunks(2).map(|n| (n[0] << 4) | n[1]).collect())
+}
+
+/// A decoded trie node.
+enum Node<'a> {
+    Empty,
+    Leaf {
+        partial: Nibbles<'a>,
+        value: NodeValue<'a>,
+    },
+    Branch {
+        partial: Nibbles<'a>,
+        value: Option<NodeValue<'a>>,
+        children: Box<[Option<ChildRef<'a>>; 16]>,
+    },
+}
+
+impl<'a> Node<'a> {
+    fn value_mut(&mut self) -> Option<&mut NodeValue<'a>> {
+        match self {
+            Node::Empty => None,
+            Node::Leaf { value, .. } => Some(value),
+            Node::Branch { value, .. } => value.as_mut(),
+        }
+    }
+
+    fn next_omitted_child(&self, from: usize) -> Option<usize> {
+        let Node::Branch { children, .. } = self else {
+            return None;
+        };
+        (from..16).find(|&idx| matches!(children[idx], Some(ChildRef::Inline([]))))
+    }
+}
+
+/// The value stored in a leaf or branch node.
+enum NodeValue<'a> {
+    Inline(&'a [u8]),
+    Hashed([u8; 32]),
+}
+
+/// A reference from a branch node to one of its children.
+enum ChildRef<'a> {
+    Inline(&'a [u8]),
+    Hash([u8; 32]),
+}
+
+/// The partial key of a node. If there is an odd number of nibbles, the first
+/// byte is padded with an empty nibble.
+struct Nibbles<'a> {
+    bytes: &'a [u8],
+    len: usize,
+}
+
+impl Nibbles<'_> {
+    fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        let padding = self.len % 2;
+        (padding..self.len + padding).map(|idx| {
+            let byte = self.bytes[idx / 2];
+            if idx % 2 == 0 { byte >> 4 } else { byte & 0x0F }
+        })
+    }
+}
+
+fn decode_node(bytes: &[u8]) -> Result<Node<'_>, StorageProofError> {
+    decode_node_inner(bytes).map_err(StorageProofError::CannotDecodeNode)
+}
+
+fn decode_node_inner(bytes: &[u8]) -> Result<Node<'_>, parity_scale_codec::Error> {
+    let input = &mut &*bytes;
+    let first = u8::decode(input)?;
+
+    let node = if first == EMPTY_TRIE {
+        Node::Empty
+    } else {
+        // (is leaf, has value, is value hashed, number of nibbles)
+        let (is_leaf, has_value, is_hashed, len) = match first & 0b1100_0000 {
+            LEAF_PREFIX_MASK => (true, true, false, decode_size(first, input, 2)?),
+            BRANCH_WITH_VALUE_MASK => (false, true, false, decode_size(first, input, 2)?),
+            BRANCH_WITHOUT_VALUE_MASK => (false, false, false, decode_size(first, input, 2)?),
+            _ if first & 0b1110_0000 == HASHED_VALUE_LEAF_PREFIX_MASK => {
+                (true, true, true, decode_size(first, input, 3)?)
+            }
+            _ if first & 0b1111_0000 == HASHED_VALUE_BRANCH_PREFIX_MASK => {
+                (false, true, true, decode_size(first, input, 4)?)
+            }
+            _ => return Err("Unsupported trie node header".into()),
+        };
+
+        let partial_bytes = take(input, len.div_ceil(2))?;
+        if !len.is_multiple_of(2) && partial_bytes[0] & 0xF0 != 0 {
+            return Err("Invalid padding in trie node partial key".into());
+        }
+        let partial = Nibbles {
+            bytes: partial_bytes,
+            len,
+        };
+
+        if is_leaf {
+            let value = decode_value(input, is_hashed)?;
+            Node::Leaf { partial, value }
+        } else {
+            let bitmap = u16::decode(input)?;
+            let value = if has_value {
+                Some(decode_value(input, is_hashed)?)
+            } else {
+                None
+            };
+            let mut children = Box::new([const { None }; 16]);
+            for (idx, child) in children.iter_mut().enumerate() {
+                if bitmap & (1 << idx) == 0 {
+                    continue;
+                }
+                let len = Compact::<u32>::decode(input)?.0 as usize;
+                let bytes = take(input, len)?;
+                *child = Some(match <[u8; 32]>::try_from(bytes) {
+                    Ok(hash) => ChildRef::Hash(hash),
+                    Err(_) => ChildRef::Inline(bytes),
+                });
+            }
+            Node::Branch {
+                partial,
+                value,
+                children,
+            }
+        }
+    };
+
+    if !input.is_empty() {
+        return Err("Trailing bytes after trie node".into());
+    }
+    Ok(node)
+}
+
+fn decode_value<'a>(
+    input: &mut &'a [u8],
+    is_hashed: bool,
+) -> Result<NodeValue<'a>, parity_scale_codec::Error> {
+    if is_hashed {
+        let hash = <[u8; 32]>::try_from(take(input, 32)?).expect("32 bytes taken; qed");
+        Ok(NodeValue::Hashed(hash))
+    } else {
+        let len = Compact::<u32>::decode(input)?.0 as usize;
+        Ok(NodeValue::Inline(take(input, len)?))
+    }
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], parity_scale_codec::Error> {
+    if input.len() < len {
+        return Err("Not enough bytes to decode trie node".into());
+    }
+    let (taken, rest) = input.split_at(len);
+    *input = rest;
+    Ok(taken)
+}
+
+// The number of nibbles is stored in the bits of the header byte not used by the prefix,
+// followed by further bytes if it does not fit.
+fn decode_size(
+    first: u8,
+    input: &mut &[u8],
+    prefix_bits: u32,
+) -> Result<usize, parity_scale_codec::Error> {
+    let max_value = 255u8 >> prefix_bits;
+    let mut result = (first & max_value) as usize;
+    if result < max_value as usize {
+        return Ok(result);
+    }
+    result -= 1;
+    while result <= NIBBLE_SIZE_BOUND {
+        let n = u8::decode(input)? as usize;
+        if n < 255 {
+            return Ok(result + n + 1);
+        }
+        result += 255;
+    }
+    Ok(NIBBLE_SIZE_BOUND)
+}
+
+fn encode_size(size: usize, prefix: u8, prefix_bits: u32, out: &mut Vec<u8>) {
+    let size = size.min(NIBBLE_SIZE_BOUND);
+    let max_value = 255u8 >> prefix_bits;
+    let first = (max_value as usize - 1).min(size);
+    if size == first {
+        out.push(prefix | first as u8);
+        return;
+    }
+    out.push(prefix | max_value);
+    let mut rem = size - first;
+    while rem >= 256 {
+        out.push(255);
+        rem -= 255;
+    }
+    out.push((rem - 1) as u8);
+}
+
+fn encode_node(node: &Node<'_>) -> Vec<u8> {
+    let mut out = Vec::new();
+    match node {
+        Node::Empty => out.push(EMPTY_TRIE),
+        Node::Leaf { partial, value } => {
+            match value {
+                NodeValue::Inline(_) => encode_size(partial.len, LEAF_PREFIX_MASK, 2, &mut out),
+                NodeValue::Hashed(_) => {
+                    encode_size(partial.len, HASHED_VALUE_LEAF_PREFIX_MASK, 3, &mut out)
+                }
+            }
+            out.extend_from_slice(partial.bytes);
+            encode_value(value, &mut out);
+        }
+        Node::Branch {
+            partial,
+            value,
+            children,
+        } => {
+            match value {
+                None => encode_size(partial.len, BRANCH_WITHOUT_VALUE_MASK, 2, &mut out),
+                Some(NodeValue::Inline(_)) => {
+                    encode_size(partial.len, BRANCH_WITH_VALUE_MASK, 2, &mut out)
+                }
+                Some(NodeValue::Hashed(_)) => {
+                    encode_size(partial.len, HASHED_VALUE_BRANCH_PREFIX_MASK, 4, &mut out)
+                }
+            }
+            out.extend_from_slice(partial.bytes);
+
+            let bitmap = children
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.is_some())
+                .fold(0u16, |bitmap, (idx, _)| bitmap | (1 << idx));
+            bitmap.encode_to(&mut out);
+
+            if let Some(value) = value {
+                encode_value(value, &mut out);
+            }
+            for child in children.iter().flatten() {
+                match child {
+                    ChildRef::Inline(bytes) => bytes.encode_to(&mut out),
+                    ChildRef::Hash(hash) => hash.as_slice().encode_to(&mut out),
+                }
+            }
+        }
+    }
+    out
+}
+
+fn encode_value(value: &NodeValue<'_>, out: &mut Vec<u8>) {
+    match value {
+        NodeValue::Inline(bytes) => bytes.encode_to(out),
+        NodeValue::Hashed(hash) => out.extend_from_slice(hash),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::{decode_storage_value, encode_storage_key_prefix};
+    use frame_metadata::RuntimeMetadata;
+    use scale_value::scale::ValueVisitor;
+
+    struct ProofArtifact {
+        state_version: u64,
+        root: [u8; 32],
+        proof: Vec<Vec<u8>>,
+        compact_proof: Vec<Vec<u8>>,
+    }
+
+    fn proof_artifacts() -> Vec<ProofArtifact> {
+        let bytes = std::fs::read("artifacts/storage_proof_10000000_9180.json").unwrap();
+        let json: Vec<serde_json::Value> = serde_json::from_slice(&bytes).unwrap();
+        let from_hex = |s: &serde_json::Value| {
+            hex::decode(s.as_str().unwrap().trim_start_matches("0x")).unwrap()
+        };
+
+        json.iter()
+            .map(|p| ProofArtifact {
+                state_version: p["state_version"].as_u64().unwrap(),
+                root: from_hex(&p["root"]).try_into().unwrap(),
+                proof: p["proof"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(from_hex)
+                    .collect(),
+                compact_proof: p["compact_proof"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(from_hex)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    // The artifacts prove two System.Account entries, System.Number and two child trie entries.
+    fn check_verified_storage(verified: &VerifiedStorage) {
+        let accounts_bytes =
+            std::fs::read("artifacts/storage_10000000_9180_system_account.json").unwrap();
+        let accounts: Vec<(String, String)> = serde_json::from_slice(&accounts_bytes).unwrap();
+        let accounts: BTreeMap<Vec<u8>, Vec<u8>> = accounts
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    hex::decode(k.trim_start_matches("0x")).unwrap(),
+                    hex::decode(v.trim_start_matches("0x")).unwrap(),
+                )
+            })
+            .collect();
+
+        let account_prefix = encode_storage_key_prefix("System", "Account");
+        let proven_accounts: Vec<_> = verified.iter_prefix(&account_prefix).collect();
+        assert_eq!(proven_accounts.len(), 2);
+        for (key, value) in proven_accounts {
+            assert_eq!(accounts.get(key).map(|v| &**v), Some(value));
+        }
+
+        let number_key = encode_storage_key_prefix("System", "Number");
+        assert_eq!(
+            verified.get(&number_key),
+            Some(&10_000_000u32.to_le_bytes()[..])
+        );
+
+        let child = verified
+            .child_trie(b"child")
+            .expect("child trie should be proven");
+        assert_eq!(child.get(b"foo"), Some(&b"bar"[..]));
+        assert_eq!(child.get(b"long"), Some(&[7u8; 40][..]));
+        assert_eq!(child.len(), 2);
+    }
+
+    #[test]
+    fn verify_plain_proofs() {
+        for artifact in proof_artifacts() {
+            let verified = verify_storage_proof(&artifact.root, &artifact.proof).unwrap();
+            assert_eq!(verified.root(), &artifact.root);
+            check_verified_storage(&verified);
+        }
+    }
+
+    #[test]
+    fn verify_compact_proofs() {
+        for artifact in proof_artifacts() {
+            let verified =
+                verify_compact_storage_proof(&artifact.root, &artifact.compact_proof).unwrap();
+            let plain = verify_storage_proof(&artifact.root, &artifact.proof).unwrap();
+            assert_eq!(verified, plain, "state version {}", artifact.state_version);
+        }
+    }
+
+    #[test]
+    fn rejects_proofs_for_other_roots() {
+        for artifact in proof_artifacts() {
+            let mut root = artifact.root;
+            root[0] ^= 1;
+
+            assert!(matches!(
+                verify_storage_proof(&root, &artifact.proof),
+                Err(StorageProofError::MissingRootNode)
+            ));
+            assert!(matches!(
+                verify_compact_storage_proof(&root, &artifact.compact_proof),
+                Err(StorageProofError::RootMismatch { actual, .. }) if actual == artifact.root
+            ));
+        }
+    }
+
+    #[test]
+    fn tampered_proofs_prove_nothing_new() {
+        for artifact in proof_artifacts() {
+            let original = verify_storage_proof(&artifact.root, &artifact.proof).unwrap();
+
+            // Flip the last byte of each node in turn. A plain proof can then prove fewer
+            // entries, but never a different one, and a compact proof should fail to verify.
+            for idx in 0..artifact.proof.len() {
+                let mut proof = artifact.proof.clone();
+                *proof[idx].last_mut().unwrap() ^= 1;
+
+                if let Ok(verified) = verify_storage_proof(&artifact.root, &proof) {
+                    assert_ne!(verified, original);
+                    for (key, value) in verified.iter() {
+                        assert_eq!(original.get(key), Some(value));
+                    }
+                }
+            }
+            for idx in 0..artifact.compact_proof.len() {
+                let mut proof = artifact.compact_proof.clone();
+                *proof[idx].last_mut().unwrap() ^= 1;
+
+                assert!(verify_compact_storage_proof(&artifact.root, &proof).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn decode_verified_entries() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V14 metadata")
+        };
+
+        let artifact = &proof_artifacts()[1];
+        let verified = verify_storage_proof(&artifact.root, &artifact.proof).unwrap();
+
+        let number_key = encode_storage_key_prefix("System", "Number");
+        let number = decode_storage_value(
+            "System",
+            "Number",
+            &mut verified.get(&number_key).unwrap(),
+            &metadata,
+            &metadata.types,
+            ValueVisitor::new(),
+        )
+        .unwrap();
+        assert_eq!(number.as_u128(), Some(10_000_000));
+    }
+
+    #[test]
+    fn node_sizes_roundtrip() {
+        for size in [0, 1, 62, 63, 64, 300, 317, 318, 600, NIBBLE_SIZE_BOUND] {
+            for (prefix, bits) in [
+                (LEAF_PREFIX_MASK, 2),
+                (HASHED_VALUE_LEAF_PREFIX_MASK, 3),
+                (HASHED_VALUE_BRANCH_PREFIX_MASK, 4),
+            ] {
+                let mut out = Vec::new();
+                encode_size(size, prefix, bits, &mut out);
+                let decoded = decode_size(out[0], &mut &out[1..], bits).unwrap();
+                assert_eq!(decoded, size);
+            }
+        }
+    }
+}
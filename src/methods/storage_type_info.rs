@@ -14,7 +14,8 @@
 // limitations under the License.
 
 use super::Entry;
-use crate::utils::Either;
+use super::deprecation::{Deprecation, DeprecationOf};
+use crate::utils::{Either, to_docs};
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
 use alloc::string::String;
@@ -34,6 +35,14 @@ pub trait StorageTypeInfo {
     ) -> Result<StorageInfo<'_, Self::TypeId>, StorageInfoError<'_>>;
 }
 
+/// Implementations of this are responsible for handing back the docs for storage entries,
+/// where the metadata contains them. This is implemented for the same metadata versions as
+/// [`StorageTypeInfo`].
+pub trait StorageDocsInfo {
+    /// Get the docs for a given storage entry, or `None` if the entry can't be found.
+    fn storage_docs(&self, pallet_name: &str, storage_entry: &str) -> Option<Vec<&str>>;
+}
+
 /// This can be implemented for anything capable of providing information about the available Storage Entries
 pub trait StorageEntryInfo {
     /// Iterate over all of the available Storage Entries, returning [`Entry`] as we go.
@@ -84,6 +93,9 @@ pub enum StorageInfoError<'info> {
         pallet_name: Cow<'info, str>,
         id: u32,
     },
+    #[cfg(feature = "legacy")]
+    #[error("The metadata was not SCALE decoded and so cannot be read")]
+    MetadataNotDecoded,
 }
 
 impl StorageInfoError<'_> {
@@ -126,6 +138,8 @@ impl StorageInfoError<'_> {
                 pallet_name: Cow::Owned(pallet_name.into_owned()),
                 id,
             },
+            #[cfg(feature = "legacy")]
+            StorageInfoError::MetadataNotDecoded => StorageInfoError::MetadataNotDecoded,
         }
     }
 }
@@ -149,6 +163,8 @@ pub struct StorageInfo<'info, TypeId: Clone> {
     /// On Kusama for instance, this should be set to true when using metadata from any spec
     /// version below 1032 in order to enable decoding correctly from it.
     pub use_old_v9_storage_hashers: bool,
+    /// Whether the storage entry is deprecated.
+    pub deprecation: Deprecation<'info>,
 }
 
 impl<'info, TypeId: Clone + 'static> StorageInfo<'info, TypeId> {
@@ -167,6 +183,7 @@ impl<'info, TypeId: Clone + 'static> StorageInfo<'info, TypeId> {
             value_id: self.value_id,
             default_value: self.default_value.map(|v| Cow::Owned(v.into_owned())),
             use_old_v9_storage_hashers: self.use_old_v9_storage_hashers,
+            deprecation: self.deprecation.into_owned(),
         }
     }
 
@@ -191,6 +208,7 @@ impl<'info, TypeId: Clone + 'static> StorageInfo<'info, TypeId> {
             value_id: new_value_id,
             default_value: self.default_value.map(|d| Cow::Owned(d.into_owned())),
             use_old_v9_storage_hashers: false,
+            deprecation: self.deprecation.into_owned(),
         })
     }
 }
@@ -205,7 +223,7 @@ pub struct StorageKeyInfo<TypeId> {
 }
 
 /// Hasher used by storage maps
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum StorageHasher {
     /// 128-bit Blake2 hash.
     Blake2_128,
@@ -250,6 +268,48 @@ impl StorageHasher {
             StorageHasher::Blake2_128Concat | StorageHasher::Twox64Concat | StorageHasher::Identity
         )
     }
+
+    /// Hash some SCALE encoded key, returning the bytes that represent it in a storage key. This
+    /// is the fixed size hash followed by the key itself, if [`StorageHasher::ends_with_key`].
+    pub fn hash(&self, key: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.len_excluding_key() + key.len());
+        self.hash_to(key, &mut out);
+        out
+    }
+
+    /// Like [`StorageHasher::hash`], but writes the bytes to the given output.
+    pub fn hash_to(&self, key: &[u8], out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.hash_excluding_key(key)[..self.len_excluding_key()]);
+        if self.ends_with_key() {
+            out.extend_from_slice(key);
+        }
+    }
+
+    /// Hash some SCALE encoded key without allocating. Only the first
+    /// [`StorageHasher::len_excluding_key`] bytes of the returned buffer are used; the key
+    /// itself is never appended.
+    pub(crate) fn hash_excluding_key(&self, key: &[u8]) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        match self {
+            StorageHasher::Blake2_128 | StorageHasher::Blake2_128Concat => {
+                out[..16].copy_from_slice(&sp_crypto_hashing::blake2_128(key));
+            }
+            StorageHasher::Blake2_256 => {
+                out = sp_crypto_hashing::blake2_256(key);
+            }
+            StorageHasher::Twox128 => {
+                out[..16].copy_from_slice(&sp_crypto_hashing::twox_128(key));
+            }
+            StorageHasher::Twox256 => {
+                out = sp_crypto_hashing::twox_256(key);
+            }
+            StorageHasher::Twox64Concat => {
+                out[..8].copy_from_slice(&sp_crypto_hashing::twox_64(key));
+            }
+            StorageHasher::Identity => {}
+        }
+        out
+    }
 }
 
 macro_rules! impl_storage_type_info_for_v14_to_v16 {
@@ -274,6 +334,7 @@ macro_rules! impl_storage_type_info_for_v14_to_v16 {
                         value_id: value.id,
                         default_value,
                         use_old_v9_storage_hashers: false,
+                        deprecation: Deprecation::NotDeprecated,
                     }),
                     path::StorageEntryType::Map {
                         hashers,
@@ -300,6 +361,7 @@ macro_rules! impl_storage_type_info_for_v14_to_v16 {
                                 value_id,
                                 default_value,
                                 use_old_v9_storage_hashers: false,
+                                deprecation: Deprecation::NotDeprecated,
                             })
                         } else if let scale_info::TypeDef::Tuple(tuple) = &key_ty.type_def {
                             // Else, if the key is a tuple, we expect a matching number of hashers
@@ -320,6 +382,7 @@ macro_rules! impl_storage_type_info_for_v14_to_v16 {
                                     value_id,
                                     default_value,
                                     use_old_v9_storage_hashers: false,
+                                    deprecation: Deprecation::NotDeprecated,
                                 })
                             } else {
                                 // Hasher and key mismatch
@@ -374,7 +437,12 @@ macro_rules! impl_storage_type_info_for_v14_to_v16 {
                             pallet_name: Cow::Borrowed(&pallet.name),
                         })?;
 
-                    storage_entry_type_to_storage_info(&pallet.name, &storage, &self.types)
+                    let info =
+                        storage_entry_type_to_storage_info(&pallet.name, &storage, &self.types)?;
+                    Ok(StorageInfo {
+                        deprecation: pallet.deprecation_of(storage),
+                        ..info
+                    })
                 }
             }
             impl StorageEntryInfo for path::$name {
@@ -437,6 +505,27 @@ impl_storage_type_info_for_v14_to_v16!(
     to_storage_hasher_v16
 );
 
+macro_rules! impl_storage_docs_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl StorageDocsInfo for $path {
+            fn storage_docs(&self, pallet_name: &str, storage_entry: &str) -> Option<Vec<&str>> {
+                let pallet = self.pallets.iter().find(|p| p.name == pallet_name)?;
+                let entry = pallet
+                    .storage
+                    .as_ref()?
+                    .entries
+                    .iter()
+                    .find(|e| e.name == storage_entry)?;
+                Some(to_docs(&entry.docs))
+            }
+        }
+    };
+}
+
+impl_storage_docs_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_storage_docs_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_storage_docs_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
 macro_rules! to_latest_storage_hasher {
     ($ident:ident, $path:path) => {
         fn $ident(hasher: &$path) -> StorageHasher {
@@ -460,7 +549,7 @@ to_latest_storage_hasher!(to_storage_hasher_v16, frame_metadata::v16::StorageHas
 #[cfg(feature = "legacy")]
 mod legacy {
     use super::*;
-    use crate::utils::as_decoded;
+    use crate::utils::as_decoded_str;
     use alloc::format;
     use frame_metadata::decode_different::DecodeDifferent;
     use scale_info_legacy::LookupName;
@@ -477,28 +566,30 @@ mod legacy {
                         pallet_name: &str,
                         storage_entry: &str,
                     ) -> Result<StorageInfo<'_, Self::TypeId>, StorageInfoError<'_>> {
-                        let modules = as_decoded(&self.modules);
+                        let modules = as_decoded(&self.modules)?;
 
                         let m = modules
                             .iter()
-                            .find(|m| as_decoded(&m.name).as_ref() as &str == pallet_name)
+                            .find(|m| as_decoded_str(&m.name) == pallet_name)
                             .ok_or_else(|| StorageInfoError::PalletNotFound {
                                 pallet_name: pallet_name.to_owned(),
                             })?;
 
-                        let pallet_name = as_decoded(&m.name);
+                        let pallet_name = as_decoded_str(&m.name);
 
                         let storages =
-                            m.storage.as_ref().map(|s| as_decoded(s)).ok_or_else(|| {
-                                StorageInfoError::StorageNotFound {
+                            m.storage
+                                .as_ref()
+                                .map(as_decoded)
+                                .transpose()?
+                                .ok_or_else(|| StorageInfoError::StorageNotFound {
                                     name: storage_entry.to_owned(),
                                     pallet_name: Cow::Borrowed(pallet_name),
-                                }
-                            })?;
+                                })?;
 
-                        let storage = as_decoded(&storages.entries)
+                        let storage = as_decoded(&storages.entries)?
                             .iter()
-                            .find(|s| as_decoded(&s.name).as_ref() as &str == storage_entry)
+                            .find(|s| as_decoded_str(&s.name) == storage_entry)
                             .ok_or_else(|| StorageInfoError::StorageNotFound {
                                 name: storage_entry.to_owned(),
                                 pallet_name: Cow::Borrowed(pallet_name),
@@ -507,7 +598,7 @@ mod legacy {
                         let default_value = match storage.modifier {
                             path::StorageEntryModifier::Optional => None,
                             path::StorageEntryModifier::Default => {
-                                Some(Cow::Borrowed(&**as_decoded(&storage.default)))
+                                Some(Cow::Borrowed(&**as_decoded(&storage.default)?))
                             }
                         };
 
@@ -519,6 +610,7 @@ mod legacy {
                                     value_id,
                                     default_value,
                                     use_old_v9_storage_hashers: false,
+                                    deprecation: Deprecation::NotDeprecated,
                                 })
                             }
                             path::StorageEntryType::Map {
@@ -548,6 +640,7 @@ mod legacy {
                                     value_id,
                                     default_value,
                                     use_old_v9_storage_hashers: false,
+                                    deprecation: Deprecation::NotDeprecated,
                                 })
                             }
                             path::StorageEntryType::DoubleMap {
@@ -576,6 +669,7 @@ mod legacy {
                                     value_id,
                                     default_value,
                                     use_old_v9_storage_hashers: false,
+                                    deprecation: Deprecation::NotDeprecated,
                                 })
                             }
                         }
@@ -583,23 +677,23 @@ mod legacy {
                 }
                 impl StorageEntryInfo for path::$name {
                     fn storage_entries(&self) -> impl Iterator<Item = StorageEntry<'_>> {
-                        use crate::utils::as_decoded;
-                        as_decoded(&self.modules).iter().flat_map(|module| {
+                        let modules = as_decoded(&self.modules).into_iter().flatten();
+                        modules.flat_map(|module| {
                             let Some(storage) = &module.storage else {
                                 return Either::Left(core::iter::empty());
                             };
-                            let pallet = as_decoded(&module.name);
-                            let storage = as_decoded(storage);
-                            let entries = as_decoded(&storage.entries);
-
-                            Either::Right(
-                                core::iter::once(Entry::In(Cow::Borrowed(pallet.as_ref()))).chain(
-                                    entries.iter().map(|e| {
-                                        let entry = as_decoded(&e.name);
-                                        Entry::Name(Cow::Borrowed(entry.as_ref()))
-                                    }),
-                                ),
-                            )
+                            let pallet = as_decoded_str(&module.name);
+                            let entries = as_decoded(storage)
+                                .and_then(|storage| as_decoded(&storage.entries))
+                                .into_iter()
+                                .flatten();
+
+                            Either::Right(core::iter::once(Entry::In(Cow::Borrowed(pallet))).chain(
+                                entries.map(|e| {
+                                    let entry = as_decoded_str(&e.name);
+                                    Entry::Name(Cow::Borrowed(entry))
+                                }),
+                            ))
                         })
                     }
                     fn storage_in_pallet(
@@ -607,8 +701,9 @@ mod legacy {
                         pallet_name: &str,
                     ) -> impl Iterator<Item = Cow<'_, str>> {
                         let module = as_decoded(&self.modules)
-                            .iter()
-                            .find(|p| as_decoded(&p.name) == &pallet_name);
+                            .into_iter()
+                            .flatten()
+                            .find(|p| as_decoded_str(&p.name) == pallet_name);
 
                         let Some(module) = module else {
                             return Either::Left(core::iter::empty());
@@ -617,12 +712,14 @@ mod legacy {
                             return Either::Left(core::iter::empty());
                         };
 
-                        let storage = as_decoded(storage);
-                        let entries = as_decoded(&storage.entries);
+                        let entries = as_decoded(storage)
+                            .and_then(|storage| as_decoded(&storage.entries))
+                            .into_iter()
+                            .flatten();
 
-                        let module_constants = entries.iter().map(|s| {
-                            let entry_name = as_decoded(&s.name);
-                            Cow::Borrowed(&**entry_name)
+                        let module_constants = entries.map(|s| {
+                            let entry_name = as_decoded_str(&s.name);
+                            Cow::Borrowed(entry_name)
                         });
 
                         Either::Right(module_constants)
@@ -671,27 +768,30 @@ mod legacy {
             pallet_name: &str,
             storage_entry: &str,
         ) -> Result<StorageInfo<'_, Self::TypeId>, StorageInfoError<'_>> {
-            let modules = as_decoded(&self.modules);
+            let modules = as_decoded(&self.modules)?;
 
             let m = modules
                 .iter()
-                .find(|m| as_decoded(&m.name).as_ref() as &str == pallet_name)
+                .find(|m| as_decoded_str(&m.name) == pallet_name)
                 .ok_or_else(|| StorageInfoError::PalletNotFound {
                     pallet_name: pallet_name.to_owned(),
                 })?;
 
-            let pallet_name = as_decoded(&m.name);
+            let pallet_name = as_decoded_str(&m.name);
 
-            let storages = m.storage.as_ref().map(as_decoded).ok_or_else(|| {
-                StorageInfoError::StorageNotFound {
+            let storages = m
+                .storage
+                .as_ref()
+                .map(as_decoded)
+                .transpose()?
+                .ok_or_else(|| StorageInfoError::StorageNotFound {
                     name: storage_entry.to_owned(),
                     pallet_name: Cow::Borrowed(pallet_name),
-                }
-            })?;
+                })?;
 
-            let storage = as_decoded(&storages.entries)
+            let storage = as_decoded(&storages.entries)?
                 .iter()
-                .find(|s| as_decoded(&s.name).as_ref() as &str == storage_entry)
+                .find(|s| as_decoded_str(&s.name) == storage_entry)
                 .ok_or_else(|| StorageInfoError::StorageNotFound {
                     name: storage_entry.to_owned(),
                     pallet_name: Cow::Borrowed(pallet_name),
@@ -700,11 +800,11 @@ mod legacy {
             let default_value = match storage.modifier {
                 frame_metadata::v13::StorageEntryModifier::Optional => None,
                 frame_metadata::v13::StorageEntryModifier::Default => {
-                    Some(Cow::Borrowed(&**as_decoded(&storage.default)))
+                    Some(Cow::Borrowed(&**as_decoded(&storage.default)?))
                 }
             };
 
-            let storage_name = as_decoded(&storage.name);
+            let storage_name = as_decoded_str(&storage.name);
 
             match &storage.ty {
                 frame_metadata::v13::StorageEntryType::Plain(ty) => {
@@ -714,6 +814,7 @@ mod legacy {
                         value_id,
                         default_value,
                         use_old_v9_storage_hashers: false,
+                        deprecation: Deprecation::NotDeprecated,
                     })
                 }
                 frame_metadata::v13::StorageEntryType::Map {
@@ -727,6 +828,7 @@ mod legacy {
                         value_id,
                         default_value,
                         use_old_v9_storage_hashers: false,
+                        deprecation: Deprecation::NotDeprecated,
                     })
                 }
                 frame_metadata::v13::StorageEntryType::DoubleMap {
@@ -755,6 +857,7 @@ mod legacy {
                         value_id,
                         default_value,
                         use_old_v9_storage_hashers: false,
+                        deprecation: Deprecation::NotDeprecated,
                     })
                 }
                 frame_metadata::v13::StorageEntryType::NMap {
@@ -762,8 +865,8 @@ mod legacy {
                     hashers,
                     value,
                 } => {
-                    let keys = as_decoded(keys);
-                    let hashers = as_decoded(hashers);
+                    let keys = as_decoded(keys)?;
+                    let hashers = as_decoded(hashers)?;
                     let value_id = decode_lookup_name_or_err(value, pallet_name)?;
 
                     let keys: Result<Vec<_>, StorageInfoError<'_>> = if hashers.len() == keys.len()
@@ -798,6 +901,7 @@ mod legacy {
                         value_id,
                         default_value,
                         use_old_v9_storage_hashers: false,
+                        deprecation: Deprecation::NotDeprecated,
                     })
                 }
             }
@@ -805,30 +909,31 @@ mod legacy {
     }
     impl StorageEntryInfo for frame_metadata::v13::RuntimeMetadataV13 {
         fn storage_entries(&self) -> impl Iterator<Item = StorageEntry<'_>> {
-            use crate::utils::as_decoded;
-            as_decoded(&self.modules).iter().flat_map(|module| {
+            let modules = as_decoded(&self.modules).into_iter().flatten();
+            modules.flat_map(|module| {
                 let Some(storage) = &module.storage else {
                     return Either::Left(core::iter::empty());
                 };
-                let pallet = as_decoded(&module.name);
-                let storage = as_decoded(storage);
-                let entries = as_decoded(&storage.entries);
-
-                Either::Right(
-                    core::iter::once(Entry::In(Cow::Borrowed(pallet.as_ref()))).chain(
-                        entries.iter().map(|e| {
-                            let entry = as_decoded(&e.name);
-                            Entry::Name(Cow::Borrowed(entry.as_ref()))
-                        }),
-                    ),
-                )
+                let pallet = as_decoded_str(&module.name);
+                let entries = as_decoded(storage)
+                    .and_then(|storage| as_decoded(&storage.entries))
+                    .into_iter()
+                    .flatten();
+
+                Either::Right(core::iter::once(Entry::In(Cow::Borrowed(pallet))).chain(
+                    entries.map(|e| {
+                        let entry = as_decoded_str(&e.name);
+                        Entry::Name(Cow::Borrowed(entry))
+                    }),
+                ))
             })
         }
 
         fn storage_in_pallet(&self, pallet_name: &str) -> impl Iterator<Item = Cow<'_, str>> {
             let module = as_decoded(&self.modules)
-                .iter()
-                .find(|p| as_decoded(&p.name).as_ref() as &str == pallet_name);
+                .into_iter()
+                .flatten()
+                .find(|p| as_decoded_str(&p.name) == pallet_name);
 
             let Some(module) = module else {
                 return Either::Left(core::iter::empty());
@@ -837,12 +942,14 @@ mod legacy {
                 return Either::Left(core::iter::empty());
             };
 
-            let storage = as_decoded(storage);
-            let entries = as_decoded(&storage.entries);
+            let entries = as_decoded(storage)
+                .and_then(|storage| as_decoded(&storage.entries))
+                .into_iter()
+                .flatten();
 
-            let module_constants = entries.iter().map(|s| {
-                let entry_name = as_decoded(&s.name);
-                Cow::Borrowed(&**entry_name)
+            let module_constants = entries.map(|s| {
+                let entry_name = as_decoded_str(&s.name);
+                Cow::Borrowed(entry_name)
             });
 
             Either::Right(module_constants)
@@ -883,11 +990,15 @@ mod legacy {
     to_latest_storage_hasher!(to_storage_hasher_v12, frame_metadata::v12::StorageHasher);
     to_latest_storage_hasher!(to_storage_hasher_v13, frame_metadata::v13::StorageHasher);
 
+    fn as_decoded<A, B>(item: &DecodeDifferent<A, B>) -> Result<&B, StorageInfoError<'static>> {
+        crate::utils::as_decoded(item).map_err(|_| StorageInfoError::MetadataNotDecoded)
+    }
+
     fn decode_lookup_name_or_err<S: AsRef<str>>(
         s: &DecodeDifferent<&str, S>,
         pallet_name: &str,
     ) -> Result<LookupName, StorageInfoError<'static>> {
-        let ty = sanitize_type_name(as_decoded(s).as_ref());
+        let ty = sanitize_type_name(as_decoded_str(s));
         lookup_name_or_err(&ty, pallet_name)
     }
 
@@ -895,13 +1006,43 @@ mod legacy {
         s: &DecodeDifferent<&str, S>,
         pallet_name: &str,
     ) -> Result<LookupName, StorageInfoError<'static>> {
-        let ty = sanitize_type_name(as_decoded(s).as_ref());
+        let ty = sanitize_type_name(as_decoded_str(s));
         // Append a hardcoded::Linked type to the end, which we expect in the type definitions
         // to be something like { previous: Option<AccountId>, next: Option<AccountId> }:
         let ty = format!("({ty}, hardcoded::Linked)");
         lookup_name_or_err(&ty, pallet_name)
     }
 
+    macro_rules! impl_storage_docs_info_for_v8_to_v13 {
+        ($path:path) => {
+            impl StorageDocsInfo for $path {
+                fn storage_docs(
+                    &self,
+                    pallet_name: &str,
+                    storage_entry: &str,
+                ) -> Option<Vec<&str>> {
+                    let m = as_decoded(&self.modules)
+                        .ok()?
+                        .iter()
+                        .find(|m| as_decoded_str(&m.name) == pallet_name)?;
+                    let storage = as_decoded(m.storage.as_ref()?).ok()?;
+                    let entry = as_decoded(&storage.entries)
+                        .ok()?
+                        .iter()
+                        .find(|e| as_decoded_str(&e.name) == storage_entry)?;
+                    Some(to_docs(&as_decoded(&entry.documentation).ok()?[..]))
+                }
+            }
+        };
+    }
+
+    impl_storage_docs_info_for_v8_to_v13!(frame_metadata::v8::RuntimeMetadataV8);
+    impl_storage_docs_info_for_v8_to_v13!(frame_metadata::v9::RuntimeMetadataV9);
+    impl_storage_docs_info_for_v8_to_v13!(frame_metadata::v10::RuntimeMetadataV10);
+    impl_storage_docs_info_for_v8_to_v13!(frame_metadata::v11::RuntimeMetadataV11);
+    impl_storage_docs_info_for_v8_to_v13!(frame_metadata::v12::RuntimeMetadataV12);
+    impl_storage_docs_info_for_v8_to_v13!(frame_metadata::v13::RuntimeMetadataV13);
+
     fn lookup_name_or_err(
         ty: &str,
         pallet_name: &str,
@@ -923,3 +1064,40 @@ mod legacy {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_storage_hasher_hash() {
+        assert_eq!(
+            StorageHasher::Twox128.hash(b"System"),
+            hex::decode("26aa394eea5630e07c48ae0c9558cef7").unwrap()
+        );
+        assert_eq!(StorageHasher::Identity.hash(&[1, 2, 3]), vec![1, 2, 3]);
+
+        let hashed = StorageHasher::Blake2_128Concat.hash(&[1, 2, 3]);
+        assert_eq!(&hashed[..16], &sp_crypto_hashing::blake2_128(&[1, 2, 3]));
+        assert_eq!(&hashed[16..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_storage_hasher_hash_excluding_key() {
+        let hashers = [
+            StorageHasher::Blake2_128,
+            StorageHasher::Blake2_256,
+            StorageHasher::Blake2_128Concat,
+            StorageHasher::Twox128,
+            StorageHasher::Twox256,
+            StorageHasher::Twox64Concat,
+            StorageHasher::Identity,
+        ];
+        for hasher in hashers {
+            let hash_len = hasher.len_excluding_key();
+            let hashed = hasher.hash(&[1, 2, 3]);
+            let hashed_excluding_key = hasher.hash_excluding_key(&[1, 2, 3]);
+            assert_eq!(&hashed_excluding_key[..hash_len], &hashed[..hash_len]);
+        }
+    }
+}
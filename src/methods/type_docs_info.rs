@@ -0,0 +1,289 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::utils::{find_variant, to_docs};
+use alloc::vec::Vec;
+use scale_info::PortableRegistry;
+
+/// Implementations of this are responsible for handing back the docs for types and enum
+/// variants in some type registry. Legacy type registries do not contain docs, and so this is
+/// only implemented for [`PortableRegistry`] and things which contain one.
+pub trait TypeDocsInfo {
+    /// The type of type IDs that we are using to obtain type information.
+    type TypeId;
+
+    /// Get the docs for a given type, or `None` if the type can't be found.
+    fn type_docs(&self, type_id: Self::TypeId) -> Option<Vec<&str>>;
+
+    /// Get the docs for a given variant of some enum type, or `None` if the type can't be
+    /// found, is not an enum, or has no variant with the given name.
+    fn variant_docs(&self, type_id: Self::TypeId, variant_name: &str) -> Option<Vec<&str>>;
+}
+
+impl TypeDocsInfo for PortableRegistry {
+    type TypeId = u32;
+
+    fn type_docs(&self, type_id: Self::TypeId) -> Option<Vec<&str>> {
+        let ty = self.resolve(type_id)?;
+        Some(to_docs(&ty.docs))
+    }
+
+    fn variant_docs(&self, type_id: Self::TypeId, variant_name: &str) -> Option<Vec<&str>> {
+        let variant = find_variant(self, type_id, variant_name)?;
+        Some(to_docs(&variant.docs))
+    }
+}
+
+macro_rules! impl_type_docs_info_for_v14_to_v16 {
+    ($path:path) => {
+        impl TypeDocsInfo for $path {
+            type TypeId = u32;
+
+            fn type_docs(&self, type_id: Self::TypeId) -> Option<Vec<&str>> {
+                self.types.type_docs(type_id)
+            }
+
+            fn variant_docs(&self, type_id: Self::TypeId, variant_name: &str) -> Option<Vec<&str>> {
+                self.types.variant_docs(type_id, variant_name)
+            }
+        }
+    };
+}
+
+impl_type_docs_info_for_v14_to_v16!(frame_metadata::v14::RuntimeMetadataV14);
+impl_type_docs_info_for_v14_to_v16!(frame_metadata::v15::RuntimeMetadataV15);
+impl_type_docs_info_for_v14_to_v16!(frame_metadata::v16::RuntimeMetadataV16);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::any_metadata::{AnyMetadata, AnyTypeId};
+    use crate::methods::constant_type_info::ConstantDocsInfo;
+    use crate::methods::event_type_info::EventDocsInfo;
+    use crate::methods::extrinsic_type_info::ExtrinsicDocsInfo;
+    use crate::methods::runtime_api_type_info::RuntimeApiDocsInfo;
+    use crate::methods::storage_type_info::StorageDocsInfo;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::Decode;
+
+    fn load_metadata(path: &str) -> RuntimeMetadata {
+        let bytes = std::fs::read(path).unwrap();
+        RuntimeMetadata::decode(&mut &*bytes).unwrap()
+    }
+
+    // The item docs are the same whichever metadata version we look at, give or take some
+    // leading whitespace which was trimmed from later versions.
+    fn check_item_docs<Md>(metadata: &Md)
+    where
+        Md: ExtrinsicDocsInfo + EventDocsInfo + StorageDocsInfo + ConstantDocsInfo,
+    {
+        let trimmed = |docs: Option<Vec<&str>>| -> Vec<String> {
+            docs.unwrap().iter().map(|d| d.trim().to_owned()).collect()
+        };
+
+        let call_docs = trimmed(metadata.extrinsic_call_docs("Balances", "transfer"));
+        assert_eq!(
+            call_docs[0],
+            "Transfer some liquid free balance to another account."
+        );
+        let event_docs = trimmed(metadata.event_docs("Balances", "Transfer"));
+        assert!(event_docs[0].starts_with("Transfer succeeded."));
+        assert_eq!(
+            trimmed(metadata.storage_docs("System", "Account")),
+            ["The full account information for a particular account ID."]
+        );
+        assert_eq!(
+            trimmed(metadata.constant_docs("Balances", "ExistentialDeposit")),
+            ["The minimum amount required to keep an account open."]
+        );
+
+        // Items that don't exist have no docs.
+        assert!(metadata.extrinsic_call_docs("Balances", "nope").is_none());
+        assert!(metadata.event_docs("Nope", "Transfer").is_none());
+        assert!(metadata.storage_docs("System", "Nope").is_none());
+        assert!(metadata.constant_docs("Balances", "Nope").is_none());
+        assert!(
+            metadata
+                .extrinsic_call_arg_docs("Balances", "transfer", "nope")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn modern_docs() {
+        let RuntimeMetadata::V14(mut metadata) =
+            load_metadata("artifacts/metadata_10000000_9180.scale")
+        else {
+            panic!("Expected V14 metadata")
+        };
+        check_item_docs(&metadata);
+
+        assert_eq!(
+            metadata.extrinsic_call_arg_docs("Balances", "transfer", "dest"),
+            Some(vec![])
+        );
+
+        // Nothing in this metadata has argument docs, so give one some to check that
+        // they are handed back.
+        let balances_calls = metadata
+            .pallets
+            .iter()
+            .find(|p| p.name == "Balances")
+            .unwrap()
+            .calls
+            .as_ref()
+            .unwrap()
+            .ty
+            .id;
+        let ty = &mut metadata.types.types[balances_calls as usize].ty;
+        let scale_info::TypeDef::Variant(calls) = &mut ty.type_def else {
+            panic!("Expected calls to be a variant")
+        };
+        let transfer = calls
+            .variants
+            .iter_mut()
+            .find(|v| v.name == "transfer")
+            .unwrap();
+        transfer.fields[0].docs = vec!["The account to transfer to.".into()];
+        assert_eq!(
+            metadata.extrinsic_call_arg_docs("Balances", "transfer", "dest"),
+            Some(vec!["The account to transfer to."])
+        );
+
+        // Type and variant docs come from the type registry.
+        let system_event = metadata
+            .pallets
+            .iter()
+            .find(|p| p.name == "System")
+            .unwrap()
+            .event
+            .as_ref()
+            .unwrap()
+            .ty
+            .id;
+        assert_eq!(
+            metadata.type_docs(system_event),
+            Some(vec!["Event for the System pallet."])
+        );
+        assert_eq!(
+            metadata.variant_docs(system_event, "ExtrinsicSuccess"),
+            Some(vec!["An extrinsic completed successfully."])
+        );
+        assert!(metadata.variant_docs(system_event, "Nope").is_none());
+        assert!(metadata.type_docs(u32::MAX).is_none());
+
+        // AnyMetadata hands back the same docs.
+        let metadata = AnyMetadata::new(RuntimeMetadata::V14(metadata)).unwrap();
+        check_item_docs(&metadata);
+        assert_eq!(
+            metadata.extrinsic_call_arg_docs("Balances", "transfer", "dest"),
+            Some(vec!["The account to transfer to."])
+        );
+        assert_eq!(
+            metadata.variant_docs(AnyTypeId::Modern(system_event), "ExtrinsicSuccess"),
+            Some(vec!["An extrinsic completed successfully."])
+        );
+    }
+
+    #[test]
+    fn legacy_docs() {
+        let RuntimeMetadata::V12(metadata) = load_metadata("artifacts/metadata_5000000_30.scale")
+        else {
+            panic!("Expected V12 metadata")
+        };
+        check_item_docs(&metadata);
+
+        // Legacy metadata has no docs for call arguments.
+        assert_eq!(
+            metadata.extrinsic_call_arg_docs("Balances", "transfer", "dest"),
+            Some(vec![])
+        );
+
+        let legacy_types = crate::legacy_types::polkadot::relay_chain();
+        let metadata = AnyMetadata::new(RuntimeMetadata::V12(metadata))
+            .unwrap()
+            .with_legacy_types(legacy_types.for_spec_version(30))
+            .unwrap();
+        check_item_docs(&metadata);
+
+        // Legacy types carry no docs.
+        let event_ty =
+            AnyTypeId::Legacy(scale_info_legacy::LookupName::parse("builtin::Event").unwrap());
+        assert!(metadata.type_docs(event_ty.clone()).is_none());
+        assert!(metadata.variant_docs(event_ty, "Balances").is_none());
+    }
+
+    #[test]
+    fn runtime_api_docs() {
+        use frame_metadata::v16::*;
+        use scale_info::meta_type;
+
+        let api = RuntimeApiMetadata {
+            name: "Api",
+            methods: vec![RuntimeApiMethodMetadata {
+                name: "method",
+                inputs: vec![],
+                output: meta_type::<u32>(),
+                docs: vec!["Some method."],
+                deprecation_info: ItemDeprecationInfo::NotDeprecated,
+            }],
+            docs: vec!["Some API.", "More docs."],
+            version: 1u32.into(),
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+        let metadata = RuntimeMetadataV16::new(
+            vec![],
+            ExtrinsicMetadata {
+                versions: vec![4],
+                address_ty: meta_type::<u32>(),
+                call_ty: meta_type::<u32>(),
+                signature_ty: meta_type::<u32>(),
+                transaction_extensions_by_version: Default::default(),
+                transaction_extensions: vec![],
+            },
+            vec![api],
+            OuterEnums {
+                call_enum_ty: meta_type::<u32>(),
+                event_enum_ty: meta_type::<u32>(),
+                error_enum_ty: meta_type::<u32>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        );
+
+        assert_eq!(
+            metadata.runtime_api_trait_docs("Api"),
+            Some(vec!["Some API.", "More docs."])
+        );
+        assert_eq!(
+            metadata.runtime_api_docs("Api", "method"),
+            Some(vec!["Some method."])
+        );
+        assert!(metadata.runtime_api_trait_docs("Nope").is_none());
+        assert!(metadata.runtime_api_docs("Api", "nope").is_none());
+
+        let metadata = AnyMetadata::new(RuntimeMetadata::V16(metadata)).unwrap();
+        assert_eq!(
+            metadata.runtime_api_docs("Api", "method"),
+            Some(vec!["Some method."])
+        );
+
+        // V14 metadata has no Runtime APIs, and so no docs for them.
+        let metadata =
+            AnyMetadata::new(load_metadata("artifacts/metadata_10000000_9180.scale")).unwrap();
+        assert!(metadata.runtime_api_trait_docs("Core").is_none());
+    }
+}
@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::deprecation::{DeprecationPolicy, DeprecationWarning, check_deprecation};
 use super::view_function_type_info::{
     ViewFunctionInfo, ViewFunctionInfoError, ViewFunctionTypeInfo,
 };
@@ -34,6 +35,8 @@ pub enum ViewFunctionInputsEncodeError {
         /// The number of input parameters that were expected.
         num_inputs_expected: usize,
     },
+    #[error("Cannot encode View Function inputs: {0}")]
+    Deprecated(DeprecationWarning),
 }
 
 /// The default name of the Runtime API that you must call to query a View Function, where
@@ -89,6 +92,49 @@ where
     encode_view_function_inputs_with_info_to(inputs, &view_function_api_info, type_resolver, out)
 }
 
+/// Encode the Runtime API input data necessary to call a View Function, applying the given
+/// [`DeprecationPolicy`] if the View Function is marked as deprecated in the metadata.
+///
+/// This is like [`encode_view_function_inputs`], but also hands back a [`DeprecationWarning`]
+/// if the View Function is deprecated and the policy is [`DeprecationPolicy::Warn`], and returns
+/// [`ViewFunctionInputsEncodeError::Deprecated`] if the View Function is deprecated and the
+/// policy is [`DeprecationPolicy::Deny`].
+pub fn encode_view_function_inputs_checked<Info, Resolver, Inputs>(
+    pallet_name: &str,
+    function_name: &str,
+    inputs: Inputs,
+    info: &Info,
+    type_resolver: &Resolver,
+    policy: DeprecationPolicy,
+) -> Result<(Vec<u8>, Option<DeprecationWarning>), ViewFunctionInputsEncodeError>
+where
+    Inputs: IntoEncodableValues,
+    Info: ViewFunctionTypeInfo,
+    Info::TypeId: Clone + core::fmt::Debug,
+    Resolver: TypeResolver<TypeId = Info::TypeId>,
+{
+    let view_function_api_info = info
+        .view_function_info(pallet_name, function_name)
+        .map_err(|e| ViewFunctionInputsEncodeError::CannotGetInfo(e.into_owned()))?;
+
+    let warning = check_deprecation(
+        policy,
+        pallet_name,
+        function_name,
+        &view_function_api_info.deprecation,
+    )
+    .map_err(ViewFunctionInputsEncodeError::Deprecated)?;
+
+    let mut out = Vec::with_capacity(32);
+    encode_view_function_inputs_with_info_to(
+        inputs,
+        &view_function_api_info,
+        type_resolver,
+        &mut out,
+    )?;
+    Ok((out, warning))
+}
+
 /// Encode to a provided output Vec the Runtime API input data necessary to call a View Function.
 ///
 /// Unlike [`encode_view_function_inputs_to`], which obtains the View Function info internally given trait and method names,
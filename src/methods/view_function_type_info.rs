@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use super::Entry;
+use super::deprecation::{Deprecation, DeprecationOf};
 use crate::utils::Either;
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
@@ -91,6 +92,8 @@ pub struct ViewFunctionInfo<'info, TypeId: Clone> {
     pub inputs: Cow<'info, [ViewFunctionInput<'info, TypeId>]>,
     /// The output type returned from the runtime API.
     pub output_id: TypeId,
+    /// Whether the View Function is deprecated.
+    pub deprecation: Deprecation<'info>,
 }
 
 impl<'info, TypeId: Clone> ViewFunctionInfo<'info, TypeId> {
@@ -106,6 +109,7 @@ impl<'info, TypeId: Clone> ViewFunctionInfo<'info, TypeId> {
             query_id: self.query_id,
             inputs,
             output_id: self.output_id,
+            deprecation: self.deprecation.into_owned(),
         }
     }
 
@@ -143,6 +147,7 @@ impl<'info, TypeId: Clone> ViewFunctionInfo<'info, TypeId> {
             query_id: self.query_id,
             inputs: Cow::Owned(new_inputs),
             output_id: new_output_id,
+            deprecation: self.deprecation.into_owned(),
         })
     }
 }
@@ -204,6 +209,7 @@ impl ViewFunctionTypeInfo for frame_metadata::v16::RuntimeMetadataV16 {
             query_id: view_fn.id,
             inputs,
             output_id: view_fn.output.id,
+            deprecation: pallet.deprecation_of(view_fn),
         })
     }
 }
@@ -17,27 +17,138 @@ mod decodable_values;
 mod decode_with_error_tracing;
 mod either;
 mod encodable_values;
+#[cfg(all(feature = "legacy", feature = "std"))]
+mod legacy_metadata_to_v14;
+mod trim_metadata;
 #[cfg(feature = "legacy")]
 mod type_registry_from_metadata;
+mod type_shape;
 
 pub use decodable_values::{DecodableValues, IntoDecodableValues};
 pub use encodable_values::{EncodableValues, IntoEncodableValues};
 
 pub use decode_with_error_tracing::{DecodeErrorTrace, decode_with_error_tracing};
 pub use either::Either;
+pub use trim_metadata::{
+    MetadataSelection, TrimMetadataError, TrimmableMetadata, trim_metadata, trim_metadata_any,
+};
+pub(crate) use type_shape::{FieldShape, TypeShape, VariantShape, resolve_type_shape};
+
+use crate::methods::outer_enum_type_info::OuterEnum;
 
+#[cfg(all(feature = "legacy", feature = "std"))]
+pub use legacy_metadata_to_v14::{
+    LegacyMetadataConversionError, ToRuntimeMetadataV14, legacy_metadata_to_v14,
+    legacy_metadata_to_v14_any,
+};
 #[cfg(feature = "legacy")]
 pub use type_registry_from_metadata::{
-    ToTypeRegistry, type_registry_from_metadata, type_registry_from_metadata_any,
+    ToTypeRegistry, TypeRegistryFromMetadataError, type_registry_from_metadata,
+    type_registry_from_metadata_any,
 };
 
+/// A utility function to collect some docs into `&str`s. This works whether scale-info
+/// hands back `String`s, or `&'static str`s as it does in no-std mode.
+pub(crate) fn to_docs<S: AsRef<str>>(docs: &[S]) -> alloc::vec::Vec<&str> {
+    docs.iter().map(|d| d.as_ref()).collect()
+}
+
+/// Find a variant in the given enum type by name.
+pub(crate) fn find_variant<'a>(
+    types: &'a scale_info::PortableRegistry,
+    id: u32,
+    name: &str,
+) -> Option<&'a scale_info::Variant<scale_info::form::PortableForm>> {
+    match &types.resolve(id)?.type_def {
+        scale_info::TypeDef::Variant(v) => v.variants.iter().find(|v| v.name == name),
+        _ => None,
+    }
+}
+
+/// In V8 to V11 metadata, the index of a pallet's calls and events can differ from the index of
+/// the pallet. When such metadata is converted into V14 metadata via `legacy_metadata_to_v14`,
+/// the `RuntimeCall` and `RuntimeEvent` outer enums are attached to the runtime type as type
+/// parameters, and their variant indexes are the call and event indexes of each pallet. This
+/// hands back those variants, if they exist, so that pallets can be found by these indexes.
+pub(crate) trait OuterEnumPalletIndexes {
+    /// Return the variants of the given outer enum, if they are attached to the runtime type.
+    fn outer_enum_pallet_variants(
+        &self,
+        outer_enum: OuterEnum,
+    ) -> Option<&[scale_info::Variant<scale_info::form::PortableForm>]>;
+}
+
+impl OuterEnumPalletIndexes for frame_metadata::v14::RuntimeMetadataV14 {
+    fn outer_enum_pallet_variants(
+        &self,
+        outer_enum: OuterEnum,
+    ) -> Option<&[scale_info::Variant<scale_info::form::PortableForm>]> {
+        let param_name = match outer_enum {
+            OuterEnum::Call => "RuntimeCall",
+            OuterEnum::Event => "RuntimeEvent",
+            // Errors are always reported using the index of the pallet.
+            OuterEnum::Error => return None,
+        };
+        let runtime_ty = self.types.resolve(self.ty.id)?;
+        let param = runtime_ty
+            .type_params
+            .iter()
+            .find(|p| p.name == param_name)?;
+        match &self.types.resolve(param.ty?.id)?.type_def {
+            scale_info::TypeDef::Variant(v) => Some(&v.variants),
+            _ => None,
+        }
+    }
+}
+
+// Only metadata converted into V14 metadata can have outer enums whose variant indexes
+// differ from the pallet indexes.
+impl OuterEnumPalletIndexes for frame_metadata::v15::RuntimeMetadataV15 {
+    fn outer_enum_pallet_variants(
+        &self,
+        _outer_enum: OuterEnum,
+    ) -> Option<&[scale_info::Variant<scale_info::form::PortableForm>]> {
+        None
+    }
+}
+
+impl OuterEnumPalletIndexes for frame_metadata::v16::RuntimeMetadataV16 {
+    fn outer_enum_pallet_variants(
+        &self,
+        _outer_enum: OuterEnum,
+    ) -> Option<&[scale_info::Variant<scale_info::form::PortableForm>]> {
+        None
+    }
+}
+
 /// A utility function to unwrap the `DecodeDifferent` enum found in earlier metadata versions.
+///
+/// Metadata which has been SCALE decoded always contains the `Decoded` variant. The `Encode` variant
+/// only exists in metadata constructed in-process, and we return an error if we encounter it.
 #[cfg(feature = "legacy")]
-pub fn as_decoded<A, B>(item: &frame_metadata::decode_different::DecodeDifferent<A, B>) -> &B {
+pub fn as_decoded<A, B>(
+    item: &frame_metadata::decode_different::DecodeDifferent<A, B>,
+) -> Result<&B, NotDecodedError> {
     match item {
-        frame_metadata::decode_different::DecodeDifferent::Encode(_a) => {
-            panic!("Expecting decoded data")
-        }
-        frame_metadata::decode_different::DecodeDifferent::Decoded(b) => b,
+        frame_metadata::decode_different::DecodeDifferent::Encode(_a) => Err(NotDecodedError),
+        frame_metadata::decode_different::DecodeDifferent::Decoded(b) => Ok(b),
+    }
+}
+
+/// An error returned from [`as_decoded`] if the metadata given was not SCALE decoded.
+#[cfg(feature = "legacy")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("The metadata was not SCALE decoded and so cannot be read")]
+pub struct NotDecodedError;
+
+/// A utility function to obtain the `&str` from a `DecodeDifferent` string found in earlier
+/// metadata versions. Unlike [`as_decoded`], both variants can be handled here.
+#[cfg(feature = "legacy")]
+pub fn as_decoded_str<'a, S: AsRef<str>>(
+    item: &'a frame_metadata::decode_different::DecodeDifferent<&'static str, S>,
+) -> &'a str {
+    match item {
+        frame_metadata::decode_different::DecodeDifferent::Encode(a) => a,
+        frame_metadata::decode_different::DecodeDifferent::Decoded(b) => b.as_ref(),
     }
 }
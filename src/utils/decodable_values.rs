@@ -64,11 +64,11 @@ pub trait DecodableValues {
 
     /// Return the decoded target.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This method may panic if [`DecodableValues::decode_next_value`] has
-    /// not been called enough times.
-    fn decoded_target(self) -> Self::Target;
+    /// This method will return an error if [`DecodableValues::decode_next_value`]
+    /// has not been called enough times.
+    fn decoded_target(self) -> Result<Self::Target, scale_decode::Error>;
 }
 
 // Vecs
@@ -99,8 +99,8 @@ impl<T: DecodeAsType> DecodableValues for Vec<T> {
         Ok(())
     }
 
-    fn decoded_target(self) -> Self::Target {
-        self
+    fn decoded_target(self) -> Result<Self::Target, scale_decode::Error> {
+        Ok(self)
     }
 }
 
@@ -149,22 +149,21 @@ impl<const N: usize, T: DecodeAsType> DecodableValues for DecodeArrayAsTypes<N,
         self.next_idx += 1;
         Ok(())
     }
-    fn decoded_target(self) -> Self::Target {
+    fn decoded_target(self) -> Result<Self::Target, scale_decode::Error> {
         if self.next_idx != N {
-            panic!(
+            let e = format!(
                 "decode_next_value was not called enough times (expected {N} calls, got {} calls) to decode [{}; N]",
                 self.next_idx,
                 core::any::type_name::<T>()
-            )
+            );
+            return Err(scale_decode::Error::custom_string(e));
         }
 
-        // This could be done slightly more efficiently with unsafe and MaybeUninit but not worth it.
-        let mut items = self.items;
-        core::array::from_fn(|idx| {
-            items[idx]
-                .take()
-                .expect("Item should be present in DecodeArrayAsType array")
-        })
+        // Every item has been decoded given the above, so this never errors.
+        let items: Vec<T> = self.items.into_iter().flatten().collect();
+        items
+            .try_into()
+            .map_err(|_| scale_decode::Error::custom_str("Not every item in the array was decoded"))
     }
 }
 
@@ -194,7 +193,9 @@ impl DecodableValues for () {
         ))
     }
 
-    fn decoded_target(self) -> Self::Target {}
+    fn decoded_target(self) -> Result<Self::Target, scale_decode::Error> {
+        Ok(())
+    }
 }
 
 // Non-empty tuples
@@ -243,20 +244,21 @@ macro_rules! impl_tuple_decodable {
                     )*
                     Err(scale_decode::Error::custom_str("decode_next_value called but no more tuple entries to decode"))
                 }
-                fn decoded_target(self) -> Self::Target {
-                    if self.idx != TUPLE_LEN {
-                        panic!(
+                fn decoded_target(self) -> Result<Self::Target, scale_decode::Error> {
+                    let not_enough_calls = || {
+                        let e = format!(
                             "decode_next_value not called enough times (expected {TUPLE_LEN} calls, got {} calls) to decode {}",
                             self.idx,
                             core::any::type_name::<Self::Target>()
-                        )
-                    }
+                        );
+                        scale_decode::Error::custom_string(e)
+                    };
 
-                    (
+                    Ok((
                         $(
-                          self.items.$number.unwrap(),
+                          self.items.$number.ok_or_else(not_enough_calls)?,
                         )*
-                    )
+                    ))
                 }
             }
         };
@@ -306,7 +308,7 @@ mod test {
             .unwrap_err();
 
         // This basically checks that the type of `.decoded_target()` is `()`:
-        let () = decodable.decoded_target();
+        let () = decodable.decoded_target().unwrap();
     }
 
     #[test]
@@ -336,7 +338,7 @@ mod test {
             .unwrap_err();
 
         assert_eq!(
-            decodable.decoded_target(),
+            decodable.decoded_target().unwrap(),
             (true, String::from("hello"), 123u64)
         );
     }
@@ -357,7 +359,7 @@ mod test {
             .decode_next_value(&mut &*1u32.encode(), ln("u32"), &types)
             .unwrap_err();
 
-        assert_eq!(decodable.decoded_target(), [] as [u64; 0]);
+        assert_eq!(decodable.decoded_target().unwrap(), [] as [u64; 0]);
     }
 
     #[test]
@@ -386,6 +388,25 @@ mod test {
             .decode_next_value(&mut &*4u32.encode(), ln("u32"), &types)
             .unwrap_err();
 
-        assert_eq!(decodable.decoded_target(), [1u64, 2u64, 3u64]);
+        assert_eq!(decodable.decoded_target().unwrap(), [1u64, 2u64, 3u64]);
+    }
+
+    #[test]
+    fn test_decoded_target_errors_if_not_enough_values() {
+        // We just need some basic types to test with.
+        let types = crate::legacy_types::polkadot::relay_chain();
+        let types = types.for_spec_version(0);
+
+        let mut decodable = <(bool, u64)>::into_decodable_values();
+        decodable
+            .decode_next_value(&mut &*true.encode(), ln("bool"), &types)
+            .unwrap();
+        decodable.decoded_target().unwrap_err();
+
+        let mut decodable = <[u64; 2]>::into_decodable_values();
+        decodable
+            .decode_next_value(&mut &*1u8.encode(), ln("u8"), &types)
+            .unwrap();
+        decodable.decoded_target().unwrap_err();
     }
 }
@@ -51,6 +51,22 @@ where
             // Reset cursor incase it's been consumed by the above call, and decode using the
             // tracing visitor to hopefully return a better error.
             *cursor = initial;
+
+            // The tracing visitor allocates space for every item in a sequence up front, so don't
+            // use it if the bytes claim to contain sequences longer than the bytes themselves.
+            let length_check = scale_decode::visitor::decode_with_visitor(
+                &mut &*initial,
+                type_id.clone(),
+                types,
+                LengthCheckVisitor::new(initial.len()),
+            );
+            if let Err(LengthCheckError::TooLong) = length_check {
+                return Err(DecodeErrorTrace {
+                    original_error: format!("{e:?}"),
+                    tracing_error: String::new(),
+                });
+            }
+
             let res = scale_value::scale::tracing::decode_as_type(cursor, type_id.clone(), types)
                 .map(|v| v.map_context(|id| format!("{id:?}")))
                 .map_err(|te| DecodeErrorTrace {
@@ -98,6 +114,124 @@ where
     }
 }
 
+// A visitor which walks over some value, returning an error if any sequence or array
+// claims to contain more items than the given maximum. Items are decoded directly rather
+// than via the iterators, which would skip over (and thus not check) each item first.
+#[cfg(feature = "error-tracing")]
+struct LengthCheckVisitor<R> {
+    max_len: usize,
+    marker: core::marker::PhantomData<R>,
+}
+
+#[cfg(feature = "error-tracing")]
+impl<R> LengthCheckVisitor<R> {
+    fn new(max_len: usize) -> Self {
+        LengthCheckVisitor {
+            max_len,
+            marker: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "error-tracing")]
+impl<R> Clone for LengthCheckVisitor<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "error-tracing")]
+impl<R> Copy for LengthCheckVisitor<R> {}
+
+#[cfg(feature = "error-tracing")]
+enum LengthCheckError {
+    TooLong,
+    Decode,
+}
+
+#[cfg(feature = "error-tracing")]
+impl From<scale_decode::visitor::DecodeError> for LengthCheckError {
+    fn from(_e: scale_decode::visitor::DecodeError) -> Self {
+        LengthCheckError::Decode
+    }
+}
+
+#[cfg(feature = "error-tracing")]
+const _: () = {
+    use scale_decode::visitor::{TypeIdFor, Unexpected, types};
+    use scale_type_resolver::TypeResolver;
+
+    impl<R: TypeResolver> scale_decode::Visitor for LengthCheckVisitor<R> {
+        type Value<'scale, 'resolver> = ();
+        type Error = LengthCheckError;
+        type TypeResolver = R;
+
+        fn visit_unexpected<'scale, 'resolver>(
+            self,
+            _unexpected: Unexpected,
+        ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+            Ok(())
+        }
+        fn visit_sequence<'scale, 'resolver>(
+            self,
+            value: &mut types::Sequence<'scale, 'resolver, R>,
+            _type_id: TypeIdFor<Self>,
+        ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+            if value.remaining() > self.max_len {
+                return Err(LengthCheckError::TooLong);
+            }
+            while let Some(res) = value.decode_item(self) {
+                res?;
+            }
+            Ok(())
+        }
+        fn visit_array<'scale, 'resolver>(
+            self,
+            value: &mut types::Array<'scale, 'resolver, R>,
+            _type_id: TypeIdFor<Self>,
+        ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+            if value.remaining() > self.max_len {
+                return Err(LengthCheckError::TooLong);
+            }
+            while let Some(res) = value.decode_item(self) {
+                res?;
+            }
+            Ok(())
+        }
+        fn visit_composite<'scale, 'resolver>(
+            self,
+            value: &mut types::Composite<'scale, 'resolver, R>,
+            _type_id: TypeIdFor<Self>,
+        ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+            while let Some(res) = value.decode_item(self) {
+                res?;
+            }
+            Ok(())
+        }
+        fn visit_tuple<'scale, 'resolver>(
+            self,
+            value: &mut types::Tuple<'scale, 'resolver, R>,
+            _type_id: TypeIdFor<Self>,
+        ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+            while let Some(res) = value.decode_item(self) {
+                res?;
+            }
+            Ok(())
+        }
+        fn visit_variant<'scale, 'resolver>(
+            self,
+            value: &mut types::Variant<'scale, 'resolver, R>,
+            _type_id: TypeIdFor<Self>,
+        ) -> Result<Self::Value<'scale, 'resolver>, Self::Error> {
+            let fields = value.fields();
+            while let Some(res) = fields.decode_item(self) {
+                res?;
+            }
+            Ok(())
+        }
+    }
+};
+
 /// A tracing decode error.
 #[derive(Clone, Debug, thiserror::Error)]
 pub struct DecodeErrorTrace {
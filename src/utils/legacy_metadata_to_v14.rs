@@ -0,0 +1,1055 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::methods::extrinsic_type_info::ExtrinsicInfoError;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use frame_metadata::v14::RuntimeMetadataV14;
+use scale_info::form::PortableForm;
+use scale_info::{
+    Field, Path, PortableRegistry, PortableType, Type, TypeDefArray, TypeDefBitSequence,
+    TypeDefCompact, TypeDefComposite, TypeDefPrimitive, TypeDefSequence, TypeDefTuple,
+    TypeDefVariant, TypeParameter, Variant,
+};
+use scale_info_legacy::LookupName;
+use scale_type_resolver::{
+    BitsOrderFormat, BitsStoreFormat, FieldIter, PathIter, Primitive, ResolvedTypeVisitor,
+    TypeResolver, UnhandledKind, VariantIter,
+};
+
+/// An error converting legacy metadata into [`RuntimeMetadataV14`].
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum LegacyMetadataConversionError {
+    #[error("Metadata version {0} cannot be converted to V14 metadata")]
+    UnsupportedVersion(u32),
+    #[error("Cannot resolve type {name}:\n\n{reason}")]
+    CannotResolveType { name: String, reason: String },
+    #[error("Type {name} was not found")]
+    TypeNotFound { name: String },
+    #[error("Type {name} could not be converted: {kind:?} types are not supported")]
+    UnhandledType { name: String, kind: UnhandledKind },
+    #[error("Cannot get extrinsic information:\n\n{0}")]
+    CannotGetExtrinsicInfo(ExtrinsicInfoError<'static>),
+    #[error("The metadata was not SCALE decoded and so cannot be read")]
+    MetadataNotDecoded,
+}
+
+/// Convert some legacy (V8 to V13) metadata into [`RuntimeMetadataV14`]. The types referenced in
+/// the legacy metadata are resolved using the given type resolver (usually a
+/// [`scale_info_legacy::TypeRegistrySet`] for the spec version that the metadata is from) and
+/// are added to the [`scale_info::PortableRegistry`] in the returned metadata. Calls, events,
+/// errors, storage entries and constants all point to concrete type IDs in this registry.
+///
+/// In V8 to V11 metadata, the index of a pallet's calls and events can differ, because pallets
+/// without calls or events are not counted when working out the index of them. V14 metadata has a
+/// single index per pallet, and so each pallet is given the index that its errors are reported
+/// with (ie its position in the metadata). The variants of the `RuntimeCall` and `RuntimeEvent`
+/// types in the returned metadata are given the correct call and event indexes, and these types
+/// are attached to the runtime type as the `RuntimeCall` and `RuntimeEvent` type parameters.
+/// Calls and events in the returned metadata are looked up by pallet index using these, and so
+/// decode just as they would with the legacy metadata.
+///
+/// The legacy types for a chain are often incomplete. Any calls, events, storage entries or constants
+/// whose types cannot be resolved are left out of the returned metadata, so that attempting to decode
+/// them will fail rather than producing incorrect results. The implicit data for transaction extensions
+/// is also often not described, since it is not needed to decode extrinsics, and is given the unit type
+/// when this is the case. The types needed to decode the extrinsic address, signature and transaction
+/// extensions must exist, else an error is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::extrinsics::decode_extrinsic;
+/// use frame_decode::metadata::legacy_metadata_to_v14;
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+/// let RuntimeMetadata::V12(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let legacy_types = frame_decode::legacy_types::polkadot::relay_chain();
+/// let types = legacy_types.for_spec_version(30);
+///
+/// // Convert the legacy metadata into V14 metadata:
+/// let metadata = legacy_metadata_to_v14(&metadata, &types).unwrap();
+///
+/// // We can then decode historic extrinsics using just this V14 metadata:
+/// let extrinsics_bytes = std::fs::read("artifacts/exts_5000000_30.json").unwrap();
+/// let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+///
+/// for ext_hex in extrinsics_hex {
+///     let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+///     let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+///     println!("{}.{}", ext.pallet_name(), ext.call_name());
+/// }
+/// ```
+pub fn legacy_metadata_to_v14<Md, Resolver>(
+    metadata: &Md,
+    types: &Resolver,
+) -> Result<RuntimeMetadataV14, LegacyMetadataConversionError>
+where
+    Md: ToRuntimeMetadataV14,
+    Resolver: TypeResolver<TypeId = LookupName>,
+{
+    metadata.to_runtime_metadata_v14(types)
+}
+
+/// This is like [`legacy_metadata_to_v14`], except it can be handed the outer [`frame_metadata::RuntimeMetadata`]
+/// enum. V14 metadata is returned as-is, and an error is returned for metadata that cannot be converted.
+pub fn legacy_metadata_to_v14_any<Resolver>(
+    metadata: &frame_metadata::RuntimeMetadata,
+    types: &Resolver,
+) -> Result<RuntimeMetadataV14, LegacyMetadataConversionError>
+where
+    Resolver: TypeResolver<TypeId = LookupName>,
+{
+    use frame_metadata::RuntimeMetadata;
+    match metadata {
+        RuntimeMetadata::V8(m) => m.to_runtime_metadata_v14(types),
+        RuntimeMetadata::V9(m) => m.to_runtime_metadata_v14(types),
+        RuntimeMetadata::V10(m) => m.to_runtime_metadata_v14(types),
+        RuntimeMetadata::V11(m) => m.to_runtime_metadata_v14(types),
+        RuntimeMetadata::V12(m) => m.to_runtime_metadata_v14(types),
+        RuntimeMetadata::V13(m) => m.to_runtime_metadata_v14(types),
+        RuntimeMetadata::V14(m) => Ok(m.clone()),
+        other => Err(LegacyMetadataConversionError::UnsupportedVersion(
+            other.version(),
+        )),
+    }
+}
+
+/// This is used with the [`legacy_metadata_to_v14`] function to convert legacy metadata into
+/// [`RuntimeMetadataV14`]. It is not intended to be implemented on anything else.
+pub trait ToRuntimeMetadataV14: sealed::Sealed {
+    /// Convert this metadata into [`RuntimeMetadataV14`], using the given types.
+    fn to_runtime_metadata_v14<Resolver>(
+        &self,
+        types: &Resolver,
+    ) -> Result<RuntimeMetadataV14, LegacyMetadataConversionError>
+    where
+        Resolver: TypeResolver<TypeId = LookupName>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+const _: () = {
+    use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
+    use crate::methods::storage_type_info::{StorageHasher, StorageInfoError, StorageTypeInfo};
+    use crate::utils::as_decoded_str;
+    use frame_metadata::v14;
+
+    macro_rules! impl_for_v8_to_v13 {
+        ($path:path, $storage_modifier:path, |$m:ident| $extrinsic_version:expr $(, $builtin_index:ident)?) => {
+            impl sealed::Sealed for $path {}
+            impl ToRuntimeMetadataV14 for $path {
+                fn to_runtime_metadata_v14<Resolver>(
+                    &self,
+                    types: &Resolver,
+                ) -> Result<RuntimeMetadataV14, LegacyMetadataConversionError>
+                where
+                    Resolver: TypeResolver<TypeId = LookupName>,
+                {
+                    let metadata = self;
+                    let modules = as_decoded(&metadata.modules)?;
+                    let mut converter = TypeConverter::new(types);
+
+                    let mut pallets = Vec::with_capacity(modules.len());
+                    let mut call_module_variants = vec![];
+                    let mut event_module_variants = vec![];
+
+                    let mut calls_index = 0u8;
+                    let mut events_index = 0u8;
+                    let mut errors_index = 0u8;
+
+                    for module in modules {
+                        // See `type_registry_from_metadata` for the details of how pallet
+                        // indexes are worked out in older metadatas.
+                        let (calls_index, events_index, errors_index) = {
+                            let out = (calls_index, events_index, errors_index);
+                            if module.calls.is_some() {
+                                calls_index += 1;
+                            }
+                            if module.event.is_some() {
+                                events_index += 1;
+                            }
+                            errors_index += 1;
+
+                            out
+                        };
+
+                        $(
+                            let $builtin_index = true;
+                            let (calls_index, events_index, errors_index) = if $builtin_index {
+                                (module.index, module.index, module.index)
+                            } else {
+                                (calls_index, events_index, errors_index)
+                            };
+                        )?
+
+                        let module_name: &str = as_decoded_str(&module.name);
+
+                        //// 1. Calls
+                        let calls = match &module.calls {
+                            Some(calls) => {
+                                let mut variants = Vec::new();
+                                for (c_idx, call) in as_decoded(calls)?.iter().enumerate() {
+                                    let fields = as_decoded(&call.arguments)?
+                                        .iter()
+                                        .map(|arg| {
+                                            let name: &str = as_decoded_str(&arg.name);
+                                            let ty: &str = as_decoded_str(&arg.ty);
+                                            Some(Field::new(
+                                                Some(name.into()),
+                                                converter.try_parse_type_id(ty, module_name)?.into(),
+                                                Some(ty.into()),
+                                                Vec::new(),
+                                            ))
+                                        })
+                                        .collect::<Option<_>>();
+                                    let Some(fields) = fields else { continue };
+
+                                    let call_name: &str = as_decoded_str(&call.name);
+                                    variants.push(Variant::new(
+                                        call_name.into(),
+                                        fields,
+                                        c_idx as u8,
+                                        to_docs(&call.documentation)?,
+                                    ));
+                                }
+
+                                let call_ty = converter.add_type(Type::new(
+                                    pallet_path(module_name, "Call"),
+                                    [],
+                                    TypeDefVariant::new(variants),
+                                    Vec::new(),
+                                ));
+                                call_module_variants.push(outer_variant(module_name, calls_index, call_ty));
+                                Some(v14::PalletCallMetadata { ty: call_ty.into() })
+                            }
+                            None => None,
+                        };
+
+                        //// 2. Events
+                        let event = match &module.event {
+                            Some(events) => {
+                                let mut variants = Vec::new();
+                                for (e_idx, event) in as_decoded(events)?.iter().enumerate() {
+                                    let fields = as_decoded(&event.arguments)?
+                                        .iter()
+                                        .map(|ty| {
+                                            let ty: &str = ty.as_ref();
+                                            Some(Field::new(
+                                                None,
+                                                converter.try_parse_type_id(ty, module_name)?.into(),
+                                                Some(ty.into()),
+                                                Vec::new(),
+                                            ))
+                                        })
+                                        .collect::<Option<_>>();
+                                    let Some(fields) = fields else { continue };
+
+                                    let event_name: &str = as_decoded_str(&event.name);
+                                    variants.push(Variant::new(
+                                        event_name.into(),
+                                        fields,
+                                        e_idx as u8,
+                                        to_docs(&event.documentation)?,
+                                    ));
+                                }
+
+                                let event_ty = converter.add_type(Type::new(
+                                    pallet_path(module_name, "Event"),
+                                    [],
+                                    TypeDefVariant::new(variants),
+                                    Vec::new(),
+                                ));
+                                event_module_variants.push(outer_variant(module_name, events_index, event_ty));
+                                Some(v14::PalletEventMetadata { ty: event_ty.into() })
+                            }
+                            None => None,
+                        };
+
+                        //// 3. Errors. These never have any fields in legacy metadata.
+                        let error = {
+                            let mut variants = Vec::new();
+                            for (e_idx, error) in as_decoded(&module.errors)?.iter().enumerate() {
+                                let error_name: &str = as_decoded_str(&error.name);
+                                variants.push(Variant::new(
+                                    error_name.into(),
+                                    Vec::new(),
+                                    e_idx as u8,
+                                    to_docs(&error.documentation)?,
+                                ));
+                            }
+
+                            if variants.is_empty() {
+                                None
+                            } else {
+                                let error_ty = converter.add_type(Type::new(
+                                    pallet_path(module_name, "Error"),
+                                    [],
+                                    TypeDefVariant::new(variants),
+                                    Vec::new(),
+                                ));
+                                Some(v14::PalletErrorMetadata { ty: error_ty.into() })
+                            }
+                        };
+
+                        //// 4. Storage
+                        let storage = match &module.storage {
+                            Some(storage) => {
+                                let storage = as_decoded(storage)?;
+                                let mut entries = Vec::new();
+                                for entry in as_decoded(&storage.entries)? {
+                                    let entry_name: &str = as_decoded_str(&entry.name);
+                                    let info = match metadata.storage_info(module_name, entry_name) {
+                                        Ok(info) => info,
+                                        Err(StorageInfoError::MetadataNotDecoded) => {
+                                            return Err(LegacyMetadataConversionError::MetadataNotDecoded)
+                                        }
+                                        Err(_) => continue,
+                                    };
+
+                                    let Some(value) = converter.try_type_id(info.value_id.clone()) else { continue };
+                                    let ty = if info.keys.is_empty() {
+                                        v14::StorageEntryType::Plain(value.into())
+                                    } else {
+                                        let hashers = info.keys.iter().map(|k| to_v14_hasher(k.hasher)).collect();
+                                        let key_ids: Option<Vec<u32>> = info
+                                            .keys
+                                            .iter()
+                                            .map(|k| converter.try_type_id(k.key_id.clone()))
+                                            .collect();
+                                        let Some(key_ids) = key_ids else { continue };
+                                        let key = if key_ids.len() == 1 {
+                                            key_ids[0]
+                                        } else {
+                                            converter.add_type(TypeDefTuple::new_portable(key_ids.into_iter().map(Into::into)).into())
+                                        };
+                                        v14::StorageEntryType::Map {
+                                            hashers,
+                                            key: key.into(),
+                                            value: value.into(),
+                                        }
+                                    };
+
+                                    let modifier = match entry.modifier {
+                                        <$storage_modifier>::Optional => v14::StorageEntryModifier::Optional,
+                                        <$storage_modifier>::Default => v14::StorageEntryModifier::Default,
+                                    };
+
+                                    entries.push(v14::StorageEntryMetadata {
+                                        name: entry_name.into(),
+                                        modifier,
+                                        ty,
+                                        default: info.default_value.map(|d| d.into_owned()).unwrap_or_default(),
+                                        docs: to_docs(&entry.documentation)?,
+                                    });
+                                }
+
+                                let prefix: &str = as_decoded_str(&storage.prefix);
+                                Some(v14::PalletStorageMetadata {
+                                    prefix: prefix.into(),
+                                    entries,
+                                })
+                            }
+                            None => None,
+                        };
+
+                        //// 5. Constants
+                        let mut constants = Vec::new();
+                        for constant in as_decoded(&module.constants)? {
+                            let name: &str = as_decoded_str(&constant.name);
+                            let ty: &str = as_decoded_str(&constant.ty);
+                            let Some(ty) = converter.try_parse_type_id(ty, module_name) else { continue };
+                            constants.push(v14::PalletConstantMetadata {
+                                name: name.into(),
+                                ty: ty.into(),
+                                value: as_decoded(&constant.value)?.clone(),
+                                docs: to_docs(&constant.documentation)?,
+                            });
+                        }
+
+                        pallets.push(v14::PalletMetadata {
+                            name: module_name.into(),
+                            storage,
+                            calls,
+                            event,
+                            constants,
+                            error,
+                            index: errors_index,
+                        });
+                    }
+
+                    //// 6. The outer call and event enums.
+                    let call_ty = converter.add_type(Type::new(
+                        Path::from_segments_unchecked(["RuntimeCall".into()]),
+                        [],
+                        TypeDefVariant::new(call_module_variants),
+                        Vec::new(),
+                    ));
+                    let event_ty = converter.add_type(Type::new(
+                        Path::from_segments_unchecked(["RuntimeEvent".into()]),
+                        [],
+                        TypeDefVariant::new(event_module_variants),
+                        Vec::new(),
+                    ));
+
+                    //// 7. Extrinsic information
+                    let signature_info = metadata
+                        .extrinsic_signature_info()
+                        .map_err(|e| LegacyMetadataConversionError::CannotGetExtrinsicInfo(e.into_owned()))?;
+                    let extension_info = metadata
+                        .extrinsic_extension_info(None)
+                        .map_err(|e| LegacyMetadataConversionError::CannotGetExtrinsicInfo(e.into_owned()))?;
+
+                    let address_ty = converter.type_id(signature_info.address_id)?;
+                    let signature_ty = converter.type_id(signature_info.signature_id)?;
+                    let signed_extensions = extension_info
+                        .extension_ids
+                        .into_iter()
+                        .map(|ext| Ok(v14::SignedExtensionMetadata {
+                            identifier: ext.name.as_ref().into(),
+                            ty: converter.type_id(ext.id)?.into(),
+                            additional_signed: converter.try_type_id(ext.implicit_id)
+                                .unwrap_or_else(|| converter.add_type(TypeDefTuple::new_portable([]).into()))
+                                .into(),
+                        }))
+                        .collect::<Result<Vec<_>, LegacyMetadataConversionError>>()?;
+                    let extra_ty = converter.add_type(
+                        TypeDefTuple::new_portable(signed_extensions.iter().map(|e| e.ty)).into(),
+                    );
+
+                    let bytes_ty = converter.add_type(TypeDefPrimitive::U8.into());
+                    let bytes_ty = converter.add_type(TypeDefSequence::new(bytes_ty.into()).into());
+                    let extrinsic_ty = converter.add_type(Type::new(
+                        Path::from_segments_unchecked(
+                            ["sp_runtime", "generic", "unchecked_extrinsic", "UncheckedExtrinsic"].map(Into::into),
+                        ),
+                        [
+                            TypeParameter::new_portable("Address".into(), Some(address_ty.into())),
+                            TypeParameter::new_portable("Call".into(), Some(call_ty.into())),
+                            TypeParameter::new_portable("Signature".into(), Some(signature_ty.into())),
+                            TypeParameter::new_portable("Extra".into(), Some(extra_ty.into())),
+                        ],
+                        TypeDefComposite::new([Field::new(None, bytes_ty.into(), None, Vec::new())]),
+                        Vec::new(),
+                    ));
+
+                    let $m = metadata;
+                    let extrinsic = v14::ExtrinsicMetadata {
+                        ty: extrinsic_ty.into(),
+                        version: $extrinsic_version,
+                        signed_extensions,
+                    };
+
+                    // The outer enums hold the call and event index of each pallet, which
+                    // can differ from the pallet index, so we attach them to the runtime type
+                    // in order that calls and events can be looked up by these indexes.
+                    let runtime_ty = converter.add_type(Type::new(
+                        Path::from_segments_unchecked(["Runtime".into()]),
+                        [
+                            TypeParameter::new_portable("RuntimeCall".into(), Some(call_ty.into())),
+                            TypeParameter::new_portable("RuntimeEvent".into(), Some(event_ty.into())),
+                        ],
+                        TypeDefComposite::new([]),
+                        Vec::new(),
+                    ));
+
+                    Ok(RuntimeMetadataV14 {
+                        types: converter.finish(),
+                        pallets,
+                        extrinsic,
+                        ty: runtime_ty.into(),
+                    })
+                }
+            }
+        };
+    }
+
+    fn to_v14_hasher(hasher: StorageHasher) -> v14::StorageHasher {
+        match hasher {
+            StorageHasher::Blake2_128 => v14::StorageHasher::Blake2_128,
+            StorageHasher::Blake2_256 => v14::StorageHasher::Blake2_256,
+            StorageHasher::Blake2_128Concat => v14::StorageHasher::Blake2_128Concat,
+            StorageHasher::Twox128 => v14::StorageHasher::Twox128,
+            StorageHasher::Twox256 => v14::StorageHasher::Twox256,
+            StorageHasher::Twox64Concat => v14::StorageHasher::Twox64Concat,
+            StorageHasher::Identity => v14::StorageHasher::Identity,
+        }
+    }
+
+    impl_for_v8_to_v13!(
+        frame_metadata::v8::RuntimeMetadataV8,
+        frame_metadata::v8::StorageEntryModifier,
+        |_m| 4
+    );
+    impl_for_v8_to_v13!(
+        frame_metadata::v9::RuntimeMetadataV9,
+        frame_metadata::v9::StorageEntryModifier,
+        |_m| 4
+    );
+    impl_for_v8_to_v13!(
+        frame_metadata::v10::RuntimeMetadataV10,
+        frame_metadata::v10::StorageEntryModifier,
+        |_m| 4
+    );
+    impl_for_v8_to_v13!(
+        frame_metadata::v11::RuntimeMetadataV11,
+        frame_metadata::v11::StorageEntryModifier,
+        |m| m.extrinsic.version
+    );
+    impl_for_v8_to_v13!(
+        frame_metadata::v12::RuntimeMetadataV12,
+        frame_metadata::v12::StorageEntryModifier,
+        |m| m.extrinsic.version,
+        use_builtin_index
+    );
+    impl_for_v8_to_v13!(
+        frame_metadata::v13::RuntimeMetadataV13,
+        frame_metadata::v13::StorageEntryModifier,
+        |m| m.extrinsic.version,
+        use_builtin_index
+    );
+};
+
+fn as_decoded<A, B>(
+    item: &frame_metadata::decode_different::DecodeDifferent<A, B>,
+) -> Result<&B, LegacyMetadataConversionError> {
+    crate::utils::as_decoded(item).map_err(|_| LegacyMetadataConversionError::MetadataNotDecoded)
+}
+
+fn to_docs<A>(
+    docs: &frame_metadata::decode_different::DecodeDifferent<A, Vec<String>>,
+) -> Result<Vec<String>, LegacyMetadataConversionError> {
+    as_decoded(docs).cloned()
+}
+
+fn pallet_path(pallet_name: &str, ident: &str) -> Path<PortableForm> {
+    Path::from_segments_unchecked([format!("pallet_{pallet_name}"), ident.into()])
+}
+
+fn outer_variant(pallet_name: &str, pallet_index: u8, ty: u32) -> Variant<PortableForm> {
+    Variant::new(
+        pallet_name.into(),
+        vec![Field::new(None, ty.into(), None, Vec::new())],
+        pallet_index,
+        Vec::new(),
+    )
+}
+
+// This converts legacy types into types in a `PortableRegistry`. Type IDs are handed out as
+// types are referenced, and the types themselves are resolved and added in `finish()`.
+struct TypeConverter<'r, Resolver> {
+    resolver: &'r Resolver,
+    state: TypeConverterState,
+}
+
+#[derive(Default)]
+struct TypeConverterState {
+    ids: BTreeMap<LookupName, u32>,
+    types: Vec<Option<Type<PortableForm>>>,
+    pending: Vec<(LookupName, u32)>,
+}
+
+impl TypeConverterState {
+    // Return the type ID for a given legacy type, reserving one if it's not been seen before.
+    fn type_id(&mut self, name: LookupName) -> u32 {
+        if let Some(id) = self.ids.get(&name) {
+            return *id;
+        }
+        let id = self.types.len() as u32;
+        self.types.push(None);
+        self.ids.insert(name.clone(), id);
+        self.pending.push((name, id));
+        id
+    }
+
+    // Remove any types added since the given number of types existed.
+    fn rollback(&mut self, num_types: usize) {
+        self.types.truncate(num_types);
+        self.ids.retain(|_, id| (*id as usize) < num_types);
+        self.pending.clear();
+    }
+
+    // Add a type which does not correspond to any legacy type, returning its ID.
+    fn add_type(&mut self, ty: Type<PortableForm>) -> u32 {
+        let id = self.types.len() as u32;
+        self.types.push(Some(ty));
+        id
+    }
+}
+
+impl<'r, Resolver> TypeConverter<'r, Resolver>
+where
+    Resolver: TypeResolver<TypeId = LookupName>,
+{
+    fn new(resolver: &'r Resolver) -> Self {
+        TypeConverter {
+            resolver,
+            state: TypeConverterState::default(),
+        }
+    }
+
+    // Return the type ID for the given legacy type, resolving it and any types that it
+    // references. If this fails, any types that were added in the attempt are removed again.
+    fn type_id(&mut self, name: LookupName) -> Result<u32, LegacyMetadataConversionError> {
+        let checkpoint = self.state.types.len();
+        let id = self.state.type_id(name);
+
+        while let Some((name, id)) = self.state.pending.pop() {
+            let visitor = ToPortableType {
+                name: &name,
+                state: &mut self.state,
+            };
+            let ty = self
+                .resolver
+                .resolve_type(name.clone(), visitor)
+                .map_err(|e| LegacyMetadataConversionError::CannotResolveType {
+                    name: name.to_string(),
+                    reason: e.to_string(),
+                })
+                .and_then(|ty| ty);
+
+            match ty {
+                Ok(ty) => self.state.types[id as usize] = Some(ty),
+                Err(e) => {
+                    self.state.rollback(checkpoint);
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(id)
+    }
+
+    // Like [`TypeConverter::type_id`], but returns `None` if the type cannot be resolved.
+    fn try_type_id(&mut self, name: LookupName) -> Option<u32> {
+        self.type_id(name).ok()
+    }
+
+    // Parse the given type name in the context of some pallet and return its type ID,
+    // or `None` if this fails.
+    fn try_parse_type_id(&mut self, name: &str, pallet_name: &str) -> Option<u32> {
+        let lookup_name = LookupName::parse(name).ok()?.in_pallet(pallet_name);
+        self.try_type_id(lookup_name)
+    }
+
+    fn add_type(&mut self, ty: Type<PortableForm>) -> u32 {
+        self.state.add_type(ty)
+    }
+
+    fn finish(self) -> PortableRegistry {
+        let types = self
+            .state
+            .types
+            .into_iter()
+            .enumerate()
+            .map(|(id, ty)| PortableType::new(id as u32, ty.expect("types are always resolved")))
+            .collect();
+
+        PortableRegistry { types }
+    }
+}
+
+// A visitor which converts a resolved legacy type into a portable one.
+struct ToPortableType<'a> {
+    name: &'a LookupName,
+    state: &'a mut TypeConverterState,
+}
+
+impl<'resolver> ResolvedTypeVisitor<'resolver> for ToPortableType<'_> {
+    type TypeId = LookupName;
+    type Value = Result<Type<PortableForm>, LegacyMetadataConversionError>;
+
+    fn visit_unhandled(self, kind: UnhandledKind) -> Self::Value {
+        Err(LegacyMetadataConversionError::UnhandledType {
+            name: self.name.to_string(),
+            kind,
+        })
+    }
+
+    fn visit_not_found(self) -> Self::Value {
+        Err(LegacyMetadataConversionError::TypeNotFound {
+            name: self.name.to_string(),
+        })
+    }
+
+    fn visit_composite<Path, Fields>(self, path: Path, fields: Fields) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+        Fields: FieldIter<'resolver, Self::TypeId>,
+    {
+        let fields = fields
+            .map(|f| {
+                let id = self.state.type_id(f.id);
+                Field::new(f.name.map(Into::into), id.into(), None, Vec::new())
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Type::new(
+            to_path(path),
+            [],
+            TypeDefComposite::new(fields),
+            Vec::new(),
+        ))
+    }
+
+    fn visit_variant<Path, Fields, Var>(self, path: Path, variants: Var) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+        Fields: FieldIter<'resolver, Self::TypeId>,
+        Var: VariantIter<'resolver, Fields>,
+    {
+        let variants = variants
+            .map(|v| {
+                let fields = v
+                    .fields
+                    .map(|f| {
+                        let id = self.state.type_id(f.id);
+                        Field::new(f.name.map(Into::into), id.into(), None, Vec::new())
+                    })
+                    .collect();
+                Variant::new(v.name.into(), fields, v.index, Vec::new())
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Type::new(
+            to_path(path),
+            [],
+            TypeDefVariant::new(variants),
+            Vec::new(),
+        ))
+    }
+
+    fn visit_sequence<Path>(self, path: Path, type_id: Self::TypeId) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+    {
+        let id = self.state.type_id(type_id);
+        Ok(Type::new(
+            to_path(path),
+            [],
+            TypeDefSequence::new(id.into()),
+            Vec::new(),
+        ))
+    }
+
+    fn visit_array(self, type_id: Self::TypeId, len: usize) -> Self::Value {
+        let id = self.state.type_id(type_id);
+        Ok(TypeDefArray::new(len as u32, id.into()).into())
+    }
+
+    fn visit_tuple<TypeIds>(self, type_ids: TypeIds) -> Self::Value
+    where
+        TypeIds: ExactSizeIterator<Item = Self::TypeId>,
+    {
+        let ids = type_ids
+            .map(|id| self.state.type_id(id).into())
+            .collect::<Vec<_>>();
+        Ok(TypeDefTuple::new_portable(ids).into())
+    }
+
+    fn visit_primitive(self, primitive: Primitive) -> Self::Value {
+        Ok(to_primitive(primitive).into())
+    }
+
+    fn visit_compact(self, type_id: Self::TypeId) -> Self::Value {
+        let id = self.state.type_id(type_id);
+        Ok(TypeDefCompact::new(id.into()).into())
+    }
+
+    fn visit_bit_sequence(
+        self,
+        store_format: BitsStoreFormat,
+        order_format: BitsOrderFormat,
+    ) -> Self::Value {
+        let store = match store_format {
+            BitsStoreFormat::U8 => TypeDefPrimitive::U8,
+            BitsStoreFormat::U16 => TypeDefPrimitive::U16,
+            BitsStoreFormat::U32 => TypeDefPrimitive::U32,
+            BitsStoreFormat::U64 => TypeDefPrimitive::U64,
+        };
+        let order = match order_format {
+            BitsOrderFormat::Lsb0 => "Lsb0",
+            BitsOrderFormat::Msb0 => "Msb0",
+        };
+
+        let store_id = self.state.add_type(store.into());
+        let order_id = self.state.add_type(Type::new(
+            Path::from_segments_unchecked(["bitvec", "order", order].map(Into::into)),
+            [],
+            TypeDefComposite::new([]),
+            Vec::new(),
+        ));
+        Ok(TypeDefBitSequence::new_portable(store_id.into(), order_id.into()).into())
+    }
+}
+
+fn to_path<'a>(path: impl Iterator<Item = &'a str>) -> Path<PortableForm> {
+    Path::from_segments_unchecked(path.map(Into::into))
+}
+
+fn to_primitive(primitive: Primitive) -> TypeDefPrimitive {
+    match primitive {
+        Primitive::Bool => TypeDefPrimitive::Bool,
+        Primitive::Char => TypeDefPrimitive::Char,
+        Primitive::Str => TypeDefPrimitive::Str,
+        Primitive::U8 => TypeDefPrimitive::U8,
+        Primitive::U16 => TypeDefPrimitive::U16,
+        Primitive::U32 => TypeDefPrimitive::U32,
+        Primitive::U64 => TypeDefPrimitive::U64,
+        Primitive::U128 => TypeDefPrimitive::U128,
+        Primitive::U256 => TypeDefPrimitive::U256,
+        Primitive::I8 => TypeDefPrimitive::I8,
+        Primitive::I16 => TypeDefPrimitive::I16,
+        Primitive::I32 => TypeDefPrimitive::I32,
+        Primitive::I64 => TypeDefPrimitive::I64,
+        Primitive::I128 => TypeDefPrimitive::I128,
+        Primitive::I256 => TypeDefPrimitive::I256,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::error_type_info::ErrorTypeInfo;
+    use crate::methods::event_decoder::decode_event;
+    use crate::methods::event_type_info::EventTypeInfo;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
+    use crate::methods::storage_type_info::StorageTypeInfo;
+    use frame_metadata::RuntimeMetadata;
+    use parity_scale_codec::{Compact, Decode, Encode};
+    use scale_value::scale::ValueVisitor;
+
+    #[test]
+    fn converted_metadata_decodes_like_legacy_metadata() {
+        let metadata_bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+        let RuntimeMetadata::V12(legacy_metadata) =
+            RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap()
+        else {
+            panic!("Expected V12 metadata")
+        };
+
+        let historic_types = crate::legacy_types::polkadot::relay_chain();
+        let mut types = historic_types.for_spec_version(30).to_owned();
+        let metadata_types = crate::helpers::type_registry_from_metadata(&legacy_metadata).unwrap();
+        types.prepend(metadata_types);
+
+        let metadata = legacy_metadata_to_v14(&legacy_metadata, &types).unwrap();
+
+        // Pallets keep the same names and indexes.
+        let modules = crate::utils::as_decoded(&legacy_metadata.modules).unwrap();
+        assert_eq!(metadata.pallets.len(), modules.len());
+        for (pallet, module) in metadata.pallets.iter().zip(modules) {
+            assert_eq!(pallet.name, crate::utils::as_decoded_str(&module.name));
+            assert_eq!(pallet.index, module.index);
+        }
+
+        // Extrinsics decode to the same shape either way.
+        let extrinsics_bytes = std::fs::read("artifacts/exts_5000000_30.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+        for ext_hex in extrinsics_hex {
+            let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+            let legacy_ext = decode_extrinsic(&mut &*ext_bytes, &legacy_metadata, &types).unwrap();
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+
+            assert_eq!(ext.pallet_name(), legacy_ext.pallet_name());
+            assert_eq!(ext.call_name(), legacy_ext.call_name());
+            assert_eq!(ext.call_data_range(), legacy_ext.call_data_range());
+            assert_eq!(ext.is_signed(), legacy_ext.is_signed());
+
+            for (arg, legacy_arg) in ext.call_data().zip(legacy_ext.call_data()) {
+                let value = scale_decode::visitor::decode_with_visitor(
+                    &mut &ext_bytes[arg.range()],
+                    *arg.ty(),
+                    &metadata.types,
+                    ValueVisitor::new(),
+                )
+                .unwrap();
+                let legacy_value = scale_decode::visitor::decode_with_visitor(
+                    &mut &ext_bytes[legacy_arg.range()],
+                    legacy_arg.ty().clone(),
+                    &types,
+                    ValueVisitor::new(),
+                )
+                .unwrap();
+                assert_eq!(value.remove_context(), legacy_value.remove_context());
+            }
+        }
+
+        // Storage entries point at the equivalent types.
+        let info = metadata.storage_info("System", "Account").unwrap();
+        let legacy_info = legacy_metadata.storage_info("System", "Account").unwrap();
+        assert_eq!(info.keys.len(), legacy_info.keys.len());
+        assert_eq!(info.default_value, legacy_info.default_value);
+    }
+
+    // Build some V9 to V11 metadata whose pallets have calls and events in different positions,
+    // so that the call, event and error indexes of each pallet differ. Every pallet has an error.
+    macro_rules! metadata_with_mismatched_indexes {
+        ($v:ident, $metadata:ident $(, $extrinsic:expr)?) => {{
+            use frame_metadata::decode_different::DecodeDifferent::Decoded;
+            use frame_metadata::$v::*;
+
+            let module = |name: &str, has_calls: bool, has_events: bool| ModuleMetadata {
+                name: Decoded(name.into()),
+                storage: None,
+                calls: has_calls.then(|| {
+                    Decoded(vec![FunctionMetadata {
+                        name: Decoded("call".into()),
+                        arguments: Decoded(vec![FunctionArgumentMetadata {
+                            name: Decoded("value".into()),
+                            ty: Decoded("u32".into()),
+                        }]),
+                        documentation: Decoded(vec![]),
+                    }])
+                }),
+                event: has_events.then(|| {
+                    Decoded(vec![EventMetadata {
+                        name: Decoded("Event".into()),
+                        arguments: Decoded(vec!["u32".into()]),
+                        documentation: Decoded(vec![]),
+                    }])
+                }),
+                constants: Decoded(vec![]),
+                errors: Decoded(vec![ErrorMetadata {
+                    name: Decoded(format!("{name}Error")),
+                    documentation: Decoded(vec![]),
+                }]),
+            };
+
+            $metadata {
+                modules: Decoded(vec![
+                    module("A", true, true),
+                    module("B", true, false),
+                    module("C", false, false),
+                    module("D", false, true),
+                    module("E", false, true),
+                    module("F", true, true),
+                ]),
+                $(extrinsic: $extrinsic,)?
+            }
+        }};
+    }
+
+    #[test]
+    fn legacy_call_and_event_indexes_are_kept() {
+        fn check<Md: ToRuntimeMetadataV14>(legacy_metadata: &Md) {
+            let types = crate::legacy_types::kusama::relay_chain()
+                .for_spec_version(1020)
+                .to_owned();
+            let metadata = legacy_metadata_to_v14(legacy_metadata, &types).unwrap();
+
+            let outer_indexes = |name: &str| -> Vec<(String, u8)> {
+                let ty = metadata
+                    .types
+                    .types
+                    .iter()
+                    .find(|t| t.ty.path.segments == [name])
+                    .unwrap();
+                let scale_info::TypeDef::Variant(v) = &ty.ty.type_def else {
+                    panic!("{name} should be a variant")
+                };
+                v.variants
+                    .iter()
+                    .map(|v| (v.name.clone(), v.index))
+                    .collect()
+            };
+
+            let call_indexes = outer_indexes("RuntimeCall");
+            let event_indexes = outer_indexes("RuntimeEvent");
+            let pallet_indexes: Vec<_> = metadata
+                .pallets
+                .iter()
+                .map(|p| (p.name.clone(), p.index))
+                .collect();
+
+            let named = |v: &[(&str, u8)]| -> Vec<(String, u8)> {
+                v.iter().map(|(n, i)| (n.to_string(), *i)).collect()
+            };
+            assert_eq!(call_indexes, named(&[("A", 0), ("B", 1), ("F", 2)]));
+            assert_eq!(
+                event_indexes,
+                named(&[("A", 0), ("D", 1), ("E", 2), ("F", 3)])
+            );
+            assert_eq!(
+                pallet_indexes,
+                named(&[("A", 0), ("B", 1), ("C", 2), ("D", 3), ("E", 4), ("F", 5)])
+            );
+
+            // Events decode via the outer event type using the event index:
+            let event_ty = metadata
+                .types
+                .types
+                .iter()
+                .find(|t| t.ty.path.segments == ["RuntimeEvent"])
+                .unwrap()
+                .id;
+            let event_bytes = (3u8, 0u8, 123u32).encode();
+            let event =
+                scale_value::scale::decode_as_type(&mut &*event_bytes, event_ty, &metadata.types)
+                    .unwrap();
+            let scale_value::ValueDef::Variant(event) = event.value else {
+                panic!("RuntimeEvent should decode to a variant")
+            };
+            assert_eq!(event.name, "F");
+
+            // Calls, events and errors are found by their respective indexes:
+            let call_bytes = (2u8, 0u8, 123u32).encode();
+            let mut ext_bytes = Compact(call_bytes.len() as u32 + 1).encode();
+            ext_bytes.push(4);
+            ext_bytes.extend(call_bytes);
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+            assert_eq!(ext.pallet_name(), "F");
+            assert_eq!(ext.call_name(), "call");
+
+            let event = decode_event(&mut &*event_bytes, &metadata, &metadata.types).unwrap();
+            assert_eq!(event.pallet_name(), "F");
+            assert_eq!(event.event_name(), "Event");
+
+            let error = metadata.error_info_by_index(2, 0).unwrap();
+            assert_eq!(error.pallet_name, "C");
+            assert_eq!(error.error_name, "CError");
+
+            // And the right indexes are handed back when looking them up by name:
+            let call = metadata.extrinsic_call_info_by_name("F", "call").unwrap();
+            assert_eq!((call.pallet_index, call.call_index), (2, 0));
+            let event = metadata.event_info_by_name("F", "Event").unwrap();
+            assert_eq!((event.pallet_index, event.event_index), (3, 0));
+            let error = metadata.error_info_by_name("F", "FError").unwrap();
+            assert_eq!((error.pallet_index, error.error_index), (5, 0));
+        }
+
+        check(&metadata_with_mismatched_indexes!(v9, RuntimeMetadataV9));
+        check(&metadata_with_mismatched_indexes!(v10, RuntimeMetadataV10));
+        check(&metadata_with_mismatched_indexes!(
+            v11,
+            RuntimeMetadataV11,
+            ExtrinsicMetadata {
+                version: 4,
+                signed_extensions: vec![],
+            }
+        ));
+    }
+}
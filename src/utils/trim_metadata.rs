@@ -0,0 +1,789 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use frame_metadata::RuntimeMetadata;
+use frame_metadata::v14::{RuntimeMetadataV14, StorageEntryType};
+use frame_metadata::v15::RuntimeMetadataV15;
+use frame_metadata::v16::RuntimeMetadataV16;
+use scale_info::form::PortableForm;
+use scale_info::interner::UntrackedSymbol;
+use scale_info::{PortableRegistry, TypeDef, Variant};
+
+/// An error returned trying to trim metadata with [`trim_metadata`] or [`trim_metadata_any`].
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TrimMetadataError {
+    #[error("Cannot trim metadata V{0}: only V14, V15 and V16 metadata can be trimmed")]
+    UnsupportedVersion(u32),
+    #[error("Pallet not found: {pallet_name}")]
+    PalletNotFound { pallet_name: String },
+    #[error("Call not found: {call_name} in pallet {pallet_name}")]
+    CallNotFound {
+        pallet_name: String,
+        call_name: String,
+    },
+    #[error("Storage item not found: {entry_name} in pallet {pallet_name}")]
+    StorageEntryNotFound {
+        pallet_name: String,
+        entry_name: String,
+    },
+    #[error("Runtime API trait `{trait_name}` not found")]
+    RuntimeApiTraitNotFound { trait_name: String },
+    #[error("Runtime API method `{method_name}` not found in trait `{trait_name}`")]
+    RuntimeApiMethodNotFound {
+        trait_name: String,
+        method_name: String,
+    },
+    #[error("Cannot find type with ID {id}, or it is not a variant type")]
+    TypeNotFound { id: u32 },
+}
+
+/// The pallets, calls, storage entries and Runtime APIs to keep when trimming metadata via
+/// [`trim_metadata`] or [`trim_metadata_any`].
+///
+/// - Selecting a pallet keeps everything in it.
+/// - Selecting individual calls or storage entries from a pallet keeps only those items from the
+///   pallet; its events, errors, constants (and in V16, its View Functions and associated types)
+///   are all removed.
+/// - Selecting a Runtime API trait keeps every method in it, while selecting individual methods
+///   keeps only those methods.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::metadata::MetadataSelection;
+///
+/// let selection = MetadataSelection::new()
+///     .pallet("System")
+///     .call("Balances", "transfer_keep_alive")
+///     .storage_entry("Balances", "TotalIssuance")
+///     .runtime_api_method("Core", "version");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetadataSelection {
+    pallets: BTreeMap<String, PalletSelection>,
+    runtime_apis: BTreeMap<String, RuntimeApiSelection>,
+}
+
+#[derive(Debug, Clone)]
+enum PalletSelection {
+    All,
+    Items {
+        calls: BTreeSet<String>,
+        storage_entries: BTreeSet<String>,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum RuntimeApiSelection {
+    All,
+    Methods(BTreeSet<String>),
+}
+
+impl MetadataSelection {
+    /// Create a new, empty selection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep everything in the given pallet.
+    pub fn pallet(mut self, pallet_name: impl Into<String>) -> Self {
+        self.pallets
+            .insert(pallet_name.into(), PalletSelection::All);
+        self
+    }
+
+    /// Keep the given call from the given pallet.
+    pub fn call(mut self, pallet_name: impl Into<String>, call_name: impl Into<String>) -> Self {
+        if let Some((calls, _)) = self.pallet_items(pallet_name.into()) {
+            calls.insert(call_name.into());
+        }
+        self
+    }
+
+    /// Keep the given storage entry from the given pallet.
+    pub fn storage_entry(
+        mut self,
+        pallet_name: impl Into<String>,
+        entry_name: impl Into<String>,
+    ) -> Self {
+        if let Some((_, storage_entries)) = self.pallet_items(pallet_name.into()) {
+            storage_entries.insert(entry_name.into());
+        }
+        self
+    }
+
+    /// Keep every method in the given Runtime API trait.
+    pub fn runtime_api(mut self, trait_name: impl Into<String>) -> Self {
+        self.runtime_apis
+            .insert(trait_name.into(), RuntimeApiSelection::All);
+        self
+    }
+
+    /// Keep the given method from the given Runtime API trait.
+    pub fn runtime_api_method(
+        mut self,
+        trait_name: impl Into<String>,
+        method_name: impl Into<String>,
+    ) -> Self {
+        let selection = self
+            .runtime_apis
+            .entry(trait_name.into())
+            .or_insert_with(|| RuntimeApiSelection::Methods(BTreeSet::new()));
+        if let RuntimeApiSelection::Methods(methods) = selection {
+            methods.insert(method_name.into());
+        }
+        self
+    }
+
+    // Return the individual calls and storage entries selected in some pallet, or None if the
+    // whole pallet has been selected.
+    fn pallet_items(
+        &mut self,
+        pallet_name: String,
+    ) -> Option<(&mut BTreeSet<String>, &mut BTreeSet<String>)> {
+        let selection = self
+            .pallets
+            .entry(pallet_name)
+            .or_insert_with(|| PalletSelection::Items {
+                calls: BTreeSet::new(),
+                storage_entries: BTreeSet::new(),
+            });
+        match selection {
+            PalletSelection::All => None,
+            PalletSelection::Items {
+                calls,
+                storage_entries,
+            } => Some((calls, storage_entries)),
+        }
+    }
+}
+
+/// Trim some V14, V15 or V16 metadata down to contain only the items in the given
+/// [`MetadataSelection`].
+///
+/// The type registry in the returned metadata contains only the types needed by the remaining
+/// items, and has been re-numbered accordingly. Pallet and call indexes are unchanged, and so the
+/// trimmed metadata can still be used to encode and decode the selected calls and storage entries.
+///
+/// Information needed to encode and decode extrinsics (for instance the signature, address and
+/// transaction extension types) is always retained. Custom values are always removed.
+///
+/// # Example
+///
+/// ```rust
+/// use frame_decode::extrinsics::ExtrinsicTypeInfo;
+/// use frame_decode::metadata::{MetadataSelection, trim_metadata};
+/// use frame_metadata::RuntimeMetadata;
+/// use parity_scale_codec::Decode;
+///
+/// let metadata_bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+/// let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*metadata_bytes).unwrap() else { return };
+///
+/// let selection = MetadataSelection::new().call("Balances", "transfer_keep_alive");
+/// let trimmed = trim_metadata(metadata, &selection).unwrap();
+///
+/// assert!(trimmed.extrinsic_call_info_by_name("Balances", "transfer_keep_alive").is_ok());
+/// assert!(trimmed.extrinsic_call_info_by_name("Balances", "transfer").is_err());
+/// ```
+pub fn trim_metadata<Md: TrimmableMetadata>(
+    metadata: Md,
+    selection: &MetadataSelection,
+) -> Result<Md, TrimMetadataError> {
+    metadata.trim(selection)
+}
+
+/// Like [`trim_metadata`], but accepts any [`RuntimeMetadata`], returning an error if it is not
+/// V14, V15 or V16 metadata.
+pub fn trim_metadata_any(
+    metadata: RuntimeMetadata,
+    selection: &MetadataSelection,
+) -> Result<RuntimeMetadata, TrimMetadataError> {
+    match metadata {
+        RuntimeMetadata::V14(m) => m.trim(selection).map(RuntimeMetadata::V14),
+        RuntimeMetadata::V15(m) => m.trim(selection).map(RuntimeMetadata::V15),
+        RuntimeMetadata::V16(m) => m.trim(selection).map(RuntimeMetadata::V16),
+        other => Err(TrimMetadataError::UnsupportedVersion(other.version())),
+    }
+}
+
+/// This is implemented for the metadata versions which can be trimmed via [`trim_metadata`].
+pub trait TrimmableMetadata: Sized + sealed::Sealed {
+    /// Trim the metadata down to the given selection.
+    fn trim(self, selection: &MetadataSelection) -> Result<Self, TrimMetadataError>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for frame_metadata::v14::RuntimeMetadataV14 {}
+    impl Sealed for frame_metadata::v15::RuntimeMetadataV15 {}
+    impl Sealed for frame_metadata::v16::RuntimeMetadataV16 {}
+}
+
+type Symbol = UntrackedSymbol<TypeId>;
+
+// Remove any pallets which were not selected, and trim the ones which were only partially selected.
+// Any calls which are not selected are removed from the pallet call enums in the type registry.
+// This is the same for each metadata version, give or take the `$trim_extras` block which is run
+// against partially selected pallets.
+macro_rules! trim_pallets {
+    ($metadata:ident, $selection:ident, |$pallet:ident| $trim_extras:block) => {{
+        let mut pallets = Vec::new();
+        for mut $pallet in core::mem::take(&mut $metadata.pallets) {
+            let Some(pallet_selection) = $selection.pallets.get($pallet.name.as_ref() as &str)
+            else {
+                continue;
+            };
+
+            if let PalletSelection::Items {
+                calls,
+                storage_entries,
+            } = pallet_selection
+            {
+                let mut storage = $pallet.storage.take();
+                let entries = storage.as_mut().map(|s| &mut s.entries);
+                if let Some(entry_name) =
+                    retain_named(entries, storage_entries, |e| e.name.as_ref())
+                {
+                    return Err(TrimMetadataError::StorageEntryNotFound {
+                        pallet_name: $pallet.name.to_string(),
+                        entry_name: entry_name.clone(),
+                    });
+                }
+                $pallet.storage = storage.filter(|s| !s.entries.is_empty());
+
+                if calls.is_empty() {
+                    $pallet.calls = None;
+                } else {
+                    let call_ty = $pallet.calls.as_ref().map(|c| c.ty.id);
+                    trim_calls(&mut $metadata.types, $pallet.name.as_ref(), call_ty, calls)?;
+                }
+
+                $pallet.event = None;
+                $pallet.error = None;
+                $pallet.constants = Vec::new();
+                $trim_extras
+            }
+
+            pallets.push($pallet);
+        }
+
+        if let Some(pallet_name) = $selection.pallets.keys().find(|name| {
+            !pallets
+                .iter()
+                .any(|p| p.name.as_ref() as &str == name.as_str())
+        }) {
+            return Err(TrimMetadataError::PalletNotFound {
+                pallet_name: pallet_name.clone(),
+            });
+        }
+
+        $metadata.pallets = pallets;
+    }};
+}
+
+// Push the type IDs used in some pallet. Each version has the same fields for this.
+macro_rules! push_pallet_type_ids {
+    ($ids:ident, $pallet:ident) => {{
+        if let Some(storage) = &mut $pallet.storage {
+            for entry in &mut storage.entries {
+                push_storage_type_ids(&mut $ids, &mut entry.ty);
+            }
+        }
+        if let Some(calls) = &mut $pallet.calls {
+            $ids.push(&mut calls.ty);
+        }
+        if let Some(event) = &mut $pallet.event {
+            $ids.push(&mut event.ty);
+        }
+        if let Some(error) = &mut $pallet.error {
+            $ids.push(&mut error.ty);
+        }
+        for constant in &mut $pallet.constants {
+            $ids.push(&mut constant.ty);
+        }
+    }};
+}
+
+// Remove any Runtime APIs which were not selected. Each version has the same fields for this.
+macro_rules! trim_runtime_apis {
+    ($metadata:ident, $selection:ident) => {{
+        for (trait_name, api_selection) in &$selection.runtime_apis {
+            let Some(api) = $metadata
+                .apis
+                .iter_mut()
+                .find(|a| a.name.as_ref() as &str == trait_name)
+            else {
+                return Err(TrimMetadataError::RuntimeApiTraitNotFound {
+                    trait_name: trait_name.clone(),
+                });
+            };
+            if let RuntimeApiSelection::Methods(methods) = api_selection {
+                if let Some(method_name) =
+                    retain_named(Some(&mut api.methods), methods, |m| m.name.as_ref())
+                {
+                    return Err(TrimMetadataError::RuntimeApiMethodNotFound {
+                        trait_name: trait_name.clone(),
+                        method_name: method_name.clone(),
+                    });
+                }
+            }
+        }
+        $metadata.apis.retain(|a| {
+            $selection
+                .runtime_apis
+                .contains_key(a.name.as_ref() as &str)
+        });
+    }};
+}
+
+// Remove the variants from each of the outer enums which point to pallets that no longer exist
+// or no longer have calls, events or errors. V15 and V16 have the same fields for this.
+macro_rules! trim_outer_enums {
+    ($metadata:ident) => {{
+        let call_indexes = $metadata.pallets.iter().filter(|p| p.calls.is_some());
+        let event_indexes = $metadata.pallets.iter().filter(|p| p.event.is_some());
+        let error_indexes = $metadata.pallets.iter().filter(|p| p.error.is_some());
+        let outer_enums = [
+            (
+                $metadata.outer_enums.call_enum_ty.id,
+                call_indexes.map(|p| p.index).collect(),
+            ),
+            (
+                $metadata.outer_enums.event_enum_ty.id,
+                event_indexes.map(|p| p.index).collect(),
+            ),
+            (
+                $metadata.outer_enums.error_enum_ty.id,
+                error_indexes.map(|p| p.index).collect(),
+            ),
+        ];
+        for (id, indexes) in outer_enums {
+            trim_outer_enum(&mut $metadata.types, id, indexes);
+        }
+    }};
+}
+
+impl TrimmableMetadata for RuntimeMetadataV14 {
+    fn trim(mut self, selection: &MetadataSelection) -> Result<Self, TrimMetadataError> {
+        if !selection.runtime_apis.is_empty() {
+            let trait_name = selection.runtime_apis.keys().next().unwrap().clone();
+            return Err(TrimMetadataError::RuntimeApiTraitNotFound { trait_name });
+        }
+
+        // V14 metadata doesn't point to the outer enums, so we find them by looking for
+        // variant types containing each of the pallet call or event types.
+        let call_enum_ty = find_outer_enum(
+            &self.types,
+            self.pallets
+                .iter()
+                .filter_map(|p| Some((p.index, p.calls.as_ref()?.ty.id))),
+        );
+        let event_enum_ty = find_outer_enum(
+            &self.types,
+            self.pallets
+                .iter()
+                .filter_map(|p| Some((p.index, p.event.as_ref()?.ty.id))),
+        );
+
+        trim_pallets!(self, selection, |pallet| {});
+
+        if let Some(id) = call_enum_ty {
+            let indexes = self.pallets.iter().filter(|p| p.calls.is_some());
+            trim_outer_enum(&mut self.types, id, indexes.map(|p| p.index).collect());
+        }
+        if let Some(id) = event_enum_ty {
+            let indexes = self.pallets.iter().filter(|p| p.event.is_some());
+            trim_outer_enum(&mut self.types, id, indexes.map(|p| p.index).collect());
+        }
+
+        let RuntimeMetadataV14 {
+            types,
+            pallets,
+            extrinsic,
+            ty,
+        } = &mut self;
+
+        let mut ids = vec![ty, &mut extrinsic.ty];
+        for ext in &mut extrinsic.signed_extensions {
+            ids.push(&mut ext.ty);
+            ids.push(&mut ext.additional_signed);
+        }
+        for pallet in pallets {
+            push_pallet_type_ids!(ids, pallet);
+        }
+        retain_types(types, ids)?;
+
+        Ok(self)
+    }
+}
+
+impl TrimmableMetadata for RuntimeMetadataV15 {
+    fn trim(mut self, selection: &MetadataSelection) -> Result<Self, TrimMetadataError> {
+        trim_pallets!(self, selection, |pallet| {});
+        trim_runtime_apis!(self, selection);
+        trim_outer_enums!(self);
+        self.custom.map.clear();
+
+        let RuntimeMetadataV15 {
+            types,
+            pallets,
+            extrinsic,
+            ty,
+            apis,
+            outer_enums,
+            custom: _,
+        } = &mut self;
+
+        let mut ids = vec![
+            ty,
+            &mut extrinsic.address_ty,
+            &mut extrinsic.call_ty,
+            &mut extrinsic.signature_ty,
+            &mut extrinsic.extra_ty,
+            &mut outer_enums.call_enum_ty,
+            &mut outer_enums.event_enum_ty,
+            &mut outer_enums.error_enum_ty,
+        ];
+        for ext in &mut extrinsic.signed_extensions {
+            ids.push(&mut ext.ty);
+            ids.push(&mut ext.additional_signed);
+        }
+        for pallet in pallets {
+            push_pallet_type_ids!(ids, pallet);
+        }
+        for api in apis {
+            for method in &mut api.methods {
+                ids.extend(method.inputs.iter_mut().map(|i| &mut i.ty));
+                ids.push(&mut method.output);
+            }
+        }
+        retain_types(types, ids)?;
+
+        Ok(self)
+    }
+}
+
+impl TrimmableMetadata for RuntimeMetadataV16 {
+    fn trim(mut self, selection: &MetadataSelection) -> Result<Self, TrimMetadataError> {
+        trim_pallets!(self, selection, |pallet| {
+            pallet.associated_types = Vec::new();
+            pallet.view_functions = Vec::new();
+        });
+        trim_runtime_apis!(self, selection);
+        trim_outer_enums!(self);
+        self.custom.map.clear();
+
+        // Remove deprecation info for any calls which no longer exist.
+        for pallet in &mut self.pallets {
+            if let Some(calls) = &mut pallet.calls {
+                let indexes = variant_indexes(&self.types, calls.ty.id);
+                calls.deprecation_info.0.retain(|i, _| indexes.contains(i));
+            }
+        }
+
+        let RuntimeMetadataV16 {
+            types,
+            pallets,
+            extrinsic,
+            apis,
+            outer_enums,
+            custom: _,
+        } = &mut self;
+
+        let mut ids = vec![
+            &mut extrinsic.address_ty,
+            &mut extrinsic.call_ty,
+            &mut extrinsic.signature_ty,
+            &mut outer_enums.call_enum_ty,
+            &mut outer_enums.event_enum_ty,
+            &mut outer_enums.error_enum_ty,
+        ];
+        for ext in &mut extrinsic.transaction_extensions {
+            ids.push(&mut ext.ty);
+            ids.push(&mut ext.implicit);
+        }
+        for pallet in pallets {
+            push_pallet_type_ids!(ids, pallet);
+            ids.extend(pallet.associated_types.iter_mut().map(|a| &mut a.ty));
+            for view_function in &mut pallet.view_functions {
+                ids.extend(view_function.inputs.iter_mut().map(|i| &mut i.ty));
+                ids.push(&mut view_function.output);
+            }
+        }
+        for api in apis {
+            for method in &mut api.methods {
+                ids.extend(method.inputs.iter_mut().map(|i| &mut i.ty));
+                ids.push(&mut method.output);
+            }
+        }
+        retain_types(types, ids)?;
+
+        Ok(self)
+    }
+}
+
+// Retain only the items whose names are given, returning the first name which wasn't found.
+fn retain_named<'a, T>(
+    items: Option<&mut Vec<T>>,
+    names: &'a BTreeSet<String>,
+    name_of: impl Fn(&T) -> &str,
+) -> Option<&'a String> {
+    let Some(items) = items else {
+        return names.first();
+    };
+    items.retain(|item| names.contains(name_of(item)));
+    names
+        .iter()
+        .find(|name| !items.iter().any(|item| name_of(item) == name.as_str()))
+}
+
+// Retain only the selected calls in the given pallet call enum.
+fn trim_calls(
+    types: &mut PortableRegistry,
+    pallet_name: &str,
+    call_ty: Option<u32>,
+    calls: &BTreeSet<String>,
+) -> Result<(), TrimMetadataError> {
+    let variants = call_ty.and_then(|id| variants_mut(types, id));
+    if let Some(call_name) = retain_named(variants, calls, |v| v.name.as_ref()) {
+        return Err(TrimMetadataError::CallNotFound {
+            pallet_name: pallet_name.to_string(),
+            call_name: call_name.clone(),
+        });
+    }
+    Ok(())
+}
+
+// Retain only the variants of an outer enum which correspond to the given pallet indexes.
+fn trim_outer_enum(types: &mut PortableRegistry, id: u32, pallet_indexes: BTreeSet<u8>) {
+    if let Some(variants) = variants_mut(types, id) {
+        variants.retain(|v| pallet_indexes.contains(&v.index));
+    }
+}
+
+// Find the outer enum which contains a variant pointing to each of the given pallet types.
+fn find_outer_enum(
+    types: &PortableRegistry,
+    pallet_types: impl Iterator<Item = (u8, u32)>,
+) -> Option<u32> {
+    let pallet_types: BTreeMap<u8, u32> = pallet_types.collect();
+    if pallet_types.is_empty() {
+        return None;
+    }
+
+    types.types.iter().find_map(|ty| {
+        let TypeDef::Variant(def) = &ty.ty.type_def else {
+            return None;
+        };
+        let is_outer_enum = def.variants.len() == pallet_types.len()
+            && def.variants.iter().all(|v| {
+                v.fields.len() == 1 && pallet_types.get(&v.index) == Some(&v.fields[0].ty.id)
+            });
+        is_outer_enum.then_some(ty.id)
+    })
+}
+
+fn variants_mut(types: &mut PortableRegistry, id: u32) -> Option<&mut Vec<Variant<PortableForm>>> {
+    match &mut types.types.get_mut(id as usize)?.ty.type_def {
+        TypeDef::Variant(def) => Some(&mut def.variants),
+        _ => None,
+    }
+}
+
+fn variant_indexes(types: &PortableRegistry, id: u32) -> BTreeSet<u8> {
+    match types.resolve(id).map(|ty| &ty.type_def) {
+        Some(TypeDef::Variant(def)) => def.variants.iter().map(|v| v.index).collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+fn push_storage_type_ids<'a>(
+    ids: &mut Vec<&'a mut Symbol>,
+    ty: &'a mut StorageEntryType<PortableForm>,
+) {
+    match ty {
+        StorageEntryType::Plain(value) => ids.push(value),
+        StorageEntryType::Map { key, value, .. } => {
+            ids.push(key);
+            ids.push(value);
+        }
+    }
+}
+
+// Retain only the types needed for the given type IDs, and update the IDs to point to
+// their new locations in the registry.
+fn retain_types(
+    types: &mut PortableRegistry,
+    ids: Vec<&mut Symbol>,
+) -> Result<(), TrimMetadataError> {
+    let num_types = types.types.len();
+    if let Some(id) = ids.iter().find(|id| id.id as usize >= num_types) {
+        return Err(TrimMetadataError::TypeNotFound { id: id.id });
+    }
+
+    let roots: BTreeSet<u32> = ids.iter().map(|id| id.id).collect();
+    let new_ids = types.retain(|id| roots.contains(&id));
+    for id in ids {
+        *id = new_ids[&id.id].into();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::methods::event_type_info::EventTypeInfo;
+    use crate::methods::extrinsic_decoder::decode_extrinsic;
+    use crate::methods::extrinsic_type_info::ExtrinsicTypeInfo;
+    use crate::methods::storage_type_info::StorageTypeInfo;
+    use parity_scale_codec::{Decode, Encode};
+    use scale_value::scale::ValueVisitor;
+
+    fn load_metadata_v14() -> RuntimeMetadataV14 {
+        let bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+        let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*bytes).unwrap() else {
+            panic!("expected V14 metadata")
+        };
+        metadata
+    }
+
+    #[test]
+    fn trimmed_metadata_still_decodes_selected_items() {
+        let metadata = load_metadata_v14();
+        let selection = MetadataSelection::new()
+            .pallet("Timestamp")
+            .call("Balances", "transfer_keep_alive")
+            .storage_entry("System", "Account");
+        let trimmed = trim_metadata(metadata.clone(), &selection).unwrap();
+
+        assert!(trimmed.encoded_size() * 10 < metadata.encoded_size());
+
+        let pallet_names: Vec<_> = trimmed.pallets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(pallet_names, ["System", "Timestamp", "Balances"]);
+
+        // Calls are kept at the same indexes.
+        let call = metadata
+            .extrinsic_call_info_by_name("Balances", "transfer_keep_alive")
+            .unwrap();
+        let trimmed_call = trimmed
+            .extrinsic_call_info_by_name("Balances", "transfer_keep_alive")
+            .unwrap();
+        assert_eq!(call.pallet_index, trimmed_call.pallet_index);
+        assert_eq!(call.call_index, trimmed_call.call_index);
+        assert!(
+            trimmed
+                .extrinsic_call_info_by_name("Balances", "transfer")
+                .is_err()
+        );
+        assert!(
+            trimmed
+                .extrinsic_call_info_by_name("System", "remark")
+                .is_err()
+        );
+
+        assert!(trimmed.storage_info("System", "Account").is_ok());
+        assert!(trimmed.storage_info("System", "Number").is_err());
+        assert!(
+            trimmed
+                .event_info_by_name("System", "ExtrinsicSuccess")
+                .is_err()
+        );
+
+        // The outer call enum only points to the remaining pallet calls.
+        let call_enum_ty = trimmed
+            .types
+            .resolve(trimmed.extrinsic.ty.id)
+            .unwrap()
+            .type_params[1]
+            .ty
+            .unwrap();
+        let TypeDef::Variant(call_enum) = &trimmed.types.resolve(call_enum_ty.id).unwrap().type_def
+        else {
+            panic!("outer call enum should be a variant")
+        };
+        let call_enum_names: Vec<_> = call_enum.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(call_enum_names, ["Timestamp", "Balances"]);
+
+        // Selected extrinsics decode to the same values with either metadata.
+        let extrinsics_bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+        let extrinsics_hex: Vec<String> = serde_json::from_slice(&extrinsics_bytes).unwrap();
+        let mut num_decoded = 0;
+        for ext_hex in extrinsics_hex {
+            let ext_bytes = hex::decode(ext_hex.trim_start_matches("0x")).unwrap();
+            let ext = decode_extrinsic(&mut &*ext_bytes, &metadata, &metadata.types).unwrap();
+            if ext.pallet_name() != "Timestamp" {
+                continue;
+            }
+
+            let trimmed_ext = decode_extrinsic(&mut &*ext_bytes, &trimmed, &trimmed.types).unwrap();
+            assert_eq!(ext.call_name(), trimmed_ext.call_name());
+            for (arg, trimmed_arg) in ext.call_data().zip(trimmed_ext.call_data()) {
+                let value = scale_decode::visitor::decode_with_visitor(
+                    &mut &ext_bytes[arg.range()],
+                    *arg.ty(),
+                    &metadata.types,
+                    ValueVisitor::new(),
+                )
+                .unwrap();
+                let trimmed_value = scale_decode::visitor::decode_with_visitor(
+                    &mut &ext_bytes[trimmed_arg.range()],
+                    *trimmed_arg.ty(),
+                    &trimmed.types,
+                    ValueVisitor::new(),
+                )
+                .unwrap();
+                assert_eq!(value.remove_context(), trimmed_value.remove_context());
+            }
+            num_decoded += 1;
+        }
+        assert!(num_decoded > 0);
+    }
+
+    #[test]
+    fn missing_items_are_reported() {
+        let metadata = load_metadata_v14();
+
+        let selection = MetadataSelection::new().pallet("Nope");
+        assert!(matches!(
+            trim_metadata(metadata.clone(), &selection),
+            Err(TrimMetadataError::PalletNotFound { pallet_name }) if pallet_name == "Nope"
+        ));
+
+        let selection = MetadataSelection::new().call("Balances", "nope");
+        assert!(matches!(
+            trim_metadata(metadata.clone(), &selection),
+            Err(TrimMetadataError::CallNotFound { call_name, .. }) if call_name == "nope"
+        ));
+
+        let selection = MetadataSelection::new().storage_entry("Balances", "Nope");
+        assert!(matches!(
+            trim_metadata(metadata.clone(), &selection),
+            Err(TrimMetadataError::StorageEntryNotFound { entry_name, .. }) if entry_name == "Nope"
+        ));
+
+        let selection = MetadataSelection::new().runtime_api("Core");
+        assert!(matches!(
+            trim_metadata(metadata, &selection),
+            Err(TrimMetadataError::RuntimeApiTraitNotFound { .. })
+        ));
+    }
+}
@@ -13,6 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use alloc::string::String;
+
+/// An error returned trying to build a type registry from some legacy metadata.
+#[non_exhaustive]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TypeRegistryFromMetadataError {
+    #[error("Cannot parse type name:\n\n{0}")]
+    CannotParseTypeName(#[from] scale_info_legacy::lookup_name::ParseError),
+    #[error("The metadata was not SCALE decoded and so cannot be read")]
+    MetadataNotDecoded,
+    #[error("Cannot build a valid type name from {name}: {reason}")]
+    InvalidTypeName { name: String, reason: String },
+}
+
 /// [`frame_metadata::RuntimeMetadata`] contains information about runtime calls and events. This
 /// function adds this information into a [`scale_info_legacy::TypeRegistry`] which can then be referenced
 /// by other types. The main types you'll be able to reference from this set are:
@@ -26,7 +41,7 @@
 /// - `builtin::module::call::$PALLET` - A variant containing the calls in a specific pallet.
 pub fn type_registry_from_metadata<Md: ToTypeRegistry>(
     metadata: &Md,
-) -> Result<scale_info_legacy::TypeRegistry, scale_info_legacy::lookup_name::ParseError> {
+) -> Result<scale_info_legacy::TypeRegistry, TypeRegistryFromMetadataError> {
     metadata.to_type_registry()
 }
 
@@ -34,7 +49,7 @@ pub fn type_registry_from_metadata<Md: ToTypeRegistry>(
 /// enum and will extract types from it where appropriate (handing back no types for deprecated or modern metadata).
 pub fn type_registry_from_metadata_any(
     metadata: &frame_metadata::RuntimeMetadata,
-) -> Result<scale_info_legacy::TypeRegistry, scale_info_legacy::lookup_name::ParseError> {
+) -> Result<scale_info_legacy::TypeRegistry, TypeRegistryFromMetadataError> {
     use frame_metadata::RuntimeMetadata;
     match metadata {
         RuntimeMetadata::V0(_d) => Ok(scale_info_legacy::TypeRegistry::empty()),
@@ -63,7 +78,7 @@ pub trait ToTypeRegistry: sealed::Sealed {
     /// Return a type registry.
     fn to_type_registry(
         &self,
-    ) -> Result<scale_info_legacy::TypeRegistry, scale_info_legacy::lookup_name::ParseError>;
+    ) -> Result<scale_info_legacy::TypeRegistry, TypeRegistryFromMetadataError>;
 }
 
 mod sealed {
@@ -71,23 +86,45 @@ mod sealed {
 }
 
 const _: () = {
-    use super::as_decoded;
+    use super::as_decoded_str;
     use alloc::borrow::ToOwned;
     use alloc::format;
+    use alloc::string::ToString;
     use alloc::vec;
     use alloc::vec::Vec;
+    use frame_metadata::decode_different::DecodeDifferent;
     use scale_info_legacy::InsertName;
+    use scale_info_legacy::lookup_name::ParseError;
     use scale_info_legacy::type_shape::{Field, TypeShape, Variant, VariantDesc};
     use scale_info_legacy::{LookupName, TypeRegistry};
 
+    fn as_decoded<A, B>(item: &DecodeDifferent<A, B>) -> Result<&B, TypeRegistryFromMetadataError> {
+        super::as_decoded(item).map_err(|_| TypeRegistryFromMetadataError::MetadataNotDecoded)
+    }
+
+    // Module names from the metadata end up in these type names, and so they may not parse.
+    fn insert_name(name: &str) -> Result<InsertName, TypeRegistryFromMetadataError> {
+        InsertName::parse(name).map_err(|e| TypeRegistryFromMetadataError::InvalidTypeName {
+            name: name.to_owned(),
+            reason: e.to_string(),
+        })
+    }
+
+    fn lookup_name(name: &str) -> Result<LookupName, TypeRegistryFromMetadataError> {
+        LookupName::parse(name).map_err(|e| TypeRegistryFromMetadataError::InvalidTypeName {
+            name: name.to_owned(),
+            reason: e.to_string(),
+        })
+    }
+
     macro_rules! impl_for_v8_to_v13 {
         ($path:path $(, $builtin_index:ident)?) => {
             impl sealed::Sealed for $path {}
             impl ToTypeRegistry for $path {
-                fn to_type_registry(&self) -> Result<scale_info_legacy::TypeRegistry, scale_info_legacy::lookup_name::ParseError> {
+                fn to_type_registry(&self) -> Result<scale_info_legacy::TypeRegistry, TypeRegistryFromMetadataError> {
                     let metadata = self;
                     let mut new_types = TypeRegistry::empty();
-                    let modules = as_decoded(&metadata.modules);
+                    let modules = as_decoded(&metadata.modules)?;
 
                     let mut call_module_variants: Vec<Variant> = vec![];
                     let mut event_module_variants: Vec<Variant> = vec![];
@@ -129,28 +166,26 @@ const _: () = {
                             };
                         )?
 
-                        // as_ref to work when scale-info returns `&static str`
-                        // instead of `String` in no-std mode.
-                        let module_name: &str = as_decoded(&module.name).as_ref();
+                        let module_name: &str = as_decoded_str(&module.name);
 
                         //// 1. Add calls to the type registry
                         if let Some(calls) = &module.calls.as_ref() {
-                            let calls = as_decoded(calls);
+                            let calls = as_decoded(calls)?;
 
                             // Iterate over each call in the module and turn into variants:
                             let mut call_variants: Vec<Variant> = vec![];
                             for (c_idx, call) in calls.iter().enumerate() {
-                                let call_name: &str = as_decoded(&call.name).as_ref();
-                                let args = as_decoded(&call.arguments)
+                                let call_name: &str = as_decoded_str(&call.name);
+                                let args = as_decoded(&call.arguments)?
                                     .iter()
                                     .map(|arg| {
-                                        let name: &str = as_decoded(&arg.name).as_ref();
+                                        let name: &str = as_decoded_str(&arg.name);
                                         Ok(Field {
                                             name: name.to_owned(),
-                                            value: LookupName::parse(&as_decoded(&arg.ty))?.in_pallet(module_name),
+                                            value: LookupName::parse(as_decoded_str(&arg.ty))?.in_pallet(module_name),
                                         })
                                     })
-                                    .collect::<Result<_,_>>()?;
+                                    .collect::<Result<_, ParseError>>()?;
 
                                 call_variants.push(Variant {
                                     index: c_idx as u8,
@@ -161,11 +196,11 @@ const _: () = {
 
                             // Store these call variants in the types:
                             let call_enum_name_str = format!("builtin::module::call::{module_name}");
-                            let call_enum_insert_name = InsertName::parse(&call_enum_name_str).unwrap();
+                            let call_enum_insert_name = insert_name(&call_enum_name_str)?;
                             new_types.insert(call_enum_insert_name, TypeShape::EnumOf(call_variants));
 
                             // Reference it in the modules enum we're building:
-                            let call_enum_lookup_name = LookupName::parse(&call_enum_name_str).unwrap();
+                            let call_enum_lookup_name = lookup_name(&call_enum_name_str)?;
                             call_module_variants.push(Variant {
                                 index: calls_index,
                                 name: module_name.to_owned(),
@@ -175,17 +210,17 @@ const _: () = {
 
                         //// 2. Add events to the type registry
                         if let Some(events) = &module.event.as_ref() {
-                            let events = as_decoded(events);
+                            let events = as_decoded(events)?;
 
                             let mut event_variants: Vec<Variant> = vec![];
                             for (e_idx, event)in events.iter().enumerate() {
-                                let event_name: &str = as_decoded(&event.name).as_ref();
-                                let args = as_decoded(&event.arguments)
+                                let event_name: &str = as_decoded_str(&event.name);
+                                let args = as_decoded(&event.arguments)?
                                     .iter()
                                     .map(|arg| {
                                         Ok(LookupName::parse(&arg)?.in_pallet(module_name))
                                     })
-                                    .collect::<Result<_,_>>()?;
+                                    .collect::<Result<_, ParseError>>()?;
 
                                 event_variants.push(Variant {
                                     index: e_idx as u8,
@@ -196,11 +231,11 @@ const _: () = {
 
                             // Store event variants in the types:
                             let event_enum_name_str = format!("builtin::module::event::{module_name}");
-                            let event_enum_insert_name = InsertName::parse(&event_enum_name_str).unwrap();
+                            let event_enum_insert_name = insert_name(&event_enum_name_str)?;
                             new_types.insert(event_enum_insert_name, TypeShape::EnumOf(event_variants));
 
                             // Reference it in the modules enum we're building:
-                            let event_enum_lookup_name = LookupName::parse(&event_enum_name_str).unwrap();
+                            let event_enum_lookup_name = lookup_name(&event_enum_name_str)?;
                             event_module_variants.push(Variant {
                                 index: events_index,
                                 name: module_name.to_owned(),
@@ -212,8 +247,8 @@ const _: () = {
                         //// (this changed sometime after V14 metadata). We assume that variant indexes start from 0 and
                         //// increment. I'm not sure how to test this at the time of writing.
                         {
-                            let error_variants = as_decoded(&module.errors).iter().enumerate().map(|(e_idx, error)| {
-                                let event_name: &str = as_decoded(&error.name).as_ref();
+                            let error_variants = as_decoded(&module.errors)?.iter().enumerate().map(|(e_idx, error)| {
+                                let event_name: &str = as_decoded_str(&error.name);
                                 Variant {
                                     index: e_idx as u8,
                                     name: event_name.to_owned(),
@@ -223,11 +258,11 @@ const _: () = {
 
                             // Store error variants in the types:
                             let error_enum_name_str = format!("builtin::module::error::{module_name}");
-                            let error_enum_insert_name = InsertName::parse(&error_enum_name_str).unwrap();
+                            let error_enum_insert_name = insert_name(&error_enum_name_str)?;
                             new_types.insert(error_enum_insert_name, TypeShape::EnumOf(error_variants));
 
                             // Reference it in the modules enum we're building:
-                            let error_enum_lookup_name = LookupName::parse(&error_enum_name_str).unwrap();
+                            let error_enum_lookup_name = lookup_name(&error_enum_name_str)?;
                             error_module_variants.push(Variant {
                                 index: errors_index,
                                 name: module_name.to_owned(),
@@ -238,17 +273,17 @@ const _: () = {
 
                     // Store the module call variants in the types:
                     let calls_enum_name_str = "builtin::Call";
-                    let calls_enum_insert_name = InsertName::parse(&calls_enum_name_str).unwrap();
+                    let calls_enum_insert_name = insert_name(&calls_enum_name_str)?;
                     new_types.insert(calls_enum_insert_name, TypeShape::EnumOf(call_module_variants));
 
                     // Store the module event variants in the types:
                     let events_enum_name_str = "builtin::Event";
-                    let events_enum_insert_name = InsertName::parse(&events_enum_name_str).unwrap();
+                    let events_enum_insert_name = insert_name(&events_enum_name_str)?;
                     new_types.insert(events_enum_insert_name, TypeShape::EnumOf(event_module_variants));
 
                     // Store the module error variants in the types:
                     let errors_enum_name_str = "builtin::Error";
-                    let errors_enum_insert_name = InsertName::parse(&errors_enum_name_str).unwrap();
+                    let errors_enum_insert_name = insert_name(&errors_enum_name_str)?;
                     new_types.insert(errors_enum_insert_name, TypeShape::EnumOf(error_module_variants));
 
                     Ok(new_types)
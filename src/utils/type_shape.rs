@@ -0,0 +1,135 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use scale_type_resolver::{
+    BitsOrderFormat, BitsStoreFormat, FieldIter, PathIter, Primitive, ResolvedTypeVisitor,
+    TypeResolver, UnhandledKind, VariantIter,
+};
+
+/// The shape of a single type, pointing to the IDs of any types that it contains. Paths, docs
+/// and type IDs are not part of the shape, so two types with the same shape encode identically.
+pub enum TypeShape<TypeId> {
+    Composite(Vec<FieldShape<TypeId>>),
+    Variant(Vec<VariantShape<TypeId>>),
+    Sequence(TypeId),
+    Array(TypeId, usize),
+    Tuple(Vec<TypeId>),
+    Primitive(Primitive),
+    Compact(TypeId),
+    BitSequence(BitsStoreFormat, BitsOrderFormat),
+    Unhandled(UnhandledKind),
+}
+
+pub struct FieldShape<TypeId> {
+    pub name: Option<String>,
+    pub id: TypeId,
+}
+
+pub struct VariantShape<TypeId> {
+    pub index: u8,
+    pub name: String,
+    pub fields: Vec<FieldShape<TypeId>>,
+}
+
+/// Resolve the shape of the given type.
+pub fn resolve_type_shape<Resolver: TypeResolver>(
+    resolver: &Resolver,
+    id: Resolver::TypeId,
+) -> Result<TypeShape<Resolver::TypeId>, Resolver::Error> {
+    resolver.resolve_type(id, ShapeVisitor(core::marker::PhantomData))
+}
+
+fn field_shapes<'r, TypeId: 'r>(fields: impl FieldIter<'r, TypeId>) -> Vec<FieldShape<TypeId>> {
+    fields
+        .map(|f| FieldShape {
+            name: f.name.map(|n| n.to_string()),
+            id: f.id,
+        })
+        .collect()
+}
+
+struct ShapeVisitor<TypeId>(core::marker::PhantomData<TypeId>);
+
+impl<'resolver, TypeId: scale_type_resolver::TypeId + 'static> ResolvedTypeVisitor<'resolver>
+    for ShapeVisitor<TypeId>
+{
+    type TypeId = TypeId;
+    type Value = TypeShape<TypeId>;
+
+    fn visit_unhandled(self, kind: UnhandledKind) -> Self::Value {
+        TypeShape::Unhandled(kind)
+    }
+
+    fn visit_composite<Path, Fields>(self, _path: Path, fields: Fields) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+        Fields: FieldIter<'resolver, Self::TypeId>,
+    {
+        TypeShape::Composite(field_shapes(fields))
+    }
+
+    fn visit_variant<Path, Fields, Var>(self, _path: Path, variants: Var) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+        Fields: FieldIter<'resolver, Self::TypeId>,
+        Var: VariantIter<'resolver, Fields>,
+    {
+        let mut variants: Vec<_> = variants
+            .map(|v| VariantShape {
+                index: v.index,
+                name: v.name.to_string(),
+                fields: field_shapes(v.fields),
+            })
+            .collect();
+        variants.sort_by_key(|v| v.index);
+        TypeShape::Variant(variants)
+    }
+
+    fn visit_sequence<Path>(self, _path: Path, type_id: Self::TypeId) -> Self::Value
+    where
+        Path: PathIter<'resolver>,
+    {
+        TypeShape::Sequence(type_id)
+    }
+
+    fn visit_array(self, type_id: Self::TypeId, len: usize) -> Self::Value {
+        TypeShape::Array(type_id, len)
+    }
+
+    fn visit_tuple<TypeIds>(self, type_ids: TypeIds) -> Self::Value
+    where
+        TypeIds: ExactSizeIterator<Item = Self::TypeId>,
+    {
+        TypeShape::Tuple(type_ids.collect())
+    }
+
+    fn visit_primitive(self, primitive: Primitive) -> Self::Value {
+        TypeShape::Primitive(primitive)
+    }
+
+    fn visit_compact(self, type_id: Self::TypeId) -> Self::Value {
+        TypeShape::Compact(type_id)
+    }
+
+    fn visit_bit_sequence(
+        self,
+        store_format: BitsStoreFormat,
+        order_format: BitsOrderFormat,
+    ) -> Self::Value {
+        TypeShape::BitSequence(store_format, order_format)
+    }
+}
@@ -0,0 +1,983 @@
+// Copyright (C) 2022-2025 Parity Technologies (UK) Ltd. (admin@parity.io)
+// This file is a part of the frame-decode crate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//         http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Throw arbitrary bytes and arbitrary metadata at the public decode and encode functions. None
+//! of these should ever panic; they should always hand back either a value or an error.
+//!
+//! Values are decoded using an [`IgnoreVisitor`], since the visitor given is outside of our
+//! control; `scale_value`'s visitor, for instance, allocates space for sequences up front based
+//! on the length that the bytes claim they have.
+
+use frame_decode::constants::{ConstantEntryInfo, ConstantTypeInfo, decode_constant};
+use frame_decode::custom_values::{CustomValueEntryInfo, decode_custom_value};
+use frame_decode::errors::decode_dispatch_error;
+use frame_decode::events::{EventTypeInfo, decode_event, decode_event_records};
+use frame_decode::extrinsics::{
+    ExtrinsicTypeInfo, decode_call_data, decode_call_tree, decode_extrinsic,
+    decode_standard_extensions, encode_call_data,
+};
+use frame_decode::helpers::type_registry_from_metadata_any;
+use frame_decode::metadata::{AnyMetadata, legacy_metadata_to_v14_any};
+use frame_decode::outer_enums::{decode_runtime_call, decode_runtime_error, decode_runtime_event};
+use frame_decode::receipts::decode_extrinsic_receipts;
+use frame_decode::runtime_apis::{
+    RuntimeApiEntryInfo, decode_runtime_api_response, encode_runtime_api_inputs,
+};
+use frame_decode::storage::{
+    DecodeStorageKeyOptions, StorageEntryInfo, StoragePrefixIndex, StorageTypeInfo,
+    decode_any_storage_key, decode_storage_key, decode_storage_key_values,
+    decode_storage_key_with_options, decode_storage_value, encode_storage_key,
+    encode_storage_key_prefix, verify_compact_storage_proof, verify_storage_proof,
+};
+use frame_decode::view_functions::{
+    ViewFunctionEntryInfo, decode_view_function_response, encode_view_function_inputs,
+};
+use frame_metadata::RuntimeMetadata;
+use frame_metadata::v12::RuntimeMetadataV12;
+use frame_metadata::v14::RuntimeMetadataV14;
+use frame_metadata::v16::{
+    CustomMetadata, CustomValueMetadata, EnumDeprecationInfo, ExtrinsicMetadata,
+    FunctionParamMetadata, ItemDeprecationInfo, OuterEnums, PalletCallMetadata,
+    PalletConstantMetadata, PalletEventMetadata, PalletMetadata, PalletStorageMetadata,
+    PalletViewFunctionMetadata, RuntimeApiMetadata, RuntimeApiMethodMetadata, RuntimeMetadataV16,
+    StorageEntryMetadata, StorageEntryModifier, StorageEntryType, StorageHasher,
+    TransactionExtensionMetadata,
+};
+use parity_scale_codec::{Compact, Decode};
+use proptest::prelude::*;
+use scale_decode::visitor::IgnoreVisitor;
+use scale_info::{MetaType, meta_type};
+use scale_info_legacy::{ChainTypeRegistry, TypeRegistrySet};
+use scale_type_resolver::TypeResolver;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+static MODERN: LazyLock<RuntimeMetadataV14> = LazyLock::new(|| {
+    let bytes = std::fs::read("artifacts/metadata_10000000_9180.scale").unwrap();
+    let RuntimeMetadata::V14(metadata) = RuntimeMetadata::decode(&mut &*bytes).unwrap() else {
+        panic!("Expected V14 metadata")
+    };
+    metadata
+});
+
+static LEGACY: LazyLock<(RuntimeMetadataV12, TypeRegistrySet<'static>)> = LazyLock::new(|| {
+    let bytes = std::fs::read("artifacts/metadata_5000000_30.scale").unwrap();
+    let RuntimeMetadata::V12(metadata) = RuntimeMetadata::decode(&mut &*bytes).unwrap() else {
+        panic!("Expected V12 metadata")
+    };
+    let historic_types = frame_decode::legacy_types::polkadot::relay_chain();
+    let mut types = historic_types.for_spec_version(30).to_owned();
+    types.prepend(frame_decode::helpers::type_registry_from_metadata(&metadata).unwrap());
+    (metadata, types)
+});
+
+static MODERN_EXTRINSICS: LazyLock<Vec<Vec<u8>>> = LazyLock::new(|| {
+    let bytes = std::fs::read("artifacts/exts_10000000_9180.json").unwrap();
+    let hexes: Vec<String> = serde_json::from_slice(&bytes).unwrap();
+    hexes
+        .iter()
+        .map(|h| hex::decode(h.trim_start_matches("0x")).unwrap())
+        .collect()
+});
+
+struct Proof {
+    root: [u8; 32],
+    proof: Vec<Vec<u8>>,
+    compact_proof: Vec<Vec<u8>>,
+}
+
+static PROOFS: LazyLock<Vec<Proof>> = LazyLock::new(|| {
+    let bytes = std::fs::read("artifacts/storage_proof_10000000_9180.json").unwrap();
+    let json: Vec<serde_json::Value> = serde_json::from_slice(&bytes).unwrap();
+    let from_hex =
+        |s: &serde_json::Value| hex::decode(s.as_str().unwrap().trim_start_matches("0x")).unwrap();
+    let from_hex_array =
+        |s: &serde_json::Value| s.as_array().unwrap().iter().map(from_hex).collect();
+    json.iter()
+        .map(|p| Proof {
+            root: from_hex(&p["root"]).try_into().unwrap(),
+            proof: from_hex_array(&p["proof"]),
+            compact_proof: from_hex_array(&p["compact_proof"]),
+        })
+        .collect()
+});
+
+fn bytes(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..max_len)
+}
+
+// Overwrite some bytes and then truncate, to take some valid bytes and make them less so.
+fn mutate(mut bytes: Vec<u8>, changes: &[(usize, u8)], truncate_to: usize) -> Vec<u8> {
+    for &(idx, byte) in changes {
+        if !bytes.is_empty() {
+            let len = bytes.len();
+            bytes[idx % len] = byte;
+        }
+    }
+    bytes.truncate(truncate_to);
+    bytes
+}
+
+fn storage_entries<Info: StorageEntryInfo>(info: &Info) -> Vec<(String, String)> {
+    info.storage_tuples()
+        .map(|(p, e)| (p.into_owned(), e.into_owned()))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn extrinsics_from_arbitrary_bytes(bytes in bytes(512)) {
+        let metadata = &*MODERN;
+        if let Ok(ext) = decode_extrinsic(&mut &*bytes, metadata, &metadata.types) {
+            let _ = decode_standard_extensions(&ext, &bytes, &metadata.types);
+            // The bytes given may not line up with those that were decoded.
+            let _ = decode_standard_extensions(&ext, &[], &metadata.types);
+        }
+        let _ = decode_call_data(&mut &*bytes, metadata, &metadata.types);
+        let _ = decode_call_tree(&mut &*bytes, metadata, &metadata.types);
+
+        let (metadata, types) = &*LEGACY;
+        let _ = decode_extrinsic(&mut &*bytes, metadata, types);
+        let _ = decode_call_data(&mut &*bytes, metadata, types);
+        let _ = decode_call_tree(&mut &*bytes, metadata, types);
+    }
+
+    #[test]
+    fn extrinsics_from_mutated_bytes(
+        idx in any::<prop::sample::Index>(),
+        changes in prop::collection::vec((any::<usize>(), any::<u8>()), 0..4),
+        truncate_to in any::<usize>(),
+    ) {
+        let metadata = &*MODERN;
+        let ext_bytes = idx.get(&MODERN_EXTRINSICS).clone();
+        let truncate_to = truncate_to % (ext_bytes.len() + 1);
+        let bytes = mutate(ext_bytes, &changes, truncate_to);
+
+        if let Ok(ext) = decode_extrinsic(&mut &*bytes, metadata, &metadata.types) {
+            let _ = decode_standard_extensions(&ext, &bytes, &metadata.types);
+            let _ = decode_standard_extensions(&ext, &bytes[..bytes.len() / 2], &metadata.types);
+            let _ = ext.signer_payload(&bytes[..bytes.len() / 2], &(), metadata, &metadata.types);
+        }
+        let _ = decode_extrinsic_receipts(&[&bytes], &bytes, metadata, &metadata.types);
+    }
+
+    #[test]
+    fn storage_keys_from_arbitrary_bytes(
+        idx in any::<prop::sample::Index>(),
+        suffix in bytes(128),
+    ) {
+        let metadata = &*MODERN;
+        let entries = storage_entries(metadata);
+        let (pallet, entry) = idx.get(&entries);
+
+        // Arbitrary bytes, which are likely too short to even contain a prefix:
+        let _ = decode_storage_key(pallet, entry, &mut &*suffix, metadata, &metadata.types);
+
+        // Arbitrary bytes following a valid prefix:
+        let mut key = encode_storage_key_prefix(pallet, entry).to_vec();
+        key.extend_from_slice(&suffix);
+        if let Ok(decoded) = decode_storage_key(pallet, entry, &mut &*key, metadata, &metadata.types) {
+            let _ = decode_storage_key_values::<Vec<scale_value::Value>, _>(&key, &decoded, &metadata.types);
+            let _ = decode_storage_key_values::<(u64, u64), _>(&key, &decoded, &metadata.types);
+            // The key bytes given may not line up with the decoded key.
+            let _ = decode_storage_key_values::<[u64; 1], _>(&suffix, &decoded, &metadata.types);
+        }
+        let _ = decode_storage_key_with_options(pallet, entry, &mut &*key, metadata, &metadata.types, DecodeStorageKeyOptions::new().verify_hashes(true));
+
+        let index = StoragePrefixIndex::new(metadata);
+        let _ = decode_any_storage_key(&mut &*suffix, &index, metadata, &metadata.types);
+        let _ = decode_any_storage_key(&mut &*key, &index, metadata, &metadata.types);
+
+        let (metadata, types) = &*LEGACY;
+        let entries = storage_entries(metadata);
+        let (pallet, entry) = idx.get(&entries);
+        let mut key = encode_storage_key_prefix(pallet, entry).to_vec();
+        key.extend_from_slice(&suffix);
+        let _ = decode_storage_key(pallet, entry, &mut &*suffix, metadata, types);
+        let _ = decode_storage_key(pallet, entry, &mut &*key, metadata, types);
+        let _ = decode_storage_key_with_options(pallet, entry, &mut &*key, metadata, types, DecodeStorageKeyOptions::new().verify_hashes(true));
+    }
+
+    #[test]
+    fn storage_values_from_arbitrary_bytes(
+        idx in any::<prop::sample::Index>(),
+        bytes in bytes(256),
+    ) {
+        let metadata = &*MODERN;
+        let entries = storage_entries(metadata);
+        let (pallet, entry) = idx.get(&entries);
+        let _ = decode_storage_value(pallet, entry, &mut &*bytes, metadata, &metadata.types, IgnoreVisitor::new());
+
+        let (metadata, types) = &*LEGACY;
+        let entries = storage_entries(metadata);
+        let (pallet, entry) = idx.get(&entries);
+        let _ = decode_storage_value(pallet, entry, &mut &*bytes, metadata, types, IgnoreVisitor::new());
+    }
+
+    #[test]
+    fn storage_keys_from_arbitrary_values(
+        idx in any::<prop::sample::Index>(),
+        values in prop::collection::vec(any::<u64>(), 0..4),
+    ) {
+        let metadata = &*MODERN;
+        let entries = storage_entries(metadata);
+        let (pallet, entry) = idx.get(&entries);
+        let _ = encode_storage_key(pallet, entry, values.clone(), metadata, &metadata.types);
+
+        let (metadata, types) = &*LEGACY;
+        let entries = storage_entries(metadata);
+        let (pallet, entry) = idx.get(&entries);
+        let _ = encode_storage_key(pallet, entry, values, metadata, types);
+    }
+
+    #[test]
+    fn events_and_errors_from_arbitrary_bytes(bytes in bytes(512)) {
+        let metadata = &*MODERN;
+        let _ = decode_event(&mut &*bytes, metadata, &metadata.types);
+        if let Ok(records) = decode_event_records(&bytes, metadata, &metadata.types) {
+            for record in records {
+                if record.is_err() {
+                    break;
+                }
+            }
+        }
+
+        let dispatch_error_ty = metadata
+            .types
+            .types
+            .iter()
+            .find(|t| t.ty.path.segments == ["sp_runtime", "DispatchError"])
+            .unwrap()
+            .id;
+        let _ = decode_dispatch_error(&mut &*bytes, dispatch_error_ty, metadata, &metadata.types);
+
+        let (metadata, types) = &*LEGACY;
+        let _ = decode_event(&mut &*bytes, metadata, types);
+    }
+
+    #[test]
+    fn storage_proofs_from_arbitrary_nodes(
+        nodes in prop::collection::vec(bytes(128), 1..8),
+    ) {
+        // Use the first node as the root so that the rest of the nodes are looked at.
+        let root = sp_crypto_hashing::blake2_256(&nodes[0]);
+        let _ = verify_storage_proof(&root, &nodes);
+        let _ = verify_compact_storage_proof(&root, &nodes);
+    }
+
+    #[test]
+    fn storage_proofs_from_mutated_nodes(
+        idx in any::<prop::sample::Index>(),
+        node_idx in any::<prop::sample::Index>(),
+        changes in prop::collection::vec((any::<usize>(), any::<u8>()), 0..4),
+        truncate_to in any::<usize>(),
+    ) {
+        let Proof { root, proof, compact_proof } = idx.get(&PROOFS);
+        for mut nodes in [proof.clone(), compact_proof.clone()] {
+            let node_idx = node_idx.index(nodes.len());
+            let truncate_to = truncate_to % (nodes[node_idx].len() + 1);
+            nodes[node_idx] = mutate(nodes[node_idx].clone(), &changes, truncate_to);
+
+            let _ = verify_storage_proof(root, &nodes);
+            let _ = verify_compact_storage_proof(root, &nodes);
+        }
+    }
+
+    #[test]
+    fn call_data_from_arbitrary_values(
+        idx in any::<prop::sample::Index>(),
+        values in prop::collection::vec(any::<u128>(), 0..4),
+    ) {
+        let metadata = &*MODERN;
+        let calls: Vec<(String, String)> = metadata
+            .pallets
+            .iter()
+            .filter_map(|p| p.calls.as_ref().map(|c| (p, c.ty.id)))
+            .flat_map(|(p, ty)| {
+                let Some(scale_info::TypeDef::Variant(v)) = metadata.types.resolve(ty).map(|t| &t.type_def) else {
+                    return vec![];
+                };
+                v.variants.iter().map(|v| (p.name.clone(), v.name.clone())).collect()
+            })
+            .collect();
+        let (pallet, call) = idx.get(&calls);
+
+        let call_data = scale_value::Composite::unnamed(values.into_iter().map(scale_value::Value::u128));
+        let _ = encode_call_data(pallet, call, &call_data, metadata, &metadata.types);
+    }
+}
+
+// The types that arbitrary metadata can refer to.
+fn palette_type(idx: usize) -> MetaType {
+    match idx % 14 {
+        0 => meta_type::<u8>(),
+        1 => meta_type::<u32>(),
+        2 => meta_type::<u64>(),
+        3 => meta_type::<u128>(),
+        4 => meta_type::<bool>(),
+        5 => meta_type::<String>(),
+        6 => meta_type::<Vec<u8>>(),
+        7 => meta_type::<Option<u32>>(),
+        8 => meta_type::<(u8, String)>(),
+        9 => meta_type::<[u8; 32]>(),
+        10 => meta_type::<Compact<u64>>(),
+        11 => meta_type::<BTreeMap<u32, Vec<u16>>>(),
+        12 => meta_type::<Result<u8, String>>(),
+        _ => meta_type::<Vec<Option<(bool, u16)>>>(),
+    }
+}
+
+// The enum types that arbitrary metadata can use for calls and events.
+fn palette_enum_type(idx: usize) -> MetaType {
+    match idx % 3 {
+        0 => meta_type::<Option<u32>>(),
+        1 => meta_type::<Result<u8, String>>(),
+        _ => meta_type::<Option<Vec<u8>>>(),
+    }
+}
+
+fn palette_hasher(idx: usize) -> StorageHasher {
+    match idx % 7 {
+        0 => StorageHasher::Blake2_128,
+        1 => StorageHasher::Blake2_256,
+        2 => StorageHasher::Blake2_128Concat,
+        3 => StorageHasher::Twox128,
+        4 => StorageHasher::Twox256,
+        5 => StorageHasher::Twox64Concat,
+        _ => StorageHasher::Identity,
+    }
+}
+
+const NAMES: [&str; 4] = ["A", "B", "C", "D"];
+
+/// The shape of some arbitrary V16 metadata. Type, hasher and extension choices are indexes
+/// into the palettes above.
+#[derive(Debug, Clone)]
+struct ArbitraryMetadata {
+    calls: Option<usize>,
+    event: Option<usize>,
+    storage: Vec<(Vec<usize>, usize, usize, Vec<u8>)>,
+    constants: Vec<(usize, Vec<u8>)>,
+    view_functions: Vec<(Vec<usize>, usize)>,
+    api_methods: Vec<(Vec<usize>, usize)>,
+    custom_values: Vec<(usize, Vec<u8>)>,
+    extensions: Vec<usize>,
+    extension_indexes: Vec<u32>,
+    address_and_signature: (usize, usize),
+}
+
+impl ArbitraryMetadata {
+    fn strategy() -> impl Strategy<Value = Self> {
+        let types = || prop::collection::vec(any::<usize>(), 0..3);
+        let items = |n| prop::collection::vec(any::<usize>(), n);
+        (
+            (any::<Option<usize>>(), any::<Option<usize>>()),
+            prop::collection::vec(
+                (items(1..3), any::<usize>(), any::<usize>(), bytes(16)),
+                0..4,
+            ),
+            prop::collection::vec((any::<usize>(), bytes(16)), 0..4),
+            prop::collection::vec((types(), any::<usize>()), 0..4),
+            prop::collection::vec((types(), any::<usize>()), 0..4),
+            prop::collection::vec((any::<usize>(), bytes(16)), 0..4),
+            (types(), prop::collection::vec(0..4u32, 0..4)),
+            (any::<usize>(), any::<usize>()),
+        )
+            .prop_map(
+                |(
+                    (calls, event),
+                    storage,
+                    constants,
+                    view_functions,
+                    api_methods,
+                    custom_values,
+                    (extensions, extension_indexes),
+                    address_and_signature,
+                )| ArbitraryMetadata {
+                    calls,
+                    event,
+                    storage,
+                    constants,
+                    view_functions,
+                    api_methods,
+                    custom_values,
+                    extensions,
+                    extension_indexes,
+                    address_and_signature,
+                },
+            )
+    }
+
+    fn to_metadata(&self) -> RuntimeMetadataV16 {
+        let params = |tys: &[usize]| {
+            tys.iter()
+                .zip(NAMES)
+                .map(|(ty, name)| FunctionParamMetadata {
+                    name,
+                    ty: palette_type(*ty),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let storage_entries = self
+            .storage
+            .iter()
+            .zip(NAMES)
+            .map(|((hashers, key, value, default), name)| {
+                let ty = if hashers.len() == 1 {
+                    StorageEntryType::Map {
+                        hashers: vec![palette_hasher(hashers[0])],
+                        key: palette_type(*key),
+                        value: palette_type(*value),
+                    }
+                } else {
+                    StorageEntryType::Map {
+                        hashers: hashers.iter().map(|h| palette_hasher(*h)).collect(),
+                        key: meta_type::<(u32, String)>(),
+                        value: palette_type(*value),
+                    }
+                };
+                StorageEntryMetadata {
+                    name,
+                    modifier: StorageEntryModifier::Default,
+                    ty,
+                    default: default.clone(),
+                    docs: vec![],
+                    deprecation_info: ItemDeprecationInfo::NotDeprecated,
+                }
+            })
+            .chain([StorageEntryMetadata {
+                name: "Plain",
+                modifier: StorageEntryModifier::Optional,
+                ty: StorageEntryType::Plain(palette_type(self.address_and_signature.0)),
+                default: vec![],
+                docs: vec![],
+                deprecation_info: ItemDeprecationInfo::NotDeprecated,
+            }])
+            .collect();
+
+        let pallet = PalletMetadata {
+            name: "Pallet",
+            storage: Some(PalletStorageMetadata {
+                prefix: "Pallet",
+                entries: storage_entries,
+            }),
+            calls: self.calls.map(|ty| PalletCallMetadata {
+                ty: palette_enum_type(ty),
+                deprecation_info: EnumDeprecationInfo(BTreeMap::new()),
+            }),
+            event: self.event.map(|ty| PalletEventMetadata {
+                ty: palette_enum_type(ty),
+                deprecation_info: EnumDeprecationInfo(BTreeMap::new()),
+            }),
+            constants: self
+                .constants
+                .iter()
+                .zip(NAMES)
+                .map(|((ty, value), name)| PalletConstantMetadata {
+                    name,
+                    ty: palette_type(*ty),
+                    value: value.clone(),
+                    docs: vec![],
+                    deprecation_info: ItemDeprecationInfo::NotDeprecated,
+                })
+                .collect(),
+            error: None,
+            associated_types: vec![],
+            view_functions: self
+                .view_functions
+                .iter()
+                .zip(NAMES)
+                .map(|((inputs, output), name)| PalletViewFunctionMetadata {
+                    id: [0; 32],
+                    name,
+                    inputs: params(inputs),
+                    output: palette_type(*output),
+                    docs: vec![],
+                    deprecation_info: ItemDeprecationInfo::NotDeprecated,
+                })
+                .collect(),
+            index: 0,
+            docs: vec![],
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+
+        let api = RuntimeApiMetadata {
+            name: "Api",
+            methods: self
+                .api_methods
+                .iter()
+                .zip(NAMES)
+                .map(|((inputs, output), name)| RuntimeApiMethodMetadata {
+                    name,
+                    inputs: params(inputs),
+                    output: palette_type(*output),
+                    docs: vec![],
+                    deprecation_info: ItemDeprecationInfo::NotDeprecated,
+                })
+                .collect(),
+            docs: vec![],
+            version: 1u32.into(),
+            deprecation_info: ItemDeprecationInfo::NotDeprecated,
+        };
+
+        let extrinsic = ExtrinsicMetadata {
+            versions: vec![4, 5],
+            address_ty: palette_type(self.address_and_signature.0),
+            call_ty: palette_enum_type(self.calls.unwrap_or(0)),
+            signature_ty: palette_type(self.address_and_signature.1),
+            // These indexes may not point at real extensions:
+            transaction_extensions_by_version: BTreeMap::from_iter([(
+                0,
+                self.extension_indexes.iter().map(|&i| Compact(i)).collect(),
+            )]),
+            transaction_extensions: self
+                .extensions
+                .iter()
+                .zip(NAMES)
+                .map(|(ty, identifier)| TransactionExtensionMetadata {
+                    identifier,
+                    ty: palette_type(*ty),
+                    implicit: meta_type::<()>(),
+                })
+                .collect(),
+        };
+
+        let custom = CustomMetadata {
+            map: self
+                .custom_values
+                .iter()
+                .zip(NAMES)
+                .map(|((ty, value), name)| {
+                    (
+                        name,
+                        CustomValueMetadata {
+                            ty: palette_type(*ty),
+                            value: value.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        };
+
+        RuntimeMetadataV16::new(
+            vec![pallet],
+            extrinsic,
+            vec![api],
+            OuterEnums {
+                call_enum_ty: palette_enum_type(self.calls.unwrap_or(0)),
+                event_enum_ty: palette_enum_type(self.event.unwrap_or(0)),
+                error_enum_ty: meta_type::<Option<u8>>(),
+            },
+            custom,
+        )
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn arbitrary_metadata_and_bytes(
+        shape in ArbitraryMetadata::strategy(),
+        bytes in bytes(128),
+        values in prop::collection::vec(any::<u64>(), 0..3),
+    ) {
+        let metadata = shape.to_metadata();
+        let types = &metadata.types;
+
+        for (pallet, constant) in metadata.constant_tuples() {
+            let _ = decode_constant(&pallet, &constant, &metadata, types, IgnoreVisitor::new());
+        }
+        for value in metadata.custom_values() {
+            let _ = decode_custom_value(&value.name, &metadata, types, IgnoreVisitor::new());
+        }
+        for (api, method) in metadata.runtime_api_tuples() {
+            let _ = decode_runtime_api_response(&api, &method, &mut &*bytes, &metadata, types, IgnoreVisitor::new());
+            let _ = encode_runtime_api_inputs(&api, &method, values.clone(), &metadata, types);
+        }
+        for (pallet, function) in metadata.view_function_tuples() {
+            let _ = decode_view_function_response(&pallet, &function, &mut &*bytes, &metadata, types, IgnoreVisitor::new());
+            let _ = encode_view_function_inputs(&pallet, &function, values.clone(), &metadata, types);
+        }
+
+        let index = StoragePrefixIndex::new(&metadata);
+        for (pallet, entry) in storage_entries(&metadata) {
+            let mut key = encode_storage_key_prefix(&pallet, &entry).to_vec();
+            key.extend_from_slice(&bytes);
+            if let Ok(decoded) = decode_storage_key(&pallet, &entry, &mut &*key, &metadata, types) {
+                let _ = decode_storage_key_values::<Vec<scale_value::Value>, _>(&key, &decoded, types);
+            }
+            let _ = decode_any_storage_key(&mut &*key, &index, &metadata, types);
+            let _ = decode_storage_value(&pallet, &entry, &mut &*bytes, &metadata, types, IgnoreVisitor::new());
+            let _ = encode_storage_key(&pallet, &entry, values.clone(), &metadata, types);
+        }
+
+        if let Ok(ext) = decode_extrinsic(&mut &*bytes, &metadata, types) {
+            let _ = decode_standard_extensions(&ext, &bytes, types);
+        }
+        let _ = decode_call_data(&mut &*bytes, &metadata, types);
+        let _ = decode_call_tree(&mut &*bytes, &metadata, types);
+        let _ = decode_event(&mut &*bytes, &metadata, types);
+        let _ = decode_runtime_call(&mut &*bytes, &metadata, types);
+        let _ = decode_runtime_event(&mut &*bytes, &metadata, types);
+        let _ = decode_runtime_error(&mut &*bytes, &metadata, types);
+    }
+}
+
+static LEGACY_TYPES: LazyLock<ChainTypeRegistry> =
+    LazyLock::new(frame_decode::legacy_types::polkadot::relay_chain);
+
+// The type names that arbitrary legacy metadata can refer to. Some of these are unknown or
+// cannot be parsed at all.
+const LEGACY_TYPE_NAMES: [&str; 14] = [
+    "u8",
+    "u32",
+    "u64",
+    "u128",
+    "bool",
+    "Vec<u8>",
+    "Option<u32>",
+    "(u8, bool)",
+    "[u8; 32]",
+    "Compact<u64>",
+    "T::AccountId",
+    "Balance",
+    "NotAType",
+    "Foo<Bar",
+];
+
+// Module names end up in type names, and so some of these cannot be parsed as part of one.
+const LEGACY_MODULE_NAMES: [&str; 5] = ["System", "Balances", "Foo<Bar", "A::B", ""];
+
+const LEGACY_EXTENSION_NAMES: [&str; 4] = [
+    "CheckNonce",
+    "CheckMortality",
+    "ChargeTransactionPayment",
+    "NotAnExtension",
+];
+
+fn legacy_type_name(idx: usize) -> String {
+    LEGACY_TYPE_NAMES[idx % LEGACY_TYPE_NAMES.len()].to_owned()
+}
+
+// The kind, hashers, key types, value type and default value of a legacy storage entry.
+type LegacyStorageEntry = (usize, (usize, usize), (usize, usize), usize, Vec<u8>);
+
+/// The shape of a module in some arbitrary V8 to V13 metadata. Type choices are indexes into
+/// [`LEGACY_TYPE_NAMES`].
+#[derive(Debug, Clone)]
+struct ArbitraryLegacyModule {
+    name: usize,
+    index: u8,
+    calls: Option<Vec<Vec<usize>>>,
+    events: Option<Vec<Vec<usize>>>,
+    storage: Vec<LegacyStorageEntry>,
+    constants: Vec<(usize, Vec<u8>)>,
+    errors: usize,
+}
+
+/// The shape of some arbitrary V8 to V13 metadata.
+#[derive(Debug, Clone)]
+struct ArbitraryLegacyMetadata {
+    modules: Vec<ArbitraryLegacyModule>,
+    extensions: Vec<usize>,
+}
+
+impl ArbitraryLegacyMetadata {
+    fn strategy() -> impl Strategy<Value = Self> {
+        let types = || prop::collection::vec(any::<usize>(), 0..3);
+        let items = || prop::collection::vec(types(), 0..3);
+        let module = (
+            (any::<usize>(), any::<u8>()),
+            (prop::option::of(items()), prop::option::of(items())),
+            prop::collection::vec(
+                (
+                    any::<usize>(),
+                    any::<(usize, usize)>(),
+                    any::<(usize, usize)>(),
+                    any::<usize>(),
+                    bytes(16),
+                ),
+                0..4,
+            ),
+            prop::collection::vec((any::<usize>(), bytes(16)), 0..3),
+            any::<usize>(),
+        )
+            .prop_map(
+                |((name, index), (calls, events), storage, constants, errors)| {
+                    ArbitraryLegacyModule {
+                        name,
+                        index,
+                        calls,
+                        events,
+                        storage,
+                        constants,
+                        errors,
+                    }
+                },
+            );
+        (
+            prop::collection::vec(module, 0..4),
+            prop::collection::vec(any::<usize>(), 0..4),
+        )
+            .prop_map(|(modules, extensions)| ArbitraryLegacyMetadata {
+                modules,
+                extensions,
+            })
+    }
+
+    fn to_metadatas(&self) -> Vec<RuntimeMetadata> {
+        vec![
+            RuntimeMetadata::V8(legacy_metadata!(self, v8, RuntimeMetadataV8, is_linked)),
+            RuntimeMetadata::V9(legacy_metadata!(self, v9, RuntimeMetadataV9, is_linked)),
+            RuntimeMetadata::V10(legacy_metadata!(self, v10, RuntimeMetadataV10, is_linked)),
+            RuntimeMetadata::V11(legacy_metadata!(
+                self,
+                v11,
+                RuntimeMetadataV11,
+                unused,
+                extrinsic
+            )),
+            RuntimeMetadata::V12(legacy_metadata!(
+                self,
+                v12,
+                RuntimeMetadataV12,
+                unused,
+                extrinsic,
+                index
+            )),
+            RuntimeMetadata::V13(legacy_metadata!(
+                self,
+                v13,
+                RuntimeMetadataV13,
+                unused,
+                extrinsic,
+                index
+            )),
+        ]
+    }
+}
+
+// Build some legacy metadata of the given version from an `ArbitraryLegacyMetadata`. The
+// `extrinsic` and then `index` idents are given for versions which have these fields.
+macro_rules! legacy_metadata {
+    ($shape:expr, $v:ident, $metadata:ident, $linked:ident $(, $extrinsic:ident $(, $index:ident)?)?) => {{
+        use frame_metadata::decode_different::DecodeDifferent::Decoded;
+        use frame_metadata::$v::*;
+
+        let shape: &ArbitraryLegacyMetadata = $shape;
+        let ty = |idx: &usize| Decoded(legacy_type_name(*idx));
+        // The hashers which exist in every legacy metadata version:
+        let hasher = |idx: &usize| match idx % 5 {
+            0 => StorageHasher::Blake2_128,
+            1 => StorageHasher::Blake2_256,
+            2 => StorageHasher::Twox128,
+            3 => StorageHasher::Twox256,
+            _ => StorageHasher::Twox64Concat,
+        };
+
+        let modules = shape
+            .modules
+            .iter()
+            .map(|m| {
+                let name = LEGACY_MODULE_NAMES[m.name % LEGACY_MODULE_NAMES.len()];
+                let entries = m
+                    .storage
+                    .iter()
+                    .zip(NAMES)
+                    .map(|((kind, hashers, keys, value, default), name)| StorageEntryMetadata {
+                        name: Decoded(name.to_owned()),
+                        modifier: if default.is_empty() {
+                            StorageEntryModifier::Optional
+                        } else {
+                            StorageEntryModifier::Default
+                        },
+                        ty: match kind % 3 {
+                            0 => StorageEntryType::Plain(ty(value)),
+                            1 => StorageEntryType::Map {
+                                hasher: hasher(&hashers.0),
+                                key: ty(&keys.0),
+                                value: ty(value),
+                                $linked: false,
+                            },
+                            _ => StorageEntryType::DoubleMap {
+                                hasher: hasher(&hashers.0),
+                                key1: ty(&keys.0),
+                                key2: ty(&keys.1),
+                                value: ty(value),
+                                key2_hasher: hasher(&hashers.1),
+                            },
+                        },
+                        default: Decoded(default.clone()),
+                        documentation: Decoded(vec![]),
+                    })
+                    .collect();
+
+                ModuleMetadata {
+                    name: Decoded(name.to_owned()),
+                    storage: Some(Decoded(StorageMetadata {
+                        prefix: Decoded(name.to_owned()),
+                        entries: Decoded(entries),
+                    })),
+                    calls: m.calls.as_ref().map(|calls| {
+                        Decoded(
+                            calls
+                                .iter()
+                                .zip(NAMES)
+                                .map(|(args, name)| FunctionMetadata {
+                                    name: Decoded(name.to_owned()),
+                                    arguments: Decoded(
+                                        args.iter()
+                                            .zip(NAMES)
+                                            .map(|(arg, name)| FunctionArgumentMetadata {
+                                                name: Decoded(name.to_lowercase()),
+                                                ty: ty(arg),
+                                            })
+                                            .collect(),
+                                    ),
+                                    documentation: Decoded(vec![]),
+                                })
+                                .collect(),
+                        )
+                    }),
+                    event: m.events.as_ref().map(|events| {
+                        Decoded(
+                            events
+                                .iter()
+                                .zip(NAMES)
+                                .map(|(args, name)| EventMetadata {
+                                    name: Decoded(name.to_owned()),
+                                    arguments: Decoded(
+                                        args.iter().map(|arg| legacy_type_name(*arg)).collect(),
+                                    ),
+                                    documentation: Decoded(vec![]),
+                                })
+                                .collect(),
+                        )
+                    }),
+                    constants: Decoded(
+                        m.constants
+                            .iter()
+                            .zip(NAMES)
+                            .map(|((constant_ty, value), name)| ModuleConstantMetadata {
+                                name: Decoded(name.to_owned()),
+                                ty: ty(constant_ty),
+                                value: Decoded(value.clone()),
+                                documentation: Decoded(vec![]),
+                            })
+                            .collect(),
+                    ),
+                    errors: Decoded(
+                        NAMES[..m.errors % NAMES.len()]
+                            .iter()
+                            .map(|name| ErrorMetadata {
+                                name: Decoded(name.to_string()),
+                                documentation: Decoded(vec![]),
+                            })
+                            .collect(),
+                    ),
+                    $($($index: m.index,)?)?
+                }
+            })
+            .collect();
+
+        $metadata {
+            modules: Decoded(modules),
+            $($extrinsic: ExtrinsicMetadata {
+                version: 4,
+                signed_extensions: shape
+                    .extensions
+                    .iter()
+                    .map(|e| Decoded(LEGACY_EXTENSION_NAMES[e % LEGACY_EXTENSION_NAMES.len()].to_owned()))
+                    .collect(),
+            },)?
+        }
+    }};
+}
+use legacy_metadata;
+
+// Decode and encode everything that we can with some metadata, given arbitrary bytes and values.
+fn decode_everything<Info, Resolver>(info: &Info, types: &Resolver, bytes: &[u8], values: &[u64])
+where
+    Info: ExtrinsicTypeInfo<TypeId = Resolver::TypeId>
+        + EventTypeInfo<TypeId = Resolver::TypeId>
+        + StorageTypeInfo<TypeId = Resolver::TypeId>
+        + StorageEntryInfo
+        + ConstantTypeInfo<TypeId = Resolver::TypeId>
+        + ConstantEntryInfo,
+    Resolver: TypeResolver,
+    Resolver::TypeId: core::fmt::Debug + Clone + 'static,
+{
+    for (pallet, constant) in info.constant_tuples() {
+        let _ = decode_constant(&pallet, &constant, info, types, IgnoreVisitor::new());
+    }
+    for (pallet, entry) in storage_entries(info) {
+        let mut key = encode_storage_key_prefix(&pallet, &entry).to_vec();
+        key.extend_from_slice(bytes);
+        if let Ok(decoded) = decode_storage_key(&pallet, &entry, &mut &*key, info, types) {
+            let _ = decode_storage_key_values::<Vec<scale_value::Value>, _>(&key, &decoded, types);
+        }
+        let _ = decode_storage_key_with_options(
+            &pallet,
+            &entry,
+            &mut &*key,
+            info,
+            types,
+            DecodeStorageKeyOptions::new().verify_hashes(true),
+        );
+        let _ = decode_storage_value(
+            &pallet,
+            &entry,
+            &mut &*bytes,
+            info,
+            types,
+            IgnoreVisitor::new(),
+        );
+        let _ = encode_storage_key(&pallet, &entry, values.to_vec(), info, types);
+    }
+    let _ = decode_extrinsic(&mut &*bytes, info, types);
+    let _ = decode_call_data(&mut &*bytes, info, types);
+    let _ = decode_event(&mut &*bytes, info, types);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn arbitrary_legacy_metadata_and_bytes(
+        shape in ArbitraryLegacyMetadata::strategy(),
+        bytes in bytes(128),
+        values in prop::collection::vec(any::<u64>(), 0..3),
+    ) {
+        for metadata in shape.to_metadatas() {
+            // Convert the metadata to V14 and decode using that:
+            if let Ok(metadata_types) = type_registry_from_metadata_any(&metadata) {
+                let mut types = LEGACY_TYPES.for_spec_version(30);
+                types.prepend(metadata_types);
+                if let Ok(converted) = legacy_metadata_to_v14_any(&metadata, &types) {
+                    decode_everything(&converted, &converted.types, &bytes, &values);
+                }
+            }
+
+            // And decode using the legacy metadata itself:
+            let Ok(metadata) = AnyMetadata::new(metadata) else { continue };
+            let Ok(metadata) = metadata.with_legacy_types(LEGACY_TYPES.for_spec_version(30)) else {
+                continue
+            };
+            decode_everything(&metadata, &metadata, &bytes, &values);
+        }
+    }
+}